@@ -0,0 +1,338 @@
+//! Caching of the compilation results, e.g. for a dev server in watch mode.
+//!
+//! ```
+//! use fervid::{cache::CompileCache, CompileOptions};
+//!
+//! let cache = CompileCache::default();
+//! let options = || CompileOptions {
+//!     filename: "Component.vue".into(),
+//!     ..Default::default()
+//! };
+//!
+//! let source = "<template><div>hello</div></template>";
+//! let first = cache.compile(source, options()).unwrap();
+//! let second = cache.compile(source, options()).unwrap();
+//! assert!(std::sync::Arc::ptr_eq(&first, &second));
+//! ```
+
+use fxhash::FxHashMap;
+use sha2::{digest::Output, Digest, Sha256};
+use std::{
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+use crate::{compile, errors::CompileError, CompileOptions, CompileResult, ComponentPropTypes};
+
+/// SHA-256 digests are used instead of 64-bit hashes,
+/// so that a collision cannot return the result of another file
+type CacheDigest = Output<Sha256>;
+
+/// Options digest -> (source digest, result)
+type CacheEntries = FxHashMap<CacheDigest, (CacheDigest, Arc<CompileResult>)>;
+
+/// Thread-safe cache of [`CompileResult`]s.
+///
+/// Only the latest result is kept for each set of options (which includes the filename),
+/// so that editing a file replaces its previous result instead of growing the cache.
+#[derive(Default)]
+pub struct CompileCache {
+    entries: RwLock<CacheEntries>,
+}
+
+impl CompileCache {
+    /// Compiles the `source` unless it was already compiled with the same options.
+    ///
+    /// The `source` is always compiled when options cannot be compared,
+    /// i.e. when `is_custom_element` or a plugin has no cache key
+    /// (see [`CustomElementPredicate::with_cache_key`](crate::CustomElementPredicate::with_cache_key)
+    /// and [`SfcPlugin::cache_key`](crate::plugins::SfcPlugin::cache_key)).
+    /// Compilation failures are not cached.
+    pub fn compile(
+        &self,
+        source: &str,
+        options: CompileOptions,
+    ) -> Result<Arc<CompileResult>, CompileError> {
+        let Some(options_digest) = digest_options(&options) else {
+            return compile(source, options).map(Arc::new);
+        };
+
+        let source_digest = Sha256::digest(source.as_bytes());
+
+        // Fast path
+        if let Some((cached_source_digest, cached)) = self.read_entries().get(&options_digest) {
+            if *cached_source_digest == source_digest {
+                return Ok(Arc::clone(cached));
+            }
+        }
+
+        // The lock is not held while compiling, so that other files are not blocked.
+        // When the same file is compiled concurrently, the first stored result wins.
+        let compiled = Arc::new(compile(source, options)?);
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        match entries.get(&options_digest) {
+            Some((cached_source_digest, cached)) if *cached_source_digest == source_digest => {
+                Ok(Arc::clone(cached))
+            }
+            _ => {
+                entries.insert(options_digest, (source_digest, Arc::clone(&compiled)));
+                Ok(compiled)
+            }
+        }
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.read_entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read_entries().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    fn read_entries(&self) -> RwLockReadGuard<'_, CacheEntries> {
+        // A panic in another thread cannot leave the map in an invalid state
+        self.entries.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns `None` when the options contain something which cannot be hashed
+fn digest_options(options: &CompileOptions) -> Option<CacheDigest> {
+    let mut hasher = DigestHasher::default();
+    options.filename.hash(&mut hasher);
+    options.id.hash(&mut hasher);
+    options.root.hash(&mut hasher);
+    options.is_prod.hash(&mut hasher);
+    options.ssr.hash(&mut hasher);
+    options.gen_default_as.hash(&mut hasher);
+    options.reactivity_transform.hash(&mut hasher);
+    options.compat.hash(&mut hasher);
+    options.sanitize_href.hash(&mut hasher);
+    options.warn_deprecated_globals.hash(&mut hasher);
+    options.unroll_static_for.hash(&mut hasher);
+    options.delimiters.hash(&mut hasher);
+    options.whitespace.hash(&mut hasher);
+    if let Some(ref is_custom_element) = options.is_custom_element {
+        is_custom_element.cache_key()?.hash(&mut hasher);
+    }
+    options.prepend_imports.hash(&mut hasher);
+    hash_component_prop_types(&options.component_prop_types, &mut hasher);
+    options.source_map.hash(&mut hasher);
+    for plugin in options.plugins.iter() {
+        plugin.name().hash(&mut hasher);
+        plugin.cache_key()?.hash(&mut hasher);
+    }
+    Some(hasher.0.finalize())
+}
+
+/// Feeds the [`Hash`] implementations into SHA-256
+#[derive(Default)]
+struct DigestHasher(Sha256);
+
+impl Hasher for DigestHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.0.clone().finalize()[..8]);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Hashes the prop types in a stable order, unlike the iteration order of `HashMap`
+fn hash_component_prop_types(component_prop_types: &ComponentPropTypes, hasher: &mut impl Hasher) {
+    let mut components: Vec<_> = component_prop_types.iter().collect();
+    components.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (component_name, prop_types) in components {
+        component_name.hash(hasher);
+
+        let mut props: Vec<_> = prop_types.iter().collect();
+        props.sort_by(|a, b| a.0.cmp(b.0));
+        props.hash(hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        plugins::{PluginContext, SfcPlugin},
+        CustomElementPredicate,
+    };
+
+    use super::*;
+
+    fn options(is_prod: bool) -> CompileOptions<'static> {
+        CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(is_prod),
+            ..Default::default()
+        }
+    }
+
+    const SOURCE: &str = "<template><div>{{ msg }}</div></template>";
+
+    #[test]
+    fn it_returns_cached_result() {
+        let cache = CompileCache::default();
+
+        let first = cache.compile(SOURCE, options(true)).unwrap();
+        let second = cache.compile(SOURCE, options(true)).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn it_recompiles_changed_source() {
+        let cache = CompileCache::default();
+
+        let first = cache.compile(SOURCE, options(true)).unwrap();
+        let changed = cache
+            .compile("<template><span>{{ msg }}</span></template>", options(true))
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &changed));
+        assert!(changed.code.contains("span"));
+
+        // Previous version of the file is replaced
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn it_recompiles_with_changed_options() {
+        let cache = CompileCache::default();
+
+        let prod = cache.compile(SOURCE, options(true)).unwrap();
+        let dev = cache.compile(SOURCE, options(false)).unwrap();
+        assert!(!Arc::ptr_eq(&prod, &dev));
+        assert_eq!(cache.len(), 2);
+
+        // Both are still cached
+        let prod_again = cache.compile(SOURCE, options(true)).unwrap();
+        assert!(Arc::ptr_eq(&prod, &prod_again));
+    }
+
+    #[test]
+    fn it_distinguishes_custom_element_predicates_by_cache_key() {
+        let cache = CompileCache::default();
+        let source = "<template><my-widget /><x-widget /></template>";
+        let with_predicate = |prefix: &'static str, key: u64| CompileOptions {
+            is_custom_element: Some(
+                CustomElementPredicate::new(move |tag| tag.starts_with(prefix)).with_cache_key(key),
+            ),
+            ..options(true)
+        };
+
+        let first = cache.compile(source, with_predicate("my-", 1)).unwrap();
+        let second = cache.compile(source, with_predicate("x-", 2)).unwrap();
+        assert!(first.code.contains("_resolveComponent(\"x-widget\")"));
+        assert!(second.code.contains("_resolveComponent(\"my-widget\")"));
+
+        let first_again = cache.compile(source, with_predicate("my-", 1)).unwrap();
+        assert!(Arc::ptr_eq(&first, &first_again));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn it_does_not_cache_with_custom_element_predicate_without_cache_key() {
+        let cache = CompileCache::default();
+        let source = "<template><my-widget /><x-widget /></template>";
+        let with_predicate = |prefix: &'static str| CompileOptions {
+            is_custom_element: Some(CustomElementPredicate::new(move |tag| {
+                tag.starts_with(prefix)
+            })),
+            ..options(true)
+        };
+
+        let first = cache.compile(source, with_predicate("my-")).unwrap();
+        let second = cache.compile(source, with_predicate("x-")).unwrap();
+        assert!(first.code.contains("_resolveComponent(\"x-widget\")"));
+        assert!(second.code.contains("_resolveComponent(\"my-widget\")"));
+        assert!(cache.is_empty());
+    }
+
+    struct Footer(&'static str, Option<u64>);
+
+    impl SfcPlugin for Footer {
+        fn name(&self) -> &str {
+            "footer"
+        }
+
+        fn cache_key(&self) -> Option<u64> {
+            self.1
+        }
+
+        fn after_codegen(&self, _ctx: &mut PluginContext, output: &mut String) {
+            output.push_str(self.0);
+        }
+    }
+
+    #[test]
+    fn it_distinguishes_plugins_by_cache_key() {
+        let cache = CompileCache::default();
+        let with_footer = |footer: &'static str, key: u64| CompileOptions {
+            plugins: vec![Arc::new(Footer(footer, Some(key)))],
+            ..options(true)
+        };
+
+        let first = cache.compile(SOURCE, with_footer("// first", 1)).unwrap();
+        let second = cache.compile(SOURCE, with_footer("// second", 2)).unwrap();
+        assert!(first.code.ends_with("// first"));
+        assert!(second.code.ends_with("// second"));
+
+        let first_again = cache.compile(SOURCE, with_footer("// first", 1)).unwrap();
+        assert!(Arc::ptr_eq(&first, &first_again));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn it_does_not_cache_with_plugins_without_cache_key() {
+        let cache = CompileCache::default();
+        let with_footer = |footer: &'static str| CompileOptions {
+            plugins: vec![Arc::new(Footer(footer, None))],
+            ..options(true)
+        };
+
+        let first = cache.compile(SOURCE, with_footer("// first")).unwrap();
+        let second = cache.compile(SOURCE, with_footer("// second")).unwrap();
+        assert!(first.code.ends_with("// first"));
+        assert!(second.code.ends_with("// second"));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn it_is_shared_between_threads() {
+        let cache = CompileCache::default();
+        let options = CompileOptions {
+            plugins: vec![Arc::new(Footer("// footer", Some(1)))],
+            ..options(true)
+        };
+
+        let results: Vec<Arc<CompileResult>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| cache.compile(SOURCE, options.clone()).unwrap()))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Thread must not panic"))
+                .collect()
+        });
+
+        // The first stored result is returned to all the threads
+        let cached = cache.compile(SOURCE, options).unwrap();
+        assert!(results.iter().all(|result| Arc::ptr_eq(result, &cached)));
+        assert_eq!(cache.len(), 1);
+    }
+}
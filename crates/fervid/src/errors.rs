@@ -1,5 +1,7 @@
 //! Error definitions for the glue code of `fervid`
 
+use crate::plugins::PluginError;
+use fervid_core::error::{Severity, SeverityLevel};
 use fervid_parser::ParseError as SfcParseError;
 use fervid_transform::error::TransformError;
 use swc_core::common::Spanned;
@@ -18,7 +20,10 @@ pub enum CompileError {
     SfcParse(SfcParseError),
 
     /// An error during the transformation of an SFC.
-    TransformError(TransformError)
+    TransformError(TransformError),
+
+    /// An error reported by one of the compilation plugins.
+    Plugin(PluginError),
 }
 
 impl std::fmt::Display for CompileError {
@@ -39,11 +44,75 @@ impl From<TransformError> for CompileError {
     }
 }
 
+impl From<PluginError> for CompileError {
+    fn from(value: PluginError) -> Self {
+        Self::Plugin(value)
+    }
+}
+
 impl Spanned for CompileError {
     fn span(&self) -> swc_core::common::Span {
         match self {
             CompileError::SfcParse(e) => e.span,
-            CompileError::TransformError(e) => e.span()
+            CompileError::TransformError(e) => e.span(),
+            CompileError::Plugin(e) => e.span,
+        }
+    }
+}
+
+/// A message about the compiled SFC which does not stop the compilation,
+/// e.g. to be displayed in an error overlay of a dev server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Start and end byte offsets in the source
+    pub span: (usize, usize),
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Hint,
+}
+
+impl From<&CompileError> for Diagnostic {
+    fn from(value: &CompileError) -> Self {
+        // `BytePos` is 1-based, dummy spans are reported at the start of the source
+        let span = value.span();
+        let span = (
+            span.lo.0.saturating_sub(1) as usize,
+            span.hi.0.saturating_sub(1) as usize,
+        );
+
+        let severity_level = match value {
+            CompileError::TransformError(TransformError::CssError(e)) => e.get_severity(),
+            CompileError::TransformError(TransformError::TemplateError(e)) => e.get_severity(),
+            _ => SeverityLevel::RecoverableError,
+        };
+        let severity = match severity_level {
+            SeverityLevel::Hint => DiagnosticSeverity::Hint,
+            SeverityLevel::Warning => DiagnosticSeverity::Warning,
+            SeverityLevel::RecoverableError | SeverityLevel::UnrecoverableError => {
+                DiagnosticSeverity::Error
+            }
+        };
+
+        let message = match value {
+            CompileError::SfcParse(e) => format!("{:?}", e.kind),
+            CompileError::TransformError(TransformError::CssError(e)) => format!("{:?}", e.kind),
+            CompileError::TransformError(TransformError::ScriptError(e)) => format!("{:?}", e.kind),
+            CompileError::TransformError(TransformError::TemplateError(e)) => {
+                format!("{:?}", e.kind)
+            }
+            CompileError::Plugin(e) => format!("{}: {}", e.plugin_name, e.message),
+        };
+
+        Diagnostic {
+            span,
+            severity,
+            message,
         }
     }
 }
@@ -0,0 +1,116 @@
+//! Hot Module Replacement support for the dev builds.
+
+use fervid_core::fervid_atom;
+use sha2::{Digest, Sha256};
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{Expr, Ident, KeyValueProp, Lit, ObjectLit, Prop, PropName, PropOrSpread, Str},
+};
+
+/// Generates an id in the format of `@vitejs/plugin-vue`,
+/// i.e. first 8 hex characters of the SHA-256 of the file path relative to the project `root`.
+/// Files outside of the `root` (or when there is no `root`) are hashed by their full path.
+///
+/// Windows path separators are normalized, so that the id is the same on every platform.
+pub fn generate_hmr_id(file_path: &str, root: Option<&str>) -> String {
+    let normalized = file_path.replace('\\', "/");
+    let relative = root
+        .map(|root| root.replace('\\', "/"))
+        .and_then(|root| {
+            let root = root.trim_end_matches('/');
+            normalized
+                .strip_prefix(root)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(ToOwned::to_owned)
+        })
+        .unwrap_or(normalized);
+    let digest = Sha256::digest(relative.as_bytes());
+
+    let mut id = String::with_capacity(8);
+    for byte in digest.iter().take(4) {
+        id.push_str(&format!("{:02x}", byte));
+    }
+    id
+}
+
+/// Adds `__hmrId: id`, e.g. `__hmrId: "e2e0c102"`
+pub(crate) fn attach_hmr_id(exported_obj: &mut ObjectLit, id: &str) {
+    exported_obj
+        .props
+        .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident {
+                span: DUMMY_SP,
+                sym: fervid_atom!("__hmrId"),
+                optional: false,
+            }),
+            value: Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: id.into(),
+                raw: None,
+            }))),
+        }))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_generates_known_id() {
+        assert_eq!(
+            generate_hmr_id("/home/user/project/src/App.vue", None),
+            "e2e0c102"
+        );
+        assert_eq!(
+            generate_hmr_id("/home/user/project/src/components/Button.vue", None),
+            "64a06633"
+        );
+    }
+
+    #[test]
+    fn it_hashes_path_relative_to_root() {
+        // Same as `sha256("src/App.vue")`, independent of where the project is located
+        assert_eq!(
+            generate_hmr_id("/home/user/project/src/App.vue", Some("/home/user/project")),
+            "7a7a37b1"
+        );
+        assert_eq!(
+            generate_hmr_id("/srv/project/src/App.vue", Some("/srv/project/")),
+            "7a7a37b1"
+        );
+        assert_eq!(
+            generate_hmr_id("C:\\project\\src\\App.vue", Some("C:\\project")),
+            "7a7a37b1"
+        );
+
+        // Outside of the root
+        assert_eq!(
+            generate_hmr_id("/home/user/project/src/App.vue", Some("/home/user/other")),
+            "e2e0c102"
+        );
+        assert_eq!(
+            generate_hmr_id("/home/user/project/src/App.vue", Some("/home/user/proj")),
+            "e2e0c102"
+        );
+    }
+
+    #[test]
+    fn it_generates_different_ids() {
+        assert_ne!(
+            generate_hmr_id("/home/user/project/src/App.vue", None),
+            generate_hmr_id("/home/user/project/src/Main.vue", None)
+        );
+    }
+
+    #[test]
+    fn it_normalizes_windows_separators() {
+        assert_eq!(
+            generate_hmr_id("\\home\\user\\project\\src\\App.vue", None),
+            "e2e0c102"
+        );
+        assert_eq!(
+            generate_hmr_id("C:\\project\\src\\App.vue", None),
+            generate_hmr_id("C:/project/src/App.vue", None)
+        );
+    }
+}
@@ -22,6 +22,14 @@
 //! let mut transform_errors = Vec::new();
 //! let transform_options = fervid_transform::TransformSfcOptions {
 //!   is_prod: true,
+//!   is_compat: false,
+//!   reactivity_transform: false,
+//!   sanitize_href: false,
+//!   warn_deprecated_globals: true,
+//!   unroll_static_for: false,
+//!   whitespace: Default::default(),
+//!   is_custom_element: None,
+//!   component_prop_types: Default::default(),
 //!   scope_id: "filehash",
 //!   filename: "input.vue"
 //! };
@@ -44,38 +52,55 @@
 //! );
 //!
 //! // (Optional) Stringify the code
-//! let compiled_code = fervid_codegen::CodegenContext::stringify(input, &sfc_module, FileName::Custom("input.vue".into()), false, false);
+//! let pure_annotations = ctx.generate_pure_annotations();
+//! let compiled_code = fervid_codegen::CodegenContext::stringify(input, &sfc_module, FileName::Custom("input.vue".into()), Some(&pure_annotations), false, false);
 //! ```
 
 extern crate lazy_static;
 
+pub mod cache;
 pub mod errors;
+pub mod hmr;
 #[deprecated]
 pub mod parser_old;
+pub mod plugins;
 
-use errors::CompileError;
+use errors::{CompileError, Diagnostic};
 use fervid_codegen::CodegenContext;
 pub use fervid_core::*;
 use fervid_parser::SfcParser;
-use fervid_transform::{style::should_transform_style_block, transform_sfc, TransformSfcOptions};
+use fervid_transform::{
+    script::prepend_imports,
+    style::{create_style_scope, should_transform_style_block},
+    transform_sfc, BindingsHelper, TransformSfcOptions,
+};
+pub use fervid_transform::{ComponentPropTypes, PropType, PropTypes};
 use fxhash::FxHasher32;
+use plugins::{PluginContext, SfcPlugin};
 use std::{
     borrow::Cow,
     hash::{Hash, Hasher},
+    sync::Arc,
+};
+use swc_core::{
+    common::{BytePos, FileName, Span},
+    ecma::ast::{Expr, ImportDecl},
 };
-use swc_core::{common::FileName, ecma::ast::Expr};
+use swc_ecma_parser::Syntax;
 
-// TODO Add severity to errors
 // TODO Better structs
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CompileOptions<'o> {
     // ast?: RootNode;
     pub filename: Cow<'o, str>,
     pub id: Cow<'o, str>,
+    /// Project root, the `__hmrId` of dev builds is derived from the `filename` relative to it
+    pub root: Option<Cow<'o, str>>,
     // pub scoped: Option<bool>,
     // pub slotted: Option<bool>,
     pub is_prod: Option<bool>,
+    /// Generate an `ssrRender` function to be used with `renderToString` from `vue/server-renderer`
     pub ssr: Option<bool>,
     // pub ssrCssVars?: string[],
     // pub inMap?: RawSourceMap,
@@ -93,8 +118,39 @@ pub struct CompileOptions<'o> {
 
     // script
     pub gen_default_as: Option<Cow<'o, str>>,
+    /// Enables the experimental reactivity transform (`$ref`, `$computed`, `$$`)
+    pub reactivity_transform: Option<bool>,
+
+    // template
+    /// Vue 2 compatibility mode (`@vue/compat`)
+    pub compat: Option<bool>,
+    /// Wrap dynamic `:href`, `:src` and `:action` bindings in `sanitizeUrl()`.
+    /// When disabled, such bindings produce a warning instead.
+    ///
+    /// `sanitizeUrl` is not imported by the compiler, it must be provided by the user,
+    /// e.g. imported in `<script setup>` or registered in `app.config.globalProperties`.
+    pub sanitize_href: Option<bool>,
+    /// Warn about `$parent` and `$root` access in the template (enabled by default)
+    pub warn_deprecated_globals: Option<bool>,
+    /// Unroll `v-for` over small lists of literals, e.g. `['a', 'b'] as const`, at compile time
+    pub unroll_static_for: Option<bool>,
+    /// Interpolation delimiters to use instead of `{{` and `}}`, e.g. `("[[", "]]")`
+    pub delimiters: Option<(Cow<'o, str>, Cow<'o, str>)>,
+    /// How the whitespace in the `<template>` is handled, condensed by default
+    pub whitespace: Option<WhitespaceMode>,
+    /// Imports added to every compiled SFC, e.g. `import { useRouter } from 'vue-router'`.
+    /// Names already declared by the SFC are not imported again.
+    pub prepend_imports: Vec<Cow<'o, str>>,
+    /// Tags which are custom elements, e.g. `<my-widget>` registered with `customElements.define`.
+    /// These are rendered as plain elements instead of being resolved as components.
+    pub is_custom_element: Option<CustomElementPredicate>,
+    /// Runtime prop types of the components used in the `<template>`, keyed by component name.
+    /// Literal values passed to these props are checked against their types.
+    pub component_prop_types: ComponentPropTypes,
 
     // fervid-specific
+    /// Hooks into the compilation of each SFC block, see [`plugins::SfcPlugin`]
+    pub plugins: Vec<Arc<dyn SfcPlugin + Send + Sync>>,
     pub source_map: Option<bool>,
 }
 
@@ -102,6 +158,8 @@ pub struct CompileResult {
     pub code: String,
     pub file_hash: String,
     pub errors: Vec<CompileError>,
+    /// [`CompileResult::errors`] with their severity and source offsets
+    pub diagnostics: Vec<Diagnostic>,
     pub styles: Vec<CompileEmittedStyle>,
     pub other_assets: Vec<CompileEmittedAsset>,
     pub source_map: Option<String>,
@@ -121,19 +179,91 @@ pub struct CompileEmittedAsset {
     pub content: String,
 }
 
+/// State of a single SFC compilation, shared by all the stages of [`compile_with_context`].
+///
+/// A context can be reused for several SFCs, its state is reset before each compilation.
+#[derive(Debug)]
+pub struct CompileContext<'o> {
+    pub options: CompileOptions<'o>,
+    /// Errors, warnings and hints reported by all the stages
+    pub diagnostics: Vec<Diagnostic>,
+    /// Import declarations of the compiled module, e.g. of `vue` or of the used components
+    pub imports: Vec<ImportDecl>,
+    /// Bindings collected by the script analysis and used by the template codegen
+    pub scope: BindingsHelper,
+}
+
+impl<'o> CompileContext<'o> {
+    pub fn new(options: CompileOptions<'o>) -> Self {
+        CompileContext {
+            options,
+            diagnostics: Vec::new(),
+            imports: Vec::new(),
+            scope: BindingsHelper::default(),
+        }
+    }
+}
+
 /// A more general-purpose SFC compilation function.
 /// Not production-ready yet.
 pub fn compile(source: &str, options: CompileOptions) -> Result<CompileResult, CompileError> {
+    compile_with_context(source, &mut CompileContext::new(options))
+}
+
+/// Same as [`compile`], but keeps the state of the compilation in `ctx`
+/// for the caller to inspect afterwards
+pub fn compile_with_context(
+    source: &str,
+    ctx: &mut CompileContext,
+) -> Result<CompileResult, CompileError> {
+    ctx.diagnostics.clear();
+    ctx.imports.clear();
+    ctx.scope = BindingsHelper::default();
+
     let mut all_errors = Vec::<CompileError>::new();
 
     // Options
+    let options = &ctx.options;
     let is_prod = options.is_prod.unwrap_or_default();
+    let is_ssr = options.ssr.unwrap_or(false);
+    let plugins = &options.plugins;
+    let mut plugin_ctx = PluginContext::new(&options.filename, is_prod, is_ssr);
 
     // Parse
     let mut sfc_parsing_errors = Vec::new();
     let mut parser = SfcParser::new(source, &mut sfc_parsing_errors);
-    let sfc = parser.parse_sfc()?;
-    all_errors.extend(sfc_parsing_errors.into_iter().map(From::from));
+    if let Some((ref start, ref end)) = options.delimiters {
+        parser.set_delimiters(start, end);
+    }
+    let mut sfc = match parser.parse_sfc() {
+        Ok(sfc) => sfc,
+        Err(e) => {
+            let error = CompileError::from(e);
+            ctx.diagnostics.push(Diagnostic::from(&error));
+            return Err(error);
+        }
+    };
+    report_errors(sfc_parsing_errors, &mut all_errors, &mut ctx.diagnostics);
+
+    // Let the plugins pre-process the blocks
+    if let Some(ref mut template) = sfc.template {
+        plugin_ctx.run(plugins, template, |p, ctx, block| {
+            p.before_template(ctx, block)
+        });
+    }
+    for script in [&mut sfc.script_legacy, &mut sfc.script_setup]
+        .into_iter()
+        .flatten()
+    {
+        plugin_ctx.run(plugins, script, |p, ctx, block| {
+            p.transform_script(ctx, block)
+        });
+    }
+    for style in sfc.styles.iter_mut() {
+        plugin_ctx.run(plugins, style, |p, ctx, block| {
+            p.transform_style(ctx, block)
+        });
+    }
 
     // For scopes
     // TODO Research if it's better to compute that on the caller site or here
@@ -148,36 +278,91 @@ pub fn compile(source: &str, options: CompileOptions) -> Result<CompileResult, C
     let mut transform_errors = Vec::new();
     let transform_options = TransformSfcOptions {
         is_prod,
+        is_compat: options.compat.unwrap_or(false),
+        reactivity_transform: options.reactivity_transform.unwrap_or(false),
+        sanitize_href: options.sanitize_href.unwrap_or(false),
+        warn_deprecated_globals: options.warn_deprecated_globals.unwrap_or(true),
+        unroll_static_for: options.unroll_static_for.unwrap_or(false),
+        whitespace: options.whitespace.unwrap_or_default(),
+        is_custom_element: options.is_custom_element.clone(),
+        component_prop_types: options.component_prop_types.clone(),
         scope_id: &file_hash,
         filename: &options.filename,
     };
-    let transform_result = transform_sfc(sfc, transform_options, &mut transform_errors);
-    all_errors.extend(transform_errors.into_iter().map(From::from));
+    let mut transform_result = transform_sfc(sfc, transform_options, &mut transform_errors);
+    report_errors(transform_errors, &mut all_errors, &mut ctx.diagnostics);
+
+    if !options.prepend_imports.is_empty() {
+        let mut prepend_errors = Vec::new();
+        let import_decls = parse_prepend_imports(&options.prepend_imports, &mut prepend_errors);
+        report_errors(prepend_errors, &mut all_errors, &mut ctx.diagnostics);
+        prepend_imports(&mut transform_result.module, import_decls);
+    }
+
+    // HMR is only used by the client dev builds.
+    // A non-empty `id` is used as is, e.g. when the bundler provides its own.
+    if !is_prod && !is_ssr {
+        let hmr_id = if options.id.is_empty() {
+            hmr::generate_hmr_id(&options.filename, options.root.as_deref())
+        } else {
+            options.id.to_string()
+        };
+        hmr::attach_hmr_id(&mut transform_result.exported_obj, &hmr_id);
+    }
 
     // Codegen
-    let mut ctx = CodegenContext::with_bindings_helper(transform_result.bindings_helper);
+    let mut codegen_ctx = CodegenContext::with_bindings_helper(transform_result.bindings_helper);
+    codegen_ctx.is_ssr = is_ssr;
+    let has_scoped_style = transform_result
+        .style_blocks
+        .iter()
+        .any(|block| block.is_scoped);
+    // Hoisted vnodes are created outside of `render`, where `__scopeId` is not applied
+    codegen_ctx.hoist_static = !is_ssr && !has_scoped_style;
+    if is_ssr && has_scoped_style {
+        codegen_ctx.scope_id = Some(create_style_scope(&file_hash).into());
+    }
 
     let template_expr: Option<Expr> = transform_result
         .template_block
-        .and_then(|template_block| ctx.generate_sfc_template(&template_block));
+        .and_then(|template_block| codegen_ctx.generate_sfc_template(&template_block));
 
-    let sfc_module = ctx.generate_module(
+    let sfc_module = codegen_ctx.generate_module(
         template_expr,
         *transform_result.module,
         transform_result.exported_obj,
         transform_result.setup_fn,
         options.gen_default_as.as_deref(),
     );
+    ctx.imports.extend(
+        sfc_module
+            .body
+            .iter()
+            .filter_map(|module_item| module_item.as_module_decl()?.as_import().cloned()),
+    );
 
     // Convert AST to string
-    let (code, source_map) = CodegenContext::stringify(
+    let pure_annotations = codegen_ctx.generate_pure_annotations();
+    ctx.scope = std::mem::take(&mut codegen_ctx.bindings_helper);
+    let (mut code, source_map) = CodegenContext::stringify(
         &source,
         &sfc_module,
         FileName::Custom(options.filename.to_string()),
+        Some(&pure_annotations),
         options.source_map.unwrap_or(false),
         false,
     );
 
+    // Let the plugins post-process the code
+    plugin_ctx.run(plugins, &mut code, |p, ctx, output| {
+        p.after_codegen(ctx, output)
+    });
+    report_errors(
+        plugin_ctx.take_errors(),
+        &mut all_errors,
+        &mut ctx.diagnostics,
+    );
+
     let styles = transform_result
         .style_blocks
         .into_iter()
@@ -206,12 +391,55 @@ pub fn compile(source: &str, options: CompileOptions) -> Result<CompileResult, C
         code,
         file_hash,
         errors: all_errors,
+        diagnostics: ctx.diagnostics.clone(),
         styles,
         other_assets,
         source_map,
     })
 }
 
+/// Adds the errors of a compilation stage both to the result and to the diagnostics
+fn report_errors<E: Into<CompileError>>(
+    errors: Vec<E>,
+    all_errors: &mut Vec<CompileError>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for error in errors {
+        let error = error.into();
+        diagnostics.push(Diagnostic::from(&error));
+        all_errors.push(error);
+    }
+}
+
+/// Parses the import declarations of [`CompileOptions::prepend_imports`].
+/// Statements other than imports are ignored.
+fn parse_prepend_imports(
+    prepend_imports: &[Cow<str>],
+    all_errors: &mut Vec<CompileError>,
+) -> Vec<ImportDecl> {
+    let mut import_decls = Vec::with_capacity(prepend_imports.len());
+
+    for raw in prepend_imports.iter() {
+        let mut errors = Vec::new();
+        let mut parser = SfcParser::new(raw, &mut errors);
+        let span = Span::new(BytePos(0), BytePos(raw.len() as u32), Default::default());
+        let parsed = parser.parse_module(raw, Syntax::Typescript(Default::default()), span);
+
+        match parsed {
+            Ok(module) => import_decls.extend(
+                module
+                    .body
+                    .into_iter()
+                    .filter_map(|module_item| module_item.module_decl()?.import()),
+            ),
+            Err(e) => all_errors.push(e.into()),
+        }
+        all_errors.extend(errors.into_iter().map(From::from));
+    }
+
+    import_decls
+}
+
 /// Naive implementation of the SFC compilation, meaning that:
 /// - it handles the standard flow without plugins;
 /// - it compiles to `String` instead of SWC module;
@@ -242,6 +470,14 @@ pub fn compile_sync_naive(source: &str, is_prod: bool) -> Result<String, String>
     let mut transform_errors = Vec::new();
     let transform_options = TransformSfcOptions {
         is_prod,
+        is_compat: false,
+        reactivity_transform: false,
+        sanitize_href: false,
+        warn_deprecated_globals: true,
+        unroll_static_for: false,
+        whitespace: WhitespaceMode::Condense,
+        is_custom_element: None,
+        component_prop_types: Default::default(),
         scope_id: &file_hash,
         filename: "anonymous.vue".into(),
     };
@@ -249,6 +485,11 @@ pub fn compile_sync_naive(source: &str, is_prod: bool) -> Result<String, String>
 
     // Codegen
     let mut ctx = CodegenContext::with_bindings_helper(transform_result.bindings_helper);
+    // Hoisted vnodes are created outside of `render`, where `__scopeId` is not applied
+    ctx.hoist_static = !transform_result
+        .style_blocks
+        .iter()
+        .any(|block| block.is_scoped);
 
     let template_expr: Option<Expr> = transform_result
         .template_block
@@ -262,8 +503,606 @@ pub fn compile_sync_naive(source: &str, is_prod: bool) -> Result<String, String>
         None,
     );
 
-    let (compiled_code, _map) =
-        CodegenContext::stringify(&source, &sfc_module, FileName::Anon, false, false);
+    let pure_annotations = ctx.generate_pure_annotations();
+    let (compiled_code, _map) = CodegenContext::stringify(
+        &source,
+        &sfc_module,
+        FileName::Anon,
+        Some(&pure_annotations),
+        false,
+        false,
+    );
 
     Ok(compiled_code)
 }
+
+#[cfg(test)]
+mod tests {
+    use fervid_transform::error::{TemplateError, TemplateErrorKind, TransformError};
+
+    use super::*;
+    use crate::errors::DiagnosticSeverity;
+
+    fn compile_tsx(script: &str) -> CompileResult {
+        compile_tsx_with_imports(script, vec![])
+    }
+
+    fn compile_tsx_with_imports(
+        script: &str,
+        prepend_imports: Vec<Cow<'static, str>>,
+    ) -> CompileResult {
+        let source = format!("<script lang=\"tsx\">{script}</script>");
+        let options = CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            prepend_imports,
+            ..Default::default()
+        };
+
+        compile(&source, options).expect("Compilation must succeed")
+    }
+
+    fn compile_sfc(source: &str) -> CompileResult {
+        let options = CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            ..Default::default()
+        };
+
+        compile(source, options).expect("Compilation must succeed")
+    }
+
+    #[test]
+    fn it_reports_parser_errors_in_result() {
+        // Recoverable parser error, compilation continues
+        let source = "<template><div>first</div></template><template><div>second</div></template>";
+        let result = compile_sfc(source);
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(result.errors[0], CompileError::SfcParse(_)));
+        assert!(result.code.contains("first"));
+        assert!(!result.code.contains("second"));
+
+        // The same error is reported as a diagnostic pointing to the source
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+        assert!(diagnostic.span.0 <= diagnostic.span.1);
+        assert!(diagnostic.span.1 <= source.len());
+    }
+
+    #[test]
+    fn it_uses_script_bindings_in_template() {
+        // Imported component is used directly instead of being resolved
+        let mut ctx = CompileContext::new(CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            ..Default::default()
+        });
+        let result = compile_with_context(
+            "<script setup>import Foo from './Foo.vue'</script><template><Foo /></template>",
+            &mut ctx,
+        )
+        .expect("Compilation must succeed");
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("import Foo from './Foo.vue'"));
+        assert!(!result.code.contains("_resolveComponent"));
+
+        // The import collected by the script analysis is kept in the context
+        assert!(ctx
+            .scope
+            .user_imports
+            .contains_key(&FervidAtom::from("Foo")));
+        assert!(ctx
+            .imports
+            .iter()
+            .any(|import| &*import.src.value == "./Foo.vue"));
+        assert!(ctx.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn it_does_not_share_state_between_compilations() {
+        let mut ctx = CompileContext::new(CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            ..Default::default()
+        });
+        let mut compile_sfc = |source: &str| {
+            compile_with_context(source, &mut ctx).expect("Compilation must succeed")
+        };
+
+        let first = compile_sfc(
+            "<script setup>import Foo from './Foo.vue'</script><template><Foo /></template>",
+        );
+        assert!(!first.code.contains("_resolveComponent"));
+
+        // `Foo` is not known to the second SFC
+        let second = compile_sfc("<template><Foo /></template><template></template>");
+        assert!(second.code.contains("_resolveComponent(\"Foo\")"));
+        assert_eq!(second.errors.len(), 1);
+        assert_eq!(second.diagnostics.len(), 1);
+
+        // Errors of the second SFC do not leak into the next one
+        let third = compile_sfc("<template><Foo /></template>");
+        assert!(third.code.contains("_resolveComponent(\"Foo\")"));
+        assert!(third.errors.is_empty());
+        assert!(third.diagnostics.is_empty());
+
+        // The context only holds the state of the last compilation
+        assert!(ctx.diagnostics.is_empty());
+        assert!(ctx.scope.user_imports.is_empty());
+        assert!(!ctx
+            .imports
+            .iter()
+            .any(|import| &*import.src.value == "./Foo.vue"));
+    }
+
+    #[test]
+    fn it_skips_compilation_inside_v_pre() {
+        let result = compile_sfc(
+            "<template><div v-pre>{{ raw }}<span :title=\"raw\" @click=\"raw()\">{{ raw }}</span></div></template>",
+        );
+        assert!(result.errors.is_empty());
+
+        // Interpolations are kept as text, directives as attributes
+        let code = &result.code;
+        assert_eq!(code.matches("\"{{ raw }}\"").count(), 2);
+        assert!(code.contains("\":title\""));
+        assert!(code.contains("\"@click\""));
+        assert!(!code.contains("_toDisplayString"));
+        assert!(!code.contains("_ctx.raw"));
+    }
+
+    #[test]
+    fn it_compiles_teleport() {
+        // Static target
+        let result = compile_sfc(
+            "<template><Teleport to=\"#target\"><div>modal</div></Teleport></template>",
+        );
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("_Teleport"));
+        assert!(result.code.contains("\"#target\""));
+        assert!(!result.code.contains("\"to\""));
+        assert!(!result.code.contains("_withCtx"));
+
+        // Dynamic target is patched as a prop
+        let result = compile_sfc(
+            "<script setup>let portalTarget = '#target'</script><template><Teleport :to=\"portalTarget\" :disabled=\"false\"><div>modal</div></Teleport></template>",
+        );
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("_Teleport"));
+        assert!(result.code.contains("\"to\""));
+        assert!(!result.code.contains("_withCtx"));
+    }
+
+    #[test]
+    fn it_compiles_define_emits() {
+        let runtime = compile_sfc(
+            "<script setup>const emit = defineEmits(['update:modelValue', 'close'])</script>
+            <template><button @click=\"emit('close')\">close</button></template>",
+        );
+        let typed = compile_sfc(
+            "<script setup lang=\"ts\">
+            const emit = defineEmits<{
+                (e: 'update:modelValue', value: string): void
+                (e: 'close'): void
+            }>()
+            </script>
+            <template><button @click=\"emit('close')\">close</button></template>",
+        );
+
+        for result in [runtime, typed] {
+            assert!(result.errors.is_empty());
+
+            // Events are declared in the component options in the original order
+            let code = &result.code;
+            let emits_idx = code.find("emits: [").expect("`emits` must be generated");
+            let model_idx = code
+                .find("update:modelValue")
+                .expect("Event must be declared");
+            let close_idx = code[model_idx..]
+                .find("close")
+                .expect("Event must be declared");
+            assert!(emits_idx < model_idx);
+            assert!(!code[model_idx..model_idx + close_idx].contains(']'));
+
+            // `const emit = defineEmits()` is replaced with the setup context
+            assert!(!code.contains("defineEmits"));
+            assert!(code.contains("__emit"));
+        }
+    }
+
+    #[test]
+    fn it_compiles_define_options() {
+        let result = compile_sfc(
+            "<script setup>
+            defineOptions({ name: 'MyComp', inheritAttrs: false })
+            const msg = 'hello'
+            </script>
+            <template><div>{{ msg }}</div></template>",
+        );
+        assert!(result.errors.is_empty());
+
+        let code = &result.code;
+        assert!(code.contains("inheritAttrs: false"));
+        assert!(code.contains("name: 'MyComp'"));
+
+        // The options are a part of the component, not of `setup`
+        let setup_idx = code.find("setup(").expect("`setup` must be generated");
+        assert!(code.find("inheritAttrs").unwrap() < setup_idx);
+        assert!(!code.contains("defineOptions"));
+    }
+
+    #[test]
+    fn it_compiles_top_level_await() {
+        let result = compile_sfc(
+            "<script setup>
+            const data = await fetch('/api/data')
+            </script>
+            <template><div>{{ data }}</div></template>",
+        );
+        assert!(result.errors.is_empty());
+
+        let code = &result.code;
+        assert!(code.contains("async setup("));
+        assert!(code.contains("let __temp, __restore"));
+        assert!(code.contains("_withAsyncContext("));
+        assert!(code.contains("fetch('/api/data')"));
+        assert!(code.contains("__temp = await __temp"));
+    }
+
+    #[test]
+    fn it_compiles_css_modules() {
+        let result = compile_sfc(
+            "<template><div :class=\"[$style.red, classes.blue]\">hello</div></template>
+            <style module>.red { color: red }</style>
+            <style module=\"classes\">.blue { color: blue }</style>",
+        );
+        assert!(result.errors.is_empty());
+
+        let code = &result.code;
+        assert!(code.contains("_useCssModule(\"$style\").red"));
+        assert!(code.contains("_useCssModule(\"classes\").blue"));
+        assert!(!code.contains("_ctx.$style"));
+        assert!(!code.contains("_ctx.classes"));
+
+        // Modules are not declared in `setup`
+        assert!(!code.contains("setup("));
+    }
+
+    #[test]
+    fn it_compiles_css_modules_with_options_setup() {
+        let result = compile_sfc(
+            "<script>export default { setup() { const classes = 'own'; return { classes } } }</script>
+            <template><div :class=\"[$style.red, classes]\">hello</div></template>
+            <style module>.red { color: red }</style>
+            <style module=\"classes\">.blue { color: blue }</style>",
+        );
+        assert!(result.errors.is_empty());
+
+        // User `setup` is left as is
+        let code = &result.code;
+        assert_eq!(code.matches("setup(").count(), 1);
+        assert!(code.contains("_useCssModule(\"$style\").red"));
+
+        // Bindings returned from `setup` take precedence over the modules
+        assert!(!code.contains("_useCssModule(\"classes\")"));
+    }
+
+    #[test]
+    fn it_compiles_event_option_modifiers() {
+        let result = compile_sfc(
+            "<template><div @click.capture=\"fn\" @scroll.passive=\"fn\"></div></template>
+            <script setup>function fn() {}</script>",
+        );
+        assert!(result.errors.is_empty());
+
+        // Options are a part of the prop key, not of the runtime modifiers
+        let code = &result.code;
+        assert!(code.contains("onClickCapture: fn"));
+        assert!(code.contains("onScrollPassive: fn"));
+        assert!(!code.contains("_withModifiers"));
+    }
+
+    #[test]
+    fn it_compiles_v_bind_modifiers() {
+        let result = compile_sfc(
+            "<template><div :foo-bar.camel=\"x\" :text-content.prop=\"x\" :title.attr=\"x\"></div></template>
+            <script setup>const x = 1</script>",
+        );
+        assert!(result.errors.is_empty());
+
+        let code = &result.code;
+        assert!(code.contains("fooBar: x"));
+        assert!(code.contains("\".text-content\": x"));
+        assert!(code.contains("\"^title\": x"));
+    }
+
+    #[test]
+    fn it_compiles_named_slots() {
+        let compile_children = |children: &str| {
+            let source = format!(
+                "<template><Layout>{}</Layout></template>
+                <script setup>import Layout from './Layout.vue'</script>",
+                children
+            );
+            let result = compile_sfc(&source);
+            assert!(result.errors.is_empty());
+            result.code
+        };
+
+        // Only the named slots
+        let code = compile_children(
+            "<template #header=\"{ title }\">{{ title }}</template>
+            <template #footer>bottom</template>",
+        );
+        assert!(code.contains("header: _withCtx(("));
+        assert!(code.contains("footer: _withCtx(("));
+        assert!(!code.contains("\"default\""));
+        assert!(!code.contains("_ctx.title"));
+        assert!(code.contains("_: 1"));
+
+        // Only the default slot
+        let code = compile_children("<span>content</span>");
+        assert!(code.contains("\"default\": _withCtx(("));
+        assert!(!code.contains("header"));
+        assert!(code.contains("_: 1"));
+
+        // Both, children outside of `<template>` go to the default slot
+        let code = compile_children(
+            "<template #header>top</template>
+            <span>content</span>
+            <template #footer>bottom</template>",
+        );
+        assert!(code.contains("header: _withCtx("));
+        assert!(code.contains("\"default\": _withCtx("));
+        assert!(code.contains("footer: _withCtx("));
+        assert_eq!(code.matches("_: 1").count(), 1);
+    }
+
+    #[test]
+    fn it_compiles_scoped_slot_props() {
+        let compile_template = |template: &str| {
+            let source = format!(
+                "<template>{}</template>
+                <script setup>
+                import {{ ref }} from 'vue'
+                import List from './List.vue'
+                const item = ref(null)
+                </script>",
+                template
+            );
+            let result = compile_sfc(&source);
+            assert!(result.errors.is_empty());
+            result.code
+        };
+
+        // Slot props shadow the `setup` bindings and are not taken from `_ctx`
+        let code = compile_template(
+            "<List><template v-slot:default=\"{ item, index }\">{{ item.name }} {{ index }}</template></List>",
+        );
+        assert!(code.contains("item.name"));
+        assert!(!code.contains("_ctx.item"));
+        assert!(!code.contains("_ctx.index"));
+        assert!(!code.contains("item.value.name"));
+        assert!(!code.contains("_unref(item)"));
+
+        // Same for `v-slot` on the component itself
+        let code = compile_template("<List v-slot=\"{ item }\">{{ item.name }}</List>");
+        assert!(code.contains("item.name"));
+        assert!(!code.contains("_ctx.item"));
+        assert!(!code.contains("_unref(item)"));
+
+        // Outside of the slot, `item` is the `setup` ref again
+        let code = compile_template(
+            "<div><List v-slot=\"{ item }\">{{ item.name }}</List>{{ item }}</div>",
+        );
+        assert!(code.contains("_unref(item)") || code.contains("item.value"));
+    }
+
+    #[test]
+    fn it_compiles_dynamic_slot_names() {
+        let compile_slot = |v_slot: &str| {
+            let source = format!(
+                "<template><Comp><template {}>hello</template></Comp></template>
+                <script setup>import Comp from './Comp.vue'; const name = 'foo'</script>",
+                v_slot
+            );
+            let result = compile_sfc(&source);
+            assert!(result.errors.is_empty());
+            result.code
+        };
+
+        // Stable slots
+        let code = compile_slot("v-slot:default");
+        assert!(code.contains("\"default\": _withCtx"));
+        assert!(code.contains("_: 1"));
+
+        // Dynamic slots, also marked with the `DYNAMIC_SLOTS` patch flag
+        let code = compile_slot("v-slot:[name]");
+        assert!(code.contains("[name]: _withCtx"));
+        assert!(code.contains("_: 2"));
+        assert!(code.contains("1024"));
+    }
+
+    #[test]
+    fn it_compiles_multiple_roots() {
+        let result = compile_sfc("<template><div>first</div><div>{{ msg }}</div></template>");
+        assert!(result.errors.is_empty());
+
+        // Roots are wrapped in a `64 /* STABLE_FRAGMENT */` block
+        let code = &result.code;
+        assert!(code.contains("(_openBlock(), _createElementBlock(_Fragment, null, ["));
+        assert!(code.contains("], 64))"));
+        assert!(code.contains("Fragment as _Fragment"));
+    }
+
+    #[test]
+    fn it_hoists_static_elements_without_scoped_styles() {
+        let template = "<template><div><span class=\"a\">hello</span>{{ msg }}</div></template>";
+
+        let result = compile_sfc(&format!("{}<style>.a {{ color: red }}</style>", template));
+        assert!(result
+            .code
+            .contains("const _hoisted_1 = /*#__PURE__*/ _createElementVNode(\"span\""));
+
+        // Hoisted vnodes would not get the `data-v-*` attribute
+        let result = compile_sfc(&format!(
+            "{}<style scoped>.a {{ color: red }}</style>",
+            template
+        ));
+        assert!(!result.code.contains("_hoisted_"));
+        assert!(result.code.contains("_createElementVNode(\"span\""));
+        assert!(result.code.contains("__scopeId"));
+    }
+
+    #[test]
+    fn it_compiles_components_named_after_native_tags() {
+        let result = compile_sfc("<template><Button>ok</Button><button>ok</button></template>");
+        assert!(result.code.contains("_resolveComponent(\"Button\")"));
+        assert!(result.code.contains("_createElementVNode(\"button\""));
+    }
+
+    #[test]
+    fn it_compiles_self_references() {
+        // Compiled as `Component.vue`
+        let result = compile_sfc("<template><Component /><Other /></template>");
+        let code = &result.code;
+        assert!(code.contains("_resolveComponent(\"Component\", true)"));
+        assert!(code.contains("_resolveComponent(\"Other\")"));
+    }
+
+    #[test]
+    fn it_checks_external_component_prop_types() {
+        let source = "<template><Counter :count=\"'five'\" :step=\"1\" /></template>";
+        let mut counter_props = std::collections::HashMap::default();
+        counter_props.insert(
+            FervidAtom::from("count"),
+            PropTypes::from_slice(&[PropType::Number]),
+        );
+        counter_props.insert(
+            FervidAtom::from("step"),
+            PropTypes::from_slice(&[PropType::Number]),
+        );
+        let mut component_prop_types = ComponentPropTypes::default();
+        component_prop_types.insert(FervidAtom::from("Counter"), counter_props);
+
+        let options = CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            component_prop_types,
+            ..Default::default()
+        };
+
+        let result = compile(source, options).expect("Compilation must succeed");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            &result.errors[0],
+            CompileError::TransformError(TransformError::TemplateError(TemplateError {
+                kind: TemplateErrorKind::ComponentPropTypeMismatch,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn it_compiles_custom_elements() {
+        let source = "<template><div><my-widget :value=\"msg\"></my-widget></div></template>";
+        let options = |is_custom_element| CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            is_custom_element,
+            ..Default::default()
+        };
+
+        // Unknown tags are components by default
+        let result = compile(source, options(None)).expect("Compilation must succeed");
+        assert!(result.code.contains("_resolveComponent(\"my-widget\")"));
+
+        // Matching tags are plain elements
+        let is_custom_element = CustomElementPredicate::new(|tag| tag.starts_with("my-"));
+        let result =
+            compile(source, options(Some(is_custom_element))).expect("Compilation must succeed");
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("_createElementVNode(\"my-widget\""));
+        assert!(!result.code.contains("resolveComponent"));
+    }
+
+    #[test]
+    fn it_compiles_tsx_render_function() {
+        // Typed props, JSX is left for the bundler
+        let result = compile_tsx(
+            "export default defineComponent({
+                props: { msg: String },
+                setup(props: { msg: string }) {
+                    return () => <div class=\"msg\">{props.msg}</div>
+                }
+            })",
+        );
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("_defineComponent("));
+        assert!(result.code.contains("<div class=\"msg\">{props.msg}</div>"));
+
+        // Render function without output
+        let result = compile_tsx(
+            "export default defineComponent({
+                setup() {
+                    return () => null
+                }
+            })",
+        );
+        assert!(result.errors.is_empty());
+        assert!(result.code.contains("_defineComponent("));
+        assert!(result.code.contains("null"));
+    }
+
+    #[test]
+    fn it_prepends_imports() {
+        let result = compile_tsx_with_imports(
+            "import { useRoute } from './my-router'
+            export default defineComponent({
+                setup() {
+                    return { router: useRouter(), route: useRoute() }
+                }
+            })",
+            vec![
+                Cow::Borrowed("import { useRouter, useRoute } from 'vue-router'"),
+                Cow::Borrowed("import { useI18n } from 'vue-i18n'"),
+            ],
+        );
+        assert!(result.errors.is_empty());
+
+        // `useRoute` is already imported by the user
+        let code = &result.code;
+        assert!(code.contains("import { useRouter } from 'vue-router'"));
+        assert!(code.contains("import { useI18n } from 'vue-i18n'"));
+        assert!(code.contains("import { useRoute } from './my-router'"));
+
+        // Invalid import
+        let result = compile_tsx_with_imports(
+            "export default defineComponent({})",
+            vec![Cow::Borrowed("import { from 'vue-router'")],
+        );
+        assert!(!result.errors.is_empty());
+    }
+
+    #[test]
+    fn it_generates_source_map() {
+        let source = "<script>export default { data: () => ({ msg: 'hi' }) }</script>";
+        let options = |source_map| CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            source_map,
+            ..Default::default()
+        };
+
+        let result = compile(source, options(None)).expect("Compilation must succeed");
+        assert!(result.source_map.is_none());
+
+        let result = compile(source, options(Some(true))).expect("Compilation must succeed");
+        let map = result.source_map.expect("Source map must be generated");
+        assert!(map.contains("\"sources\":[\"Component.vue\"]"));
+        assert!(map.contains("msg: 'hi'"));
+        assert!(map.contains("\"mappings\":\""));
+        assert!(!map.contains("\"mappings\":\"\""));
+    }
+}
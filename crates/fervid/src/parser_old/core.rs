@@ -13,6 +13,7 @@ use swc_core::ecma::ast::Module;
 use super::attributes::parse_attributes;
 use super::ecma::{parse_js, parse_js_module};
 use super::html_utils::{classify_element_kind, html_name, space0, TagKind};
+use crate::errors::{Diagnostic, DiagnosticSeverity};
 use fervid_core::{
     fervid_atom, AttributeOrBinding, ElementKind, ElementNode, FervidAtom, Interpolation, Node,
     SfcCustomBlock, SfcDescriptor, SfcScriptBlock, SfcScriptLang, SfcStyleBlock, SfcTemplateBlock,
@@ -25,16 +26,28 @@ use fervid_core::{
 /// - the `.0` element is the remaining input. It should be any trailing whitespace if parsing succeeded;
 /// - the `.1` element is a vector of root blocks, i.e. all `<script>`, `<template>`, `<style>` and custom blocks.
 ///
+/// Diagnostics which do not stop the parsing (e.g. mismatched end tags) are returned alongside.
+///
 /// This function does not modify whitespace inside the blocks.
 ///
 /// To optimize template node, use the `fervid_transform` crate
-pub fn parse_sfc(mut input: &str) -> IResult<&str, SfcDescriptor> {
+pub fn parse_sfc(source: &str) -> (IResult<&str, SfcDescriptor>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let result = parse_sfc_blocks(source, &mut diagnostics);
+    (result, diagnostics)
+}
+
+fn parse_sfc_blocks<'a>(
+    source: &'a str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> IResult<&'a str, SfcDescriptor> {
     let mut result = SfcDescriptor::default();
+    let mut input = source;
 
     // Adapted from Nom's `many0`
     loop {
         let len = input.len();
-        match parse_root_block(input, &mut result) {
+        match parse_root_block(input, source, &mut result, diagnostics) {
             Err(nom::Err::Error(_)) => return Ok((input, result)),
             Err(e) => return Err(e),
             Ok(new_input) => {
@@ -54,13 +67,16 @@ pub fn parse_sfc(mut input: &str) -> IResult<&str, SfcDescriptor> {
 
 fn parse_root_block<'a>(
     input: &'a str,
+    source: &str,
     out: &mut SfcDescriptor,
+    diagnostics: &mut Vec<Diagnostic>,
 ) -> Result<&'a str, nom::Err<nom::error::Error<&'a str>>> {
     // Remove leading space
     let input = input.trim_start();
 
     // Read starting tag
-    let (input, (starting_tag, is_self_closing)) = parse_element_starting_tag(input)?;
+    let (input, (starting_tag, is_self_closing)) =
+        parse_element_starting_tag(input, source, diagnostics)?;
 
     // Mutually exclusive flags
     let is_script = starting_tag.tag_name == fervid_atom!("script");
@@ -131,7 +147,7 @@ fn parse_root_block<'a>(
         }
 
         // Parse children, this may Err (and is not handled yet)
-        let (input, children) = parse_node_children(input)?;
+        let (input, children) = parse_node_children(input, source, diagnostics)?;
 
         // End tag as well. No checks are present that it is the same
         // In the future, this checks may be implemented
@@ -221,11 +237,15 @@ fn parse_root_block<'a>(
             AttributeOrBinding::RegularAttribute { name, .. } if name.eq("scoped")
         )
     });
-    let is_module = starting_tag.attributes.iter().any(|attr| {
-        matches!(
-            attr,
-            AttributeOrBinding::RegularAttribute { name, .. } if name.eq("module")
-        )
+    let module_name = starting_tag.attributes.iter().find_map(|attr| match attr {
+        AttributeOrBinding::RegularAttribute { name, value, .. } if name.eq("module") => {
+            Some(if value.is_empty() {
+                FervidAtom::from("$style")
+            } else {
+                value.to_owned()
+            })
+        }
+        _ => None,
     });
 
     // Check self-closing, ignore such styles
@@ -241,14 +261,21 @@ fn parse_root_block<'a>(
         lang: lang.into(),
         content: content.into(),
         is_scoped,
-        is_module,
+        module_name,
+        vars: None,
         span: DUMMY_SP
     });
 
     Ok(input)
 }
 
-fn parse_element_starting_tag(input: &str) -> IResult<&str, (StartingTag, bool)> {
+#[cfg_attr(not(dbg_print), allow(unused_variables))]
+fn parse_element_starting_tag<'a>(
+    input: &'a str,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> IResult<&'a str, (StartingTag, bool)> {
+    let start = input;
     let (input, (_, tag_name, (attributes, directives), _, ending_bracket)) = tuple((
         tag("<"),
         html_name,
@@ -258,10 +285,11 @@ fn parse_element_starting_tag(input: &str) -> IResult<&str, (StartingTag, bool)>
     ))(input)?;
 
     #[cfg(dbg_print)]
-    {
-        println!("Tag name: {:?}", tag_name);
-        println!("Attributes: {:?}", attributes);
-    }
+    diagnostics.push(Diagnostic {
+        span: span_between(source, start, input),
+        severity: DiagnosticSeverity::Hint,
+        message: format!("Tag name: {:?}, attributes: {:?}", tag_name, attributes),
+    });
 
     Ok((
         input,
@@ -305,8 +333,13 @@ fn parse_interpolation_node(input: &str) -> IResult<&str, Node> {
 // todo implement different processing ways:
 // 1: parse node start and then recursively parse children
 // 2: parse node start and seek the ending tag
-pub fn parse_element_node(input: &str) -> IResult<&str, Node> {
-    let (input, (starting_tag, is_self_closing)) = parse_element_starting_tag(input)?;
+pub fn parse_element_node<'a>(
+    input: &'a str,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> IResult<&'a str, Node> {
+    let (input, (starting_tag, is_self_closing)) =
+        parse_element_starting_tag(input, source, diagnostics)?;
 
     let element_kind = classify_element_kind(&starting_tag.tag_name);
 
@@ -326,18 +359,22 @@ pub fn parse_element_node(input: &str) -> IResult<&str, Node> {
         ));
     }
 
-    let (input, children) = parse_node_children(input)?;
+    let (input, children) = parse_node_children(input, source, diagnostics)?;
 
     // parse end tag
+    let end_tag_start = input;
     let (input, end_tag) = parse_element_end_tag(input)?;
 
     // todo pass a stack of elements instead of a single tag
-    // todo handle the error? soft/hard error -> either return Err or proceed and warn
     if !starting_tag.tag_name.eq(end_tag) {
-        println!(
-            "End tag does not match start tag: <{}> </{}>",
-            &starting_tag.tag_name, &end_tag
-        );
+        diagnostics.push(Diagnostic {
+            span: span_between(source, end_tag_start, input),
+            severity: DiagnosticSeverity::Warning,
+            message: format!(
+                "End tag does not match start tag: <{}> </{}>",
+                &starting_tag.tag_name, &end_tag
+            ),
+        });
     }
 
     Ok((
@@ -389,11 +426,42 @@ fn parse_comment_node(input: &str) -> IResult<&str, Node> {
     Ok((input, Node::Comment(comment.into(), DUMMY_SP)))
 }
 
-fn parse_node_children(input: &str) -> IResult<&str, Vec<Node>> {
+fn parse_node_children<'a>(
+    input: &'a str,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> IResult<&'a str, Vec<Node>> {
     many0(alt((
         parse_interpolation_node,
         parse_comment_node,
-        parse_element_node,
+        |input| parse_element_node(input, source, diagnostics),
         parse_text_node,
     )))(input)
 }
+
+/// Byte offsets of the `start` and `end` remainders of the `source`
+fn span_between(source: &str, start: &str, end: &str) -> (usize, usize) {
+    (source.len() - start.len(), source.len() - end.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_mismatched_end_tags() {
+        let input = "<template><div><p>hello</span></div></template>";
+        let (result, diagnostics) = parse_sfc(input);
+        assert!(result.is_ok());
+
+        let end_tag_start = input.find("</span>").unwrap();
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                span: (end_tag_start, end_tag_start + "</span>".len()),
+                severity: DiagnosticSeverity::Warning,
+                message: "End tag does not match start tag: <p> </span>".to_owned(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Plugin API for hooking into the SFC compilation pipeline.
+//!
+//! Plugins are called in the order they are specified in [`crate::CompileOptions::plugins`].
+//! Each hook has a default no-op implementation, so that a plugin only implements what it needs.
+
+use fervid_core::{SfcScriptBlock, SfcStyleBlock, SfcTemplateBlock};
+use std::sync::Arc;
+use swc_core::common::Span;
+
+/// A hook into the compilation of each SFC block.
+///
+/// ## Example
+/// ```
+/// use fervid::plugins::{PluginContext, SfcPlugin};
+///
+/// struct Banner;
+///
+/// impl SfcPlugin for Banner {
+///     fn name(&self) -> &str {
+///         "banner"
+///     }
+///
+///     fn after_codegen(&self, ctx: &mut PluginContext, output: &mut String) {
+///         output.push_str(&format!("\n// Compiled from {}", ctx.filename));
+///     }
+/// }
+/// ```
+pub trait SfcPlugin {
+    /// Name of the plugin, used when reporting its errors
+    fn name(&self) -> &str;
+
+    /// Identifies the behaviour of the plugin for [`CompileCache`](crate::cache::CompileCache),
+    /// e.g. a hash of its configuration.
+    /// Compilations using a plugin without a key are never cached.
+    fn cache_key(&self) -> Option<u64> {
+        None
+    }
+
+    /// Called for `<template>` before it is transformed
+    fn before_template(&self, _ctx: &mut PluginContext, _block: &mut SfcTemplateBlock) {}
+
+    /// Called for `<script>` and `<script setup>` before they are transformed
+    fn transform_script(&self, _ctx: &mut PluginContext, _block: &mut SfcScriptBlock) {}
+
+    /// Called for each `<style>` before it is transformed (e.g. before scoping)
+    fn transform_style(&self, _ctx: &mut PluginContext, _block: &mut SfcStyleBlock) {}
+
+    /// Called with the generated module code
+    fn after_codegen(&self, _ctx: &mut PluginContext, _output: &mut String) {}
+}
+
+impl std::fmt::Debug for dyn SfcPlugin + Send + Sync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SfcPlugin({})", self.name())
+    }
+}
+
+/// Compiles the `<style>` blocks with the given `lang` to CSS, e.g. using a Sass compiler.
+/// The resulting CSS is then handled as `<style lang="css">`, including scoping.
+///
+/// Errors returned by the preprocessor are reported as [`PluginError`]s,
+/// and the block is left as is.
+///
+/// The preprocessor is opaque to [`CompileCache`](crate::cache::CompileCache),
+/// use [`StylePreprocessor::with_cache_key`] to let it cache the results.
+///
+/// ## Example
+/// ```
+/// use fervid::plugins::StylePreprocessor;
+///
+/// let sass = StylePreprocessor::new("sass", |source| {
+///     // Call the Sass compiler here
+///     Ok(source.to_owned())
+/// })
+/// .with_cache_key(1);
+/// ```
+pub struct StylePreprocessor<F>
+where
+    F: Fn(&str) -> Result<String, String>,
+{
+    lang: String,
+    name: String,
+    preprocess: F,
+    cache_key: Option<u64>,
+}
+
+impl<F> StylePreprocessor<F>
+where
+    F: Fn(&str) -> Result<String, String>,
+{
+    pub fn new(lang: impl Into<String>, preprocess: F) -> Self {
+        let lang = lang.into();
+        let name = format!("{}-preprocessor", lang);
+
+        StylePreprocessor {
+            lang,
+            name,
+            preprocess,
+            cache_key: None,
+        }
+    }
+
+    /// Identifies the preprocessor configuration, see [`SfcPlugin::cache_key`]
+    pub fn with_cache_key(mut self, cache_key: u64) -> Self {
+        self.cache_key = Some(cache_key);
+        self
+    }
+}
+
+impl<F> SfcPlugin for StylePreprocessor<F>
+where
+    F: Fn(&str) -> Result<String, String>,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn cache_key(&self) -> Option<u64> {
+        self.cache_key
+    }
+
+    fn transform_style(&self, ctx: &mut PluginContext, block: &mut SfcStyleBlock) {
+        if *block.lang != *self.lang {
+            return;
+        }
+
+        match (self.preprocess)(&block.content) {
+            Ok(css) => {
+                block.content = css.into();
+                block.lang = "css".into();
+            }
+            Err(message) => ctx.report_error(block.span, message),
+        }
+    }
+}
+
+/// Information about the compiled SFC available to plugins
+pub struct PluginContext<'c> {
+    pub filename: &'c str,
+    pub is_prod: bool,
+    pub is_ssr: bool,
+    plugin_name: String,
+    errors: Vec<PluginError>,
+}
+
+#[derive(Debug)]
+pub struct PluginError {
+    /// Name of the plugin which reported the error
+    pub plugin_name: String,
+    pub message: String,
+    pub span: Span,
+}
+
+impl<'c> PluginContext<'c> {
+    pub fn new(filename: &'c str, is_prod: bool, is_ssr: bool) -> Self {
+        PluginContext {
+            filename,
+            is_prod,
+            is_ssr,
+            plugin_name: String::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Reports an error on behalf of the currently running plugin.
+    /// The compilation continues, and the error ends up in [`crate::CompileResult::errors`].
+    pub fn report_error(&mut self, span: Span, message: impl Into<String>) {
+        self.errors.push(PluginError {
+            plugin_name: self.plugin_name.to_owned(),
+            message: message.into(),
+            span,
+        });
+    }
+
+    /// Calls `hook` for each of the `plugins`, keeping track of which plugin is running
+    pub(crate) fn run<T: ?Sized>(
+        &mut self,
+        plugins: &[Arc<dyn SfcPlugin + Send + Sync>],
+        target: &mut T,
+        hook: impl Fn(&dyn SfcPlugin, &mut Self, &mut T),
+    ) {
+        for plugin in plugins.iter() {
+            self.plugin_name = plugin.name().to_owned();
+            hook(plugin.as_ref(), self, target);
+        }
+    }
+
+    pub(crate) fn take_errors(&mut self) -> Vec<PluginError> {
+        std::mem::take(&mut self.errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use fervid_core::Node;
+
+    use crate::{compile, errors::CompileError, CompileOptions};
+
+    use super::*;
+
+    fn compile_with(
+        source: &str,
+        plugins: Vec<Arc<dyn SfcPlugin + Send + Sync>>,
+    ) -> crate::CompileResult {
+        let options = CompileOptions {
+            filename: Cow::Borrowed("Component.vue"),
+            is_prod: Some(true),
+            plugins,
+            ..Default::default()
+        };
+
+        compile(source, options).expect("Compilation must succeed")
+    }
+
+    struct Banner;
+
+    impl SfcPlugin for Banner {
+        fn name(&self) -> &str {
+            "banner"
+        }
+
+        fn after_codegen(&self, ctx: &mut PluginContext, output: &mut String) {
+            output.push_str(&format!("\n// Compiled from {}", ctx.filename));
+        }
+    }
+
+    struct Translate;
+
+    impl SfcPlugin for Translate {
+        fn name(&self) -> &str {
+            "translate"
+        }
+
+        fn before_template(&self, _ctx: &mut PluginContext, block: &mut SfcTemplateBlock) {
+            fn translate(nodes: &mut [Node]) {
+                for node in nodes.iter_mut() {
+                    match node {
+                        Node::Text(text, _) if text.trim() == "hello" => {
+                            *text = "bonjour".into();
+                        }
+                        Node::Element(element) => translate(&mut element.children),
+                        _ => {}
+                    }
+                }
+            }
+
+            translate(&mut block.roots);
+        }
+    }
+
+    struct Failing;
+
+    impl SfcPlugin for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn transform_style(&self, ctx: &mut PluginContext, block: &mut SfcStyleBlock) {
+            if block.lang != "css" {
+                ctx.report_error(block.span, "Only CSS is supported");
+            }
+        }
+    }
+
+    #[test]
+    fn it_appends_a_comment() {
+        let result = compile_with(
+            "<template><div>hello</div></template>",
+            vec![Arc::new(Banner)],
+        );
+
+        assert!(result.code.ends_with("\n// Compiled from Component.vue"));
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn it_modifies_template_nodes() {
+        let source = "<template><div><span>hello</span></div></template>";
+
+        let result = compile_with(source, vec![]);
+        assert!(result.code.contains("hello"));
+
+        let result = compile_with(source, vec![Arc::new(Translate)]);
+        assert!(result.code.contains("bonjour"));
+        assert!(!result.code.contains("hello"));
+    }
+
+    #[test]
+    fn it_reports_plugin_errors() {
+        let result = compile_with(
+            "<template><div>hello</div></template><style lang=\"scss\">.a { .b {} }</style>",
+            vec![Arc::new(Failing), Arc::new(Banner)],
+        );
+
+        // Errors do not stop the compilation
+        assert!(result.code.ends_with("\n// Compiled from Component.vue"));
+
+        assert_eq!(result.errors.len(), 1);
+        let CompileError::Plugin(ref plugin_error) = result.errors[0] else {
+            panic!("Not a plugin error");
+        };
+        assert_eq!(plugin_error.plugin_name, "failing");
+        assert_eq!(plugin_error.message, "Only CSS is supported");
+    }
+
+    #[test]
+    fn it_preprocesses_styles() {
+        let sass = || {
+            StylePreprocessor::new("sass", |source| {
+                assert_eq!(source.trim(), ".a\n  color: red");
+                Ok(String::from(".a { color: red }"))
+            })
+        };
+        let source = "<template><div class=\"a\">hello</div></template>\n<style scoped lang=\"sass\">\n.a\n  color: red\n</style>";
+
+        let result = compile_with(source, vec![Arc::new(sass())]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.styles.len(), 1);
+        assert_eq!(result.styles[0].lang, "css");
+        assert!(result.styles[0].is_compiled);
+        assert!(result.styles[0].code.contains("[data-v-"));
+
+        // Without the preprocessor, the block is left for the bundler
+        let result = compile_with(source, vec![]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.styles[0].lang, "sass");
+        assert!(!result.styles[0].is_compiled);
+
+        // Other languages are not preprocessed
+        let result = compile_with(
+            "<template><div>hello</div></template><style>.a { color: red }</style>",
+            vec![Arc::new(sass())],
+        );
+        assert!(result.errors.is_empty());
+        assert_eq!(result.styles[0].code, ".a { color: red }");
+    }
+
+    #[test]
+    fn it_reports_preprocessor_errors() {
+        let failing_sass =
+            StylePreprocessor::new("sass", |_| Err(String::from("Undefined variable")));
+
+        let result = compile_with(
+            "<template><div>hello</div></template><style lang=\"sass\">.a\n  color: $red</style>",
+            vec![Arc::new(failing_sass)],
+        );
+
+        assert_eq!(result.errors.len(), 1);
+        let CompileError::Plugin(ref plugin_error) = result.errors[0] else {
+            panic!("Not a plugin error");
+        };
+        assert_eq!(plugin_error.plugin_name, "sass-preprocessor");
+        assert_eq!(plugin_error.message, "Undefined variable");
+
+        // The block is left as is
+        assert_eq!(result.styles[0].lang, "sass");
+        assert!(!result.styles[0].is_compiled);
+    }
+}
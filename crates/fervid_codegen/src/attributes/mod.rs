@@ -7,12 +7,15 @@ use swc_core::{
     common::{Span, Spanned, DUMMY_SP},
     ecma::ast::{
         ArrayLit, ArrowExpr, BinExpr, BinaryOp, BlockStmt, BlockStmtOrExpr, CallExpr, Callee,
-        ComputedPropName, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ObjectLit, Prop, PropName,
-        PropOrSpread, Str,
+        ComputedPropName, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ObjectLit, ParenExpr, Prop,
+        PropName, PropOrSpread, SpreadElement, Str,
     },
 };
 
-use crate::{context::CodegenContext, utils::str_to_propname};
+use crate::{
+    context::CodegenContext,
+    utils::{capitalize, str_to_propname},
+};
 
 lazy_static! {
     static ref CSS_RE: Regex =
@@ -135,12 +138,24 @@ impl CodegenContext {
                     result_hints.v_on_no_event = Some(v_on);
                 }
 
+                // `:[null]` and `@[null]` never bind anything, so they are omitted
+                AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Expr(argument)),
+                    ..
+                })
+                | AttributeOrBinding::VOn(VOnDirective {
+                    event: Some(StrOrExpr::Expr(argument)),
+                    ..
+                }) if is_null_or_undefined(argument) => {}
+
                 // `v-bind` directive, shortcut `:`, e.g. `:custom-prop="value"`
                 AttributeOrBinding::VBind(VBindDirective {
                     argument: Some(argument),
                     value,
+                    is_camel,
+                    is_prop,
+                    is_attr,
                     span,
-                    ..
                 }) => {
                     // Transform the raw expression
                     // let was_transformed =
@@ -152,26 +167,82 @@ impl CodegenContext {
                     result_hints.props_patch_flag =
                         result_hints.props_patch_flag || was_transformed;
 
+                    // `.prop` and `.attr` tell the runtime how to set the binding,
+                    // SSR renders all of them as attributes
+                    let prefix = match (*is_prop, *is_attr) {
+                        _ if self.is_ssr => None,
+                        (true, _) => Some("."),
+                        (_, true) => Some("^"),
+                        _ => None,
+                    };
+
+                    // Static `.camel` arguments are already camelized by the transform
                     let key = match argument {
-                        StrOrExpr::Str(s) => str_to_propname(s, span),
+                        StrOrExpr::Str(s) => match prefix {
+                            Some(prefix) => str_to_propname(&format!("{}{}", prefix, s), span),
+                            None => str_to_propname(s, span),
+                        },
                         StrOrExpr::Expr(expr) => {
                             // Dynamic prop needs a `_normalizeProps` call
                             // TODO Take from patch flags?
                             result_hints.needs_normalize_props = true;
 
-                            // `[key_transformed || ""]`
-                            PropName::Computed(ComputedPropName {
+                            // `[key_transformed || ""]`,
+                            // lower precedence keys are wrapped, e.g. `[(a ? b : c) || ""]`
+                            let left = match **expr {
+                                Expr::Arrow(_)
+                                | Expr::Assign(_)
+                                | Expr::Bin(_)
+                                | Expr::Cond(_)
+                                | Expr::Seq(_) => Box::new(Expr::Paren(ParenExpr {
+                                    span,
+                                    expr: expr.to_owned(),
+                                })),
+                                _ => expr.to_owned(),
+                            };
+                            let mut key_expr = Box::from(Expr::Bin(BinExpr {
                                 span,
-                                expr: Box::from(Expr::Bin(BinExpr {
+                                op: BinaryOp::LogicalOr,
+                                left,
+                                right: Box::from(Expr::Lit(Lit::Str(Str {
+                                    span,
+                                    value: FervidAtom::from(""),
+                                    raw: None,
+                                }))),
+                            }));
+
+                            // `_camelize(key || "")`
+                            if *is_camel {
+                                key_expr = Box::new(self.call_vue_import(
+                                    VueImports::Camelize,
+                                    vec![key_expr],
                                     span,
-                                    op: BinaryOp::LogicalOr,
-                                    left: expr.to_owned(), // ?
-                                    right: Box::from(Expr::Lit(Lit::Str(Str {
+                                ));
+                            }
+
+                            // `"." + (key || "")`
+                            if let Some(prefix) = prefix {
+                                if matches!(*key_expr, Expr::Bin(_)) {
+                                    key_expr = Box::new(Expr::Paren(ParenExpr {
                                         span,
-                                        value: FervidAtom::from(""),
+                                        expr: key_expr,
+                                    }));
+                                }
+                                key_expr = Box::new(Expr::Bin(BinExpr {
+                                    span,
+                                    op: BinaryOp::Add,
+                                    left: Box::new(Expr::Lit(Lit::Str(Str {
+                                        span,
+                                        value: prefix.into(),
                                         raw: None,
                                     }))),
-                                })),
+                                    right: key_expr,
+                                }));
+                            }
+
+                            PropName::Computed(ComputedPropName {
+                                span,
+                                expr: key_expr,
                             })
                         }
                     };
@@ -201,52 +272,36 @@ impl CodegenContext {
                         .to_owned()
                         .unwrap_or_else(|| Box::new(empty_arrow_expr(span)));
 
-                    // To generate as an array of `["modifier1", "modifier2"]`
-                    let modifiers: Vec<Option<ExprOrSpread>> = modifiers
-                        .iter()
-                        .map(|modifier| {
-                            Some(ExprOrSpread {
-                                spread: None,
-                                expr: Box::from(Expr::Lit(Lit::Str(Str {
-                                    span,
-                                    value: modifier.to_owned(),
-                                    raw: None,
-                                }))),
-                            })
-                        })
-                        .collect();
-
-                    let handler_expr = if modifiers.len() != 0 {
-                        let with_modifiers_import =
-                            self.get_and_add_import_ident(VueImports::WithModifiers);
-
-                        // `_withModifiers(transformed, ["modifier"]))`
-                        Box::new(Expr::Call(CallExpr {
+                    // Guards are checked at runtime, event options become a part of the event name
+                    let modifiers = resolve_v_on_modifiers(event, modifiers);
+                    let mut handler_expr = handler;
+                    if !modifiers.non_key.is_empty() {
+                        // `_withModifiers(handler, ["stop"])`
+                        handler_expr = Box::new(self.call_vue_import(
+                            VueImports::WithModifiers,
+                            vec![handler_expr, modifiers_array(&modifiers.non_key, span)],
                             span,
-                            callee: Callee::Expr(Box::from(Expr::Ident(Ident {
-                                span,
-                                sym: with_modifiers_import,
-                                optional: false,
-                            }))),
-                            args: vec![
-                                ExprOrSpread {
-                                    expr: handler,
-                                    spread: None,
-                                },
-                                ExprOrSpread {
-                                    expr: Box::from(Expr::Array(ArrayLit {
-                                        span,
-                                        elems: modifiers,
-                                    })),
-                                    spread: None,
-                                },
-                            ],
-                            type_args: None,
-                        }))
-                    } else {
-                        // No modifiers, leave expression the same
-                        handler
+                        ));
+                    }
+                    let is_keyboard_event = match event {
+                        StrOrExpr::Str(event_name) => is_keyboard_event(event_name),
+                        StrOrExpr::Expr(_) => true,
                     };
+                    if !modifiers.key.is_empty() && is_keyboard_event {
+                        // `_withKeys(handler, ["enter"])`
+                        handler_expr = Box::new(self.call_vue_import(
+                            VueImports::WithKeys,
+                            vec![handler_expr, modifiers_array(&modifiers.key, span)],
+                            span,
+                        ));
+                    }
+
+                    // e.g. `Once` for `@click.once`, which makes `onClickOnce`
+                    let options_postfix: String = modifiers
+                        .event_options
+                        .iter()
+                        .map(|option| capitalize(option))
+                        .collect();
 
                     // TODO Cache
 
@@ -268,21 +323,58 @@ impl CodegenContext {
 
                     match event {
                         StrOrExpr::Str(event_name_str) => {
+                            // `click.right` and `click.middle` do not fire `click`
+                            let mut event_name = event_name_str.to_string();
+                            if event_name == "onClick" {
+                                if modifiers.non_key.iter().any(|m| m == "right") {
+                                    event_name = String::from("onContextmenu");
+                                } else if modifiers.non_key.iter().any(|m| m == "middle") {
+                                    event_name = String::from("onMouseup");
+                                }
+                            }
+                            event_name.push_str(&options_postfix);
+
                             // e.g. `onClick: _ctx.handleClick` or `onClick: _withModifiers(() => {}, ["stop"])
                             out.push(PropOrSpread::Prop(Box::from(Prop::KeyValue(
                                 KeyValueProp {
-                                    key: str_to_propname(&event_name_str, span),
+                                    key: str_to_propname(&event_name, span),
                                     value: handler_expr,
                                 },
                             ))));
                         }
 
-                        // TODO Instead of pushing to `out`, signify that `mergeProps` and `toHandlerKey` are needed
+                        // `@[[dynamic, 'hover']]` is bound at runtime,
+                        // e.g. `..._toHandlers({ [_ctx.dynamic]: handler, hover: handler })`
+                        StrOrExpr::Expr(event_name_expr)
+                            if matches!(**event_name_expr, Expr::Array(_)) =>
+                        {
+                            let Expr::Array(ref events) = **event_name_expr else {
+                                unreachable!()
+                            };
+                            out.push(self.generate_event_array_handlers(
+                                events,
+                                handler_expr,
+                                &options_postfix,
+                                span,
+                            ));
+                        }
+
+                        // `@[event]` is `[_toHandlerKey(event)]`, e.g. `click` becomes `onClick` at runtime
                         StrOrExpr::Expr(event_name_expr) => {
+                            result_hints.needs_normalize_props = true;
+
+                            let handler_key = self.call_vue_import(
+                                VueImports::ToHandlerKey,
+                                vec![event_name_expr.to_owned()],
+                                span,
+                            );
                             out.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
                                 key: PropName::Computed(ComputedPropName {
                                     span: DUMMY_SP,
-                                    expr: event_name_expr.to_owned(),
+                                    expr: with_options_postfix(
+                                        Box::new(handler_key),
+                                        &options_postfix,
+                                    ),
                                 }),
                                 value: handler_expr,
                             }))));
@@ -302,6 +394,65 @@ impl CodegenContext {
         result_hints
     }
 
+    /// Generates `..._toHandlers({ [event1]: handler, event2: handler })`
+    /// for the events in `@[[event1, 'event2']]="handler"`.
+    /// `options_postfix` is appended to each event, e.g. `Once` in `@[[event1, 'event2']].once`
+    fn generate_event_array_handlers(
+        &mut self,
+        events: &ArrayLit,
+        handler: Box<Expr>,
+        options_postfix: &str,
+        span: Span,
+    ) -> PropOrSpread {
+        let handlers: Vec<PropOrSpread> = events
+            .elems
+            .iter()
+            .filter_map(|elem| {
+                // Holes and spreads do not name an event
+                let ExprOrSpread { spread: None, expr } = elem.as_ref()? else {
+                    return None;
+                };
+
+                let key = match **expr {
+                    Expr::Lit(Lit::Str(ref event)) => {
+                        str_to_propname(&format!("{}{}", event.value, options_postfix), span)
+                    }
+                    _ => PropName::Computed(ComputedPropName {
+                        span,
+                        expr: with_options_postfix(expr.to_owned(), options_postfix),
+                    }),
+                };
+
+                Some(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key,
+                    value: handler.to_owned(),
+                }))))
+            })
+            .collect();
+
+        let to_handlers_import = self.get_and_add_import_ident(VueImports::ToHandlers);
+
+        PropOrSpread::Spread(SpreadElement {
+            dot3_token: span,
+            expr: Box::new(Expr::Call(CallExpr {
+                span,
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                    span,
+                    sym: to_handlers_import,
+                    optional: false,
+                }))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Object(ObjectLit {
+                        span,
+                        props: handlers,
+                    })),
+                }],
+                type_args: None,
+            })),
+        })
+    }
+
     /// Process `class` attribute. We may have a regular one, a bound one, both or neither.
     /// Returns `true` when there were JavaScript bindings
     fn generate_class_bindings(
@@ -555,6 +706,115 @@ fn generate_regular_style(style: &str, span: Span) -> ObjectLit {
     result
 }
 
+/// `v-on` modifiers split by how they are applied
+#[derive(Debug, Default)]
+struct VOnModifiers<'m> {
+    /// Checked by `_withKeys`, e.g. `enter` or `esc`
+    key: Vec<&'m FervidAtom>,
+    /// Checked by `_withModifiers`, e.g. `stop` or `ctrl`
+    non_key: Vec<&'m FervidAtom>,
+    /// Passed to `addEventListener` as the event name postfix, e.g. `once` in `onClickOnce`
+    event_options: Vec<&'m FervidAtom>,
+}
+
+/// Splits the modifiers the same way as `resolveModifiers` of `@vue/compiler-dom`.
+/// `left` and `right` are keys for the keyboard events and mouse buttons otherwise,
+/// for the dynamic events both checks are generated.
+fn resolve_v_on_modifiers<'m>(event: &StrOrExpr, modifiers: &'m [FervidAtom]) -> VOnModifiers<'m> {
+    let mut result = VOnModifiers::default();
+
+    for modifier in modifiers.iter() {
+        match &**modifier {
+            "passive" | "once" | "capture" => result.event_options.push(modifier),
+
+            "stop" | "prevent" | "self" | "ctrl" | "shift" | "alt" | "meta" | "exact"
+            | "middle" => result.non_key.push(modifier),
+
+            "left" | "right" => match event {
+                StrOrExpr::Str(event_name) if is_keyboard_event(event_name) => {
+                    result.key.push(modifier)
+                }
+                StrOrExpr::Str(_) => result.non_key.push(modifier),
+                StrOrExpr::Expr(_) => {
+                    result.key.push(modifier);
+                    result.non_key.push(modifier);
+                }
+            },
+
+            _ => result.key.push(modifier),
+        }
+    }
+
+    result
+}
+
+/// Checks the transformed event name, e.g. `onKeyup`
+fn is_keyboard_event(event_name: &str) -> bool {
+    matches!(
+        event_name.to_ascii_lowercase().as_str(),
+        "onkeyup" | "onkeydown" | "onkeypress"
+    )
+}
+
+/// Generates `["modifier1", "modifier2"]`
+fn modifiers_array(modifiers: &[&FervidAtom], span: Span) -> Box<Expr> {
+    Box::new(Expr::Array(ArrayLit {
+        span,
+        elems: modifiers
+            .iter()
+            .map(|modifier| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span,
+                        value: (*modifier).to_owned(),
+                        raw: None,
+                    }))),
+                })
+            })
+            .collect(),
+    }))
+}
+
+/// Generates `event + "Once"` for a dynamic event
+fn with_options_postfix(event: Box<Expr>, options_postfix: &str) -> Box<Expr> {
+    if options_postfix.is_empty() {
+        return event;
+    }
+
+    // Lower precedence events are wrapped, e.g. `(a ? b : c) + "Once"`
+    let left = match *event {
+        Expr::Arrow(_) | Expr::Assign(_) | Expr::Bin(_) | Expr::Cond(_) | Expr::Seq(_) => {
+            Box::new(Expr::Paren(ParenExpr {
+                span: DUMMY_SP,
+                expr: event,
+            }))
+        }
+        _ => event,
+    };
+
+    Box::new(Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op: BinaryOp::Add,
+        left,
+        right: Box::new(Expr::Lit(Lit::Str(Str {
+            span: DUMMY_SP,
+            value: options_postfix.into(),
+            raw: None,
+        }))),
+    }))
+}
+
+/// Checks for `null` and `undefined`, possibly wrapped in parens
+fn is_null_or_undefined(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Null(_)) => true,
+        Expr::Ident(ident) => &ident.sym == "undefined",
+        Expr::Paren(paren) => is_null_or_undefined(&paren.expr),
+        _ => false,
+    }
+}
+
 /// Generates () => {}
 fn empty_arrow_expr(span: Span) -> Expr {
     Expr::Arrow(ArrowExpr {
@@ -573,7 +833,7 @@ fn empty_arrow_expr(span: Span) -> Expr {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{AttributeOrBinding, VOnDirective};
+    use fervid_core::{AttributeOrBinding, StrOrExpr, VBindDirective, VOnDirective, VueImports};
     use swc_core::{common::DUMMY_SP, ecma::ast::ObjectLit};
 
     use crate::{
@@ -666,6 +926,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_v_bind_modifiers() {
+        let v_bind = |argument: StrOrExpr, is_camel: bool, is_prop: bool, is_attr: bool| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(argument),
+                value: js("value"),
+                is_camel,
+                is_prop,
+                is_attr,
+                span: DUMMY_SP,
+            })
+        };
+
+        // :textContent.prop="value"
+        test_out(
+            vec![v_bind("textContent".into(), false, true, false)],
+            r#"{".textContent":value}"#,
+        );
+
+        // :title.attr="value"
+        test_out(
+            vec![v_bind("title".into(), false, false, true)],
+            r#"{"^title":value}"#,
+        );
+
+        // :[key].camel="value"
+        test_out(
+            vec![v_bind(StrOrExpr::Expr(js("key")), true, false, false)],
+            r#"{[_camelize(key||"")]:value}"#,
+        );
+
+        // :[key].camel.prop="value"
+        test_out(
+            vec![v_bind(StrOrExpr::Expr(js("key")), true, true, false)],
+            r#"{["."+_camelize(key||"")]:value}"#,
+        );
+
+        // :[key].attr="value"
+        test_out(
+            vec![v_bind(StrOrExpr::Expr(js("key")), false, false, true)],
+            r#"{["^"+(key||"")]:value}"#,
+        );
+
+        // Attributes are rendered as is in SSR
+        let mut ctx = CodegenContext {
+            is_ssr: true,
+            ..Default::default()
+        };
+        let mut out = ObjectLit {
+            span: DUMMY_SP,
+            props: vec![],
+        };
+        ctx.generate_attributes(
+            &[v_bind("textContent".into(), false, true, false)],
+            &mut out.props,
+        );
+        assert_eq!(crate::test_utils::to_str(out), r"{textContent:value}");
+    }
+
     #[test]
     fn it_generates_v_on() {
         // @click
@@ -747,6 +1066,208 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_v_on_modifiers() {
+        let v_on = |event: StrOrExpr, modifiers: &[&str]| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some(event),
+                handler: Some(js("handler")),
+                modifiers: modifiers.iter().map(|m| (*m).into()).collect(),
+                span: DUMMY_SP,
+            })
+        };
+
+        // @click.once="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["once"])],
+            r"{onClickOnce:handler}",
+        );
+
+        // @click.capture="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["capture"])],
+            r"{onClickCapture:handler}",
+        );
+
+        // @scroll.passive="handler"
+        test_out(
+            vec![v_on("onScroll".into(), &["passive"])],
+            r"{onScrollPassive:handler}",
+        );
+
+        // @click.capture.once.passive="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["capture", "once", "passive"])],
+            r"{onClickCaptureOncePassive:handler}",
+        );
+
+        // @click.stop.once="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["stop", "once"])],
+            r#"{onClickOnce:_withModifiers(handler,["stop"])}"#,
+        );
+
+        // @keyup.enter.ctrl="handler"
+        test_out(
+            vec![v_on("onKeyup".into(), &["enter", "ctrl"])],
+            r#"{onKeyup:_withKeys(_withModifiers(handler,["ctrl"]),["enter"])}"#,
+        );
+
+        // @keydown.left="handler"
+        test_out(
+            vec![v_on("onKeydown".into(), &["left"])],
+            r#"{onKeydown:_withKeys(handler,["left"])}"#,
+        );
+
+        // @click.enter="handler" does not check keys for non-keyboard events
+        test_out(
+            vec![v_on("onClick".into(), &["enter"])],
+            r"{onClick:handler}",
+        );
+
+        // @click.right="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["right"])],
+            r#"{onContextmenu:_withModifiers(handler,["right"])}"#,
+        );
+
+        // @click.middle="handler"
+        test_out(
+            vec![v_on("onClick".into(), &["middle"])],
+            r#"{onMouseup:_withModifiers(handler,["middle"])}"#,
+        );
+
+        // @[event].left.once="handler"
+        test_out(
+            vec![v_on(StrOrExpr::Expr(js("event")), &["left", "once"])],
+            r#"{[_toHandlerKey(event)+"Once"]:_withKeys(_withModifiers(handler,["left"]),["left"])}"#,
+        );
+
+        // @[[dynamic, 'hover']].once="handler"
+        test_out(
+            vec![v_on(StrOrExpr::Expr(js("[dynamic, 'hover']")), &["once"])],
+            r#"{..._toHandlers({[dynamic+"Once"]:handler,hoverOnce:handler})}"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_v_on_key_modifiers() {
+        let v_on = |event: &str, modifiers: &[&str]| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some(event.into()),
+                handler: Some(js("submit")),
+                modifiers: modifiers.iter().map(|m| (*m).into()).collect(),
+                span: DUMMY_SP,
+            })
+        };
+
+        // @keyup.enter="submit"
+        test_out(
+            vec![v_on("onKeyup", &["enter"])],
+            r#"{onKeyup:_withKeys(submit,["enter"])}"#,
+        );
+
+        // Aliases are resolved by the runtime
+        // @keydown.esc.space.up.down.tab="submit"
+        test_out(
+            vec![v_on("onKeydown", &["esc", "space", "up", "down", "tab"])],
+            r#"{onKeydown:_withKeys(submit,["esc","space","up","down","tab"])}"#,
+        );
+
+        // Any other key name is checked against the hyphenated `event.key`
+        // @keypress.page-down="submit"
+        test_out(
+            vec![v_on("onKeypress", &["page-down"])],
+            r#"{onKeypress:_withKeys(submit,["page-down"])}"#,
+        );
+
+        // @keyup.ctrl.enter.exact.prevent.once="submit"
+        test_out(
+            vec![v_on(
+                "onKeyup",
+                &["ctrl", "enter", "exact", "prevent", "once"],
+            )],
+            r#"{onKeyupOnce:_withKeys(_withModifiers(submit,["ctrl","exact","prevent"]),["enter"])}"#,
+        );
+
+        // `_withKeys` is imported
+        let mut ctx = CodegenContext::default();
+        let mut out = ObjectLit {
+            span: DUMMY_SP,
+            props: vec![],
+        };
+        ctx.generate_attributes(&[v_on("onKeyup", &["enter"])], &mut out.props);
+        assert!(ctx
+            .bindings_helper
+            .vue_imports
+            .contains(VueImports::WithKeys));
+    }
+
+    #[test]
+    fn it_omits_null_dynamic_arguments() {
+        let v_bind = |argument: &str| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Expr(js(argument))),
+                value: js("value"),
+                is_camel: false,
+                is_prop: false,
+                is_attr: false,
+                span: DUMMY_SP,
+            })
+        };
+
+        // :[null]="value"
+        test_out(vec![v_bind("null")], "{}");
+
+        // :[undefined]="value"
+        test_out(vec![v_bind("undefined")], "{}");
+
+        // :[condition ? 'href' : null]="value" cannot be omitted at compile time
+        test_out(
+            vec![v_bind("condition ? 'href' : null")],
+            r#"{[(condition?"href":null)||""]:value}"#,
+        );
+
+        // @[null]="handler"
+        test_out(
+            vec![
+                regular_attribute("id", "foo"),
+                AttributeOrBinding::VOn(VOnDirective {
+                    event: Some(StrOrExpr::Expr(js("null"))),
+                    handler: Some(js("handler")),
+                    modifiers: vec![],
+                    span: DUMMY_SP,
+                }),
+            ],
+            r#"{id:"foo"}"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_v_on_event_array() {
+        // @[[dynamic, 'hover']]="handler"
+        test_out(
+            vec![AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Expr(js("[dynamic, 'hover']"))),
+                handler: Some(js("handler")),
+                modifiers: vec![],
+                span: DUMMY_SP,
+            })],
+            r"{..._toHandlers({[dynamic]:handler,hover:handler})}",
+        );
+
+        // @[[dynamic]].stop="handler"
+        test_out(
+            vec![AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Expr(js("[dynamic]"))),
+                handler: Some(js("handler")),
+                modifiers: vec!["stop".into()],
+                span: DUMMY_SP,
+            })],
+            r#"{..._toHandlers({[dynamic]:_withModifiers(handler,["stop"])})}"#,
+        );
+    }
+
     fn test_out(input: Vec<AttributeOrBinding>, expected: &str) {
         let mut ctx = CodegenContext::default();
         let mut out = ObjectLit {
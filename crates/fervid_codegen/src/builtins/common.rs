@@ -1,48 +1,191 @@
-use fervid_core::{fervid_atom, AttributeOrBinding, ElementNode};
+use std::ops::Range;
+
+use fervid_core::{AttributeOrBinding, VBindDirective, VOnDirective, VueImports};
 use swc_core::{
-    common::{Span, DUMMY_SP},
-    ecma::ast::{Expr, Ident, KeyValueProp, Lit, Number, ObjectLit, Prop, PropName, PropOrSpread},
+    common::Span,
+    ecma::ast::{
+        Bool, CallExpr, Callee, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ObjectLit, Prop,
+        PropName, PropOrSpread,
+    },
 };
 
 use crate::CodegenContext;
 
 impl CodegenContext {
-    /// Generates attributes if any are present, returns `None` otherwise
+    /// Generates attributes if any are present, returns `None` otherwise.
+    ///
+    /// `v-bind="obj"` and `v-on="obj"` are merged with the other attributes in the order of appearance,
+    /// e.g. `<Teleport to="body" v-bind="obj">` becomes `_mergeProps({ to: "body" }, _ctx.obj)`,
+    /// so that the latter wins in case of a conflict.
     pub(crate) fn generate_builtin_attrs(
         &mut self,
         attributes: &[AttributeOrBinding],
         span: Span,
     ) -> Option<Expr> {
-        if attributes.len() != 0 {
+        if attributes.len() == 0 {
+            return None;
+        }
+
+        if !attributes.iter().any(is_spread) {
             let mut attrs = Vec::with_capacity(attributes.len());
             self.generate_attributes(&attributes, &mut attrs);
-            Some(Expr::Object(ObjectLit { span, props: attrs }))
-        } else {
-            None
+            return Some(Expr::Object(ObjectLit { span, props: attrs }));
+        }
+
+        self.generate_merged_attributes(attributes, false, span, |ctx, range, _| {
+            let mut attrs = Vec::with_capacity(range.len());
+            ctx.generate_attributes(&attributes[range], &mut attrs);
+            attrs
+        })
+    }
+
+    /// Merges the attributes with the `v-bind="obj"` and `v-on="obj"` spreads
+    /// in the order of appearance, or returns `None` when there is nothing to merge.
+    /// The attributes between the spreads are grouped into objects by `generate_group`,
+    /// which also gets whether the group is the last one.
+    ///
+    /// E.g. `foo="bar" v-bind="obj"` becomes `_mergeProps({ foo: "bar" }, _ctx.obj)`,
+    /// while the sole `v-bind="obj"` becomes `_normalizeProps(_guardReactiveProps(_ctx.obj))`.
+    /// `v-on="obj"` becomes `_toHandlers(_ctx.obj)`, which keeps the case of the event names
+    /// when `preserve_handlers_case` is set (i.e. on native elements).
+    pub(crate) fn generate_merged_attributes(
+        &mut self,
+        attributes: &[AttributeOrBinding],
+        preserve_handlers_case: bool,
+        span: Span,
+        mut generate_group: impl FnMut(&mut Self, Range<usize>, bool) -> Vec<PropOrSpread>,
+    ) -> Option<Expr> {
+        let mut merged_args = Vec::new();
+        let mut group_start = 0;
+        let mut is_last_spread_v_bind = false;
+        for (idx, attr) in attributes.iter().enumerate() {
+            let spread = match attr {
+                AttributeOrBinding::VBind(VBindDirective {
+                    argument: None,
+                    value,
+                    ..
+                }) => {
+                    is_last_spread_v_bind = true;
+                    value.to_owned()
+                }
+                AttributeOrBinding::VOn(VOnDirective {
+                    event: None,
+                    handler: Some(handlers),
+                    ..
+                }) => {
+                    is_last_spread_v_bind = false;
+                    let mut args = vec![handlers.to_owned()];
+                    if preserve_handlers_case {
+                        args.push(Box::new(Expr::Lit(Lit::Bool(Bool { span, value: true }))));
+                    }
+                    Box::new(self.call_vue_import(VueImports::ToHandlers, args, span))
+                }
+                _ => continue,
+            };
+
+            let props = generate_group(self, group_start..idx, false);
+            if !props.is_empty() {
+                merged_args.push(Box::new(Expr::Object(ObjectLit { span, props })));
+            }
+            merged_args.push(spread);
+            group_start = idx + 1;
+        }
+
+        let props = generate_group(self, group_start..attributes.len(), true);
+        if !props.is_empty() {
+            merged_args.push(Box::new(Expr::Object(ObjectLit { span, props })));
+        }
+
+        match merged_args.len() {
+            0 => None,
+
+            // Sole `v-bind="obj"`
+            1 if is_last_spread_v_bind && group_start != 0 => {
+                let spread = merged_args.pop().expect("Length is checked");
+                Some(self.generate_props_from_attrs(&spread, span))
+            }
+
+            // Attributes object or sole `_toHandlers(_ctx.obj)`
+            1 => merged_args
+                .pop()
+                .map(|arg| self.normalize_dynamic_props(*arg, span)),
+
+            _ => Some(self.call_vue_import(VueImports::MergeProps, merged_args, span)),
         }
     }
 
-    /// Generates the slots expression for builtins.
+    /// Generates `_normalizeProps(_guardReactiveProps(expr))`.
     ///
-    /// Additionally adds `_: 1` to the slots object.
-    pub(crate) fn generate_builtin_slots(&mut self, element_node: &ElementNode) -> Option<Expr> {
-        let mut slots = self.generate_component_children(element_node);
-        if let Some(Expr::Object(ref mut obj)) = slots {
-            obj.props
-                .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                    key: PropName::Ident(Ident {
-                        span: DUMMY_SP,
-                        sym: fervid_atom!("_"),
-                        optional: false,
-                    }),
-                    value: Box::new(Expr::Lit(Lit::Num(Number {
-                        span: DUMMY_SP,
-                        value: 1.0,
-                        raw: None,
-                    }))),
-                }))));
+    /// This is used when an object is the only source of props, e.g. `<slot v-bind="$attrs" />`
+    /// becomes `_renderSlot(_ctx.$slots, "default", _normalizeProps(_guardReactiveProps(_ctx.$attrs)))`.
+    /// The object must not be mutated by the runtime, therefore it is guarded.
+    pub(crate) fn generate_props_from_attrs(&mut self, expr: &Expr, span: Span) -> Expr {
+        let guarded = self.call_vue_import(
+            VueImports::GuardReactiveProps,
+            vec![Box::new(expr.to_owned())],
+            span,
+        );
+        self.call_vue_import(VueImports::NormalizeProps, vec![Box::new(guarded)], span)
+    }
+
+    /// Wraps the props object in `_normalizeProps` when some of its keys are dynamic,
+    /// e.g. `{ [_ctx.key || ""]: value }` becomes `_normalizeProps({ [_ctx.key || ""]: value })`,
+    /// because the key may turn out to be `class` or `style`.
+    /// Other expressions, as well as all the props in SSR, are returned as is.
+    pub(crate) fn normalize_dynamic_props(&mut self, props: Expr, span: Span) -> Expr {
+        let Expr::Object(ref object) = props else {
+            return props;
+        };
+
+        let has_dynamic_keys = object.props.iter().any(|prop| {
+            matches!(prop, PropOrSpread::Prop(prop) if matches!(
+                **prop,
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Computed(_),
+                    ..
+                })
+            ))
+        });
+        if !has_dynamic_keys || self.is_ssr {
+            return props;
         }
 
-        slots
+        self.call_vue_import(VueImports::NormalizeProps, vec![Box::new(props)], span)
     }
+
+    /// Generates `_import(args)`
+    pub(crate) fn call_vue_import(
+        &mut self,
+        import: VueImports,
+        args: Vec<Box<Expr>>,
+        span: Span,
+    ) -> Expr {
+        Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                span,
+                sym: self.get_and_add_import_ident(import),
+                optional: false,
+            }))),
+            args: args
+                .into_iter()
+                .map(|expr| ExprOrSpread { spread: None, expr })
+                .collect(),
+            type_args: None,
+        })
+    }
+}
+
+/// `v-bind="obj"` or `v-on="obj"`
+#[inline]
+fn is_spread(attr: &AttributeOrBinding) -> bool {
+    matches!(
+        attr,
+        AttributeOrBinding::VBind(VBindDirective { argument: None, .. })
+            | AttributeOrBinding::VOn(VOnDirective {
+                event: None,
+                handler: Some(_),
+                ..
+            })
+    )
 }
@@ -81,7 +81,7 @@ impl CodegenContext {
         // 2. Do not identify the node as a builtin if it does not have `is` attribute;
         // 7. Update the README and the progress.
 
-        let component_builtin_slots = self.generate_builtin_slots(element_node);
+        let component_builtin_slots = self.generate_component_children(element_node);
 
         self.generate_componentlike(
             identifier,
@@ -170,6 +170,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_component_is_expressions() {
+        let test_is = |is: &str, expected_is: &str| {
+            // <component :is="..."></component>
+            test_out(
+                ElementNode {
+                    kind: ElementKind::Builtin(BuiltinType::Component),
+                    starting_tag: StartingTag {
+                        tag_name: "component".into(),
+                        attributes: vec![v_bind_attribute("is", is)],
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                &format!(r#"(_openBlock(),_createBlock(_resolveDynamicComponent({expected_is})))"#),
+            )
+        };
+
+        // Component reference
+        test_is("$setup.MyComponent", "$setup.MyComponent");
+
+        // String variable and literal
+        test_is("tagName", "tagName");
+        test_is("'my-component'", r#""my-component""#);
+
+        // Conditional expression
+        test_is(
+            "isActive ? ActiveView : 'div'",
+            r#"isActive?ActiveView:"div""#,
+        );
+    }
+
     #[test]
     fn it_generates_component_builtin_attrs() {
         // <component is="div" foo="bar" :baz="qux"></component>
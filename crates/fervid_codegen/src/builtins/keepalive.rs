@@ -46,7 +46,8 @@ impl CodegenContext {
                 PatchFlagsSet::default()
             },
             props: vec![],
-            should_use_block
+            should_use_block,
+            ..Default::default()
         };
 
         self.generate_componentlike(
@@ -62,10 +63,13 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{BuiltinType, ElementKind, Node, StartingTag};
+    use fervid_core::{
+        BuiltinType, Conditional, ConditionalNodeSequence, ElementKind, Node, StartingTag,
+        VForDirective, VueDirectives,
+    };
     use swc_core::common::DUMMY_SP;
 
-    use crate::test_utils::{regular_attribute, v_bind_attribute};
+    use crate::test_utils::{js, regular_attribute, v_bind_attribute, v_bind_spread_attribute};
 
     use super::*;
 
@@ -155,6 +159,120 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_keepalive_v_bind_spread() {
+        // <keep-alive v-bind="attrs" :max="10"></keep-alive>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::KeepAlive),
+                starting_tag: StartingTag {
+                    tag_name: "keep-alive".into(),
+                    attributes: vec![
+                        v_bind_spread_attribute("attrs"),
+                        v_bind_attribute("max", "10"),
+                    ],
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"_createVNode(_KeepAlive,_mergeProps(attrs,{max:10}))"#,
+        )
+    }
+
+    #[test]
+    fn it_generates_keepalive_conditional_children() {
+        let keepalive = |children: Vec<Node>| ElementNode {
+            kind: ElementKind::Builtin(BuiltinType::KeepAlive),
+            starting_tag: StartingTag {
+                tag_name: "keep-alive".into(),
+                attributes: vec![],
+                directives: None,
+            },
+            children,
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+        let child = |tag_name: &str, kind: ElementKind| ElementNode {
+            kind,
+            starting_tag: StartingTag {
+                tag_name: tag_name.into(),
+                attributes: vec![],
+                directives: None,
+            },
+            children: vec![],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+        let conditional = |condition: &str, node: ElementNode| Conditional {
+            condition: *js(condition),
+            node,
+        };
+
+        // <keep-alive><div v-if="show" /></keep-alive>
+        test_out(
+            keepalive(vec![Node::ConditionalSeq(ConditionalNodeSequence {
+                if_node: Box::new(conditional("show", child("div", ElementKind::Element))),
+                else_if_nodes: vec![],
+                else_node: None,
+            })]),
+            r#"(_openBlock(),_createBlock(_KeepAlive,null,[show?(_openBlock(),_createElementBlock("div")):_createCommentVNode("v-if",true)],1024))"#,
+        );
+
+        // <keep-alive><AsyncComp v-if="show" /></keep-alive>
+        test_out(
+            keepalive(vec![Node::ConditionalSeq(ConditionalNodeSequence {
+                if_node: Box::new(conditional(
+                    "show",
+                    child("AsyncComp", ElementKind::Component),
+                )),
+                else_if_nodes: vec![],
+                else_node: None,
+            })]),
+            r#"(_openBlock(),_createBlock(_KeepAlive,null,[show?(_openBlock(),_createBlock(_component_AsyncComp)):_createCommentVNode("v-if",true)],1024))"#,
+        );
+
+        // <keep-alive>
+        //   <CompA v-if="tab === 'a'" />
+        //   <CompB v-else-if="tab === 'b'" />
+        //   <CompC v-else />
+        // </keep-alive>
+        test_out(
+            keepalive(vec![Node::ConditionalSeq(ConditionalNodeSequence {
+                if_node: Box::new(conditional(
+                    "tab === 'a'",
+                    child("CompA", ElementKind::Component),
+                )),
+                else_if_nodes: vec![conditional(
+                    "tab === 'b'",
+                    child("CompB", ElementKind::Component),
+                )],
+                else_node: Some(Box::new(child("CompC", ElementKind::Component))),
+            })]),
+            r#"(_openBlock(),_createBlock(_KeepAlive,null,[tab==="a"?(_openBlock(),_createBlock(_component_CompA)):tab==="b"?(_openBlock(),_createBlock(_component_CompB)):(_openBlock(),_createBlock(_component_CompC))],1024))"#,
+        );
+
+        // <keep-alive><div v-for="item in list" /></keep-alive>
+        let mut v_for_child = child("div", ElementKind::Element);
+        v_for_child.starting_tag.directives = Some(Box::new(VueDirectives {
+            v_for: Some(VForDirective {
+                iterable: js("list"),
+                itervar: js("item"),
+                patch_flags: PatchFlags::UnkeyedFragment.into(),
+                span: DUMMY_SP,
+            }),
+            ..Default::default()
+        }));
+        test_out(
+            keepalive(vec![Node::Element(v_for_child)]),
+            r#"(_openBlock(),_createBlock(_KeepAlive,null,[(_openBlock(),_createElementBlock(_Fragment,null,_renderList(list,(item)=>_createElementVNode("div")),256))],1024))"#,
+        );
+    }
+
     fn test_out(input: ElementNode, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_keepalive(&input);
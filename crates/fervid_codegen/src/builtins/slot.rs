@@ -1,7 +1,13 @@
-use fervid_core::{check_attribute_name, fervid_atom, AttributeOrBinding, ElementNode, VueImports};
-use swc_core::ecma::ast::{
-    ArrayLit, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, MemberExpr, MemberProp, ObjectLit,
-    Str,
+use fervid_core::{
+    check_attribute_name, fervid_atom, AttributeOrBinding, ElementNode, Node, VBindDirective,
+    VueImports,
+};
+use swc_core::{
+    common::Span,
+    ecma::ast::{
+        ArrayLit, ArrowExpr, BlockStmtOrExpr, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit,
+        MemberExpr, MemberProp, ObjectLit, Str,
+    },
 };
 
 use crate::CodegenContext;
@@ -11,7 +17,7 @@ impl CodegenContext {
     ///
     /// A typical code (stringified) has the following form:
     /// ```js
-    /// renderSlot(_ctx.$slots, "slot-name", /*optional*/ { slot: attributes }, /*optional*/ [slot, children])
+    /// renderSlot(_ctx.$slots, "slot-name", /*optional*/ { slot: attributes }, /*optional*/ () => [slot, children])
     /// ```
     pub fn generate_slot(&mut self, element_node: &ElementNode) -> Expr {
         let span = element_node.span;
@@ -88,7 +94,30 @@ impl CodegenContext {
         });
 
         // Third arg (optional): attributes
-        if has_attributes {
+        let has_v_bind_spread = element_node.starting_tag.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                AttributeOrBinding::VBind(VBindDirective { argument: None, .. })
+            )
+        });
+        if has_v_bind_spread {
+            // `v-bind="$attrs"` is merged with the other attributes, `name` is excluded
+            let attributes: Vec<AttributeOrBinding> = element_node
+                .starting_tag
+                .attributes
+                .iter()
+                .filter(|attr| !check_attribute_name(attr, "name"))
+                .cloned()
+                .collect();
+            let attrs_expr = self
+                .generate_builtin_attrs(&attributes, span)
+                .expect("There is at least one attribute");
+
+            render_slot_args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(attrs_expr),
+            });
+        } else if has_attributes {
             let mut attrs_obj = ObjectLit {
                 span,
                 props: Vec::with_capacity(element_node.starting_tag.attributes.len()),
@@ -129,30 +158,15 @@ impl CodegenContext {
             })
         }
 
-        // Fourth arg (optional): children
-        if has_children {
-            let slot_children = self
-                .generate_element_children(element_node, false)
-                .0
-                .into_iter()
-                .map(|expr| {
-                    Some(ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(expr),
-                    })
-                })
-                .collect();
-
+        // Fourth arg (optional): fallback children
+        if let Some(fallback) = self.generate_slot_fallback(&element_node.children, span) {
             render_slot_args.push(ExprOrSpread {
                 spread: None,
-                expr: Box::new(Expr::Array(ArrayLit {
-                    span,
-                    elems: slot_children,
-                })),
+                expr: Box::new(fallback),
             });
         }
 
-        // `renderSlot(_ctx.$slots, "slot-name", { slot: attributes }, [slot, children])`
+        // `renderSlot(_ctx.$slots, "slot-name", { slot: attributes }, () => [slot, children])`
         Expr::Call(CallExpr {
             span,
             callee: Callee::Expr(Box::new(Expr::Ident(Ident {
@@ -164,14 +178,58 @@ impl CodegenContext {
             type_args: None,
         })
     }
+
+    /// Generates the fallback content of a slot, e.g. `() => [child1, child2]`.
+    ///
+    /// Returns `None` when there are no children,
+    /// meaning that the fallback argument should be omitted altogether.
+    pub fn generate_slot_fallback(&mut self, children: &[Node], span: Span) -> Option<Expr> {
+        if children.is_empty() {
+            return None;
+        }
+
+        let mut fallback_children = Vec::with_capacity(children.len());
+        self.generate_node_sequence(
+            &mut children.iter(),
+            &mut fallback_children,
+            children.len(),
+            false,
+        );
+
+        let elems = fallback_children
+            .into_iter()
+            .map(|expr| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr),
+                })
+            })
+            .collect();
+
+        // `() => [child1, child2]`
+        Some(Expr::Arrow(ArrowExpr {
+            span,
+            params: vec![],
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Array(ArrayLit {
+                span,
+                elems,
+            })))),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{BuiltinType, ElementKind, Node, StartingTag};
+    use fervid_core::{
+        BuiltinType, ElementKind, Interpolation, Node, PatchFlags, PatchHints, StartingTag,
+    };
     use swc_core::common::DUMMY_SP;
 
-    use crate::test_utils::{regular_attribute, v_bind_attribute};
+    use crate::test_utils::{js, regular_attribute, v_bind_attribute, v_bind_spread_attribute};
 
     use super::*;
 
@@ -279,6 +337,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_attrs_forwarding() {
+        // <slot v-bind="$attrs" />
+        test_out(
+            slot!(vec![v_bind_spread_attribute("$attrs")], vec![]),
+            r#"_renderSlot(_ctx.$slots,"default",_normalizeProps(_guardReactiveProps($attrs)))"#,
+        );
+
+        // <slot name="foo" v-bind="$attrs" />
+        test_out(
+            slot!(
+                vec![
+                    regular_attribute("name", "foo"),
+                    v_bind_spread_attribute("$attrs"),
+                ],
+                vec![]
+            ),
+            r#"_renderSlot(_ctx.$slots,"foo",_normalizeProps(_guardReactiveProps($attrs)))"#,
+        );
+
+        // <slot foo="bar" v-bind="$attrs" :baz="qux" />
+        test_out(
+            slot!(
+                vec![
+                    regular_attribute("foo", "bar"),
+                    v_bind_spread_attribute("$attrs"),
+                    v_bind_attribute("baz", "qux"),
+                ],
+                vec![]
+            ),
+            r#"_renderSlot(_ctx.$slots,"default",_mergeProps({foo:"bar"},$attrs,{baz:qux}))"#,
+        );
+    }
+
     #[test]
     fn it_generates_children() {
         // <slot>
@@ -315,7 +407,7 @@ mod tests {
                     })
                 ]
             ),
-            r#"_renderSlot(_ctx.$slots,"default",{},[_createElementVNode("div",null,"Placeholder"),_createVNode(_component_foo_component)])"#,
+            r#"_renderSlot(_ctx.$slots,"default",{},()=>[_createElementVNode("div",null,"Placeholder"),_createVNode(_component_foo_component)])"#,
         );
     }
 
@@ -359,7 +451,99 @@ mod tests {
                     })
                 ]
             ),
-            r#"_renderSlot(_ctx.$slots,"test-slot",{foo:"bar",baz:qux},[_createElementVNode("div",null,"Placeholder"),_createVNode(_component_foo_component)])"#,
+            r#"_renderSlot(_ctx.$slots,"test-slot",{foo:"bar",baz:qux},()=>[_createElementVNode("div",null,"Placeholder"),_createVNode(_component_foo_component)])"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_slot_fallback() {
+        macro_rules! fallback {
+            ($children: expr) => {{
+                let mut ctx = CodegenContext::default();
+                ctx.generate_slot_fallback(&$children, DUMMY_SP)
+                    .map(|expr| crate::test_utils::to_str(expr))
+            }};
+        }
+
+        let div = |text: &str| {
+            Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children: vec![Node::Text(text.into(), DUMMY_SP)],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })
+        };
+
+        // Text
+        assert_eq!(
+            fallback!(vec![Node::Text("Fallback".into(), DUMMY_SP)]).as_deref(),
+            Some(r#"()=>[_createTextVNode("Fallback")]"#)
+        );
+
+        // Element
+        assert_eq!(
+            fallback!(vec![div("Fallback")]).as_deref(),
+            Some(r#"()=>[_createElementVNode("div",null,"Fallback")]"#)
+        );
+
+        // Multiple children
+        assert_eq!(
+            fallback!(vec![div("one"), div("two")]).as_deref(),
+            Some(
+                r#"()=>[_createElementVNode("div",null,"one"),_createElementVNode("div",null,"two")]"#
+            )
+        );
+
+        // Empty
+        assert_eq!(fallback!(Vec::<Node>::new()), None);
+
+        // Nested dynamic content
+        assert_eq!(
+            fallback!(vec![Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "span".into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children: vec![Node::Interpolation(Interpolation {
+                    value: js("msg"),
+                    template_scope: 0,
+                    patch_flag: true,
+                    span: DUMMY_SP,
+                })],
+                template_scope: 0,
+                patch_hints: PatchHints {
+                    flags: PatchFlags::Text.into(),
+                    props: vec![],
+                    should_use_block: false,
+                    ..Default::default()
+                },
+                span: DUMMY_SP,
+            })])
+            .as_deref(),
+            Some(r#"()=>[_createElementVNode("span",null,_toDisplayString(msg),1)]"#)
+        );
+    }
+
+    #[test]
+    fn it_omits_empty_fallback() {
+        // <slot name="foo" bar="baz"></slot>
+        test_out(
+            slot!(
+                vec![
+                    regular_attribute("name", "foo"),
+                    regular_attribute("bar", "baz")
+                ],
+                vec![]
+            ),
+            r#"_renderSlot(_ctx.$slots,"foo",{bar:"baz"})"#,
         );
     }
 
@@ -18,7 +18,7 @@ impl CodegenContext {
         let suspense_attrs =
             self.generate_builtin_attrs(&element_node.starting_tag.attributes, span);
 
-        let suspense_slots = self.generate_builtin_slots(element_node);
+        let suspense_slots = self.generate_component_children(element_node);
 
         self.generate_componentlike(
             suspense_identifier,
@@ -33,7 +33,7 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{BuiltinType, ElementKind, Node, StartingTag};
+    use fervid_core::{BuiltinType, ElementKind, Node, StartingTag, VSlotDirective, VueDirectives};
     use swc_core::common::DUMMY_SP;
 
     use crate::test_utils::{regular_attribute, v_bind_attribute};
@@ -126,6 +126,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_suspense_fallback() {
+        // <suspense>
+        //   <template #fallback>Loading...</template>
+        //   <AsyncComp />
+        // </suspense>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::Suspense),
+                starting_tag: StartingTag {
+                    tag_name: "suspense".into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children: vec![
+                    Node::Element(ElementNode {
+                        kind: ElementKind::Element,
+                        starting_tag: StartingTag {
+                            tag_name: "template".into(),
+                            attributes: vec![],
+                            directives: Some(Box::new(VueDirectives {
+                                v_slot: Some(VSlotDirective {
+                                    slot_name: Some("fallback".into()),
+                                    value: None,
+                                }),
+                                ..Default::default()
+                            })),
+                        },
+                        children: vec![Node::Text("Loading...".into(), DUMMY_SP)],
+                        template_scope: 0,
+                        patch_hints: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                    Node::Element(ElementNode {
+                        kind: ElementKind::Component,
+                        starting_tag: StartingTag {
+                            tag_name: "AsyncComp".into(),
+                            attributes: vec![],
+                            directives: None,
+                        },
+                        children: vec![],
+                        template_scope: 0,
+                        patch_hints: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                ],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"(_openBlock(),_createBlock(_Suspense,null,{fallback:_withCtx(()=>[_createTextVNode("Loading...")]),"default":_withCtx(()=>[_createVNode(_component_AsyncComp)]),_:1}))"#,
+        )
+    }
+
     fn test_out(input: ElementNode, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_suspense(&input);
@@ -53,7 +53,9 @@ mod tests {
     use fervid_core::{BuiltinType, ElementKind, Node, StartingTag};
     use swc_core::common::DUMMY_SP;
 
-    use crate::test_utils::{regular_attribute, v_bind_attribute};
+    use crate::test_utils::{
+        regular_attribute, v_bind_attribute, v_bind_spread_attribute, v_on_spread_attribute,
+    };
 
     use super::*;
 
@@ -143,6 +145,114 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_teleport_dynamic_to() {
+        let test_to = |to: &str, expected_to: &str| {
+            // <teleport :to="..." :disabled="!visible">foobar</teleport>
+            test_out(
+                ElementNode {
+                    kind: ElementKind::Builtin(BuiltinType::Teleport),
+                    starting_tag: StartingTag {
+                        tag_name: "teleport".into(),
+                        attributes: vec![
+                            v_bind_attribute("to", to),
+                            v_bind_attribute("disabled", "!visible"),
+                        ],
+                        directives: None,
+                    },
+                    children: vec![Node::Text("foobar".into(), DUMMY_SP)],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                &format!(
+                    r#"(_openBlock(),_createBlock(_Teleport,{{to:{expected_to},disabled:!visible}},[_createTextVNode("foobar")]))"#
+                ),
+            )
+        };
+
+        // Template literals are kept intact
+        test_to("`.container-${id}`", "`.container-${id}`");
+        test_to("`#${getTarget().trim()}`", "`#${getTarget().trim()}`");
+
+        // Conditional and call expressions
+        test_to(
+            "isMobile ? '#mobile' : '#desktop'",
+            r##"isMobile?"#mobile":"#desktop""##,
+        );
+        test_to("resolveTarget(id)", "resolveTarget(id)");
+    }
+
+    #[test]
+    fn it_generates_teleport_v_bind_spread() {
+        // <teleport to="body" v-bind="attrs"></teleport>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::Teleport),
+                starting_tag: StartingTag {
+                    tag_name: "teleport".into(),
+                    attributes: vec![
+                        regular_attribute("to", "body"),
+                        v_bind_spread_attribute("attrs"),
+                    ],
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"(_openBlock(),_createBlock(_Teleport,_mergeProps({to:"body"},attrs)))"#,
+        )
+    }
+
+    #[test]
+    fn it_generates_teleport_v_bind_spread_conflict() {
+        // The last one wins: <teleport to="body" v-bind="attrs" :to="target"></teleport>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::Teleport),
+                starting_tag: StartingTag {
+                    tag_name: "teleport".into(),
+                    attributes: vec![
+                        regular_attribute("to", "body"),
+                        v_bind_spread_attribute("attrs"),
+                        v_bind_attribute("to", "target"),
+                    ],
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"(_openBlock(),_createBlock(_Teleport,_mergeProps({to:"body"},attrs,{to:target})))"#,
+        )
+    }
+
+    #[test]
+    fn it_generates_teleport_v_on_spread() {
+        // <teleport to="body" v-on="handlers"></teleport>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::Teleport),
+                starting_tag: StartingTag {
+                    tag_name: "teleport".into(),
+                    attributes: vec![
+                        regular_attribute("to", "body"),
+                        v_on_spread_attribute("handlers"),
+                    ],
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"(_openBlock(),_createBlock(_Teleport,_mergeProps({to:"body"},_toHandlers(handlers))))"#,
+        )
+    }
+
     fn test_out(input: ElementNode, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_teleport(&input);
@@ -1,23 +1,51 @@
-use fervid_core::{ElementNode, VueImports};
+use fervid_core::{BuiltinType, ElementNode, VueImports};
 use swc_core::ecma::ast::{Expr, Ident};
 
 use crate::CodegenContext;
 
+/// Whether the builtin extends `BaseTransition` in Vue runtime,
+/// i.e. it is either `<Transition>` or `<TransitionGroup>`
+pub fn is_base_transition_subtype(builtin: BuiltinType) -> bool {
+    matches!(
+        builtin,
+        BuiltinType::Transition | BuiltinType::TransitionGroup
+    )
+}
+
 impl CodegenContext {
     pub fn generate_transition(&mut self, element_node: &ElementNode) -> Expr {
+        self.generate_base_transition_subtype(element_node, BuiltinType::Transition)
+    }
+
+    /// Generates either `<Transition>` or `<TransitionGroup>`.
+    /// The props (both the common `BaseTransition` ones like `name` or `appear`
+    /// and the specific ones like `mode` or `tag`) are forwarded as is,
+    /// because their validation is done by the Vue runtime.
+    pub(crate) fn generate_base_transition_subtype(
+        &mut self,
+        element_node: &ElementNode,
+        builtin: BuiltinType,
+    ) -> Expr {
+        debug_assert!(is_base_transition_subtype(builtin));
+
         let span = element_node.span;
 
-        // _Transition
+        // _Transition or _TransitionGroup
+        let vue_import = if let BuiltinType::TransitionGroup = builtin {
+            VueImports::TransitionGroup
+        } else {
+            VueImports::Transition
+        };
         let transition_identifier = Expr::Ident(Ident {
             span,
-            sym: self.get_and_add_import_ident(VueImports::Transition),
+            sym: self.get_and_add_import_ident(vue_import),
             optional: false,
         });
 
         let transition_attrs =
             self.generate_builtin_attrs(&element_node.starting_tag.attributes, span);
 
-        let transition_slots = self.generate_builtin_slots(element_node);
+        let transition_slots = self.generate_component_children(element_node);
 
         self.generate_componentlike(
             transition_identifier,
@@ -32,10 +60,10 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{BuiltinType, ElementKind, Node, StartingTag};
+    use fervid_core::{AttributeOrBinding, ElementKind, Node, StartingTag};
     use swc_core::common::DUMMY_SP;
 
-    use crate::test_utils::{regular_attribute, v_bind_attribute};
+    use crate::test_utils::{regular_attribute, v_bind_attribute, v_bind_spread_attribute};
 
     use super::*;
 
@@ -125,6 +153,89 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_transition_v_bind_spread() {
+        // <transition v-bind="attrs"></transition>
+        test_out(
+            ElementNode {
+                kind: ElementKind::Builtin(BuiltinType::Transition),
+                starting_tag: StartingTag {
+                    tag_name: "transition".into(),
+                    attributes: vec![v_bind_spread_attribute("attrs")],
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"_createVNode(_Transition,_normalizeProps(_guardReactiveProps(attrs)))"#,
+        )
+    }
+
+    #[test]
+    fn it_forwards_base_transition_props() {
+        assert!(is_base_transition_subtype(BuiltinType::Transition));
+        assert!(is_base_transition_subtype(BuiltinType::TransitionGroup));
+        assert!(!is_base_transition_subtype(BuiltinType::KeepAlive));
+
+        let check = |builtin: BuiltinType, attributes: Vec<AttributeOrBinding>, expected: &str| {
+            let element_node = ElementNode {
+                kind: ElementKind::Builtin(builtin),
+                starting_tag: StartingTag {
+                    tag_name: "transition".into(),
+                    attributes,
+                    directives: None,
+                },
+                children: vec![],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            };
+
+            let mut ctx = CodegenContext::default();
+            let out = ctx.generate_base_transition_subtype(&element_node, builtin);
+            assert_eq!(crate::test_utils::to_str(out), expected)
+        };
+
+        // Common props
+        // <transition name="fade" :appear="true"></transition>
+        check(
+            BuiltinType::Transition,
+            vec![
+                regular_attribute("name", "fade"),
+                v_bind_attribute("appear", "true"),
+            ],
+            r#"_createVNode(_Transition,{name:"fade",appear:true})"#,
+        );
+        // <transition-group name="fade" :appear="true"></transition-group>
+        check(
+            BuiltinType::TransitionGroup,
+            vec![
+                regular_attribute("name", "fade"),
+                v_bind_attribute("appear", "true"),
+            ],
+            r#"_createVNode(_TransitionGroup,{name:"fade",appear:true})"#,
+        );
+
+        // Specific props
+        // <transition mode="out-in"></transition>
+        check(
+            BuiltinType::Transition,
+            vec![regular_attribute("mode", "out-in")],
+            r#"_createVNode(_Transition,{mode:"out-in"})"#,
+        );
+        // <transition-group tag="ul" move-class="moving"></transition-group>
+        check(
+            BuiltinType::TransitionGroup,
+            vec![
+                regular_attribute("tag", "ul"),
+                regular_attribute("move-class", "moving"),
+            ],
+            r#"_createVNode(_TransitionGroup,{tag:"ul","move-class":"moving"})"#,
+        );
+    }
+
     fn test_out(input: ElementNode, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_transition(&input);
@@ -1,32 +1,11 @@
-use fervid_core::{ElementNode, VueImports};
-use swc_core::ecma::ast::{Expr, Ident};
+use fervid_core::{BuiltinType, ElementNode};
+use swc_core::ecma::ast::Expr;
 
 use crate::CodegenContext;
 
 impl CodegenContext {
     pub fn generate_transition_group(&mut self, element_node: &ElementNode) -> Expr {
-        let span = element_node.span;
-
-        // _TransitionGroup
-        let transition_group_identifier = Expr::Ident(Ident {
-            span,
-            sym: self.get_and_add_import_ident(VueImports::TransitionGroup),
-            optional: false,
-        });
-
-        let transition_group_attrs =
-            self.generate_builtin_attrs(&element_node.starting_tag.attributes, span);
-
-        let transition_group_slots = self.generate_builtin_slots(element_node);
-
-        self.generate_componentlike(
-            transition_group_identifier,
-            transition_group_attrs,
-            transition_group_slots,
-            &element_node.patch_hints,
-            false,
-            span,
-        )
+        self.generate_base_transition_subtype(element_node, BuiltinType::TransitionGroup)
     }
 }
 
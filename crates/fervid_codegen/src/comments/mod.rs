@@ -1,7 +1,7 @@
 use fervid_core::{FervidAtom, VueImports};
 use swc_core::{
     common::Span,
-    ecma::ast::{CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Str},
+    ecma::ast::{Bool, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Str},
 };
 
 use crate::context::CodegenContext;
@@ -29,6 +29,24 @@ impl CodegenContext {
             type_args: None,
         })
     }
+
+    /// Generates a placeholder for `v-if` without `v-else`.
+    /// This is `createCommentVNode("v-if", true)` in DEV and `createCommentVNode("", true)` in PROD.
+    pub fn generate_v_if_placeholder(&mut self, span: Span) -> Expr {
+        let is_prod = self.bindings_helper.is_prod;
+        let comment = if is_prod { "" } else { "v-if" };
+        let mut comment_vnode = self.generate_comment_vnode(comment, span);
+
+        // `true` is `asBlock`, so that the parent block tracks the placeholder
+        if let Expr::Call(ref mut call_expr) = comment_vnode {
+            call_expr.args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Lit(Lit::Bool(Bool { span, value: true }))),
+            });
+        }
+
+        comment_vnode
+    }
 }
 
 #[cfg(test)]
@@ -53,6 +71,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_v_if_placeholder() {
+        // DEV
+        let mut ctx = CodegenContext::default();
+        let out = ctx.generate_v_if_placeholder(DUMMY_SP);
+        assert_eq!(
+            crate::test_utils::to_str(out),
+            r#"_createCommentVNode("v-if",true)"#
+        );
+
+        // PROD
+        let mut ctx = CodegenContext::default();
+        ctx.bindings_helper.is_prod = true;
+        let out = ctx.generate_v_if_placeholder(DUMMY_SP);
+        assert_eq!(
+            crate::test_utils::to_str(out),
+            r#"_createCommentVNode("",true)"#
+        );
+    }
+
     fn test_out(input: &str, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_comment_vnode(&input, DUMMY_SP);
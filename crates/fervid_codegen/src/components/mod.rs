@@ -1,18 +1,22 @@
+use std::ops::Range;
+
 use fervid_core::{
-    fervid_atom, ComponentBinding, ElementNode, FervidAtom, Node, PatchHints, StartingTag,
-    StrOrExpr, VSlotDirective, VueDirectives, VueImports,
+    fervid_atom, AttributeOrBinding, ComponentBinding, ElementNode, FervidAtom, Node, PatchFlags,
+    PatchHints, StartingTag, StrOrExpr, VBindDirective, VSlotDirective, VueDirectives, VueImports,
 };
 use swc_core::{
     common::{Span, DUMMY_SP},
     ecma::ast::{
-        ArrayLit, ArrowExpr, BindingIdent, BlockStmtOrExpr, CallExpr, Callee, Expr, ExprOrSpread,
-        Ident, KeyValueProp, Lit, Null, Number, ObjectLit, Pat, Prop, PropOrSpread, Str,
-        VarDeclarator,
+        ArrayLit, ArrowExpr, BindingIdent, BlockStmtOrExpr, Bool, CallExpr, Callee, Expr,
+        ExprOrSpread, Ident, KeyValueProp, Lit, Null, Number, ObjectLit, Pat, Prop, PropName,
+        PropOrSpread, Str, VarDeclarator,
     },
 };
 
 use crate::{
-    context::CodegenContext, control_flow::SlottedIterator, utils::str_or_expr_to_propname,
+    context::CodegenContext,
+    control_flow::SlottedIterator,
+    utils::{str_or_expr_to_propname, str_to_propname, to_pascal_case},
 };
 
 impl CodegenContext {
@@ -26,13 +30,8 @@ impl CodegenContext {
         let component_identifier =
             self.get_component_identifier(&component_node.starting_tag.tag_name, span);
 
-        let attributes_obj = self.generate_component_attributes(component_node);
         // TODO Apply all the directives and modifications
-        let attributes_expr = if attributes_obj.props.len() != 0 {
-            Some(Expr::Object(attributes_obj))
-        } else {
-            None
-        };
+        let attributes_expr = self.generate_component_props(component_node);
 
         let children_slots = self.generate_component_children(component_node);
 
@@ -183,6 +182,11 @@ impl CodegenContext {
         create_component_expr
     }
 
+    /// Generates `_component_name = _resolveComponent("name")` for each component
+    /// which is not statically known, e.g. registered globally or loaded lazily.
+    ///
+    /// When the component is named as the file being compiled, the second argument is `true`,
+    /// so that the runtime falls back to the component itself if it is not registered.
     pub fn generate_component_resolves(&mut self) -> Vec<VarDeclarator> {
         let mut result = Vec::new();
 
@@ -212,6 +216,27 @@ impl CodegenContext {
 
         // Key is a component as used in template, value is the assigned Js identifier
         for (component_name, component_identifier) in sorted_components.iter() {
+            let maybe_self_reference = self.is_self_reference(component_name);
+
+            let mut args = Vec::with_capacity(if maybe_self_reference { 2 } else { 1 });
+            args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: (*component_name).to_owned(),
+                    raw: None,
+                }))),
+            });
+            if maybe_self_reference {
+                args.push(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: true,
+                    }))),
+                });
+            }
+
             // _component_ident_name = resolveComponent("component-name")
             result.push(VarDeclarator {
                 span: DUMMY_SP,
@@ -226,14 +251,7 @@ impl CodegenContext {
                         sym: resolve_component_ident.to_owned(),
                         optional: false,
                     }))),
-                    args: vec![ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(Expr::Lit(Lit::Str(Str {
-                            span: DUMMY_SP,
-                            value: (*component_name).to_owned(),
-                            raw: None,
-                        }))),
-                    }],
+                    args,
                     type_args: None,
                 }))),
                 definite: false,
@@ -243,13 +261,105 @@ impl CodegenContext {
         result
     }
 
-    fn generate_component_attributes<'e>(&mut self, component_node: &'e ElementNode) -> ObjectLit {
+    /// Whether the component may reference the one being compiled,
+    /// e.g. `<FooBar>` or `<foo-bar>` in `FooBar.vue`
+    fn is_self_reference(&self, component_name: &str) -> bool {
+        self.bindings_helper
+            .self_name
+            .as_ref()
+            .is_some_and(|self_name| to_pascal_case(component_name) == to_pascal_case(self_name))
+    }
+
+    /// Generates the props of a component, or `None` when there are no props.
+    ///
+    /// `v-bind="obj"` and `v-on="obj"` are merged with the other props in the order of appearance,
+    /// e.g. `<Comp foo="bar" v-bind="$attrs">` becomes `_mergeProps({ foo: "bar" }, _ctx.$attrs)`.
+    /// Unlike on native elements, `_toHandlers` does not need to preserve the case of the events.
+    /// Sole `v-bind="$attrs"` becomes `_normalizeProps(_guardReactiveProps(_ctx.$attrs))`.
+    pub(crate) fn generate_component_props(
+        &mut self,
+        component_node: &ElementNode,
+    ) -> Option<Expr> {
+        let span = DUMMY_SP;
+        let attributes = &component_node.starting_tag.attributes;
+
+        self.generate_merged_attributes(attributes, false, span, |ctx, range, is_last| {
+            ctx.generate_component_attributes_group(component_node, range, is_last)
+                .props
+        })
+    }
+
+    /// Generates the props object of a component, `v-bind="obj"` is not included
+    pub(crate) fn generate_component_attributes(
+        &mut self,
+        component_node: &ElementNode,
+    ) -> ObjectLit {
+        let attributes_len = component_node.starting_tag.attributes.len();
+        self.generate_component_attributes_group(component_node, 0..attributes_len, true)
+    }
+
+    /// Generates the props object from the attributes in `range`.
+    /// Props from the directives (e.g. `v-model`) are only included when `with_directives` is set.
+    fn generate_component_attributes_group(
+        &mut self,
+        component_node: &ElementNode,
+        range: Range<usize>,
+        with_directives: bool,
+    ) -> ObjectLit {
         let mut result_props = Vec::new();
+        let all_attributes = &component_node.starting_tag.attributes;
+        let attributes = &all_attributes[range];
+
+        // `:style` consumed as a prop is passed as is, without normalization.
+        // When there is also a static `style`, they still need to be merged.
+        // `:class` is always normalized, same as in Vue, even when it is a prop.
+        let is_style_prop = component_node.patch_hints.is_style_prop;
+        let is_consumed_prop = |attr: &AttributeOrBinding| {
+            let AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(name)),
+                ..
+            }) = attr
+            else {
+                return false;
+            };
+            is_style_prop
+                && name == "style"
+                && !all_attributes.iter().any(|attr| {
+                    matches!(attr, AttributeOrBinding::RegularAttribute { name: regular_name, .. } if regular_name == name)
+                })
+        };
+
+        if attributes.iter().any(is_consumed_prop) {
+            let (consumed_props, other_attributes): (Vec<_>, Vec<_>) =
+                attributes.iter().cloned().partition(is_consumed_prop);
+            self.generate_attributes(&other_attributes, &mut result_props);
 
-        self.generate_attributes(&component_node.starting_tag.attributes, &mut result_props);
+            for consumed_prop in consumed_props {
+                let AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str(name)),
+                    value,
+                    span,
+                    ..
+                }) = consumed_prop
+                else {
+                    unreachable!()
+                };
+                result_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: str_to_propname(&name, span),
+                    value,
+                }))));
+            }
+        } else {
+            self.generate_attributes(attributes, &mut result_props);
+        }
 
         // Process directives
-        if let Some(ref directives) = component_node.starting_tag.directives {
+        let directives = component_node
+            .starting_tag
+            .directives
+            .as_ref()
+            .filter(|_| with_directives);
+        if let Some(directives) = directives {
             // `v-model`s
             for v_model in directives.v_model.iter() {
                 self.generate_v_model_for_component(v_model, &mut result_props);
@@ -438,6 +548,29 @@ impl CodegenContext {
             ));
         }
 
+        // `_: 1 /* STABLE */` or `_: 2 /* DYNAMIC */`, which makes the runtime re-create the slots
+        let slot_flag = if component_node
+            .patch_hints
+            .flags
+            .contains(PatchFlags::DynamicSlots)
+        {
+            2.0
+        } else {
+            1.0
+        };
+        result_static_slots.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident {
+                span: DUMMY_SP,
+                sym: fervid_atom!("_"),
+                optional: false,
+            }),
+            value: Box::new(Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: slot_flag,
+                raw: None,
+            }))),
+        }))));
+
         // TODO Add `createSlots` if needed
         Some(Expr::Object(ObjectLit {
             span: component_span,
@@ -509,7 +642,7 @@ impl CodegenContext {
     }
 
     /// Creates the SWC identifier from a tag name. Will fetch from cache if present
-    fn get_component_identifier(&mut self, tag_name: &FervidAtom, span: Span) -> Expr {
+    pub(crate) fn get_component_identifier(&mut self, tag_name: &FervidAtom, span: Span) -> Expr {
         // Cached
         let existing_component_binding = self.bindings_helper.components.get(tag_name);
         match existing_component_binding {
@@ -636,10 +769,68 @@ fn null(span: Span) -> Box<Expr> {
 mod tests {
     use fervid_core::{ElementKind, Interpolation, Node, StartingTag};
 
-    use crate::test_utils::{js, regular_attribute, v_bind_attribute};
+    use crate::test_utils::{
+        js, regular_attribute, v_bind_attribute, v_bind_spread_attribute, v_on_attribute,
+        v_on_spread_attribute,
+    };
 
     use super::*;
 
+    #[test]
+    fn it_generates_component_resolves() {
+        let test = |is_prod: bool, self_name: Option<&str>, expected: &[&str]| {
+            let mut ctx = CodegenContext::default();
+            ctx.bindings_helper.is_prod = is_prod;
+            ctx.bindings_helper.self_name = self_name.map(FervidAtom::from);
+
+            // import MyButton from './MyButton.vue'
+            // const LazyChart = defineAsyncComponent(() => import('./LazyChart.vue'))
+            ctx.bindings_helper.components.insert(
+                "MyButton".into(),
+                ComponentBinding::Resolved(js("MyButton")),
+            );
+            ctx.bindings_helper.components.insert(
+                "LazyChart".into(),
+                ComponentBinding::Resolved(js("LazyChart")),
+            );
+
+            // <global-dialog /> and <RouterView />
+            ctx.get_component_identifier(&"global-dialog".into(), DUMMY_SP);
+            ctx.get_component_identifier(&"RouterView".into(), DUMMY_SP);
+
+            let resolves: Vec<String> = ctx
+                .generate_component_resolves()
+                .into_iter()
+                .map(crate::test_utils::to_str)
+                .collect();
+            assert_eq!(resolves, expected);
+        };
+
+        // Only components missing from imports are resolved, same in development and production
+        for is_prod in [false, true] {
+            test(
+                is_prod,
+                Some("App"),
+                &[
+                    r#"_component_RouterView=_resolveComponent("RouterView")"#,
+                    r#"_component_global_dialog=_resolveComponent("global-dialog")"#,
+                ],
+            );
+        }
+
+        // `<global-dialog>` in `GlobalDialog.vue` may reference the component itself
+        for is_prod in [false, true] {
+            test(
+                is_prod,
+                Some("GlobalDialog"),
+                &[
+                    r#"_component_RouterView=_resolveComponent("RouterView")"#,
+                    r#"_component_global_dialog=_resolveComponent("global-dialog",true)"#,
+                ],
+            );
+        }
+    }
+
     #[test]
     fn it_generates_basic_usage() {
         // <test-component></test-component>
@@ -703,6 +894,178 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_generates_attrs_forwarding() {
+        let test = |attributes: Vec<AttributeOrBinding>, expected: &str| {
+            test_out(
+                ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "test-component".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    kind: ElementKind::Component,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                expected,
+                false,
+            )
+        };
+
+        // <test-component v-bind="$attrs" />
+        test(
+            vec![v_bind_spread_attribute("$attrs")],
+            r#"_createVNode(_component_test_component,_normalizeProps(_guardReactiveProps($attrs)))"#,
+        );
+
+        // <test-component />
+        test(vec![], r#"_createVNode(_component_test_component)"#);
+
+        // <test-component foo="bar" v-bind="$attrs" :baz="qux" />
+        test(
+            vec![
+                regular_attribute("foo", "bar"),
+                v_bind_spread_attribute("$attrs"),
+                v_bind_attribute("baz", "qux"),
+            ],
+            r#"_createVNode(_component_test_component,_mergeProps({foo:"bar"},$attrs,{baz:qux}))"#,
+        );
+
+        // <test-component v-bind="$attrs" foo="bar" />
+        test(
+            vec![
+                v_bind_spread_attribute("$attrs"),
+                regular_attribute("foo", "bar"),
+            ],
+            r#"_createVNode(_component_test_component,_mergeProps($attrs,{foo:"bar"}))"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_v_on_object() {
+        let test = |attributes: Vec<AttributeOrBinding>, expected: &str| {
+            test_out(
+                ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "test-component".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    kind: ElementKind::Component,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                expected,
+                false,
+            )
+        };
+
+        // <test-component v-on="handlers" />
+        test(
+            vec![v_on_spread_attribute("handlers")],
+            r#"_createVNode(_component_test_component,_toHandlers(handlers))"#,
+        );
+
+        // <test-component v-on="handlers" @click="onClick" />
+        test(
+            vec![
+                v_on_spread_attribute("handlers"),
+                v_on_attribute("onClick", "onClick"),
+            ],
+            r#"_createVNode(_component_test_component,_mergeProps(_toHandlers(handlers),{onClick:onClick}))"#,
+        );
+
+        // <test-component foo="bar" v-on="handlers" v-bind="$attrs" />
+        test(
+            vec![
+                regular_attribute("foo", "bar"),
+                v_on_spread_attribute("handlers"),
+                v_bind_spread_attribute("$attrs"),
+            ],
+            r#"_createVNode(_component_test_component,_mergeProps({foo:"bar"},_toHandlers(handlers),$attrs))"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_class_and_style_props() {
+        // <my-comp class="static" :class="classes" :style="styles" />
+        let test = |attributes: Vec<AttributeOrBinding>,
+                    is_class_prop: bool,
+                    is_style_prop: bool,
+                    expected: &str| {
+            test_out(
+                ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "my-comp".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    kind: ElementKind::Component,
+                    patch_hints: PatchHints {
+                        is_class_prop,
+                        is_style_prop,
+                        ..Default::default()
+                    },
+                    span: DUMMY_SP,
+                },
+                expected,
+                false,
+            )
+        };
+
+        // Fallthrough
+        test(
+            vec![v_bind_attribute("class", "classes")],
+            false,
+            false,
+            r#"_createVNode(_component_my_comp,{class:_normalizeClass(classes)})"#,
+        );
+        test(
+            vec![v_bind_attribute("style", "styles")],
+            false,
+            false,
+            r#"_createVNode(_component_my_comp,{style:_normalizeStyle(styles)})"#,
+        );
+
+        // `class` is normalized even when consumed as a prop
+        test(
+            vec![v_bind_attribute("class", "classes")],
+            true,
+            false,
+            r#"_createVNode(_component_my_comp,{class:_normalizeClass(classes)})"#,
+        );
+
+        // Consumed as props
+        test(
+            vec![
+                v_bind_attribute("class", "classes"),
+                v_bind_attribute("style", "styles"),
+                regular_attribute("foo", "bar"),
+            ],
+            false,
+            true,
+            r#"_createVNode(_component_my_comp,{foo:"bar",class:_normalizeClass(classes),style:styles})"#,
+        );
+
+        // Static and bound `style` are still merged
+        test(
+            vec![
+                regular_attribute("style", "color: red"),
+                v_bind_attribute("style", "styles"),
+            ],
+            false,
+            true,
+            r#"_createVNode(_component_my_comp,{style:_normalizeStyle([{color:"red"},styles])})"#,
+        );
+    }
+
     #[test]
     fn it_generates_default_slot() {
         // <test-component>hello from component<div>hello from div</div></test-component>
@@ -733,7 +1096,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")])})"#,
+            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")]),_:1})"#,
             false,
         );
 
@@ -784,7 +1147,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")])})"#,
+            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")]),_:1})"#,
             false,
         );
     }
@@ -838,7 +1201,50 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")])})"#,
+            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")]),_:1})"#,
+            false,
+        );
+    }
+
+    #[test]
+    fn it_generates_dynamic_slot_name() {
+        // <test-component>
+        //   <template v-slot:[name]>hello</template>
+        // </test-component>
+        test_out(
+            ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: "test-component".into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children: vec![Node::Element(ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "template".into(),
+                        attributes: vec![],
+                        directives: Some(Box::new(VueDirectives {
+                            v_slot: Some(VSlotDirective {
+                                slot_name: Some(StrOrExpr::Expr(js("name"))),
+                                value: None,
+                            }),
+                            ..Default::default()
+                        })),
+                    },
+                    children: vec![Node::Text("hello".into(), DUMMY_SP)],
+                    template_scope: 0,
+                    kind: ElementKind::Element,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                template_scope: 0,
+                kind: ElementKind::Component,
+                patch_hints: PatchHints {
+                    flags: PatchFlags::DynamicSlots.into(),
+                    ..Default::default()
+                },
+                span: DUMMY_SP,
+            },
+            r#"_createVNode(_component_test_component,null,{[name]:_withCtx(()=>[_createTextVNode("hello")]),_:2},1024)"#,
             false,
         );
     }
@@ -921,7 +1327,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot "+_toDisplayString(one),1)]),baz:_withCtx(()=>[_createTextVNode("hello from slot "),_createElementVNode("b",null,"two")])})"#,
+            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot "+_toDisplayString(one),1)]),baz:_withCtx(()=>[_createTextVNode("hello from slot "),_createElementVNode("b",null,"two")]),_:1})"#,
             false,
         );
     }
@@ -977,7 +1383,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")])})"#,
+            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")]),_:1})"#,
             false,
         );
 
@@ -1049,7 +1455,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from default"),_createElementVNode("div",null,"hello from div")]),"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")])})"#,
+            r#"_createVNode(_component_test_component,null,{"default":_withCtx(()=>[_createTextVNode("hello from default"),_createElementVNode("div",null,"hello from div")]),"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),_:1})"#,
             false,
         );
 
@@ -1102,7 +1508,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")])})"#,
+            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from component"),_createElementVNode("div",null,"hello from div")]),_:1})"#,
             false,
         );
     }
@@ -1196,7 +1602,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from default"),_createElementVNode("div",null,"hello from div")]),baz:_withCtx(()=>[_createTextVNode("hello from baz")])})"#,
+            r#"_createVNode(_component_test_component,null,{"foo-bar":_withCtx(()=>[_createTextVNode("hello from slot")]),"default":_withCtx(()=>[_createTextVNode("hello from default"),_createElementVNode("div",null,"hello from div")]),baz:_withCtx(()=>[_createTextVNode("hello from baz")]),_:1})"#,
             false,
         );
     }
@@ -1,10 +1,27 @@
+use fervid_core::{FervidAtom, SsrImportsSet};
 use fervid_transform::BindingsHelper;
+use swc_core::ecma::ast::Expr;
 
 #[derive(Debug, Default)]
 pub struct CodegenContext {
     pub bindings_helper: BindingsHelper,
     pub is_cache_disabled: bool,
     pub next_cache_index: u8,
+    /// Generate the `ssrRender` function instead of the client `render`
+    pub is_ssr: bool,
+    /// Helpers from `vue/server-renderer` used by the SSR render function
+    pub ssr_imports: SsrImportsSet,
+    /// Value of the `v-model` of the `<select>` currently rendered by SSR,
+    /// its `<option>`s are rendered as `selected` when they match it
+    pub ssr_select_model: Option<Box<Expr>>,
+    /// Move the static subtrees out of the render function, so that they are created only once.
+    /// Must be disabled with `<style scoped>`, as hoisted vnodes do not get the `__scopeId`.
+    pub hoist_static: bool,
+    /// Static subtrees, each becomes a `const _hoisted_N` at the module level
+    pub hoisted: Vec<Expr>,
+    /// Scope of the `<style scoped>` blocks, e.g. `data-v-7ba5bd90`.
+    /// The client runtime applies it using `__scopeId`, thus only the SSR markup includes it
+    pub scope_id: Option<FervidAtom>,
 }
 
 impl CodegenContext {
@@ -8,7 +8,7 @@ use swc_core::{
     },
 };
 
-use crate::context::CodegenContext;
+use crate::{context::CodegenContext, hoisting::is_static_element};
 
 type TextNodesConcatenationVec = SmallVec<[Expr; 3]>;
 
@@ -35,13 +35,21 @@ impl CodegenContext {
         element_node: &ElementNode,
         wrap_in_block: bool,
     ) -> Expr {
-        // `v-once` logic is common for all
-        let has_v_once = element_node
-            .starting_tag
-            .directives
-            .as_ref()
-            .and_then(|directives| directives.v_once)
-            .is_some();
+        // Static subtrees are created once outside of the render function.
+        // Blocks are never hoisted, because they are opened anew on each render
+        if self.hoist_static && !wrap_in_block && is_static_element(element_node) {
+            return self.generate_hoisted_element(element_node);
+        }
+
+        // `v-once` logic is common for all.
+        // Nested `v-once` is ignored, because the outer one already caches the whole subtree
+        let has_v_once = !self.is_cache_disabled
+            && element_node
+                .starting_tag
+                .directives
+                .as_ref()
+                .and_then(|directives| directives.v_once)
+                .is_some();
 
         // Disable caching if `v-once` is present
         let old_is_cache_disabled = self.is_cache_disabled;
@@ -49,6 +57,18 @@ impl CodegenContext {
             self.is_cache_disabled = true;
         }
 
+        // Memoized elements are blocks, so that a memo hit skips patching the whole subtree.
+        // For `v-for`, the memo is generated in the `renderList` callback instead
+        let is_memoized_element = matches!(element_node.kind, ElementKind::Element)
+            && element_node
+                .starting_tag
+                .directives
+                .as_ref()
+                .is_some_and(|directives| {
+                    directives.v_memo.is_some() && directives.v_for.is_none()
+                });
+        let wrap_in_block = wrap_in_block || is_memoized_element;
+
         // Generate the relevant render code depending on ElementKind
         let mut result = match element_node.kind {
             ElementKind::Builtin(builtin_type) => self.generate_builtin(element_node, builtin_type),
@@ -36,7 +36,7 @@ impl CodegenContext {
         let else_expr = if let Some(ref else_node) = conditional_seq.else_node {
             self.generate_element_or_component(else_node, should_wrap_in_block(&else_node))
         } else {
-            self.generate_comment_vnode("v-if", DUMMY_SP)
+            self.generate_v_if_placeholder(DUMMY_SP)
         };
         conditional_exprs.push(Box::new(else_expr));
 
@@ -87,7 +87,7 @@ fn should_wrap_in_block(element_node: &ElementNode) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{Conditional, ElementKind, ElementNode, Node, StartingTag};
+    use fervid_core::{Conditional, ElementKind, ElementNode, Node, StartingTag, VueDirectives};
 
     use crate::test_utils::js;
 
@@ -116,7 +116,7 @@ mod tests {
                 else_if_nodes: vec![],
                 else_node: None,
             },
-            r#"foo||true?(_openBlock(),_createElementBlock("h1",null,"hello")):_createCommentVNode("v-if")"#,
+            r#"foo||true?(_openBlock(),_createElementBlock("h1",null,"hello")):_createCommentVNode("v-if",true)"#,
         )
     }
 
@@ -215,7 +215,7 @@ mod tests {
                 ],
                 else_node: None,
             },
-            r#"foo?(_openBlock(),_createElementBlock("h1",null,"hello")):true?(_openBlock(),_createElementBlock("h2",null,"hi")):undefined?(_openBlock(),_createElementBlock("h3",null,"bye")):_createCommentVNode("v-if")"#,
+            r#"foo?(_openBlock(),_createElementBlock("h1",null,"hello")):true?(_openBlock(),_createElementBlock("h2",null,"hi")):undefined?(_openBlock(),_createElementBlock("h3",null,"bye")):_createCommentVNode("v-if",true)"#,
         )
     }
 
@@ -291,6 +291,63 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_prod_placeholder() {
+        // <h1 v-if="foo">hello</h1>
+        let mut ctx = CodegenContext::default();
+        ctx.bindings_helper.is_prod = true;
+        let out = ctx.generate_conditional_seq(&ConditionalNodeSequence {
+            if_node: Box::new(Conditional {
+                condition: *js("foo"),
+                node: ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "h1".into(),
+                        attributes: vec![],
+                        directives: None,
+                    },
+                    children: vec![Node::Text("hello".into(), DUMMY_SP)],
+                    template_scope: 0,
+                    kind: ElementKind::Element,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+            }),
+            else_if_nodes: vec![],
+            else_node: None,
+        });
+        assert_eq!(
+            crate::test_utils::to_str(out),
+            r#"foo?(_openBlock(),_createElementBlock("h1",null,"hello")):_createCommentVNode("",true)"#
+        );
+    }
+
+    #[test]
+    fn it_does_not_generate_placeholder_for_v_show() {
+        // <h1 v-show="foo">hello</h1>
+        let mut ctx = CodegenContext::default();
+        let out = ctx.generate_element_vnode(
+            &ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: "h1".into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_show: Some(js("foo")),
+                        ..Default::default()
+                    })),
+                },
+                children: vec![Node::Text("hello".into(), DUMMY_SP)],
+                template_scope: 0,
+                kind: ElementKind::Element,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            false,
+        );
+        let out = crate::test_utils::to_str(out);
+        assert!(out.contains("_vShow"));
+        assert!(!out.contains("_createCommentVNode"));
+    }
+
     fn test_out(input: ConditionalNodeSequence, expected: &str) {
         let mut ctx = CodegenContext::default();
         let out = ctx.generate_conditional_seq(&input);
@@ -4,16 +4,16 @@ use fervid_core::{
 use swc_core::{
     atoms::Atom,
     common::{
-        collections::AHashMap, source_map::SourceMapGenConfig, BytePos, FileName, SourceMap,
-        DUMMY_SP,
+        collections::AHashMap, comments::Comments, source_map::SourceMapGenConfig, BytePos,
+        FileName, SourceMap, DUMMY_SP,
     },
     ecma::{
         ast::{
             ArrowExpr, AssignExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr, Callee,
             Decl, ExportDefaultExpr, Expr, ExprOrSpread, ExprStmt, Function, GetterProp, Ident,
             ImportDecl, MethodProp, Module, ModuleDecl, ModuleItem, ObjectLit, Param, Pat, Prop,
-            PropName, PropOrSpread, ReturnStmt, SetterProp, Stmt, Str, VarDecl, VarDeclKind,
-            VarDeclarator,
+            PropName, PropOrSpread, ReturnStmt, SetterProp, Stmt, Str, TsKeywordType,
+            TsKeywordTypeKind, TsType, TsTypeAnn, VarDecl, VarDeclKind, VarDeclarator,
         },
         visit::{noop_visit_type, Visit, VisitWith},
     },
@@ -26,6 +26,13 @@ impl CodegenContext {
     // TODO Generation mode? Is it relevant?
     // TODO Generating module? Or instead taking a module? Or generating an expression and merging?
     pub fn generate_sfc_template(&mut self, sfc_template: &SfcTemplateBlock) -> Option<Expr> {
+        // SSR generates a complete `(_ctx, _push, _parent, _attrs) => {}` arrow
+        if self.is_ssr {
+            return self
+                .generate_ssr_render_arrow(sfc_template)
+                .map(Expr::Arrow);
+        }
+
         // #11: Optimization: multiple template roots
         // and all are text nodes (must be ensured by Transformer),
         // generate node sequence
@@ -62,7 +69,11 @@ impl CodegenContext {
             match template_generation_mode {
                 // Generates the render expression and appends it to the end of the `setup` function.
                 TemplateGenerationMode::Inline => {
-                    let render_arrow = self.generate_render_arrow(template_expr);
+                    let render_arrow = match template_expr {
+                        // SSR render arrow is already complete
+                        Expr::Arrow(ssr_render_arrow) if self.is_ssr => ssr_render_arrow,
+                        template_expr => self.generate_render_arrow(template_expr),
+                    };
 
                     let setup_function = synthetic_setup_fn.get_or_insert_with(|| {
                         Box::new(Function {
@@ -90,8 +101,14 @@ impl CodegenContext {
 
                 // Generates the render Function to be used as a property in exported object.
                 // `render(_ctx, _cache, $props, $setup, $data, $options) { /*...*/ }`
+                // or `ssrRender(_ctx, _push, _parent, _attrs, $props, $setup, $data, $options) { /*...*/ }`
                 TemplateGenerationMode::RenderFn => {
-                    let render_fn = self.generate_render_fn(template_expr);
+                    let (render_fn_name, render_fn) = match template_expr {
+                        Expr::Arrow(ssr_render_arrow) if self.is_ssr => {
+                            ("ssrRender", self.generate_ssr_render_fn(ssr_render_arrow))
+                        }
+                        template_expr => ("render", self.generate_render_fn(template_expr)),
+                    };
 
                     // When a synthetic setup function is present,
                     // we need to return bindings as its last statement
@@ -118,7 +135,7 @@ impl CodegenContext {
                         .push(PropOrSpread::Prop(Box::new(Prop::Method(MethodProp {
                             key: PropName::Ident(Ident {
                                 span: DUMMY_SP,
-                                sym: FervidAtom::from("render"),
+                                sym: FervidAtom::from(render_fn_name),
                                 optional: false,
                             }),
                             function: Box::new(render_fn),
@@ -141,10 +158,28 @@ impl CodegenContext {
         }
 
         // Add the `setup` function to the exported object
-        if let Some(setup_fn) = synthetic_setup_fn {
+        if let Some(mut setup_fn) = synthetic_setup_fn {
             match setup_fn.body {
                 // Append only when function has a body and it is not empty
                 Some(ref b) if !b.stmts.is_empty() => {
+                    // In TS, `setup` which returns neither render function nor bindings
+                    // is annotated as `void`. `defineExpose` does not affect this,
+                    // because the exposed values are passed to `expose()` and not returned.
+                    let has_return = matches!(b.stmts.last(), Some(Stmt::Return(_)));
+                    if self.bindings_helper.is_ts
+                        && !has_return
+                        && !setup_fn.is_async
+                        && setup_fn.return_type.is_none()
+                    {
+                        setup_fn.return_type = Some(Box::new(TsTypeAnn {
+                            span: DUMMY_SP,
+                            type_ann: Box::new(TsType::TsKeywordType(TsKeywordType {
+                                span: DUMMY_SP,
+                                kind: TsKeywordTypeKind::TsVoidKeyword,
+                            })),
+                        }));
+                    }
+
                     sfc_export_obj
                         .props
                         .push(PropOrSpread::Prop(Box::new(Prop::Method(MethodProp {
@@ -233,6 +268,28 @@ impl CodegenContext {
                 })));
         }
 
+        // Append the SSR helpers imports
+        let used_ssr_imports = self.generate_ssr_imports();
+        if !used_ssr_imports.is_empty() {
+            script
+                .body
+                .push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: used_ssr_imports,
+                    src: Box::new(Str {
+                        span: DUMMY_SP,
+                        value: FervidAtom::from("vue/server-renderer"),
+                        raw: None,
+                    }),
+                    type_only: false,
+                    with: None,
+                    phase: Default::default(),
+                })));
+        }
+
+        // Append the hoisted static subtrees
+        script.body.extend(self.generate_hoisted_decls());
+
         // Append the default export/const
         script.body.push(gen_default_as);
 
@@ -516,6 +573,7 @@ impl CodegenContext {
         source: &str,
         module: &T,
         filename: FileName,
+        comments: Option<&dyn Comments>,
         generate_source_map: bool,
         minify: bool,
     ) -> (String, Option<String>)
@@ -543,7 +601,7 @@ impl CodegenContext {
 
             let mut emitter = Emitter {
                 cfg: emitter_cfg,
-                comments: None,
+                comments,
                 wr: writer,
                 cm: cm.clone(),
             };
@@ -618,3 +676,74 @@ impl Visit for IdentCollector {
         self.names.insert(ident.span.lo, ident.sym.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fervid_transform::BindingsHelper;
+    use swc_core::ecma::ast::{Lit, Null};
+
+    use crate::test_utils::{js, to_str};
+
+    use super::*;
+
+    #[test]
+    fn it_annotates_setup_return_type_in_ts() {
+        let check = |is_ts: bool, setup_body: &str, has_template: bool| {
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.is_ts = is_ts;
+            bindings_helper.template_generation_mode = TemplateGenerationMode::Inline;
+            let mut ctx = CodegenContext::with_bindings_helper(bindings_helper);
+
+            let setup_fn = Box::new(Function {
+                params: vec![],
+                decorators: vec![],
+                span: DUMMY_SP,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Expr(ExprStmt {
+                        span: DUMMY_SP,
+                        expr: js(setup_body),
+                    })],
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            });
+            let template_expr = has_template.then(|| Expr::Lit(Lit::Null(Null { span: DUMMY_SP })));
+
+            let module = ctx.generate_module(
+                template_expr,
+                Module {
+                    span: DUMMY_SP,
+                    body: vec![],
+                    shebang: None,
+                },
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                },
+                Some(setup_fn),
+                None,
+            );
+
+            to_str(module)
+        };
+
+        // `defineExpose`, exposed values are not returned
+        let out = check(true, "__expose({ count })", false);
+        assert!(out.contains("setup():void{__expose({count})"));
+
+        // No `defineExpose`
+        let out = check(true, "console.log(1)", false);
+        assert!(out.contains("setup():void{"));
+
+        // Render function is returned, its type is inferred
+        let out = check(true, "console.log(1)", true);
+        assert!(out.contains("setup(){"));
+
+        // Not TypeScript
+        let out = check(false, "console.log(1)", false);
+        assert!(out.contains("setup(){"));
+    }
+}
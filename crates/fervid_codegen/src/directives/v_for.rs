@@ -442,4 +442,63 @@ mod tests {
             "(_openBlock(),_createElementBlock(_Fragment,null,_renderList(3,(item,__,___,_cached)=>{const _memo=[msg.value];if(_cached&&_isMemoSame(_cached,_memo))return _cached;const _item=_createElementVNode(\"div\");_item.memo=_memo;return _item;},_cache,0),64))"
         );
     }
+
+    #[test]
+    fn it_generates_v_for_destructure() {
+        let mut ctx = CodegenContext::default();
+
+        // `<div v-for="[a, b = 0] in list" :key="a"></div>`
+        let v_for = VForDirective {
+            iterable: js("list"),
+            itervar: js("[a, b = 0]"),
+            patch_flags: PatchFlags::KeyedFragment.into(),
+            span: DUMMY_SP,
+        };
+
+        let res = ctx.generate_v_for(&v_for, js("_createElementVNode(\"div\",{key:a})"));
+
+        assert_eq!(
+            crate::test_utils::to_str(res),
+            "(_openBlock(),_createElementBlock(_Fragment,null,_renderList(list,([a,b=0])=>_createElementVNode(\"div\",{key:a})),128))"
+        );
+    }
+
+    #[test]
+    fn it_passes_v_for_iterables_through() {
+        // `_renderList` handles any iterable at runtime, the source is never transformed
+        let check = |iterable: &str, expected_iterable: &str| {
+            let mut ctx = CodegenContext::default();
+
+            // `<div v-for="item in <iterable>"></div>`
+            let v_for = VForDirective {
+                iterable: js(iterable),
+                itervar: js("item"),
+                patch_flags: PatchFlags::UnkeyedFragment.into(),
+                span: DUMMY_SP,
+            };
+
+            let res = ctx.generate_v_for(&v_for, js("_createElementVNode(\"div\")"));
+
+            assert_eq!(
+                crate::test_utils::to_str(res),
+                format!("(_openBlock(),_createElementBlock(_Fragment,null,_renderList({expected_iterable},(item)=>_createElementVNode(\"div\")),256))")
+            );
+        };
+
+        // String
+        check("'hello'", "\"hello\"");
+
+        // Number
+        check("10", "10");
+
+        // Array
+        check("[1, 2, 3]", "[1,2,3]");
+        check("list", "list");
+
+        // Object
+        check("{ a: 1, b: 2 }", "{a:1,b:2}");
+
+        // Generator function call
+        check("generate()", "generate()");
+    }
 }
@@ -28,9 +28,13 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{ElementKind, ElementNode, StartingTag, VueDirectives};
+    use fervid_core::{
+        ElementKind, ElementNode, PatchFlags, PatchHints, StartingTag, VueDirectives,
+    };
     use swc_core::ecma::ast::BinExpr;
 
+    use crate::test_utils::js;
+
     use super::*;
 
     #[test]
@@ -107,6 +111,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_v_html_with_patch_flags() {
+        test_out(
+            // <div v-html="rawHtml" />
+            ElementNode {
+                children: vec![],
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_html: Some(js("rawHtml")),
+                        ..Default::default()
+                    })),
+                },
+                template_scope: 0,
+                patch_hints: PatchHints {
+                    flags: PatchFlags::Props.into(),
+                    props: vec![fervid_atom!("innerHTML")],
+                    ..Default::default()
+                },
+                span: DUMMY_SP,
+            },
+            r#"_createElementVNode("div",{innerHTML:rawHtml},null,8,["innerHTML"])"#,
+            false,
+        )
+    }
+
     fn test_out(input: ElementNode, expected: &str, wrap_in_block: bool) {
         let is_component = matches!(input.kind, ElementKind::Component);
 
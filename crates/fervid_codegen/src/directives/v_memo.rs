@@ -74,10 +74,30 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::js;
+    use fervid_core::{ElementKind, ElementNode, Interpolation, Node, StartingTag, VueDirectives};
+
+    use crate::test_utils::{js, to_str};
 
     use super::*;
 
+    fn memoized(tag_name: &str, kind: ElementKind, memo: &str, children: Vec<Node>) -> ElementNode {
+        ElementNode {
+            starting_tag: StartingTag {
+                tag_name: tag_name.into(),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_memo: Some(js(memo)),
+                    ..Default::default()
+                })),
+            },
+            children,
+            template_scope: 0,
+            kind,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        }
+    }
+
     #[test]
     fn it_generates_v_memo() {
         let mut ctx = CodegenContext::default();
@@ -85,8 +105,62 @@ mod tests {
         let res = ctx.generate_v_memo(js("[msg.value]"), js("_createElementVNode(\"div\")"));
 
         assert_eq!(
-            crate::test_utils::to_str(res),
+            to_str(res),
             "_withMemo([msg.value],()=>_createElementVNode(\"div\"),_cache,0)"
         );
     }
+
+    #[test]
+    fn it_generates_v_memo_on_element() {
+        // <div v-memo="[a]"></div>
+        let mut ctx = CodegenContext::default();
+        let element = memoized("div", ElementKind::Element, "[a]", vec![]);
+
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&element, false)),
+            "_withMemo([a],()=>(_openBlock(),_createElementBlock(\"div\")),_cache,0)"
+        );
+    }
+
+    #[test]
+    fn it_generates_nested_v_memo() {
+        // <div v-memo="[a]"><span v-memo="[b]">{{ b }}</span></div>
+        let mut ctx = CodegenContext::default();
+        let interpolation = Node::Interpolation(Interpolation {
+            value: js("b"),
+            template_scope: 0,
+            patch_flag: false,
+            span: DUMMY_SP,
+        });
+        let span = memoized("span", ElementKind::Element, "[b]", vec![interpolation]);
+        let div = memoized(
+            "div",
+            ElementKind::Element,
+            "[a]",
+            vec![Node::Element(span)],
+        );
+
+        // Inner memo is generated first and takes the first cache entry
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&div, false)),
+            concat!(
+                "_withMemo([a],()=>(_openBlock(),_createElementBlock(\"div\",null,[",
+                "_withMemo([b],()=>(_openBlock(),_createElementBlock(\"span\",null,_toDisplayString(b))),_cache,0)",
+                "])),_cache,1)"
+            )
+        );
+    }
+
+    #[test]
+    fn it_generates_v_memo_on_component() {
+        // <Comp v-memo="[a]" />
+        let mut ctx = CodegenContext::default();
+        let component = memoized("Comp", ElementKind::Component, "[a]", vec![]);
+
+        // Components are not converted to blocks
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&component, false)),
+            "_withMemo([a],()=>_createVNode(_component_Comp),_cache,0)"
+        );
+    }
 }
@@ -108,7 +108,12 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_utils::js;
+    use fervid_core::{
+        ElementKind, ElementNode, Interpolation, Node, PatchFlags, StartingTag, VForDirective,
+        VueDirectives,
+    };
+
+    use crate::test_utils::{js, to_str, v_bind_attribute};
 
     use super::*;
 
@@ -122,15 +127,142 @@ mod tests {
         // First `v-once`
         let v_once_expr = ctx.generate_v_once(item_render_expr.to_owned());
         assert_eq!(
-            crate::test_utils::to_str(v_once_expr),
+            to_str(v_once_expr),
             "_cache[0]||(_setBlockTracking(-1),_cache[0]=_createElementVNode(\"div\"),_setBlockTracking(1),_cache[0])"
         );
 
         // Second `v-once` with increased cache index
         let v_once_expr = ctx.generate_v_once(item_render_expr);
         assert_eq!(
-            crate::test_utils::to_str(v_once_expr),
+            to_str(v_once_expr),
             "_cache[1]||(_setBlockTracking(-1),_cache[1]=_createElementVNode(\"div\"),_setBlockTracking(1),_cache[1])"
         );
     }
+
+    #[test]
+    fn it_caches_v_for_with_v_once() {
+        // `<li v-for="item in list" [v-once]></li>`
+        let li = |has_v_once: bool| ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: "li".into(),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_for: Some(VForDirective {
+                        iterable: js("list"),
+                        itervar: js("item"),
+                        patch_flags: PatchFlags::UnkeyedFragment.into(),
+                        span: DUMMY_SP,
+                    }),
+                    v_once: if has_v_once { Some(()) } else { None },
+                    ..Default::default()
+                })),
+            },
+            children: vec![],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+
+        let mut ctx = CodegenContext::default();
+
+        // The whole list is cached, not the individual items
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&li(true), false)),
+            "_cache[0]||(_setBlockTracking(-1),_cache[0]=(_openBlock(),_createElementBlock(_Fragment,null,_renderList(list,(item)=>_createElementVNode(\"li\")),256)),_setBlockTracking(1),_cache[0])"
+        );
+
+        // Cache index is incremented
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&li(true), false)),
+            "_cache[1]||(_setBlockTracking(-1),_cache[1]=(_openBlock(),_createElementBlock(_Fragment,null,_renderList(list,(item)=>_createElementVNode(\"li\")),256)),_setBlockTracking(1),_cache[1])"
+        );
+
+        // Without `v-once` the list is re-rendered
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&li(false), false)),
+            "(_openBlock(),_createElementBlock(_Fragment,null,_renderList(list,(item)=>_createElementVNode(\"li\")),256))"
+        );
+        assert_eq!(ctx.next_cache_index, 2);
+    }
+
+    #[test]
+    fn it_caches_static_v_once() {
+        let mut ctx = CodegenContext::default();
+
+        // `<div v-once>hello</div>`
+        let div = ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: "div".into(),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_once: Some(()),
+                    ..Default::default()
+                })),
+            },
+            children: vec![Node::Text("hello".into(), DUMMY_SP)],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&div, false)),
+            "_cache[0]||(_setBlockTracking(-1),_cache[0]=_createElementVNode(\"div\",null,\"hello\"),_setBlockTracking(1),_cache[0])"
+        );
+    }
+
+    #[test]
+    fn it_caches_dynamic_v_once() {
+        let mut ctx = CodegenContext::default();
+
+        // `<div v-once :title="msg">{{ msg }}<span v-once>{{ msg }}</span></div>`
+        let interpolation = || {
+            Node::Interpolation(Interpolation {
+                value: js("msg"),
+                template_scope: 0,
+                patch_flag: true,
+                span: DUMMY_SP,
+            })
+        };
+        let span = ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: "span".into(),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_once: Some(()),
+                    ..Default::default()
+                })),
+            },
+            children: vec![interpolation()],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+        let div = ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: "div".into(),
+                attributes: vec![v_bind_attribute("title", "msg")],
+                directives: Some(Box::new(VueDirectives {
+                    v_once: Some(()),
+                    ..Default::default()
+                })),
+            },
+            children: vec![interpolation(), Node::Element(span)],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        };
+
+        // `msg` is only read when the cache is empty, nested `v-once` is not cached separately
+        assert_eq!(
+            to_str(ctx.generate_element_or_component(&div, false)),
+            "_cache[0]||(_setBlockTracking(-1),_cache[0]=_createElementVNode(\"div\",{title:msg},[_createTextVNode(_toDisplayString(msg),1),_createElementVNode(\"span\",null,_toDisplayString(msg))]),_setBlockTracking(1),_cache[0])"
+        );
+        assert_eq!(ctx.next_cache_index, 1);
+        assert!(!ctx.is_cache_disabled);
+    }
 }
@@ -1,7 +1,9 @@
-use fervid_core::fervid_atom;
+use fervid_core::{fervid_atom, VueImports};
 use swc_core::{
     common::DUMMY_SP,
-    ecma::ast::{Expr, Ident, KeyValueProp, Prop, PropName, PropOrSpread},
+    ecma::ast::{
+        CallExpr, Callee, Expr, ExprOrSpread, Ident, KeyValueProp, Prop, PropName, PropOrSpread,
+    },
 };
 
 use crate::CodegenContext;
@@ -10,22 +12,54 @@ impl CodegenContext {
     /// Generates the `v-text` directive
     ///
     /// # Example
-    /// `v-text="foo + bar"` will generate `textContent: foo + bar` (without transforms)
-    pub fn generate_v_text(&self, expr: &Expr) -> PropOrSpread {
+    /// `v-text="foo + bar"` will generate `textContent: toDisplayString(foo + bar)` (without transforms).
+    ///
+    /// Literals are used as is, e.g. `v-text="'foo'"` will generate `textContent: 'foo'`.
+    pub fn generate_v_text(&mut self, expr: &Expr) -> PropOrSpread {
+        let value = if is_constant(expr) {
+            expr.to_owned()
+        } else {
+            Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                    span: DUMMY_SP,
+                    sym: self.get_and_add_import_ident(VueImports::ToDisplayString),
+                    optional: false,
+                }))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr.to_owned()),
+                }],
+                type_args: None,
+            })
+        };
+
         PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
             key: PropName::Ident(Ident {
                 span: DUMMY_SP, // TODO
                 sym: fervid_atom!("textContent"),
                 optional: false,
             }),
-            value: Box::new(expr.to_owned()),
+            value: Box::new(value),
         })))
     }
 }
 
+/// Literals and template literals without expressions do not need `toDisplayString`
+fn is_constant(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+        Expr::Paren(paren) => is_constant(&paren.expr),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use fervid_core::{ElementKind, ElementNode, StartingTag, VueDirectives};
+    use fervid_core::{
+        ElementKind, ElementNode, PatchFlags, PatchHints, StartingTag, VueDirectives,
+    };
     use swc_core::ecma::ast::BinExpr;
 
     use crate::test_utils::js;
@@ -64,7 +98,7 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createVNode(_component_test_component,{textContent:foo+bar})"#,
+            r#"_createVNode(_component_test_component,{textContent:_toDisplayString(foo+bar)})"#,
             false,
         )
     }
@@ -88,7 +122,59 @@ mod tests {
                 patch_hints: Default::default(),
                 span: DUMMY_SP,
             },
-            r#"_createElementVNode("h1",{textContent:foo+bar})"#,
+            r#"_createElementVNode("h1",{textContent:_toDisplayString(foo+bar)})"#,
+            false,
+        )
+    }
+
+    #[test]
+    fn it_generates_v_text_with_literal() {
+        test_out(
+            // <h1 v-text='"hello"' />
+            ElementNode {
+                children: vec![],
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "h1".into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_text: Some(js(r#""hello""#)),
+                        ..Default::default()
+                    })),
+                },
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"_createElementVNode("h1",{textContent:"hello"})"#,
+            false,
+        )
+    }
+
+    #[test]
+    fn it_generates_v_text_with_patch_flags() {
+        test_out(
+            // <div v-text="msg" />
+            ElementNode {
+                children: vec![],
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_text: Some(js("msg")),
+                        ..Default::default()
+                    })),
+                },
+                template_scope: 0,
+                patch_hints: PatchHints {
+                    flags: PatchFlags::Props.into(),
+                    props: vec![fervid_atom!("textContent")],
+                    ..Default::default()
+                },
+                span: DUMMY_SP,
+            },
+            r#"_createElementVNode("div",{textContent:_toDisplayString(msg)},null,8,["textContent"])"#,
             false,
         )
     }
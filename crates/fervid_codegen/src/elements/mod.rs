@@ -1,13 +1,11 @@
 use fervid_core::{
-    AttributeOrBinding, ElementNode, StartingTag, StrOrExpr, VBindDirective, VueImports,
+    AttributeOrBinding, ElementNode, StartingTag, StrOrExpr, VBindDirective, VOnDirective,
+    VueImports,
 };
 use swc_core::{
     common::DUMMY_SP,
     ecma::{
-        ast::{
-            ArrayLit, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Null, Number, ObjectLit,
-            PropOrSpread, Str,
-        },
+        ast::{ArrayLit, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Null, Number, Str},
         atoms::JsWord,
     },
 };
@@ -24,15 +22,7 @@ impl CodegenContext {
         let starting_tag = &element_node.starting_tag;
 
         // Generate attributes
-        let attributes = self.generate_element_attributes(element_node);
-        let attributes_expr = if attributes.len() != 0 {
-            Some(Expr::Object(ObjectLit {
-                span,
-                props: attributes,
-            }))
-        } else {
-            None
-        };
+        let attributes_expr = self.generate_element_attributes(element_node);
 
         // There is a special case here: `<template>` with `v-if`/`v-else-if`/`v-else`/`v-for`
         let should_generate_fragment_instead = (wrap_in_block
@@ -214,30 +204,40 @@ impl CodegenContext {
         create_element_expr
     }
 
-    fn generate_element_attributes<'e>(
-        &mut self,
-        element_node: &'e ElementNode,
-    ) -> Vec<PropOrSpread> {
-        let mut result_props = Vec::new();
-
-        self.generate_attributes(&element_node.starting_tag.attributes, &mut result_props);
+    /// Generates the attributes object, or `None` when there are no attributes.
+    ///
+    /// `v-bind="obj"` and `v-on="obj"` are merged with the other attributes in the order of appearance,
+    /// e.g. `<div id="foo" v-on="handlers">` becomes `_mergeProps({ id: "foo" }, _toHandlers(_ctx.handlers, true))`.
+    /// For native elements, `_toHandlers` preserves the case of the event names where needed.
+    fn generate_element_attributes(&mut self, element_node: &ElementNode) -> Option<Expr> {
+        let span = DUMMY_SP;
+        let attributes = &element_node.starting_tag.attributes;
 
         // Directives
+        let mut directive_props = Vec::new();
         if let Some(ref directives) = element_node.starting_tag.directives {
             for v_model in directives.v_model.iter() {
-                self.generate_v_model_for_element(v_model, &mut result_props);
+                self.generate_v_model_for_element(v_model, &mut directive_props);
             }
 
             if let Some(ref v_text) = directives.v_text {
-                result_props.push(self.generate_v_text(&v_text));
+                directive_props.push(self.generate_v_text(&v_text));
             }
 
             if let Some(ref v_html) = directives.v_html {
-                result_props.push(self.generate_v_html(&v_html));
+                directive_props.push(self.generate_v_html(&v_html));
             }
         }
 
-        result_props
+        // Directives are added to the remaining attributes
+        self.generate_merged_attributes(attributes, true, span, |ctx, range, is_last| {
+            let mut props = Vec::with_capacity(range.len());
+            ctx.generate_attributes(&attributes[range], &mut props);
+            if is_last {
+                props.append(&mut directive_props);
+            }
+            props
+        })
     }
 
     pub(crate) fn generate_element_children(
@@ -373,10 +373,15 @@ impl CodegenContext {
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{ElementKind, Interpolation, Node, StartingTag};
+    use fervid_core::{
+        ElementKind, Interpolation, Node, StartingTag, VModelDirective, VueDirectives,
+    };
 
     use super::*;
-    use crate::test_utils::{js, regular_attribute, v_bind_attribute, v_on_attribute};
+    use crate::test_utils::{
+        js, regular_attribute, v_bind_attribute, v_bind_spread_attribute, v_on_attribute,
+        v_on_spread_attribute,
+    };
 
     #[test]
     fn it_generates_basic_usage() {
@@ -409,6 +414,202 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_generates_v_on_and_v_bind_objects() {
+        let test = |attributes: Vec<AttributeOrBinding>, expected: &str| {
+            test_out(
+                ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "div".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    kind: ElementKind::Element,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                expected,
+                false,
+            )
+        };
+
+        // <div v-on="{ click: onClick, mouseenter: onEnter }" />
+        test(
+            vec![v_on_spread_attribute(
+                "{ click: onClick, mouseenter: onEnter }",
+            )],
+            r#"_createElementVNode("div",_toHandlers({click:onClick,mouseenter:onEnter},true))"#,
+        );
+
+        // <div @click="onClick" v-on="handlers" />
+        test(
+            vec![
+                v_on_attribute("onClick", "onClick"),
+                v_on_spread_attribute("handlers"),
+            ],
+            r#"_createElementVNode("div",_mergeProps({onClick:onClick},_toHandlers(handlers,true)))"#,
+        );
+
+        // <div v-on="{ onClick: onClick }" id="foo" />
+        test(
+            vec![
+                v_on_spread_attribute("{ onClick: onClick }"),
+                regular_attribute("id", "foo"),
+            ],
+            r#"_createElementVNode("div",_mergeProps(_toHandlers({onClick:onClick},true),{id:"foo"}))"#,
+        );
+
+        // <div v-bind="attrs" />
+        test(
+            vec![v_bind_spread_attribute("attrs")],
+            r#"_createElementVNode("div",_normalizeProps(_guardReactiveProps(attrs)))"#,
+        );
+
+        // <div id="foo" v-bind="attrs" />
+        test(
+            vec![
+                regular_attribute("id", "foo"),
+                v_bind_spread_attribute("attrs"),
+            ],
+            r#"_createElementVNode("div",_mergeProps({id:"foo"},attrs))"#,
+        );
+
+        // <div id="foo" v-bind="a" v-bind="b" :title="t" />
+        test(
+            vec![
+                regular_attribute("id", "foo"),
+                v_bind_spread_attribute("a"),
+                v_bind_spread_attribute("b"),
+                v_bind_attribute("title", "t"),
+            ],
+            r#"_createElementVNode("div",_mergeProps({id:"foo"},a,b,{title:t}))"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_dynamic_arguments() {
+        let test = |attributes: Vec<AttributeOrBinding>, expected: &str| {
+            test_out(
+                ElementNode {
+                    starting_tag: StartingTag {
+                        tag_name: "div".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    kind: ElementKind::Element,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                },
+                expected,
+                false,
+            )
+        };
+        let v_bind = |argument: &str| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Expr(js(argument))),
+                value: js("value"),
+                is_camel: false,
+                is_prop: false,
+                is_attr: false,
+                span: DUMMY_SP,
+            })
+        };
+        let v_on = |event: &str| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Expr(js(event))),
+                handler: Some(js("handler")),
+                modifiers: vec![],
+                span: DUMMY_SP,
+            })
+        };
+
+        // <div :[key]="value" />
+        test(
+            vec![v_bind("key")],
+            r#"_createElementVNode("div",_normalizeProps({[key||""]:value}))"#,
+        );
+
+        // <div id="foo" @[event]="handler" />
+        test(
+            vec![regular_attribute("id", "foo"), v_on("event")],
+            r#"_createElementVNode("div",_normalizeProps({id:"foo",[_toHandlerKey(event)]:handler}))"#,
+        );
+
+        // <div :[key]="value" v-bind="attrs" /> is normalized by `_mergeProps`
+        test(
+            vec![v_bind("key"), v_bind_spread_attribute("attrs")],
+            r#"_createElementVNode("div",_mergeProps({[key||""]:value},attrs))"#,
+        );
+
+        // <div id="foo" :[null]="value" @[null]="handler" /> binds nothing
+        test(
+            vec![regular_attribute("id", "foo"), v_bind("null"), v_on("null")],
+            r#"_createElementVNode("div",{id:"foo"})"#,
+        );
+    }
+
+    #[test]
+    fn it_generates_v_model_modifiers() {
+        // <tag_name v-model.modifiers="msg" />
+        let test = |tag_name: &str, modifiers: &[&str], expected: &str| {
+            let element_node = ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: tag_name.into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_model: vec![VModelDirective {
+                            argument: None,
+                            value: js("msg"),
+                            update_handler: None,
+                            modifiers: modifiers.iter().map(|&m| m.into()).collect(),
+                            span: DUMMY_SP,
+                        }],
+                        ..Default::default()
+                    })),
+                },
+                children: vec![],
+                template_scope: 0,
+                kind: ElementKind::Element,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            };
+
+            let mut ctx = CodegenContext::default();
+            let out = crate::test_utils::to_str(ctx.generate_element_vnode(&element_node, false));
+            assert!(out.starts_with("_withDirectives("), "{out}");
+            assert!(out.contains(expected), "{out}");
+
+            // Unlike components, elements do not receive the modifiers as a prop
+            assert!(!out.contains("modelModifiers"), "{out}");
+        };
+
+        // The runtime directives read the modifiers from the binding
+        test("input", &["trim"], "[[_vModelText,msg,void 0,{trim:true}]]");
+        test(
+            "input",
+            &["number"],
+            "[[_vModelText,msg,void 0,{number:true}]]",
+        );
+        test("input", &["lazy"], "[[_vModelText,msg,void 0,{lazy:true}]]");
+        test(
+            "textarea",
+            &["lazy", "trim", "number"],
+            "[[_vModelText,msg,void 0,{lazy:true,trim:true,number:true}]]",
+        );
+        test(
+            "select",
+            &["number"],
+            "[[_vModelSelect,msg,void 0,{number:true}]]",
+        );
+
+        // No modifiers
+        test("input", &[], "[[_vModelText,msg]]");
+    }
+
     #[test]
     fn it_generates_attrless() {
         // <div>hello from div</div>
@@ -430,6 +631,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_ignores_v_cloak() {
+        // <div v-cloak></div>
+        test_out(
+            ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(VueDirectives {
+                        v_cloak: Some(()),
+                        ..Default::default()
+                    })),
+                },
+                children: vec![],
+                template_scope: 0,
+                kind: ElementKind::Element,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            },
+            r#"_createElementVNode("div")"#,
+            false,
+        )
+    }
+
     #[test]
     fn it_generates_childless() {
         // <div foo="bar"></div>
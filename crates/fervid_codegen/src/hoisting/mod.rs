@@ -0,0 +1,341 @@
+use fervid_core::{AttributeOrBinding, ElementKind, ElementNode, FervidAtom, Node, VueDirectives};
+use swc_core::{
+    common::{
+        comments::{Comments, SingleThreadedComments},
+        BytePos, Span, SyntaxContext, DUMMY_SP,
+    },
+    ecma::ast::{
+        BindingIdent, Decl, Expr, ExprOrSpread, Ident, Lit, ModuleItem, Null, Number, Pat, Stmt,
+        VarDecl, VarDeclKind, VarDeclarator,
+    },
+};
+
+use crate::context::CodegenContext;
+
+impl CodegenContext {
+    /// Generates the static element once and moves it to [`CodegenContext::hoisted`].
+    /// Returns the `_hoisted_N` identifier referencing it.
+    ///
+    /// ## Example
+    /// In:
+    /// `<span class="foo">hello</span>`
+    ///
+    /// Hoisted:
+    /// ```js
+    /// const _hoisted_1 = /*#__PURE__*/ _createElementVNode("span", { class: "foo" }, "hello", -1)
+    /// ```
+    ///
+    /// Out:
+    /// `_hoisted_1`
+    pub fn generate_hoisted_element(&mut self, element_node: &ElementNode) -> Expr {
+        // Static descendants are a part of this subtree and are not hoisted on their own
+        let old_hoist_static = self.hoist_static;
+        self.hoist_static = false;
+        let mut create_element_expr = self.generate_element_vnode(element_node, false);
+        self.hoist_static = old_hoist_static;
+
+        // `createElementVNode("span", null, null, -1 /* HOISTED */)`
+        if let Expr::Call(ref mut create_element_call) = create_element_expr {
+            while create_element_call.args.len() < 3 {
+                create_element_call.args.push(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+                });
+            }
+            create_element_call.args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: -1.0,
+                    raw: None,
+                }))),
+            });
+
+            // The span is used to attach the `/*#__PURE__*/` annotation
+            create_element_call.span = pure_annotation_span(self.hoisted.len());
+        }
+
+        self.hoisted.push(create_element_expr);
+
+        Expr::Ident(hoisted_ident(self.hoisted.len()))
+    }
+
+    /// Generates `const _hoisted_N = /*#__PURE__*/ ...` for each of the hoisted subtrees
+    pub fn generate_hoisted_decls(&self) -> Vec<ModuleItem> {
+        self.hoisted
+            .iter()
+            .enumerate()
+            .map(|(idx, hoisted_expr)| {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Const,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(BindingIdent {
+                            id: hoisted_ident(idx + 1),
+                            type_ann: None,
+                        }),
+                        init: Some(Box::new(hoisted_expr.to_owned())),
+                        definite: false,
+                    }],
+                }))))
+            })
+            .collect()
+    }
+
+    /// Generates the `/*#__PURE__*/` annotations for the hoisted subtrees.
+    /// These must be passed to [`CodegenContext::stringify`], otherwise the annotations are omitted.
+    pub fn generate_pure_annotations(&self) -> SingleThreadedComments {
+        let comments = SingleThreadedComments::default();
+
+        for idx in 0..self.hoisted.len() {
+            comments.add_pure_comment(pure_annotation_span(idx).lo);
+        }
+
+        comments
+    }
+}
+
+/// Checks whether the node and all its descendants never change between renders,
+/// meaning that the node can be created once and reused.
+///
+/// Only the native elements without bindings, directives, `ref` and `key` are static,
+/// as well as text and comments.
+pub fn is_static_subtree(node: &Node) -> bool {
+    match node {
+        Node::Text(_, _) | Node::Comment(_, _) => true,
+        Node::Element(element_node) => is_static_element(element_node),
+        Node::Interpolation(_) | Node::ConditionalSeq(_) => false,
+    }
+}
+
+/// Same as [`is_static_subtree`], but for an `ElementNode`
+pub fn is_static_element(element_node: &ElementNode) -> bool {
+    if !matches!(element_node.kind, ElementKind::Element)
+        || element_node.starting_tag.tag_name == "template"
+        || !element_node.patch_hints.flags.is_empty()
+        || element_node.patch_hints.should_use_block
+    {
+        return false;
+    }
+
+    let has_only_static_attributes = element_node
+        .starting_tag
+        .attributes
+        .iter()
+        .all(is_static_attribute);
+
+    has_only_static_attributes
+        && element_node
+            .starting_tag
+            .directives
+            .as_deref()
+            .map_or(true, has_no_runtime_directives)
+        && element_node.children.iter().all(is_static_subtree)
+}
+
+/// `ref` and `key` are handled by the runtime, thus they are not static
+fn is_static_attribute(attr: &AttributeOrBinding) -> bool {
+    match attr {
+        AttributeOrBinding::RegularAttribute { name, .. } => name != "ref" && name != "key",
+        AttributeOrBinding::VBind(_) | AttributeOrBinding::VOn(_) => false,
+    }
+}
+
+/// `v-pre` and `v-cloak` do not affect the render code
+fn has_no_runtime_directives(directives: &VueDirectives) -> bool {
+    directives.custom.is_empty()
+        && directives.v_else.is_none()
+        && directives.v_else_if.is_none()
+        && directives.v_for.is_none()
+        && directives.v_html.is_none()
+        && directives.v_if.is_none()
+        && directives.v_memo.is_none()
+        && directives.v_model.is_empty()
+        && directives.v_once.is_none()
+        && directives.v_show.is_none()
+        && directives.v_slot.is_none()
+        && directives.v_text.is_none()
+}
+
+/// `_hoisted_N`, where `N` starts from 1
+fn hoisted_ident(number: usize) -> Ident {
+    Ident {
+        span: DUMMY_SP,
+        sym: FervidAtom::from(format!("_hoisted_{}", number)),
+        optional: false,
+    }
+}
+
+/// Positions starting from `BytePos::MIN_RESERVED` are reserved by SWC for synthesized comments,
+/// they are not mapped to the source and do not collide with it
+fn pure_annotation_span(idx: usize) -> Span {
+    let pos = BytePos(BytePos::MIN_RESERVED.0 + idx as u32);
+    Span {
+        lo: pos,
+        hi: pos,
+        ctxt: SyntaxContext::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{Interpolation, StartingTag};
+    use swc_core::{common::FileName, ecma::ast::Module};
+
+    use crate::test_utils::{js, regular_attribute, to_str, v_bind_attribute};
+
+    use super::*;
+
+    fn element(tag_name: &str, attributes: Vec<AttributeOrBinding>, children: Vec<Node>) -> Node {
+        Node::Element(ElementNode {
+            starting_tag: StartingTag {
+                tag_name: tag_name.into(),
+                attributes,
+                directives: None,
+            },
+            children,
+            template_scope: 0,
+            kind: ElementKind::Element,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        })
+    }
+
+    fn text(contents: &str) -> Node {
+        Node::Text(contents.into(), DUMMY_SP)
+    }
+
+    #[test]
+    fn it_checks_static_subtrees() {
+        // hello
+        assert!(is_static_subtree(&text("hello")));
+
+        // <div class="foo"><span>hello</span></div>
+        assert!(is_static_subtree(&element(
+            "div",
+            vec![regular_attribute("class", "foo")],
+            vec![element("span", vec![], vec![text("hello")])]
+        )));
+
+        // <div :class="foo"></div>
+        assert!(!is_static_subtree(&element(
+            "div",
+            vec![v_bind_attribute("class", "foo")],
+            vec![]
+        )));
+
+        // <div ref="foo"></div>
+        assert!(!is_static_subtree(&element(
+            "div",
+            vec![regular_attribute("ref", "foo")],
+            vec![]
+        )));
+
+        // <div><span>{{ foo }}</span></div>
+        let interpolation = Node::Interpolation(Interpolation {
+            value: js("foo"),
+            template_scope: 0,
+            patch_flag: false,
+            span: DUMMY_SP,
+        });
+        assert!(!is_static_subtree(&element(
+            "div",
+            vec![],
+            vec![element("span", vec![], vec![interpolation])]
+        )));
+
+        // <Comp></Comp>
+        let Node::Element(mut component) = element("Comp", vec![], vec![]) else {
+            unreachable!()
+        };
+        component.kind = ElementKind::Component;
+        assert!(!is_static_element(&component));
+    }
+
+    #[test]
+    fn it_hoists_static_subtrees() {
+        // <div>
+        //   <span class="a">hello</span>
+        //   <i :title="t"><b>c</b></i>
+        //   <p>a<b>b</b></p>
+        // </div>
+        let root = element(
+            "div",
+            vec![],
+            vec![
+                element(
+                    "span",
+                    vec![regular_attribute("class", "a")],
+                    vec![text("hello")],
+                ),
+                element(
+                    "i",
+                    vec![v_bind_attribute("title", "t")],
+                    vec![element("b", vec![], vec![text("c")])],
+                ),
+                element(
+                    "p",
+                    vec![],
+                    vec![text("a"), element("b", vec![], vec![text("b")])],
+                ),
+            ],
+        );
+
+        let mut ctx = CodegenContext {
+            hoist_static: true,
+            ..Default::default()
+        };
+        let out = ctx.generate_node(&root, true);
+
+        // The root block itself is never hoisted
+        assert_eq!(
+            to_str(out),
+            r#"(_openBlock(),_createElementBlock("div",null,[_hoisted_1,_createElementVNode("i",{title:t},[_hoisted_2]),_hoisted_3]))"#
+        );
+
+        let hoisted: Vec<String> = ctx
+            .hoisted
+            .iter()
+            .map(|hoisted| to_str(hoisted.to_owned()))
+            .collect();
+        assert_eq!(
+            hoisted,
+            vec![
+                r#"_createElementVNode("span",{class:"a"},"hello",-1)"#,
+                r#"_createElementVNode("b",null,"c",-1)"#,
+                r#"_createElementVNode("p",null,[_createTextVNode("a"),_createElementVNode("b",null,"b")],-1)"#,
+            ]
+        );
+
+        // Declarations are annotated as pure
+        let module = Module {
+            span: DUMMY_SP,
+            body: ctx.generate_hoisted_decls(),
+            shebang: None,
+        };
+        assert!(matches!(module.body[0], ModuleItem::Stmt(Stmt::Decl(_))));
+
+        let pure_annotations = ctx.generate_pure_annotations();
+        let (code, _) = CodegenContext::stringify(
+            "",
+            &module,
+            FileName::Anon,
+            Some(&pure_annotations),
+            false,
+            false,
+        );
+        assert!(code.starts_with("const _hoisted_1 = /*#__PURE__*/"));
+        assert_eq!(code.matches("/*#__PURE__*/").count(), 3);
+    }
+
+    #[test]
+    fn it_does_not_hoist_when_disabled() {
+        let mut ctx = CodegenContext::default();
+        let out = ctx.generate_node(&element("span", vec![], vec![text("hello")]), false);
+
+        assert_eq!(to_str(out), r#"_createElementVNode("span",null,"hello")"#);
+        assert!(ctx.hoisted.is_empty());
+    }
+}
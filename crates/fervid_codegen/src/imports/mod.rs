@@ -1,4 +1,4 @@
-use fervid_core::{FervidAtom, VueImports};
+use fervid_core::{FervidAtom, SsrImports, VueImports};
 use swc_core::{
     common::DUMMY_SP,
     ecma::ast::{Ident, ImportNamedSpecifier, ImportSpecifier, ModuleExportName},
@@ -21,6 +21,11 @@ impl CodegenContext {
         vue_import.as_atom()
     }
 
+    pub fn get_and_add_ssr_import_ident(&mut self, ssr_import: SsrImports) -> FervidAtom {
+        self.ssr_imports |= ssr_import;
+        ssr_import.as_atom()
+    }
+
     /// Generates all the imports used by template generation.
     /// All of the imports come from 'vue'.
     pub fn generate_imports(&self) -> Vec<ImportSpecifier> {
@@ -50,6 +55,32 @@ impl CodegenContext {
 
         result
     }
+
+    /// Generates the imports used by SSR template generation.
+    /// All of them come from 'vue/server-renderer'.
+    pub fn generate_ssr_imports(&self) -> Vec<ImportSpecifier> {
+        self.ssr_imports
+            .into_iter()
+            .map(|import| {
+                let import_raw = import.as_str();
+
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: DUMMY_SP,
+                    local: Ident {
+                        span: DUMMY_SP,
+                        sym: import.as_atom(),
+                        optional: false,
+                    },
+                    imported: Some(ModuleExportName::Ident(Ident {
+                        span: DUMMY_SP,
+                        sym: FervidAtom::from(&import_raw[1..]),
+                        optional: false,
+                    })),
+                    is_type_only: false,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +135,60 @@ mod tests {
 
         assert_eq!(crate::test_utils::to_str(vue_import_decl), "import{createBlock as _createBlock,normalizeClass as _normalizeClass,openBlock as _openBlock,toDisplayString as _toDisplayString,withCtx as _withCtx,withDirectives as _withDirectives,withModifiers as _withModifiers}from\"vue\";");
     }
+
+    #[test]
+    fn it_generates_app_and_component_imports() {
+        macro_rules! check {
+            ($import: expr, $expected: literal) => {
+                let mut ctx = CodegenContext::default();
+                assert_eq!($expected, ctx.get_and_add_import_ident($import).as_ref());
+
+                let generated_imports = ctx.generate_imports();
+                assert_eq!(1, generated_imports.len());
+                let ImportSpecifier::Named(ref named) = generated_imports[0] else {
+                    panic!("Import is not named")
+                };
+                assert!(matches!(
+                    named.imported,
+                    Some(ModuleExportName::Ident(ref imported)) if imported.sym == $expected[1..]
+                ));
+            };
+        }
+
+        check!(VueImports::H, "_h");
+        check!(VueImports::CreateApp, "_createApp");
+        check!(VueImports::DefineComponent, "_defineComponent");
+        check!(VueImports::DefineAsyncComponent, "_defineAsyncComponent");
+    }
+
+    #[test]
+    fn it_only_generates_used_imports() {
+        let mut ctx = CodegenContext::default();
+        assert!(ctx.generate_imports().is_empty());
+
+        // Duplicated usages register only once
+        ctx.add_to_imports(VueImports::H);
+        ctx.add_to_imports(VueImports::CreateApp);
+        ctx.add_to_imports(VueImports::H);
+        ctx.add_to_imports(VueImports::CreateApp);
+
+        let generated_imports = ctx.generate_imports();
+        let vue_import_decl = ImportDecl {
+            span: DUMMY_SP,
+            specifiers: generated_imports,
+            src: Box::new(Str {
+                span: DUMMY_SP,
+                value: "vue".into(),
+                raw: None,
+            }),
+            type_only: false,
+            with: None,
+            phase: Default::default(),
+        };
+
+        assert_eq!(
+            crate::test_utils::to_str(vue_import_decl),
+            "import{createApp as _createApp,h as _h}from\"vue\";"
+        );
+    }
 }
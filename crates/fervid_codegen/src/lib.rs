@@ -15,7 +15,9 @@ mod control_flow;
 mod directives;
 mod interpolation;
 mod elements;
+mod hoisting;
 mod imports;
+mod ssr;
 mod text;
 mod utils;
 
@@ -23,3 +25,4 @@ mod utils;
 mod test_utils;
 
 pub use context::CodegenContext;
+pub use hoisting::is_static_subtree;
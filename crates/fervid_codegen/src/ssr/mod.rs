@@ -0,0 +1,1590 @@
+//! Server-side rendering code generation.
+//!
+//! Instead of creating VNodes, the SSR render function pushes HTML strings into a buffer:
+//! ```js
+//! function ssrRender(_ctx, _push, _parent, _attrs) {
+//!   _push(`<div${_ssrRenderAttrs(_attrs)}>${_ssrInterpolate(_ctx.msg)}</div>`)
+//! }
+//! ```
+//!
+//! The module compiled with `ssr` enabled is then rendered in Node.js using `vue/server-renderer`:
+//! ```js
+//! import { createSSRApp } from 'vue'
+//! import { renderToString } from 'vue/server-renderer'
+//! import Component from './Component.vue'
+//!
+//! const html = await renderToString(createSSRApp(Component, { msg: 'Hello' }))
+//! ```
+
+use fervid_core::{
+    check_attribute_name, fervid_atom, AttributeOrBinding, BuiltinType, ConditionalNodeSequence,
+    ElementKind, ElementNode, FervidAtom, Node, SfcTemplateBlock, SsrImports, StrOrExpr,
+    VBindDirective, VModelDirective, VueImports,
+};
+use std::borrow::Cow;
+use swc_core::{
+    common::{Span, Spanned, DUMMY_SP},
+    ecma::ast::{
+        ArrayLit, ArrowExpr, BinExpr, BinaryOp, BlockStmt, BlockStmtOrExpr, Bool, CallExpr, Callee,
+        CondExpr, Decl, Expr, ExprOrSpread, ExprStmt, Function, Ident, IfStmt, KeyValueProp, Lit,
+        MemberExpr, MemberProp, Null, ObjectLit, Param, ParenExpr, Pat, Prop, PropName,
+        PropOrSpread, Stmt, Str, Tpl, TplElement, VarDecl, VarDeclKind,
+    },
+};
+
+use crate::CodegenContext;
+
+/// https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+const VOID_TAGS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Attributes which are only rendered when their value is truthy, e.g. `disabled`
+const BOOLEAN_ATTRS: [&str; 26] = [
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "readonly",
+    "required",
+    "reversed",
+    "scoped",
+    "seamless",
+    "selected",
+];
+
+/// Accumulates static HTML and dynamic parts of it,
+/// so that they can be pushed as a single template literal: `_push(`<div>${dynamic}</div>`)`
+#[derive(Default)]
+struct SsrBuffer {
+    quasis: Vec<String>,
+    exprs: Vec<Box<Expr>>,
+    current: String,
+}
+
+impl SsrBuffer {
+    fn push_str(&mut self, s: &str) {
+        self.current.push_str(s);
+    }
+
+    fn push_expr(&mut self, expr: Expr) {
+        self.quasis.push(std::mem::take(&mut self.current));
+        self.exprs.push(Box::new(expr));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.exprs.is_empty() && self.current.is_empty()
+    }
+
+    /// Takes the accumulated contents and generates a `_push(`...`)` statement from them
+    fn flush(&mut self, out: &mut Vec<Stmt>) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut quasis = std::mem::take(&mut self.quasis);
+        quasis.push(std::mem::take(&mut self.current));
+        let last_idx = quasis.len() - 1;
+
+        let tpl = Expr::Tpl(Tpl {
+            span: DUMMY_SP,
+            exprs: std::mem::take(&mut self.exprs),
+            quasis: quasis
+                .into_iter()
+                .enumerate()
+                .map(|(idx, cooked)| TplElement {
+                    span: DUMMY_SP,
+                    tail: idx == last_idx,
+                    raw: escape_template_literal(&cooked).into(),
+                    cooked: Some(cooked.into()),
+                })
+                .collect(),
+        });
+
+        out.push(push_stmt(tpl));
+    }
+}
+
+impl CodegenContext {
+    /// Generates the SSR render arrow, e.g.
+    /// `(_ctx, _push, _parent, _attrs) => { _push(`<div${_ssrRenderAttrs(_attrs)}></div>`) }`
+    pub fn generate_ssr_render_arrow(
+        &mut self,
+        sfc_template: &SfcTemplateBlock,
+    ) -> Option<ArrowExpr> {
+        if sfc_template.roots.is_empty() {
+            return None;
+        }
+
+        let mut body_stmts = Vec::<Stmt>::new();
+        let mut buf = SsrBuffer::default();
+
+        // Fallthrough attributes can only be applied to a single root
+        let is_single_root = sfc_template.roots.len() == 1;
+        if !is_single_root {
+            buf.push_str("<!--[-->");
+        }
+        for root in sfc_template.roots.iter() {
+            self.generate_ssr_node(root, &mut buf, &mut body_stmts, is_single_root);
+        }
+        if !is_single_root {
+            buf.push_str("<!--]-->");
+        }
+        buf.flush(&mut body_stmts);
+
+        // Resolves are computed last, because components are registered during generation
+        let mut resolves = self.generate_component_resolves();
+        resolves.extend(self.generate_directive_resolves());
+        if !resolves.is_empty() {
+            body_stmts.insert(
+                0,
+                Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Const,
+                    declare: false,
+                    decls: resolves,
+                }))),
+            );
+        }
+
+        Some(ArrowExpr {
+            span: sfc_template.span,
+            params: ["_ctx", "_push", "_parent", "_attrs"]
+                .into_iter()
+                .map(|name| Pat::Ident(ident(name).into()))
+                .collect(),
+            body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                span: DUMMY_SP,
+                stmts: body_stmts,
+            })),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        })
+    }
+
+    /// Converts the SSR render arrow to a function used as the `ssrRender` option, i.e.
+    /// `ssrRender(_ctx, _push, _parent, _attrs, $props, $setup, $data, $options) { /*...*/ }`
+    pub fn generate_ssr_render_fn(&mut self, ssr_render_arrow: ArrowExpr) -> Function {
+        let ArrowExpr {
+            span, params, body, ..
+        } = ssr_render_arrow;
+
+        let body = match *body {
+            BlockStmtOrExpr::BlockStmt(block) => block,
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr,
+                })],
+            },
+        };
+
+        let params = params
+            .into_iter()
+            .chain(
+                ["$props", "$setup", "$data", "$options"]
+                    .into_iter()
+                    .map(|name| Pat::Ident(ident(name).into())),
+            )
+            .map(|pat| Param {
+                span: DUMMY_SP,
+                decorators: vec![],
+                pat,
+            })
+            .collect();
+
+        Function {
+            params,
+            decorators: vec![],
+            span,
+            body: Some(body),
+            is_generator: false,
+            is_async: false,
+            type_params: None,
+            return_type: None,
+        }
+    }
+
+    fn generate_ssr_node(
+        &mut self,
+        node: &Node,
+        buf: &mut SsrBuffer,
+        out: &mut Vec<Stmt>,
+        is_root: bool,
+    ) {
+        match node {
+            Node::Text(contents, _) => buf.push_str(&escape_html(contents)),
+
+            Node::Interpolation(interpolation) => {
+                let interpolate_call = self.ssr_call(
+                    SsrImports::SsrInterpolate,
+                    vec![interpolation.value.to_owned()],
+                    interpolation.span,
+                );
+                buf.push_expr(interpolate_call);
+            }
+
+            Node::Comment(comment, _) => {
+                buf.push_str("<!--");
+                buf.push_str(comment);
+                buf.push_str("-->");
+            }
+
+            Node::Element(element_node) => {
+                self.generate_ssr_element_or_component(element_node, buf, out, is_root)
+            }
+
+            Node::ConditionalSeq(conditional_seq) => {
+                buf.flush(out);
+                out.push(self.generate_ssr_conditional_seq(conditional_seq, is_root));
+            }
+        }
+    }
+
+    /// Generates an element, a component or a built-in, taking `v-for` into account
+    fn generate_ssr_element_or_component(
+        &mut self,
+        element_node: &ElementNode,
+        buf: &mut SsrBuffer,
+        out: &mut Vec<Stmt>,
+        is_root: bool,
+    ) {
+        let v_for = element_node
+            .starting_tag
+            .directives
+            .as_ref()
+            .and_then(|directives| directives.v_for.as_ref());
+
+        let Some(v_for) = v_for else {
+            self.generate_ssr_element_kind(element_node, buf, out, is_root);
+            return;
+        };
+
+        // `_ssrRenderList(iterable, (item) => { /* item */ })` surrounded by fragment markers
+        buf.push_str("<!--[-->");
+        buf.flush(out);
+
+        let mut item_stmts = Vec::new();
+        let mut item_buf = SsrBuffer::default();
+        self.generate_ssr_element_kind(element_node, &mut item_buf, &mut item_stmts, false);
+        item_buf.flush(&mut item_stmts);
+
+        let item_arrow = Expr::Arrow(ArrowExpr {
+            span: v_for.span,
+            params: vec![Pat::Expr(v_for.itervar.to_owned())],
+            body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                span: DUMMY_SP,
+                stmts: item_stmts,
+            })),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        });
+
+        let render_list_call = self.ssr_call(
+            SsrImports::SsrRenderList,
+            vec![v_for.iterable.to_owned(), Box::new(item_arrow)],
+            v_for.span,
+        );
+        out.push(Stmt::Expr(ExprStmt {
+            span: v_for.span,
+            expr: Box::new(render_list_call),
+        }));
+
+        buf.push_str("<!--]-->");
+    }
+
+    fn generate_ssr_element_kind(
+        &mut self,
+        element_node: &ElementNode,
+        buf: &mut SsrBuffer,
+        out: &mut Vec<Stmt>,
+        is_root: bool,
+    ) {
+        match element_node.kind {
+            ElementKind::Element => self.generate_ssr_element(element_node, buf, out, is_root),
+            ElementKind::Component => self.generate_ssr_component(element_node, buf, out, is_root),
+            ElementKind::Builtin(BuiltinType::Slot) => {
+                buf.flush(out);
+                out.push(self.generate_ssr_slot(element_node));
+            }
+            ElementKind::Builtin(builtin_type) => {
+                // Other built-ins are rendered from their VNode:
+                // `_ssrRenderVNode(_push, vnode, _parent)`
+                buf.flush(out);
+                let span = element_node.span;
+                let vnode = self.generate_builtin(element_node, builtin_type);
+                let render_vnode_call = self.ssr_call(
+                    SsrImports::SsrRenderVNode,
+                    vec![
+                        Box::new(Expr::Ident(ident("_push"))),
+                        Box::new(vnode),
+                        Box::new(Expr::Ident(ident("_parent"))),
+                    ],
+                    span,
+                );
+                out.push(Stmt::Expr(ExprStmt {
+                    span,
+                    expr: Box::new(render_vnode_call),
+                }));
+            }
+        }
+    }
+
+    fn generate_ssr_element(
+        &mut self,
+        element_node: &ElementNode,
+        buf: &mut SsrBuffer,
+        out: &mut Vec<Stmt>,
+        is_root: bool,
+    ) {
+        let tag_name: &str = &element_node.starting_tag.tag_name;
+        let directives = element_node.starting_tag.directives.as_ref();
+
+        // `<template>` only renders its children
+        if tag_name == "template" {
+            for child in element_node.children.iter() {
+                self.generate_ssr_node(child, buf, out, false);
+            }
+            return;
+        }
+
+        buf.push_str("<");
+        buf.push_str(tag_name);
+        self.generate_ssr_element_attributes(element_node, buf, is_root);
+
+        let v_model = directives.and_then(|d| d.v_model.first());
+        match (tag_name, v_model) {
+            ("input", Some(v_model)) => self.generate_ssr_input_v_model(element_node, v_model, buf),
+            ("option", _) => self.generate_ssr_option_selected(element_node, buf),
+            _ => {}
+        }
+
+        if let Some(ref scope_id) = self.scope_id {
+            buf.push_str(" ");
+            buf.push_str(scope_id);
+        }
+        buf.push_str(">");
+
+        if VOID_TAGS.contains(&tag_name) {
+            return;
+        }
+
+        if let Some(v_html) = directives.and_then(|d| d.v_html.as_ref()) {
+            // `(html) ?? ''`, so that `null` and `undefined` render nothing, same as on the client
+            buf.push_expr(Expr::Bin(BinExpr {
+                span: v_html.span(),
+                op: BinaryOp::NullishCoalescing,
+                left: Box::new(Expr::Paren(ParenExpr {
+                    span: v_html.span(),
+                    expr: v_html.to_owned(),
+                })),
+                right: Box::new(str_lit(&fervid_atom!(""), DUMMY_SP)),
+            }));
+        } else if let Some(v_text) = directives.and_then(|d| d.v_text.as_ref()) {
+            let interpolate_call = self.ssr_call(
+                SsrImports::SsrInterpolate,
+                vec![v_text.to_owned()],
+                element_node.span,
+            );
+            buf.push_expr(interpolate_call);
+        } else if let ("textarea", Some(v_model)) = (tag_name, v_model) {
+            // `v-model` of a `<textarea>` is its content
+            let interpolate_call = self.ssr_call(
+                SsrImports::SsrInterpolate,
+                vec![v_model.value.to_owned()],
+                v_model.span,
+            );
+            buf.push_expr(interpolate_call);
+        } else {
+            let select_model = v_model
+                .filter(|_| tag_name == "select")
+                .map(|v_model| v_model.value.to_owned());
+            let is_select_model = select_model.is_some();
+            let outer_select_model = if is_select_model {
+                std::mem::replace(&mut self.ssr_select_model, select_model)
+            } else {
+                None
+            };
+
+            for child in element_node.children.iter() {
+                self.generate_ssr_node(child, buf, out, false);
+            }
+
+            if is_select_model {
+                self.ssr_select_model = outer_select_model;
+            }
+        }
+
+        buf.push_str("</");
+        buf.push_str(tag_name);
+        buf.push_str(">");
+    }
+
+    /// Static attributes are inlined to the HTML string.
+    /// Otherwise, attributes are generated as an object and rendered by `_ssrRenderAttrs`.
+    ///
+    /// Bound boolean attributes (e.g. `:disabled="cond"`) do not prevent the inlining:
+    /// they are rendered conditionally, `${_ssrIncludeBooleanAttr(cond) ? " disabled" : ""}`.
+    ///
+    /// `v-show` hides the element using `display: none` in its `style`.
+    ///
+    /// There is no DOM on the server, therefore `v-on` listeners of an element are never rendered,
+    /// regardless of their modifiers (e.g. `.capture` or `.prevent`).
+    fn generate_ssr_element_attributes(
+        &mut self,
+        element_node: &ElementNode,
+        buf: &mut SsrBuffer,
+        is_root: bool,
+    ) {
+        let is_v_on = |attr: &AttributeOrBinding| matches!(attr, AttributeOrBinding::VOn(_));
+        let attributes: Cow<[AttributeOrBinding]> =
+            if element_node.starting_tag.attributes.iter().any(is_v_on) {
+                Cow::Owned(
+                    element_node
+                        .starting_tag
+                        .attributes
+                        .iter()
+                        .filter(|attr| !is_v_on(attr))
+                        .cloned()
+                        .collect(),
+                )
+            } else {
+                Cow::Borrowed(&element_node.starting_tag.attributes)
+            };
+        let is_static = attributes.iter().all(|attr| match attr {
+            AttributeOrBinding::RegularAttribute { .. } => true,
+            AttributeOrBinding::VBind(v_bind) => boolean_attr_name(v_bind).is_some(),
+            _ => false,
+        });
+
+        // `v-show="true"` is a no-op, `v-show="false"` can be inlined
+        let v_show = element_node
+            .starting_tag
+            .directives
+            .as_ref()
+            .and_then(|d| d.v_show.as_deref())
+            .filter(|v_show| !is_bool_lit(v_show, true));
+        let is_always_hidden = v_show.is_some_and(|v_show| is_bool_lit(v_show, false));
+
+        if is_static && !is_root && (v_show.is_none() || is_always_hidden) {
+            let mut has_style = false;
+            for attr in attributes.iter() {
+                let (name, value) = match attr {
+                    AttributeOrBinding::RegularAttribute { name, value, .. } => (name, value),
+                    AttributeOrBinding::VBind(v_bind) => {
+                        if let Some(name) = boolean_attr_name(v_bind) {
+                            self.generate_ssr_boolean_attr(name, v_bind, buf);
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                buf.push_str(" ");
+                buf.push_str(name);
+                if is_always_hidden && name == "style" {
+                    has_style = true;
+                    buf.push_str("=\"");
+                    buf.push_str(&escape_html(&hide_static_style(value)));
+                    buf.push_str("\"");
+                } else if !value.is_empty() {
+                    buf.push_str("=\"");
+                    buf.push_str(&escape_html(value));
+                    buf.push_str("\"");
+                }
+            }
+            if is_always_hidden && !has_style {
+                buf.push_str(" style=\"display:none;\"");
+            }
+            return;
+        }
+
+        let mut attrs_obj = ObjectLit {
+            span: element_node.span,
+            props: Vec::with_capacity(attributes.len() + 1),
+        };
+        self.generate_attributes(&attributes, &mut attrs_obj.props);
+        if let Some(v_show) = v_show {
+            add_v_show_style(&mut attrs_obj, v_show);
+        }
+
+        let attrs_expr = if is_root {
+            self.merge_with_fallthrough_attrs(attrs_obj)
+        } else {
+            Expr::Object(attrs_obj)
+        };
+
+        let render_attrs_call = self.ssr_call(
+            SsrImports::SsrRenderAttrs,
+            vec![Box::new(attrs_expr)],
+            element_node.span,
+        );
+        buf.push_expr(render_attrs_call);
+    }
+
+    /// `:disabled="true"` is inlined as ` disabled`, `:disabled="false"` is omitted,
+    /// otherwise the attribute is rendered when `_ssrIncludeBooleanAttr(value)` is truthy
+    fn generate_ssr_boolean_attr(
+        &mut self,
+        name: &str,
+        v_bind: &VBindDirective,
+        buf: &mut SsrBuffer,
+    ) {
+        let value = &v_bind.value;
+        if is_bool_lit(value, true) {
+            buf.push_str(" ");
+            buf.push_str(name);
+            return;
+        }
+        if is_bool_lit(value, false) {
+            return;
+        }
+
+        self.generate_ssr_include_boolean_attr(name, (**value).to_owned(), v_bind.span, buf);
+    }
+
+    /// Generates `${_ssrIncludeBooleanAttr(value) ? " name" : ""}`
+    fn generate_ssr_include_boolean_attr(
+        &mut self,
+        name: &str,
+        value: Expr,
+        span: Span,
+        buf: &mut SsrBuffer,
+    ) {
+        let include_call = self.ssr_call(
+            SsrImports::SsrIncludeBooleanAttr,
+            vec![Box::new(value)],
+            span,
+        );
+        buf.push_expr(Expr::Cond(CondExpr {
+            span,
+            test: Box::new(include_call),
+            cons: Box::new(str_lit(&FervidAtom::from(format!(" {name}")), DUMMY_SP)),
+            alt: Box::new(str_lit(&fervid_atom!(""), DUMMY_SP)),
+        }));
+    }
+
+    /// `v-model` of an `<input>` is rendered as its `value` or `checked` attribute:
+    /// - `type="checkbox"`: `checked` when
+    ///   `Array.isArray(model) ? _ssrLooseContain(model, value) : model`;
+    /// - `type="radio"`: `checked` when `_ssrLooseEqual(model, value)`;
+    /// - `:type="type"`: `_ssrRenderDynamicModel(type, model, value)` decides at runtime;
+    /// - otherwise: `_ssrRenderAttr("value", model)`.
+    fn generate_ssr_input_v_model(
+        &mut self,
+        element_node: &ElementNode,
+        v_model: &VModelDirective,
+        buf: &mut SsrBuffer,
+    ) {
+        let span = v_model.span;
+        let model = &v_model.value;
+        let attributes = &element_node.starting_tag.attributes;
+        let value = value_attr(attributes).unwrap_or(Expr::Lit(Lit::Null(Null { span })));
+
+        let input_type = attributes.iter().find_map(|attr| match attr {
+            AttributeOrBinding::RegularAttribute { name, value, .. } if name == "type" => {
+                Some(StrOrExpr::Str(value.to_owned()))
+            }
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(name)),
+                value,
+                ..
+            }) if name == "type" => Some(StrOrExpr::Expr(value.to_owned())),
+            _ => None,
+        });
+
+        match input_type {
+            Some(StrOrExpr::Expr(input_type)) => {
+                let render_dynamic_model_call = self.ssr_call(
+                    SsrImports::SsrRenderDynamicModel,
+                    vec![input_type, model.to_owned(), Box::new(value)],
+                    span,
+                );
+                buf.push_expr(render_dynamic_model_call);
+            }
+            Some(StrOrExpr::Str(ref input_type)) if input_type == "checkbox" => {
+                let contain_call = self.ssr_call(
+                    SsrImports::SsrLooseContain,
+                    vec![model.to_owned(), Box::new(value)],
+                    span,
+                );
+                let is_checked = Expr::Cond(CondExpr {
+                    span,
+                    test: Box::new(is_array_call(model, span)),
+                    cons: Box::new(contain_call),
+                    alt: model.to_owned(),
+                });
+                self.generate_ssr_include_boolean_attr("checked", is_checked, span, buf);
+            }
+            Some(StrOrExpr::Str(ref input_type)) if input_type == "radio" => {
+                let equal_call = self.ssr_call(
+                    SsrImports::SsrLooseEqual,
+                    vec![model.to_owned(), Box::new(value)],
+                    span,
+                );
+                self.generate_ssr_include_boolean_attr("checked", equal_call, span, buf);
+            }
+            _ => {
+                let render_attr_call = self.ssr_call(
+                    SsrImports::SsrRenderAttr,
+                    vec![
+                        Box::new(str_lit(&fervid_atom!("value"), span)),
+                        model.to_owned(),
+                    ],
+                    span,
+                );
+                buf.push_expr(render_attr_call);
+            }
+        }
+    }
+
+    /// `<option>` of a `<select v-model>` is `selected` when
+    /// `Array.isArray(model) ? _ssrLooseContain(model, value) : _ssrLooseEqual(model, value)`.
+    /// The option value is either its `value` attribute or its static text.
+    fn generate_ssr_option_selected(&mut self, element_node: &ElementNode, buf: &mut SsrBuffer) {
+        let Some(model) = self.ssr_select_model.to_owned() else {
+            return;
+        };
+        let Some(value) = value_attr(&element_node.starting_tag.attributes)
+            .or_else(|| static_text(&element_node.children))
+        else {
+            return;
+        };
+
+        let span = element_node.span;
+        let contain_call = self.ssr_call(
+            SsrImports::SsrLooseContain,
+            vec![model.to_owned(), Box::new(value.to_owned())],
+            span,
+        );
+        let equal_call = self.ssr_call(
+            SsrImports::SsrLooseEqual,
+            vec![model.to_owned(), Box::new(value)],
+            span,
+        );
+        let is_selected = Expr::Cond(CondExpr {
+            span,
+            test: Box::new(is_array_call(&model, span)),
+            cons: Box::new(contain_call),
+            alt: Box::new(equal_call),
+        });
+        self.generate_ssr_include_boolean_attr("selected", is_selected, span, buf);
+    }
+
+    /// Generates `_push(_ssrRenderComponent(_component_foo, { props }, { slots }, _parent))`
+    fn generate_ssr_component(
+        &mut self,
+        component_node: &ElementNode,
+        buf: &mut SsrBuffer,
+        out: &mut Vec<Stmt>,
+        is_root: bool,
+    ) {
+        buf.flush(out);
+
+        let span = component_node.span;
+        let component_identifier =
+            self.get_component_identifier(&component_node.starting_tag.tag_name, span);
+
+        let attributes_obj = self.generate_component_attributes(component_node);
+        let attributes_expr = if is_root {
+            self.merge_with_fallthrough_attrs(attributes_obj)
+        } else if attributes_obj.props.is_empty() {
+            Expr::Lit(Lit::Null(Null { span }))
+        } else {
+            Expr::Object(attributes_obj)
+        };
+
+        // Slots are generated the same way as on the client.
+        // Server renderer handles the VNodes returned from them.
+        let slots = self
+            .generate_component_children(component_node)
+            .unwrap_or_else(|| Expr::Lit(Lit::Null(Null { span })));
+
+        let render_component_call = self.ssr_call(
+            SsrImports::SsrRenderComponent,
+            vec![
+                Box::new(component_identifier),
+                Box::new(attributes_expr),
+                Box::new(slots),
+                Box::new(Expr::Ident(ident("_parent"))),
+            ],
+            span,
+        );
+        out.push(push_stmt(render_component_call));
+    }
+
+    /// Generates `_ssrRenderSlot(_ctx.$slots, "name", { props }, () => { fallback }, _push, _parent)`
+    fn generate_ssr_slot(&mut self, element_node: &ElementNode) -> Stmt {
+        let span = element_node.span;
+        let attributes = &element_node.starting_tag.attributes;
+
+        // Slot name is not a part of slot props
+        let idx_of_name = attributes
+            .iter()
+            .position(|attr| check_attribute_name(attr, "name"));
+
+        let name_expr = match idx_of_name.map(|idx| &attributes[idx]) {
+            Some(AttributeOrBinding::RegularAttribute { value, .. }) => str_lit(value, span),
+            Some(AttributeOrBinding::VBind(v_bind)) => (*v_bind.value).to_owned(),
+            _ => str_lit(&fervid_atom!("default"), span),
+        };
+
+        let mut props_obj = ObjectLit {
+            span,
+            props: Vec::with_capacity(attributes.len()),
+        };
+        match idx_of_name {
+            Some(idx) => {
+                self.generate_attributes(&attributes[..idx], &mut props_obj.props);
+                self.generate_attributes(&attributes[(idx + 1)..], &mut props_obj.props);
+            }
+            None => {
+                self.generate_attributes(attributes, &mut props_obj.props);
+            }
+        }
+
+        let fallback = if element_node.children.is_empty() {
+            Expr::Lit(Lit::Null(Null { span }))
+        } else {
+            let mut fallback_stmts = Vec::new();
+            let mut fallback_buf = SsrBuffer::default();
+            for child in element_node.children.iter() {
+                self.generate_ssr_node(child, &mut fallback_buf, &mut fallback_stmts, false);
+            }
+            fallback_buf.flush(&mut fallback_stmts);
+
+            Expr::Arrow(ArrowExpr {
+                span,
+                params: vec![],
+                body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: fallback_stmts,
+                })),
+                is_async: false,
+                is_generator: false,
+                type_params: None,
+                return_type: None,
+            })
+        };
+
+        let ctx_slots = Expr::Member(MemberExpr {
+            span,
+            obj: Box::new(Expr::Ident(ident("_ctx"))),
+            prop: MemberProp::Ident(ident("$slots")),
+        });
+
+        let render_slot_call = self.ssr_call(
+            SsrImports::SsrRenderSlot,
+            vec![
+                Box::new(ctx_slots),
+                Box::new(name_expr),
+                Box::new(Expr::Object(props_obj)),
+                Box::new(fallback),
+                Box::new(Expr::Ident(ident("_push"))),
+                Box::new(Expr::Ident(ident("_parent"))),
+            ],
+            span,
+        );
+
+        Stmt::Expr(ExprStmt {
+            span,
+            expr: Box::new(render_slot_call),
+        })
+    }
+
+    /// Generates `if (a) { /* ... */ } else if (b) { /* ... */ } else { _push(`<!---->`) }`.
+    /// Each branch of a root conditional sequence is a root itself and receives the `_attrs`.
+    fn generate_ssr_conditional_seq(
+        &mut self,
+        conditional_seq: &ConditionalNodeSequence,
+        is_root: bool,
+    ) -> Stmt {
+        // Without `v-else`, a placeholder comment is rendered
+        let mut alt = match conditional_seq.else_node {
+            Some(ref else_node) => self.generate_ssr_branch(else_node, is_root),
+            None => {
+                let mut placeholder_buf = SsrBuffer::default();
+                placeholder_buf.push_str("<!---->");
+                let mut stmts = Vec::with_capacity(1);
+                placeholder_buf.flush(&mut stmts);
+                BlockStmt {
+                    span: DUMMY_SP,
+                    stmts,
+                }
+            }
+        };
+
+        let mut result = None;
+        for conditional in conditional_seq
+            .else_if_nodes
+            .iter()
+            .rev()
+            .chain(std::iter::once(conditional_seq.if_node.as_ref()))
+        {
+            let alt_stmt = match result.take() {
+                Some(if_stmt) => Stmt::If(if_stmt),
+                None => Stmt::Block(std::mem::replace(
+                    &mut alt,
+                    BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![],
+                    },
+                )),
+            };
+
+            result = Some(IfStmt {
+                span: conditional.node.span,
+                test: Box::new(conditional.condition.to_owned()),
+                cons: Box::new(Stmt::Block(
+                    self.generate_ssr_branch(&conditional.node, is_root),
+                )),
+                alt: Some(Box::new(alt_stmt)),
+            });
+        }
+
+        // There is always an `if_node`
+        Stmt::If(result.expect("Conditional sequence must have a `v-if` node"))
+    }
+
+    fn generate_ssr_branch(&mut self, element_node: &ElementNode, is_root: bool) -> BlockStmt {
+        let mut stmts = Vec::new();
+        let mut buf = SsrBuffer::default();
+        self.generate_ssr_element_or_component(element_node, &mut buf, &mut stmts, is_root);
+        buf.flush(&mut stmts);
+
+        BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        }
+    }
+
+    /// Generates `_mergeProps({ attrs }, _attrs)` or just `_attrs` when there are no own attributes
+    fn merge_with_fallthrough_attrs(&mut self, attrs_obj: ObjectLit) -> Expr {
+        let fallthrough_attrs = Expr::Ident(ident("_attrs"));
+        if attrs_obj.props.is_empty() {
+            return fallthrough_attrs;
+        }
+
+        let span = attrs_obj.span;
+        Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                span,
+                sym: self.get_and_add_import_ident(VueImports::MergeProps),
+                optional: false,
+            }))),
+            args: vec![
+                ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Object(attrs_obj)),
+                },
+                ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(fallthrough_attrs),
+                },
+            ],
+            type_args: None,
+        })
+    }
+
+    fn ssr_call(&mut self, ssr_import: SsrImports, args: Vec<Box<Expr>>, span: Span) -> Expr {
+        Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                span,
+                sym: self.get_and_add_ssr_import_ident(ssr_import),
+                optional: false,
+            }))),
+            args: args
+                .into_iter()
+                .map(|expr| ExprOrSpread { spread: None, expr })
+                .collect(),
+            type_args: None,
+        })
+    }
+}
+
+/// Merges `v-show` into the `style` of the attributes object:
+/// `{ style: [existingStyle, cond ? null : { display: "none" }] }`
+fn add_v_show_style(attrs_obj: &mut ObjectLit, v_show: &Expr) {
+    let span = v_show.span();
+
+    // `{ display: "none" }`
+    let display_none = Expr::Object(ObjectLit {
+        span,
+        props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(ident("display")),
+            value: Box::new(str_lit(&fervid_atom!("none"), span)),
+        })))],
+    });
+
+    // `cond ? null : { display: "none" }`
+    let hidden_style = if is_bool_lit(v_show, false) {
+        display_none
+    } else {
+        Expr::Cond(CondExpr {
+            span,
+            test: Box::new(v_show.to_owned()),
+            cons: Box::new(Expr::Lit(Lit::Null(Null { span }))),
+            alt: Box::new(display_none),
+        })
+    };
+
+    let existing_style = attrs_obj.props.iter_mut().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match **prop {
+            Prop::KeyValue(ref mut key_value)
+                if matches!(key_value.key, PropName::Ident(ref key) if &key.sym == "style") =>
+            {
+                Some(&mut key_value.value)
+            }
+            _ => None,
+        },
+        _ => None,
+    });
+
+    match existing_style {
+        Some(existing_style) => {
+            let old_style = std::mem::replace(
+                existing_style,
+                Box::new(Expr::Lit(Lit::Null(Null { span }))),
+            );
+            *existing_style = Box::new(Expr::Array(ArrayLit {
+                span,
+                elems: vec![
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: old_style,
+                    }),
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(hidden_style),
+                    }),
+                ],
+            }));
+        }
+        None => {
+            attrs_obj
+                .props
+                .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(ident("style")),
+                    value: Box::new(hidden_style),
+                }))));
+        }
+    }
+}
+
+/// Appends `display:none;` to a static `style` attribute value
+fn hide_static_style(style: &str) -> String {
+    let style = style.trim();
+    if style.is_empty() {
+        return String::from("display:none;");
+    }
+
+    let separator = if style.ends_with(';') { "" } else { ";" };
+    format!("{style}{separator}display:none;")
+}
+
+/// Name of a bound boolean attribute, e.g. `disabled` for `:disabled="cond"`.
+/// Bindings with `.prop` are skipped, because they are not rendered as attributes.
+fn boolean_attr_name(v_bind: &VBindDirective) -> Option<&str> {
+    let Some(StrOrExpr::Str(ref argument)) = v_bind.argument else {
+        return None;
+    };
+    if v_bind.is_prop || !BOOLEAN_ATTRS.contains(&argument.as_ref()) {
+        return None;
+    }
+    Some(argument.as_ref())
+}
+
+/// Value of the `value` attribute, either static or bound
+fn value_attr(attributes: &[AttributeOrBinding]) -> Option<Expr> {
+    attributes.iter().find_map(|attr| match attr {
+        AttributeOrBinding::RegularAttribute { name, value, span } if name == "value" => {
+            Some(str_lit(value, *span))
+        }
+        AttributeOrBinding::VBind(VBindDirective {
+            argument: Some(StrOrExpr::Str(name)),
+            value,
+            ..
+        }) if name == "value" => Some((**value).to_owned()),
+        _ => None,
+    })
+}
+
+/// Text of the children when all of them are static text, e.g. `<option>Foo</option>`
+fn static_text(children: &[Node]) -> Option<Expr> {
+    let mut text = String::new();
+    for child in children.iter() {
+        let Node::Text(contents, _) = child else {
+            return None;
+        };
+        text.push_str(contents);
+    }
+    Some(str_lit(&FervidAtom::from(text.trim()), DUMMY_SP))
+}
+
+/// `Array.isArray(expr)`
+fn is_array_call(expr: &Expr, span: Span) -> Expr {
+    Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            span,
+            obj: Box::new(Expr::Ident(ident("Array"))),
+            prop: MemberProp::Ident(ident("isArray")),
+        }))),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(expr.to_owned()),
+        }],
+        type_args: None,
+    })
+}
+
+#[inline]
+fn is_bool_lit(expr: &Expr, value: bool) -> bool {
+    matches!(expr, Expr::Lit(Lit::Bool(Bool { value: v, .. })) if *v == value)
+}
+
+/// `_push(arg)`
+fn push_stmt(arg: Expr) -> Stmt {
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Ident(ident("_push")))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(arg),
+            }],
+            type_args: None,
+        })),
+    })
+}
+
+#[inline]
+fn ident(sym: &str) -> Ident {
+    Ident {
+        span: DUMMY_SP,
+        sym: FervidAtom::from(sym),
+        optional: false,
+    }
+}
+
+#[inline]
+fn str_lit(value: &FervidAtom, span: Span) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span,
+        value: value.to_owned(),
+        raw: None,
+    }))
+}
+
+fn escape_html(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+/// Escapes the characters which have a special meaning inside a template literal
+fn escape_template_literal(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => result.push_str("\\\\"),
+            '`' => result.push_str("\\`"),
+            '$' if chars.peek() == Some(&'{') => result.push_str("\\$"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{
+        Conditional, Interpolation, StartingTag, VForDirective, VOnDirective, VueDirectives,
+    };
+
+    use crate::test_utils::{js, regular_attribute, to_str, v_bind_attribute, v_on_attribute};
+
+    use super::*;
+
+    fn element(
+        tag_name: &str,
+        attributes: Vec<AttributeOrBinding>,
+        children: Vec<Node>,
+    ) -> ElementNode {
+        ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: tag_name.into(),
+                attributes,
+                directives: None,
+            },
+            children,
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        }
+    }
+
+    fn text(contents: &str) -> Node {
+        Node::Text(contents.into(), DUMMY_SP)
+    }
+
+    fn interpolation(value: &str) -> Node {
+        Node::Interpolation(Interpolation {
+            value: js(value),
+            template_scope: 0,
+            patch_flag: false,
+            span: DUMMY_SP,
+        })
+    }
+
+    fn test_out(roots: Vec<Node>, expected: &str) {
+        let mut ctx = CodegenContext::default();
+        ctx.is_ssr = true;
+        let arrow = ctx
+            .generate_ssr_render_arrow(&SfcTemplateBlock {
+                lang: "html".into(),
+                roots,
+                span: DUMMY_SP,
+            })
+            .expect("Template is not empty");
+        assert_eq!(to_str(arrow), expected);
+    }
+
+    #[test]
+    fn it_generates_static_component() {
+        test_out(
+            vec![Node::Element(element(
+                "div",
+                vec![],
+                vec![Node::Element(element(
+                    "span",
+                    vec![regular_attribute("class", "foo")],
+                    vec![text("hello <world>")],
+                ))],
+            ))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<div${_ssrRenderAttrs(_attrs)}><span class=\"foo\">hello &lt;world&gt;</span></div>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_dynamic_props_interpolation() {
+        test_out(
+            vec![Node::Element(element(
+                "div",
+                vec![],
+                vec![Node::Element(element(
+                    "a",
+                    vec![v_bind_attribute("href", "_ctx.url")],
+                    vec![interpolation("_ctx.label")],
+                ))],
+            ))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<div${_ssrRenderAttrs(_attrs)}><a${_ssrRenderAttrs({href:_ctx.url})}>${_ssrInterpolate(_ctx.label)}</a></div>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_boolean_attributes() {
+        let test = |attributes: Vec<AttributeOrBinding>, expected_input: &str| {
+            test_out(
+                vec![Node::Element(element(
+                    "div",
+                    vec![],
+                    vec![Node::Element(element("input", attributes, vec![]))],
+                ))],
+                &format!(
+                    "(_ctx,_push,_parent,_attrs)=>{{_push(`<div${{_ssrRenderAttrs(_attrs)}}>{expected_input}</div>`);}}"
+                ),
+            );
+        };
+
+        // Static values are inlined
+        test(
+            vec![v_bind_attribute("disabled", "true")],
+            "<input disabled>",
+        );
+        test(vec![v_bind_attribute("disabled", "false")], "<input>");
+
+        // Dynamic value
+        test(
+            vec![
+                regular_attribute("type", "checkbox"),
+                v_bind_attribute("checked", "_ctx.cond"),
+            ],
+            "<input type=\"checkbox\"${_ssrIncludeBooleanAttr(_ctx.cond)?\" checked\":\"\"}>",
+        );
+
+        // Not a boolean attribute
+        test(
+            vec![v_bind_attribute("title", "false")],
+            "<input${_ssrRenderAttrs({title:false})}>",
+        );
+    }
+
+    #[test]
+    fn it_generates_v_if() {
+        let mut if_node = element("span", vec![], vec![text("yes")]);
+        if_node.starting_tag.directives = Some(Box::new(VueDirectives {
+            v_if: Some(js("_ctx.ok")),
+            ..Default::default()
+        }));
+
+        test_out(
+            vec![Node::Element(element(
+                "div",
+                vec![],
+                vec![Node::ConditionalSeq(ConditionalNodeSequence {
+                    if_node: Box::new(Conditional {
+                        condition: *js("_ctx.ok"),
+                        node: if_node,
+                    }),
+                    else_if_nodes: vec![],
+                    else_node: None,
+                })],
+            ))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<div${_ssrRenderAttrs(_attrs)}>`);if(_ctx.ok){_push(`<span>yes</span>`);}else{_push(`<!---->`);}_push(`</div>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_root_v_if_with_attrs() {
+        let mut if_node = element("span", vec![], vec![text("yes")]);
+        if_node.starting_tag.directives = Some(Box::new(VueDirectives {
+            v_if: Some(js("_ctx.ok")),
+            ..Default::default()
+        }));
+        let else_node = element("div", vec![], vec![text("no")]);
+
+        // Both branches are roots
+        test_out(
+            vec![Node::ConditionalSeq(ConditionalNodeSequence {
+                if_node: Box::new(Conditional {
+                    condition: *js("_ctx.ok"),
+                    node: if_node,
+                }),
+                else_if_nodes: vec![],
+                else_node: Some(Box::new(else_node)),
+            })],
+            "(_ctx,_push,_parent,_attrs)=>{if(_ctx.ok){_push(`<span${_ssrRenderAttrs(_attrs)}>yes</span>`);}else{_push(`<div${_ssrRenderAttrs(_attrs)}>no</div>`);}}",
+        );
+    }
+
+    #[test]
+    fn it_generates_v_html() {
+        let mut p = element("p", vec![], vec![]);
+        p.starting_tag.directives = Some(Box::new(VueDirectives {
+            v_html: Some(js("_ctx.html")),
+            ..Default::default()
+        }));
+
+        test_out(
+            vec![Node::Element(element("div", vec![], vec![Node::Element(p)]))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<div${_ssrRenderAttrs(_attrs)}><p>${(_ctx.html)??\"\"}</p></div>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_v_model() {
+        // <div><tag_name v-model="_ctx.model">children</tag_name></div>
+        let test = |tag_name: &str,
+                    attributes: Vec<AttributeOrBinding>,
+                    children: Vec<Node>,
+                    expected_element: &str| {
+            let mut model_element = element(tag_name, attributes, children);
+            model_element.starting_tag.directives = Some(Box::new(VueDirectives {
+                v_model: vec![VModelDirective {
+                    argument: None,
+                    value: js("_ctx.model"),
+                    update_handler: None,
+                    modifiers: vec![],
+                    span: DUMMY_SP,
+                }],
+                ..Default::default()
+            }));
+
+            test_out(
+                vec![Node::Element(element(
+                    "div",
+                    vec![],
+                    vec![Node::Element(model_element)],
+                ))],
+                &format!(
+                    "(_ctx,_push,_parent,_attrs)=>{{_push(`<div${{_ssrRenderAttrs(_attrs)}}>{expected_element}</div>`);}}"
+                ),
+            );
+        };
+
+        // Text input
+        test(
+            "input",
+            vec![],
+            vec![],
+            "<input${_ssrRenderAttr(\"value\",_ctx.model)}>",
+        );
+
+        // Checkbox
+        test(
+            "input",
+            vec![
+                regular_attribute("type", "checkbox"),
+                regular_attribute("value", "a"),
+            ],
+            vec![],
+            "<input type=\"checkbox\" value=\"a\"${_ssrIncludeBooleanAttr(Array.isArray(_ctx.model)?_ssrLooseContain(_ctx.model,\"a\"):_ctx.model)?\" checked\":\"\"}>",
+        );
+
+        // Radio
+        test(
+            "input",
+            vec![
+                regular_attribute("type", "radio"),
+                v_bind_attribute("value", "_ctx.value"),
+            ],
+            vec![],
+            "<input${_ssrRenderAttrs({type:\"radio\",value:_ctx.value})}${_ssrIncludeBooleanAttr(_ssrLooseEqual(_ctx.model,_ctx.value))?\" checked\":\"\"}>",
+        );
+
+        // Dynamic type
+        test(
+            "input",
+            vec![v_bind_attribute("type", "_ctx.type")],
+            vec![],
+            "<input${_ssrRenderAttrs({type:_ctx.type})}${_ssrRenderDynamicModel(_ctx.type,_ctx.model,null)}>",
+        );
+
+        // Textarea
+        test(
+            "textarea",
+            vec![],
+            vec![],
+            "<textarea>${_ssrInterpolate(_ctx.model)}</textarea>",
+        );
+
+        // Select, option value is either the `value` attribute or the text
+        test(
+            "select",
+            vec![],
+            vec![
+                Node::Element(element(
+                    "option",
+                    vec![regular_attribute("value", "a")],
+                    vec![text("A")],
+                )),
+                Node::Element(element("option", vec![], vec![text("b")])),
+            ],
+            "<select><option value=\"a\"${_ssrIncludeBooleanAttr(Array.isArray(_ctx.model)?_ssrLooseContain(_ctx.model,\"a\"):_ssrLooseEqual(_ctx.model,\"a\"))?\" selected\":\"\"}>A</option><option${_ssrIncludeBooleanAttr(Array.isArray(_ctx.model)?_ssrLooseContain(_ctx.model,\"b\"):_ssrLooseEqual(_ctx.model,\"b\"))?\" selected\":\"\"}>b</option></select>",
+        );
+    }
+
+    #[test]
+    fn it_generates_v_for() {
+        let mut item = element("li", vec![], vec![interpolation("item")]);
+        item.starting_tag.directives = Some(Box::new(VueDirectives {
+            v_for: Some(VForDirective {
+                iterable: js("_ctx.items"),
+                itervar: js("item"),
+                patch_flags: Default::default(),
+                span: DUMMY_SP,
+            }),
+            ..Default::default()
+        }));
+
+        test_out(
+            vec![Node::Element(element("ul", vec![], vec![Node::Element(item)]))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<ul${_ssrRenderAttrs(_attrs)}><!--[-->`);_ssrRenderList(_ctx.items,(item)=>{_push(`<li>${_ssrInterpolate(item)}</li>`);});_push(`<!--]--></ul>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_slot() {
+        let mut slot = element(
+            "slot",
+            vec![regular_attribute("name", "header")],
+            vec![text("fallback")],
+        );
+        slot.kind = ElementKind::Builtin(BuiltinType::Slot);
+
+        test_out(
+            vec![Node::Element(element("div", vec![], vec![Node::Element(slot)]))],
+            "(_ctx,_push,_parent,_attrs)=>{_push(`<div${_ssrRenderAttrs(_attrs)}>`);_ssrRenderSlot(_ctx.$slots,\"header\",{},()=>{_push(`fallback`);},_push,_parent);_push(`</div>`);}",
+        );
+    }
+
+    #[test]
+    fn it_generates_v_show() {
+        // <div><span v-show="v_show" style="style">hi</span></div>
+        let test = |v_show: &str, style: Option<AttributeOrBinding>, expected_span: &str| {
+            let mut span = element("span", style.into_iter().collect(), vec![text("hi")]);
+            span.starting_tag.directives = Some(Box::new(VueDirectives {
+                v_show: Some(js(v_show)),
+                ..Default::default()
+            }));
+
+            test_out(
+                vec![Node::Element(element("div", vec![], vec![Node::Element(span)]))],
+                &format!(
+                    "(_ctx,_push,_parent,_attrs)=>{{_push(`<div${{_ssrRenderAttrs(_attrs)}}>{expected_span}</div>`);}}"
+                ),
+            );
+        };
+
+        // Static `false` is inlined
+        test("false", None, "<span style=\"display:none;\">hi</span>");
+        test(
+            "false",
+            Some(regular_attribute("style", "color: red")),
+            "<span style=\"color: red;display:none;\">hi</span>",
+        );
+
+        // Static `true` changes nothing
+        test("true", None, "<span>hi</span>");
+
+        // Dynamic condition
+        test(
+            "_ctx.cond",
+            None,
+            "<span${_ssrRenderAttrs({style:_ctx.cond?null:{display:\"none\"}})}>hi</span>",
+        );
+
+        // Merged with the existing `:style`
+        test(
+            "_ctx.cond",
+            Some(v_bind_attribute("style", "_ctx.styles")),
+            "<span${_ssrRenderAttrs({style:[_normalizeStyle(_ctx.styles),_ctx.cond?null:{display:\"none\"}]})}>hi</span>",
+        );
+    }
+
+    #[test]
+    fn it_omits_element_listeners() {
+        let v_on = |modifiers: &[&str]| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some("onClick".into()),
+                handler: Some(js("_ctx.handler")),
+                modifiers: modifiers.iter().map(|m| FervidAtom::from(*m)).collect(),
+                span: DUMMY_SP,
+            })
+        };
+        let test = |attributes: Vec<AttributeOrBinding>, expected_button: &str| {
+            test_out(
+                vec![Node::Element(element(
+                    "div",
+                    vec![],
+                    vec![Node::Element(element("button", attributes, vec![text("ok")]))],
+                ))],
+                &format!(
+                    "(_ctx,_push,_parent,_attrs)=>{{_push(`<div${{_ssrRenderAttrs(_attrs)}}>{expected_button}</div>`);}}"
+                ),
+            );
+        };
+
+        // @click="handler"
+        test(vec![v_on(&[])], "<button>ok</button>");
+
+        // @click.capture="handler"
+        test(
+            vec![regular_attribute("type", "button"), v_on(&["capture"])],
+            "<button type=\"button\">ok</button>",
+        );
+
+        // @click.prevent="handler" :title="title"
+        test(
+            vec![v_on(&["prevent"]), v_bind_attribute("title", "_ctx.title")],
+            "<button${_ssrRenderAttrs({title:_ctx.title})}>ok</button>",
+        );
+    }
+
+    #[test]
+    fn it_keeps_component_listeners() {
+        // <MyInput @update:value="onUpdate" />
+        let mut component = element(
+            "MyInput",
+            vec![v_on_attribute("onUpdate:value", "_ctx.onUpdate")],
+            vec![],
+        );
+        component.kind = ElementKind::Component;
+
+        let mut ctx = CodegenContext::default();
+        ctx.is_ssr = true;
+        let arrow = ctx
+            .generate_ssr_render_arrow(&SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(element(
+                    "div",
+                    vec![],
+                    vec![Node::Element(component)],
+                ))],
+                span: DUMMY_SP,
+            })
+            .expect("Template is not empty");
+
+        // Component events are props
+        assert!(to_str(arrow).contains(r#"_ssrRenderComponent(_component_MyInput,{"onUpdate:value":_ctx.onUpdate},null,_parent)"#));
+    }
+
+    #[test]
+    fn it_renders_scope_id() {
+        // <div><span>hello</span><MyComp /></div>
+        let mut component = element("MyComp", vec![], vec![]);
+        component.kind = ElementKind::Component;
+
+        let mut ctx = CodegenContext::default();
+        ctx.is_ssr = true;
+        ctx.scope_id = Some(fervid_atom!("data-v-abcd1234"));
+        let arrow = ctx
+            .generate_ssr_render_arrow(&SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(element(
+                    "div",
+                    vec![],
+                    vec![
+                        Node::Element(element("span", vec![], vec![text("hello")])),
+                        Node::Element(component),
+                    ],
+                ))],
+                span: DUMMY_SP,
+            })
+            .expect("Template is not empty");
+
+        // Components receive the scope from their parent at runtime
+        let code = to_str(arrow);
+        assert!(code.contains(
+            "_push(`<div${_ssrRenderAttrs(_attrs)} data-v-abcd1234><span data-v-abcd1234>hello</span>`)"
+        ));
+        assert!(code.contains("_ssrRenderComponent(_component_MyComp,null,null,_parent)"));
+        assert_eq!(code.matches("data-v-abcd1234").count(), 2);
+    }
+}
@@ -55,6 +55,19 @@ pub fn v_bind_attribute(name: &str, value: &str) -> AttributeOrBinding {
     })
 }
 
+/// TEST ONLY
+#[inline]
+pub fn v_bind_spread_attribute(value: &str) -> AttributeOrBinding {
+    AttributeOrBinding::VBind(VBindDirective {
+        argument: None,
+        value: js(value),
+        is_camel: false,
+        is_prop: false,
+        is_attr: false,
+        span: DUMMY_SP,
+    })
+}
+
 /// TEST ONLY
 #[inline]
 pub fn v_on_attribute(name: &str, value: &str) -> AttributeOrBinding {
@@ -65,3 +78,14 @@ pub fn v_on_attribute(name: &str, value: &str) -> AttributeOrBinding {
         span: DUMMY_SP,
     })
 }
+
+/// TEST ONLY
+#[inline]
+pub fn v_on_spread_attribute(value: &str) -> AttributeOrBinding {
+    AttributeOrBinding::VOn(VOnDirective {
+        event: None,
+        handler: Some(js(value)),
+        modifiers: vec![],
+        span: DUMMY_SP,
+    })
+}
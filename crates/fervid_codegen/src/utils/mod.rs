@@ -78,3 +78,19 @@ pub fn to_camelcase(s: &str, buf: &mut impl Write) -> Result<(), Error> {
 
     Ok(())
 }
+
+pub fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `foo-bar` and `fooBar` become `FooBar`
+pub fn to_pascal_case(s: &str) -> String {
+    let mut camelized = String::with_capacity(s.len());
+    // Writing to a `String` never fails
+    let _ = to_camelcase(s, &mut camelized);
+    capitalize(&camelized)
+}
@@ -1,5 +1,8 @@
 use phf::phf_set;
 
+/// Checks whether `tag` is a known HTML (or SVG) tag.
+/// The lookup is case-sensitive, same as in the official compiler,
+/// so that components such as `<Button>` or `<Table>` are not mistaken for native tags.
 pub fn is_html_tag(tag: &str) -> bool {
     TAGS.contains(tag)
 }
@@ -172,3 +175,27 @@ static TAGS: phf::Set<&'static str> = phf_set! {
     "wbr",
     "xmp",
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_recognizes_html_tags() {
+        assert!(is_html_tag("div"));
+        assert!(is_html_tag("input"));
+        assert!(is_html_tag("clipPath"));
+    }
+
+    #[test]
+    fn it_does_not_recognize_components() {
+        assert!(!is_html_tag("MyComponent"));
+        assert!(!is_html_tag("myComponent"));
+        assert!(!is_html_tag("my-component"));
+
+        // Components named after native tags
+        for tag in ["Button", "Input", "Select", "Table", "Form", "Header"] {
+            assert!(!is_html_tag(tag));
+        }
+    }
+}
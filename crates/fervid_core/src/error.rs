@@ -24,6 +24,14 @@ pub trait Severity {
     fn is_warning(&self) -> bool {
         matches!(self.get_severity(), SeverityLevel::Warning)
     }
+
+    /// Returns `true` if the severity level is [`Hint`].
+    ///
+    /// [`Hint`]: SeverityLevel::Hint
+    #[must_use]
+    fn is_hint(&self) -> bool {
+        matches!(self.get_severity(), SeverityLevel::Hint)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -32,4 +40,5 @@ pub enum SeverityLevel {
     UnrecoverableError,
     RecoverableError,
     Warning,
+    Hint,
 }
@@ -14,5 +14,5 @@ pub use sfc::*;
 pub use structs::*;
 pub use template::is_from_default_slot;
 pub use vue_builtins::VUE_BUILTINS;
-pub use vue_imports::{VueImports, VueImportsSet};
+pub use vue_imports::{SsrImports, SsrImportsSet, VueImports, VueImportsSet};
 pub use utils::*;
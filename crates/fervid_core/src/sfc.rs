@@ -1,4 +1,7 @@
-use swc_core::{common::Span, ecma::ast::Module};
+use swc_core::{
+    common::Span,
+    ecma::ast::{Expr, Module},
+};
 
 use crate::{FervidAtom, Node, StartingTag};
 
@@ -31,7 +34,10 @@ pub struct SfcStyleBlock {
     pub lang: FervidAtom,
     pub content: FervidAtom,
     pub is_scoped: bool,
-    pub is_module: bool,
+    /// Name of the CSS module, `$style` for `<style module>` or `classes` for `<style module="classes">`
+    pub module_name: Option<FervidAtom>,
+    /// Legacy `<style vars="{ color }">` expression, only compiled in compat mode
+    pub vars: Option<Box<Expr>>,
     pub span: Span,
 }
 
@@ -46,4 +52,15 @@ pub struct SfcCustomBlock {
 pub enum SfcScriptLang {
     Es,
     Typescript,
+    /// `lang="jsx"`. JSX is emitted as-is, it is up to the bundler to transform it
+    Jsx,
+    /// `lang="tsx"`. JSX is emitted as-is, it is up to the bundler to transform it
+    Tsx,
+}
+
+impl SfcScriptLang {
+    #[inline]
+    pub fn is_ts(&self) -> bool {
+        matches!(self, SfcScriptLang::Typescript | SfcScriptLang::Tsx)
+    }
 }
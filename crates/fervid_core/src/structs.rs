@@ -159,7 +159,11 @@ pub struct PatchHints {
     /// Dynamic props
     pub props: Vec<JsWord>,
     /// Whether the node codegen needs to be surrounded by `(openBlock(),`
-    pub should_use_block: bool
+    pub should_use_block: bool,
+    /// Whether `class` is a declared prop of the component, i.e. it does not fall through
+    pub is_class_prop: bool,
+    /// Whether `style` is a declared prop of the component, i.e. it does not fall through
+    pub is_style_prop: bool
 }
 
 flagset::flags! {
@@ -358,6 +362,13 @@ pub struct VSlotDirective {
     pub value: Option<Box<Pat>>,
 }
 
+impl VSlotDirective {
+    /// Whether the slot name is only known at runtime, e.g. `#[name]` or `v-slot:[name]`
+    pub fn is_dynamic_slot(&self) -> bool {
+        matches!(self.slot_name, Some(StrOrExpr::Expr(_)))
+    }
+}
+
 /// A custom directive defined by a user.
 #[derive(Debug, Default, Clone)]
 pub struct VCustomDirective {
@@ -431,3 +442,66 @@ pub enum TemplateGenerationMode {
     #[default]
     RenderFn
 }
+
+/// How the whitespace in the `<template>` is handled, same as the `whitespace` option of the official compiler.
+/// Contents of `<pre>` are always preserved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WhitespaceMode {
+    /// Removes whitespace-only text between elements when it contains a newline,
+    /// and condenses any other whitespace sequence into a single space
+    #[default]
+    Condense,
+
+    /// Only removes whitespace-only text at the start and the end of element contents,
+    /// and condenses any other whitespace-only text into a single space
+    Preserve,
+}
+
+/// Tells whether a tag is a custom element, e.g. registered with `customElements.define`,
+/// same as the `isCustomElement` option of the official compiler.
+/// Matching tags are rendered as plain elements instead of being resolved as components.
+///
+/// The predicate is opaque to the compilation cache,
+/// use [`CustomElementPredicate::with_cache_key`] to let it cache the results.
+///
+/// ## Example
+/// ```
+/// use fervid_core::CustomElementPredicate;
+///
+/// let is_custom_element = CustomElementPredicate::new(|tag| tag.starts_with("my-"));
+/// assert!(is_custom_element.matches("my-widget"));
+/// ```
+#[derive(Clone)]
+pub struct CustomElementPredicate {
+    predicate: std::sync::Arc<dyn Fn(&str) -> bool + Send + Sync>,
+    cache_key: Option<u64>,
+}
+
+impl CustomElementPredicate {
+    pub fn new(predicate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        CustomElementPredicate {
+            predicate: std::sync::Arc::new(predicate),
+            cache_key: None,
+        }
+    }
+
+    /// Identifies the behaviour of the predicate, e.g. a hash of the matched prefixes
+    pub fn with_cache_key(mut self, cache_key: u64) -> Self {
+        self.cache_key = Some(cache_key);
+        self
+    }
+
+    pub fn matches(&self, tag_name: &str) -> bool {
+        (self.predicate)(tag_name)
+    }
+
+    pub fn cache_key(&self) -> Option<u64> {
+        self.cache_key
+    }
+}
+
+impl std::fmt::Debug for CustomElementPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomElementPredicate")
+    }
+}
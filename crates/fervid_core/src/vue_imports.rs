@@ -5,6 +5,12 @@ use strum_macros::{AsRefStr, EnumString, IntoStaticStr};
 flags! {
     #[derive(AsRefStr, EnumString, IntoStaticStr)]
     pub enum VueImports: u64 {
+        #[strum(serialize = "_camelize")]
+        Camelize,
+        #[strum(serialize = "_computed")]
+        Computed,
+        #[strum(serialize = "_createApp")]
+        CreateApp,
         #[strum(serialize = "_createBlock")]
         CreateBlock,
         #[strum(serialize = "_createCommentVNode")]
@@ -17,24 +23,40 @@ flags! {
         CreateTextVNode,
         #[strum(serialize = "_createVNode")]
         CreateVNode,
+        #[strum(serialize = "_customRef")]
+        CustomRef,
+        #[strum(serialize = "_defineAsyncComponent")]
+        DefineAsyncComponent,
         #[strum(serialize = "_defineComponent")]
         DefineComponent,
         #[strum(serialize = "_Fragment")]
         Fragment,
+        #[strum(serialize = "_guardReactiveProps")]
+        GuardReactiveProps,
+        #[strum(serialize = "_h")]
+        H,
         #[strum(serialize = "_isMemoSame")]
         IsMemoSame,
         #[strum(serialize = "_isRef")]
         IsRef,
         #[strum(serialize = "_KeepAlive")]
         KeepAlive,
+        #[strum(serialize = "_mergeDefaults")]
+        MergeDefaults,
         #[strum(serialize = "_mergeModels")]
         MergeModels,
+        #[strum(serialize = "_mergeProps")]
+        MergeProps,
         #[strum(serialize = "_normalizeClass")]
         NormalizeClass,
+        #[strum(serialize = "_normalizeProps")]
+        NormalizeProps,
         #[strum(serialize = "_normalizeStyle")]
         NormalizeStyle,
         #[strum(serialize = "_openBlock")]
         OpenBlock,
+        #[strum(serialize = "_ref")]
+        Ref,
         #[strum(serialize = "_renderList")]
         RenderList,
         #[strum(serialize = "_renderSlot")]
@@ -47,18 +69,30 @@ flags! {
         ResolveDynamicComponent,
         #[strum(serialize = "_setBlockTracking")]
         SetBlockTracking,
+        #[strum(serialize = "_shallowRef")]
+        ShallowRef,
         #[strum(serialize = "_Suspense")]
         Suspense,
         #[strum(serialize = "_Teleport")]
         Teleport,
         #[strum(serialize = "_toDisplayString")]
         ToDisplayString,
+        #[strum(serialize = "_toHandlerKey")]
+        ToHandlerKey,
+        #[strum(serialize = "_toHandlers")]
+        ToHandlers,
+        #[strum(serialize = "_toRef")]
+        ToRef,
         #[strum(serialize = "_Transition")]
         Transition,
         #[strum(serialize = "_TransitionGroup")]
         TransitionGroup,
         #[strum(serialize = "_unref")]
         Unref,
+        #[strum(serialize = "_useCssModule")]
+        UseCssModule,
+        #[strum(serialize = "_useCssVars")]
+        UseCssVars,
         #[strum(serialize = "_useModel")]
         UseModel,
         #[strum(serialize = "_useSlots")]
@@ -75,10 +109,14 @@ flags! {
         VModelText,
         #[strum(serialize = "_vShow")]
         VShow,
+        #[strum(serialize = "_withAsyncContext")]
+        WithAsyncContext,
         #[strum(serialize = "_withCtx")]
         WithCtx,
         #[strum(serialize = "_withDirectives")]
         WithDirectives,
+        #[strum(serialize = "_withKeys")]
+        WithKeys,
         #[strum(serialize = "_withMemo")]
         WithMemo,
         #[strum(serialize = "_withModifiers")]
@@ -99,3 +137,46 @@ impl VueImports {
 }
 
 pub type VueImportsSet = FlagSet<VueImports>;
+
+flags! {
+    /// Helpers used by the SSR render function, imported from `vue/server-renderer`
+    #[derive(AsRefStr, EnumString, IntoStaticStr)]
+    pub enum SsrImports: u32 {
+        #[strum(serialize = "_ssrIncludeBooleanAttr")]
+        SsrIncludeBooleanAttr,
+        #[strum(serialize = "_ssrInterpolate")]
+        SsrInterpolate,
+        #[strum(serialize = "_ssrLooseContain")]
+        SsrLooseContain,
+        #[strum(serialize = "_ssrLooseEqual")]
+        SsrLooseEqual,
+        #[strum(serialize = "_ssrRenderAttr")]
+        SsrRenderAttr,
+        #[strum(serialize = "_ssrRenderAttrs")]
+        SsrRenderAttrs,
+        #[strum(serialize = "_ssrRenderComponent")]
+        SsrRenderComponent,
+        #[strum(serialize = "_ssrRenderDynamicModel")]
+        SsrRenderDynamicModel,
+        #[strum(serialize = "_ssrRenderList")]
+        SsrRenderList,
+        #[strum(serialize = "_ssrRenderSlot")]
+        SsrRenderSlot,
+        #[strum(serialize = "_ssrRenderVNode")]
+        SsrRenderVNode,
+    }
+}
+
+impl SsrImports {
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    #[inline]
+    pub fn as_atom(self) -> FervidAtom {
+        self.as_str().into()
+    }
+}
+
+pub type SsrImportsSet = FlagSet<SsrImports>;
@@ -14,6 +14,14 @@ pub enum CssErrorKind {
     ParseUnrecoverable(ParseErrorKind),
     ParseDeepRecoverable(ParseErrorKind),
     ParseDeepUnrecoverable(ParseErrorKind),
+    /// Two `<style module>` blocks share the same name
+    DuplicateModuleName,
+    /// Legacy `<style vars>` compiled in compat mode.
+    /// Contains the suggested migration to `v-bind()` in CSS
+    DeprecatedStyleVars(String),
+    /// `<style vars>` used without compat mode.
+    /// Contains the suggested migration to `v-bind()` in CSS
+    StyleVarsWithoutCompat(String),
     // MinifyError(Error<MinifyErrorKind>),
     // PrinterError(Error<PrinterErrorKind>),
 }
@@ -43,6 +51,9 @@ impl Severity for CssError {
             CssErrorKind::ParseUnrecoverable(_) => SeverityLevel::UnrecoverableError,
             CssErrorKind::ParseDeepRecoverable(_) => SeverityLevel::RecoverableError,
             CssErrorKind::ParseDeepUnrecoverable(_) => SeverityLevel::UnrecoverableError,
+            CssErrorKind::DuplicateModuleName => SeverityLevel::RecoverableError,
+            CssErrorKind::DeprecatedStyleVars(_) => SeverityLevel::Warning,
+            CssErrorKind::StyleVarsWithoutCompat(_) => SeverityLevel::RecoverableError,
         }
     }
 }
@@ -87,9 +87,7 @@ impl Plugin for FarmPluginVueFervid {
                 filename: std::borrow::Cow::Borrowed(param.resolved_path),
                 id: param.module_id.clone().into(),
                 is_prod: Some(true),
-                ssr: None,
-                gen_default_as: None,
-                source_map: None
+                ..Default::default()
             },
         );
 
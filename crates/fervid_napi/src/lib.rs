@@ -55,10 +55,12 @@ impl FervidJsCompiler {
         let compile_options = CompileOptions {
             filename: Cow::Borrowed(&options.filename),
             id: Cow::Borrowed(&options.id),
+            root: self.options.root.as_deref().map(Cow::Borrowed),
             is_prod: self.options.is_production,
             ssr: self.options.ssr,
             gen_default_as: options.gen_default_as.as_ref().map(|v| Cow::Borrowed(v.as_str())),
-            source_map: self.options.source_map
+            source_map: self.options.source_map,
+            ..Default::default()
         };
 
         let native_compile_result =
@@ -15,8 +15,12 @@ pub struct FervidJsCompilerOptions {
     /// Apply production optimizations. Default: false
     pub is_production: Option<bool>,
 
-    /// TODO Support SSR
-    /// Enable SSR. Default: false
+    /// Project root, the HMR id of a component is derived from its path relative to the root.
+    /// Default: none, the full path is used
+    pub root: Option<String>,
+
+    /// Enable SSR. The compiled component exposes `ssrRender`
+    /// to be used with `renderToString` from `vue/server-renderer`. Default: false
     pub ssr: Option<bool>,
 
     /// TODO Find a performant solution to source-maps
@@ -2,7 +2,10 @@ use fervid_core::{
     AttributeOrBinding, FervidAtom, StrOrExpr, VBindDirective, VCustomDirective, VForDirective,
     VModelDirective, VOnDirective, VSlotDirective, VueDirectives,
 };
-use swc_core::{common::{BytePos, Span}, ecma::ast::Expr};
+use swc_core::{
+    common::{BytePos, Span},
+    ecma::ast::{Expr, Ident},
+};
 use swc_ecma_parser::Syntax;
 use swc_html_ast::Attribute;
 
@@ -264,12 +267,24 @@ impl SfcParser<'_, '_, '_> {
                     }
                 }
 
-                let value = expect_value!();
+                let parsed_expr = match (raw_attribute.value.as_ref(), argument.as_ref()) {
+                    (Some(value), _) => match self.parse_expr(value, ts!(), span) {
+                        Ok(parsed) => parsed,
+                        Err(expr_err) => {
+                            bail!(js, expr_err);
+                        }
+                    },
 
-                let parsed_expr = match self.parse_expr(&value, ts!(), span) {
-                    Ok(parsed) => parsed,
-                    Err(expr_err) => {
-                        bail!(js, expr_err);
+                    // Same-name shorthand (Vue 3.4+): `:foo-bar` is `:foo-bar="fooBar"`.
+                    // Not parsed, because the name may be a reserved word, e.g. `:class`
+                    (None, Some(StrOrExpr::Str(argument))) => Box::new(Expr::Ident(Ident {
+                        span,
+                        sym: FervidAtom::from(camelize(argument)),
+                        optional: false,
+                    })),
+
+                    (None, _) => {
+                        bail!(ParseErrorKind::DirectiveSyntax);
                     }
                 };
 
@@ -285,7 +300,7 @@ impl SfcParser<'_, '_, '_> {
 
             "on" => {
                 let handler = match raw_attribute.value {
-                    Some(ref value) => match self.parse_expr(&value, ts!(), span) {
+                    Some(ref value) => match self.parse_v_on_handler(&value, ts!(), span) {
                         Ok(parsed) => Some(parsed),
                         Err(expr_err) => {
                             bail!(js, expr_err);
@@ -369,13 +384,14 @@ impl SfcParser<'_, '_, '_> {
             }
 
             "slot" => {
-                let value =
-                    raw_attribute
-                        .value
-                        .and_then(|v| match self.parse_pat(&v, ts!(), span) {
-                            Ok(value) => Some(Box::new(value)),
-                            Result::Err(_) => None,
-                        });
+                // `v-slot=""` is the same as `v-slot` without bindings
+                let value = raw_attribute
+                    .value
+                    .filter(|v| !v.trim().is_empty())
+                    .and_then(|v| match self.parse_pat(&v, ts!(), span) {
+                        Ok(value) => Some(Box::new(value)),
+                        Result::Err(_) => None,
+                    });
                 push_directive!(
                     v_slot,
                     VSlotDirective {
@@ -461,6 +477,22 @@ pub fn create_regular_attribute(raw_attribute: Attribute) -> AttributeOrBinding
     }
 }
 
+/// Converts `foo-bar` to `fooBar` the same way Vue does
+fn camelize(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(next) if c == '-' && (next.is_alphanumeric() || *next == '_') => {
+                out.extend(next.to_uppercase());
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 fn split_itervar_and_iterable<'a>(
     raw: &'a str,
     original_span: Span,
@@ -547,4 +579,13 @@ mod tests {
         // A bit harder
         check!("   item   in \n \t  list   ", "item", 4, 8, "list", 19, 23);
     }
+
+    #[test]
+    fn it_camelizes() {
+        assert_eq!("id", camelize("id"));
+        assert_eq!("fooBar", camelize("foo-bar"));
+        assert_eq!("textContent", camelize("text-content"));
+        assert_eq!("a1B", camelize("a-1-b"));
+        assert_eq!("foo-", camelize("foo-"));
+    }
 }
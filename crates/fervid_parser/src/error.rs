@@ -26,6 +26,8 @@ pub enum ParseErrorKind {
     InvalidHtml(Box<swc_html_parser::error::ErrorKind>),
     /// Both `<template>` and `<script>` are missing
     MissingTemplateOrScript,
+    /// `v-slot` or its `#` shorthand on the root `<template>` block, which is not a slot
+    RootTemplateSlot,
     /// `<script>`/`<style>` content was not Text
     UnexpectedNonRawTextContent,
     /// Language not supported
@@ -24,7 +24,7 @@ pub struct SfcParser<'i, 'e, 'p> {
     pub ignore_empty: bool,
 }
 
-impl<'i, 'e> SfcParser<'i, 'e, 'static> {
+impl<'i, 'e, 'p> SfcParser<'i, 'e, 'p> {
     pub fn new(input: &'i str, errors: &'e mut Vec<ParseError>) -> Self {
         // TODO When should it fail? What do we do with errors?..
         // I was thinking of 4 strategies:
@@ -43,12 +43,24 @@ impl<'i, 'e> SfcParser<'i, 'e, 'static> {
             ignore_empty: true,
         }
     }
+
+    /// Uses custom interpolation delimiters instead of `{{` and `}}`, e.g. `[[` and `]]`
+    pub fn set_delimiters(&mut self, start: &'p str, end: &'p str) {
+        self.interpolation_start_pat = start;
+        self.interpolation_end_pat = end;
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use fervid_core::{Node, SfcDescriptor, SfcScriptLang};
-    use swc_core::ecma::ast::{ModuleDecl, ModuleItem};
+    use fervid_core::{
+        AttributeOrBinding, Node, SfcDescriptor, SfcScriptLang, StrOrExpr, VBindDirective,
+        VSlotDirective,
+    };
+    use swc_core::{
+        common::BytePos,
+        ecma::ast::{Expr, ModuleDecl, ModuleItem, Pat},
+    };
 
     use crate::{error::ParseErrorKind, ParseError, SfcParser};
 
@@ -126,10 +138,47 @@ font-weight: bold;
 
         let styles = parse(&src).styles;
         assert_eq!(4, styles.len());
-        assert!(styles[0].lang == "css" && !styles[0].is_scoped && !styles[0].is_module);
-        assert!(styles[1].lang == "css" && !styles[1].is_scoped && styles[1].is_module);
-        assert!(styles[2].lang == "css" && styles[2].is_scoped && !styles[2].is_module);
-        assert!(styles[3].lang == "css" && styles[3].is_scoped && !styles[3].is_module);
+        assert!(styles[0].lang == "css" && !styles[0].is_scoped && styles[0].module_name.is_none());
+        assert!(
+            styles[1].lang == "css"
+                && !styles[1].is_scoped
+                && styles[1].module_name.as_deref() == Some("$style")
+        );
+        assert!(styles[2].lang == "css" && styles[2].is_scoped && styles[2].module_name.is_none());
+        assert!(styles[3].lang == "css" && styles[3].is_scoped && styles[3].module_name.is_none());
+    }
+
+    #[test]
+    fn style_module_names() {
+        let (mut src, _) = padding();
+        src.push_str(
+            r#"<style module>.a { color: red; }</style>
+<style module="classes">.b { color: red; }</style>
+<style module="">.c { color: red; }</style>"#,
+        );
+
+        let styles = parse(&src).styles;
+        assert_eq!(3, styles.len());
+        assert_eq!(styles[0].module_name.as_deref(), Some("$style"));
+        assert_eq!(styles[1].module_name.as_deref(), Some("classes"));
+        assert_eq!(styles[2].module_name.as_deref(), Some("$style"));
+    }
+
+    #[test]
+    fn style_vars() {
+        let (mut src, _) = padding();
+        src.push_str(
+            r#"<style vars="{ color, size }">.a { color: var(--color); }</style>
+<style>.b { color: red; }</style>"#,
+        );
+
+        let styles = parse(&src).styles;
+        assert_eq!(2, styles.len());
+        let Some(ref vars) = styles[0].vars else {
+            panic!("vars should be parsed");
+        };
+        assert!(vars.as_object().is_some_and(|obj| obj.props.len() == 2));
+        assert!(styles[1].vars.is_none());
     }
 
     #[test]
@@ -143,6 +192,35 @@ font-weight: bold;
         assert!(matches!(script.lang, SfcScriptLang::Es));
     }
 
+    #[test]
+    fn script_block_with_jsx() {
+        let (mut src, _) = padding();
+        src.push_str(
+            r#"<script lang="tsx">
+export default defineComponent({
+  props: { msg: String },
+  setup: (props: { msg: string }) => () => <div class="msg">{props.msg}</div>
+})
+</script>
+<script setup lang="jsx">
+const render = () => <span>hi</span>
+</script>"#,
+        );
+
+        let mut errors = Vec::new();
+        let mut parser = SfcParser::new(&src, &mut errors);
+        let sfc = parser.parse_sfc().expect("JSX should be parsed");
+        assert!(errors.is_empty());
+
+        let script = sfc.script_legacy.expect(SHOULD_EXIST);
+        assert!(matches!(script.lang, SfcScriptLang::Tsx));
+        assert!(script.lang.is_ts());
+
+        let script_setup = sfc.script_setup.expect(SHOULD_EXIST);
+        assert!(matches!(script_setup.lang, SfcScriptLang::Jsx));
+        assert!(!script_setup.lang.is_ts());
+    }
+
     #[test]
     fn template_block_with_lang_and_indent() {
         let (mut src, _) = padding();
@@ -307,6 +385,66 @@ h1 { color: red }
         assert!(matches!(template.roots[4], Node::Text(_, _)));
     }
 
+    #[test]
+    fn template_slot_shorthand() {
+        let (descriptor, errors) = parse_with_errors(
+            r#"<template>
+                <Comp>
+                    <template #default="{ item }">{{ item }}</template>
+                    <template #named>named</template>
+                    <template #[dynamic]>dynamic</template>
+                    <template #footer="props">footer</template>
+                </Comp>
+            </template>"#,
+        );
+        assert!(errors.is_empty());
+
+        let template = descriptor.template.expect(SHOULD_EXIST);
+        let Some(Node::Element(comp)) = template
+            .roots
+            .iter()
+            .find(|n| matches!(n, Node::Element(_)))
+        else {
+            panic!("Component should exist");
+        };
+
+        let slots: Vec<&VSlotDirective> = comp
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Element(element) => element
+                    .starting_tag
+                    .directives
+                    .as_ref()
+                    .and_then(|d| d.v_slot.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(4, slots.len());
+
+        assert!(matches!(&slots[0].slot_name, Some(StrOrExpr::Str(name)) if name == "default"));
+        assert!(matches!(slots[0].value.as_deref(), Some(Pat::Object(_))));
+        assert!(!slots[0].is_dynamic_slot());
+
+        assert!(matches!(&slots[1].slot_name, Some(StrOrExpr::Str(name)) if name == "named"));
+        assert!(slots[1].value.is_none());
+        assert!(!slots[1].is_dynamic_slot());
+
+        assert!(slots[2].is_dynamic_slot());
+        assert!(matches!(&slots[2].slot_name, Some(StrOrExpr::Expr(expr)) if expr.is_ident()));
+
+        assert!(matches!(&slots[3].slot_name, Some(StrOrExpr::Str(name)) if name == "footer"));
+        assert!(matches!(slots[3].value.as_deref(), Some(Pat::Ident(_))));
+    }
+
+    #[test]
+    fn root_template_slot_shorthand() {
+        let (_, errors) = parse_with_errors("<template #header><div/></template>");
+        assert!(errors
+            .iter()
+            .any(|e| matches!(&e.kind, ParseErrorKind::RootTemplateSlot)));
+    }
+
     #[test]
     fn treat_empty_lang_attribute_as_the_html() {
         let template =
@@ -361,6 +499,19 @@ h1 { color: red }
         assert_eq!(1, errors.len());
     }
 
+    #[test]
+    fn it_reports_mismatched_end_tags() {
+        let input = "<template><div></span></div></template>";
+        let (descriptor, errors) = parse_with_errors(input);
+        assert!(descriptor.template.is_some());
+
+        // Stray `</span>` is reported with its location instead of being lost
+        let stray_end_tag_lo = BytePos(input.find("</span>").unwrap() as u32 + 1);
+        assert!(errors.iter().any(|e| {
+            matches!(e.kind, ParseErrorKind::InvalidHtml(_)) && e.span.lo == stray_end_tag_lo
+        }));
+    }
+
     #[test]
     fn should_parse_as_dom_by_default() {
         let (_, errors) = parse_with_errors("<template><input></template>");
@@ -420,6 +571,102 @@ h1 { color: red }
             .any(|e| matches!(&e.kind, ParseErrorKind::MissingTemplateOrScript)));
     }
 
+    #[test]
+    fn it_decodes_character_references_in_text() {
+        let (descriptor, _) = parse_with_errors(
+            "<template><div>&amp; &lt;b&gt; &#8203;&#x200B; &amp;lt; &foo; &#xZZ;</div></template>",
+        );
+
+        let template = descriptor.template.expect(SHOULD_EXIST);
+        let Some(Node::Element(div)) = template.roots.first() else {
+            panic!("Root is not an element");
+        };
+        let Some(Node::Text(ref text, _)) = div.children.first() else {
+            panic!("Not a text");
+        };
+
+        // References are decoded once, malformed ones are kept as is
+        assert_eq!("& <b> \u{200B}\u{200B} &lt; &foo; &#xZZ;", &**text);
+    }
+
+    #[test]
+    fn it_parses_same_name_v_bind_shorthand() {
+        let (descriptor, errors) = parse_with_errors(
+            r#"<template><div :id :foo-bar :class .text-content :other="val"></div></template>"#,
+        );
+        assert!(errors.is_empty());
+
+        let template = descriptor.template.expect(SHOULD_EXIST);
+        let Some(Node::Element(div)) = template.roots.first() else {
+            panic!("Root is not an element");
+        };
+
+        let bindings: Vec<(&str, &str, bool)> = div
+            .starting_tag
+            .attributes
+            .iter()
+            .map(|attr| {
+                let AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str(argument)),
+                    value,
+                    is_prop,
+                    ..
+                }) = attr
+                else {
+                    panic!("Not a v-bind with a static argument");
+                };
+                let Expr::Ident(ident) = value.as_ref() else {
+                    panic!("Not an identifier");
+                };
+                (argument.as_ref(), ident.sym.as_ref(), *is_prop)
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("id", "id", false),
+                ("foo-bar", "fooBar", false),
+                ("class", "class", false),
+                ("text-content", "textContent", true),
+                ("other", "val", false)
+            ],
+            bindings
+        );
+
+        // Dynamic arguments need a value
+        let (_, errors) = parse_with_errors("<template><div :[key]></div></template>");
+        assert!(errors
+            .iter()
+            .any(|e| matches!(&e.kind, ParseErrorKind::DirectiveSyntax)));
+    }
+
+    #[test]
+    fn it_uses_custom_delimiters() {
+        let source = "<template><div>[[ msg ]] {{ raw }}</div></template>";
+        let mut errors = Vec::new();
+        let mut parser = SfcParser::new(source, &mut errors);
+        parser.set_delimiters("[[", "]]");
+        let descriptor = parser.parse_sfc().unwrap();
+        assert!(errors.is_empty());
+
+        let template = descriptor.template.expect(SHOULD_EXIST);
+        let Some(Node::Element(div)) = template.roots.first() else {
+            panic!("Root is not an element");
+        };
+        assert_eq!(2, div.children.len());
+
+        let Node::Interpolation(ref interpolation) = div.children[0] else {
+            panic!("Not an interpolation");
+        };
+        assert!(matches!(*interpolation.value, Expr::Ident(ref ident) if ident.sym == "msg"));
+
+        // Default delimiters are now plain text
+        let Node::Text(ref text, _) = div.children[1] else {
+            panic!("Not a text");
+        };
+        assert_eq!(" {{ raw }}", &**text);
+    }
+
     fn parse(source: &str) -> SfcDescriptor {
         let mut errors = Vec::new();
         let mut parser = SfcParser::new(source, &mut errors);
@@ -1,7 +1,9 @@
 use fervid_core::{SfcScriptBlock, SfcScriptLang};
 use swc_core::{
-    common::Span,
-    ecma::ast::{Expr, Module, Pat},
+    common::{BytePos, Span, Spanned},
+    ecma::ast::{
+        ArrowExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, Expr, Ident, Module, Pat, Stmt,
+    },
 };
 use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig};
 use swc_html_ast::{Child, Element};
@@ -42,6 +44,8 @@ impl SfcParser<'_, '_, '_> {
                     lang = match attr.value.as_ref().map(|v| v.as_str()) {
                         Some("ts" | "typescript") => SfcScriptLang::Typescript,
                         None | Some("js" | "javascript") => SfcScriptLang::Es,
+                        Some("tsx") => SfcScriptLang::Tsx,
+                        Some("jsx") => SfcScriptLang::Jsx,
                         Some(_) => {
                             return Err(ParseError {
                                 kind: ParseErrorKind::UnsupportedLang,
@@ -88,10 +92,17 @@ impl SfcParser<'_, '_, '_> {
 
         let module_content = self.parse_module(
             &script_content.data,
-            if matches!(lang, SfcScriptLang::Typescript) {
-                Syntax::Typescript(TsConfig::default())
-            } else {
-                Syntax::Es(EsConfig::default())
+            match lang {
+                SfcScriptLang::Es => Syntax::Es(EsConfig::default()),
+                SfcScriptLang::Typescript => Syntax::Typescript(TsConfig::default()),
+                SfcScriptLang::Jsx => Syntax::Es(EsConfig {
+                    jsx: true,
+                    ..Default::default()
+                }),
+                SfcScriptLang::Tsx => Syntax::Typescript(TsConfig {
+                    tsx: true,
+                    ..Default::default()
+                }),
             },
             script_content.span,
         )?;
@@ -153,6 +164,82 @@ impl SfcParser<'_, '_, '_> {
         parse_result.map_err(From::from)
     }
 
+    /// Parses the `v-on` handler, which is either a single expression
+    /// or several statements, e.g. `count++; log(count)`.
+    /// Statements are wrapped in a `$event => { ... }` arrow.
+    pub fn parse_v_on_handler(
+        &mut self,
+        raw: &str,
+        syntax: Syntax,
+        span: Span,
+    ) -> Result<Box<Expr>, ParseError> {
+        // Expression must cover the whole handler except the trailing `;`
+        let handler_len = raw.trim_end().trim_end_matches(';').trim_end().len();
+        let handler_hi = BytePos(span.lo.0 + handler_len as u32);
+
+        let lexer = Lexer::new(
+            syntax,
+            // EsVersion defaults to es5
+            Default::default(),
+            StringInput::new(raw, span.lo, span.hi),
+            Some(&self.comments),
+        );
+
+        let mut parser = Parser::new_from(lexer);
+        if let Ok(expr) = parser.parse_expr() {
+            if expr.span_hi() == handler_hi {
+                self.errors
+                    .extend(parser.take_errors().into_iter().map(From::from));
+                return Ok(expr);
+            }
+        }
+
+        // Not a single expression, parse as statements
+        let lexer = Lexer::new(
+            syntax,
+            Default::default(),
+            StringInput::new(raw, span.lo, span.hi),
+            Some(&self.comments),
+        );
+
+        let mut parser = Parser::new_from(lexer);
+        let parse_result = parser.parse_script();
+
+        // Map errors to EcmaSyntaxError
+        self.errors
+            .extend(parser.take_errors().into_iter().map(From::from));
+
+        let mut stmts = parse_result.map_err(ParseError::from)?.body;
+
+        // E.g. `foo() // comment` is still a single expression
+        if let [Stmt::Expr(_)] = stmts.as_slice() {
+            let Some(Stmt::Expr(expr_stmt)) = stmts.pop() else {
+                unreachable!()
+            };
+            return Ok(expr_stmt.expr);
+        }
+
+        // `$event => { ... }`
+        let evt_param = Pat::Ident(BindingIdent {
+            id: Ident {
+                span,
+                sym: "$event".into(),
+                optional: false,
+            },
+            type_ann: None,
+        });
+
+        Ok(Box::new(Expr::Arrow(ArrowExpr {
+            span,
+            params: vec![evt_param],
+            body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt { span, stmts })),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        })))
+    }
+
     pub fn parse_pat(&mut self, raw: &str, syntax: Syntax, span: Span) -> Result<Pat, ParseError> {
         let lexer = Lexer::new(
             syntax,
@@ -1,4 +1,5 @@
 use fervid_core::{fervid_atom, SfcStyleBlock};
+use swc_ecma_parser::{EsConfig, Syntax};
 use swc_html_ast::{Child, Element};
 
 use crate::{error::ParseErrorKind, ParseError, SfcParser};
@@ -8,7 +9,8 @@ impl SfcParser<'_, '_, '_> {
         // Find the attributes
         let mut lang = fervid_atom!("css");
         let mut is_scoped = false;
-        let mut is_module = false;
+        let mut module_name = None;
+        let mut vars = None;
 
         for attr in element.attributes.into_iter() {
             if attr.name.eq("lang") {
@@ -20,7 +22,21 @@ impl SfcParser<'_, '_, '_> {
             } else if attr.name.eq("scoped") {
                 is_scoped = true;
             } else if attr.name.eq("module") {
-                is_module = true;
+                // `<style module>` is the same as `<style module="$style">`
+                module_name = Some(match attr.value {
+                    Some(attr_val) if !attr_val.is_empty() => attr_val,
+                    _ => fervid_atom!("$style"),
+                });
+            } else if attr.name.eq("vars") {
+                // Legacy `<style vars="{ color }">`
+                let Some(attr_val) = attr.value else {
+                    continue;
+                };
+
+                match self.parse_expr(&attr_val, Syntax::Es(EsConfig::default()), attr.span) {
+                    Ok(parsed) => vars = Some(parsed),
+                    Err(expr_err) => self.report_error(expr_err),
+                }
             }
         }
 
@@ -42,7 +58,8 @@ impl SfcParser<'_, '_, '_> {
                     lang,
                     content: fervid_atom!(""),
                     is_scoped,
-                    is_module,
+                    module_name,
+                    vars,
                     span: element.span,
                 });
             }
@@ -57,7 +74,8 @@ impl SfcParser<'_, '_, '_> {
             lang,
             content: style_content.data,
             is_scoped,
-            is_module,
+            module_name,
+            vars,
             span: style_content.span,
         })
     }
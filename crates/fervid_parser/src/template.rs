@@ -6,7 +6,7 @@ use swc_core::common::{BytePos, Span};
 use swc_ecma_parser::{Syntax, TsConfig};
 use swc_html_ast::{Child, Element, Text};
 
-use crate::SfcParser;
+use crate::{error::ParseErrorKind, ParseError, SfcParser};
 
 impl SfcParser<'_, '_, '_> {
     pub fn parse_template_to_ir(&mut self, root_element: Element) -> Option<SfcTemplateBlock> {
@@ -15,6 +15,16 @@ impl SfcParser<'_, '_, '_> {
         let lang_atom = fervid_atom!("lang");
         let html_atom = || fervid_atom!("html");
 
+        // `<template #header>` only makes sense inside a component
+        for attr in root_element.attributes.iter() {
+            if attr.name.starts_with('#') || attr.name.starts_with("v-slot") {
+                self.errors.push(ParseError {
+                    kind: ParseErrorKind::RootTemplateSlot,
+                    span: attr.span,
+                });
+            }
+        }
+
         let lang = root_element
             .attributes
             .into_iter()
@@ -347,6 +357,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_prefers_v_pre_over_other_directives() {
+        macro_rules! check {
+            ($input: literal, $attr_name: literal, $attr_value: literal) => {
+                let mut errors = Vec::new();
+                let mut parser = SfcParser::new($input, &mut errors);
+                let parsed = parser.parse_sfc().expect("Should parse");
+                let template = parsed.template.expect("Should have template");
+                let Some(Node::Element(div)) = template.roots.first() else {
+                    panic!("Root is not an element")
+                };
+
+                // Only `v-pre` is recognized
+                let directives = div
+                    .starting_tag
+                    .directives
+                    .as_ref()
+                    .expect("Should have directives");
+                assert!(directives.v_pre.is_some());
+                assert!(directives.v_for.is_none());
+                assert!(directives.v_if.is_none());
+                assert!(directives.v_model.is_empty());
+
+                // Other directive is kept as a raw attribute
+                assert_eq!(1, div.starting_tag.attributes.len());
+                assert!(matches!(
+                    div.starting_tag.attributes.first(),
+                    Some(AttributeOrBinding::RegularAttribute { name, value, .. }) if name == $attr_name && value == $attr_value
+                ));
+
+                // Children are not parsed
+                assert!(div.children.iter().all(|c| matches!(c, Node::Text(_, _))));
+            };
+        }
+
+        check!(
+            r#"<template><div v-pre v-for="item in list">{{ item }}</div></template>"#,
+            "v-for",
+            "item in list"
+        );
+        check!(
+            r#"<template><div v-if="visible" v-pre>{{ msg }}</div></template>"#,
+            "v-if",
+            "visible"
+        );
+        check!(
+            r#"<template><div v-pre v-model="msg"></div></template>"#,
+            "v-model",
+            "msg"
+        );
+    }
+
+    #[test]
+    fn it_parses_v_pre_inside_v_for() {
+        let mut errors = Vec::new();
+        let mut parser = SfcParser::new(
+            r#"<template><div v-for="item in list"><span v-pre>{{ item }}</span>{{ item }}</div></template>"#,
+            &mut errors,
+        );
+
+        let parsed = parser.parse_sfc().expect("Should parse");
+        let template = parsed.template.expect("Should have template");
+        let Some(Node::Element(div)) = template.roots.first() else {
+            panic!("Root is not an element")
+        };
+
+        // `v-for` still works
+        assert!(div
+            .starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|d| d.v_for.is_some()));
+
+        // `v-pre` subtree is raw
+        let Some(Node::Element(span)) = div.children.first() else {
+            panic!("First child of div is not span")
+        };
+        assert!(matches!(span.children.first(), Some(Node::Text(text, _)) if text == "{{ item }}"));
+
+        // Siblings of `v-pre` are parsed
+        assert!(matches!(div.children.last(), Some(Node::Interpolation(_))));
+    }
+
     #[test]
     fn it_resets_state_after_v_pre() {
         let mut errors = Vec::new();
@@ -383,4 +476,78 @@ mod tests {
         };
         assert!(interpolation.value.is_ident());
     }
+
+    #[test]
+    fn it_parses_multi_statement_v_on_handler() {
+        fn parse_handler(handler: &str) -> Box<swc_core::ecma::ast::Expr> {
+            let input = format!(r#"<template><button @click="{handler}"></button></template>"#);
+            let mut errors = Vec::new();
+            let mut parser = SfcParser::new(&input, &mut errors);
+            let parsed = parser.parse_sfc().expect("Should parse");
+            let template = parsed.template.expect("Should have template");
+            let Some(Node::Element(button)) = template.roots.first() else {
+                panic!("Root is not an element")
+            };
+            let Some(AttributeOrBinding::VOn(v_on)) = button.starting_tag.attributes.first() else {
+                panic!("Attribute is not v-on")
+            };
+            assert!(parser.errors.is_empty());
+            v_on.handler.to_owned().expect("Should have handler")
+        }
+
+        fn block_len(handler: &swc_core::ecma::ast::Expr) -> usize {
+            let Some(arrow) = handler.as_arrow() else {
+                panic!("Handler is not an arrow")
+            };
+            assert!(matches!(
+                arrow.params.first(),
+                Some(swc_core::ecma::ast::Pat::Ident(p)) if p.id.sym == "$event"
+            ));
+            let Some(block) = arrow.body.as_block_stmt() else {
+                panic!("Arrow body is not a block")
+            };
+            block.stmts.len()
+        }
+
+        // Single expression
+        assert!(parse_handler("count").is_ident());
+        assert!(parse_handler("count++;").is_update());
+
+        // Single statement with side effects
+        assert!(parse_handler("count = count + 1").is_assign());
+
+        // Multiple statements with `;`
+        assert_eq!(2, block_len(&parse_handler("count++; log(count)")));
+        assert_eq!(3, block_len(&parse_handler("a++; b--; log(a, b);")));
+
+        // Multiple statements on multiple lines
+        assert_eq!(2, block_len(&parse_handler("count++\n  log(count)")));
+
+        // Bare function call
+        assert!(parse_handler("log(count)").is_call());
+    }
+
+    #[test]
+    fn it_parses_empty_v_slot() {
+        let mut errors = Vec::new();
+        let mut parser = SfcParser::new(
+            r#"<template><my-comp v-slot="">text</my-comp></template>"#,
+            &mut errors,
+        );
+        let parsed = parser.parse_sfc().expect("Should parse");
+        assert!(parser.errors.is_empty());
+
+        let template = parsed.template.expect("Should have template");
+        let Some(Node::Element(my_comp)) = template.roots.first() else {
+            panic!("Root is not an element")
+        };
+        let v_slot = my_comp
+            .starting_tag
+            .directives
+            .as_ref()
+            .and_then(|d| d.v_slot.as_ref())
+            .expect("Should have v-slot");
+        assert!(v_slot.slot_name.is_none());
+        assert!(v_slot.value.is_none());
+    }
 }
@@ -4,6 +4,7 @@ use fervid_core::{fervid_atom, FervidAtom};
 
 lazy_static! {
     pub static ref VUE: FervidAtom = fervid_atom!("vue");
+    pub static ref VUE_MACROS: FervidAtom = fervid_atom!("vue/macros");
 
     // Options API atoms
     pub static ref DATA: FervidAtom = fervid_atom!("data");
@@ -17,6 +18,7 @@ lazy_static! {
     pub static ref EXPOSE: FervidAtom = fervid_atom!("expose");
     pub static ref NAME: FervidAtom = fervid_atom!("name");
     pub static ref DIRECTIVES: FervidAtom = fervid_atom!("directives");
+    pub static ref SLOTS: FervidAtom = fervid_atom!("slots");
 
     // Composition API atoms
     // pub static ref COMPUTED: FervidAtom = fervid_atom!("computed");
@@ -28,13 +30,19 @@ lazy_static! {
     pub static ref DEFINE_SLOTS: FervidAtom = fervid_atom!("defineSlots");
     pub static ref REACTIVE: FervidAtom = fervid_atom!("reactive");
     pub static ref REF: FervidAtom = fervid_atom!("ref");
+    pub static ref WITH_DEFAULTS: FervidAtom = fervid_atom!("withDefaults");
 
     // Helper atoms
     pub static ref EMIT: FervidAtom = fervid_atom!("emit");
     pub static ref EMIT_HELPER: FervidAtom = fervid_atom!("__emit");
     pub static ref EXPOSE_HELPER: FervidAtom = fervid_atom!("__expose");
+    pub static ref MERGE_DEFAULTS_HELPER: FervidAtom = fervid_atom!("_mergeDefaults");
     pub static ref MERGE_MODELS_HELPER: FervidAtom = fervid_atom!("_mergeModels");
     pub static ref MODEL_VALUE: FervidAtom = fervid_atom!("modelValue");
     pub static ref PROPS_HELPER: FervidAtom = fervid_atom!("__props");
+    pub static ref RESTORE_HELPER: FervidAtom = fervid_atom!("__restore");
+    pub static ref SANITIZE_URL: FervidAtom = fervid_atom!("sanitizeUrl");
+    pub static ref TEMP_HELPER: FervidAtom = fervid_atom!("__temp");
     pub static ref USE_MODEL_HELPER: FervidAtom = fervid_atom!("_useModel");
+    pub static ref WITH_ASYNC_CONTEXT_HELPER: FervidAtom = fervid_atom!("_withAsyncContext");
 }
@@ -1,10 +1,15 @@
+use fervid_core::{
+    error::{Severity, SeverityLevel},
+    FervidAtom,
+};
 use fervid_css::CssError;
 use swc_core::common::{Span, Spanned};
 
 #[derive(Debug)]
 pub enum TransformError {
     CssError(CssError),
-    ScriptError(ScriptError)
+    ScriptError(ScriptError),
+    TemplateError(TemplateError),
 }
 
 #[derive(Debug)]
@@ -23,8 +28,25 @@ pub enum ScriptErrorKind {
     DefineEmitsTypeAndNonTypeArguments,
     /// "defineEmits() type cannot mixed call signature and property syntax"
     DefineEmitsMixedCallAndPropertySyntax,
+    /// `defineOptions` declares `props`, `emits`, `expose` or `slots`,
+    /// which must be declared using their own macros
+    DefineOptionsMacroOption,
+    /// `defineOptions` was called with type arguments
+    DefineOptionsTypeArguments,
+    /// `defineProps` called with 0 type arguments (e.g. `defineProps<>()`)
+    DefinePropsMalformed,
+    /// `defineProps` was called with both runtime and type arguments
+    DefinePropsTypeAndNonTypeArguments,
+    /// `defineSlots` was called with runtime arguments, slots are only declared using types
+    DefineSlotsArguments,
     /// Duplicate `defineEmits` call
     DuplicateDefineEmits,
+    /// Two `defineModel` calls with the same model name
+    DuplicateDefineModel,
+    /// Duplicate `defineOptions` call
+    DuplicateDefineOptions,
+    /// Duplicate `defineSlots` call
+    DuplicateDefineSlots,
     /// Different imports using the same local symbol,
     /// e.g `import foo from './foo'` and `import { foo } from './bar'`.
     DuplicateImport,
@@ -46,8 +68,128 @@ pub enum ScriptErrorKind {
     ResolveTypeUnsupportedIndexType,
     /// Unsupported computed key in type referenced by a macro
     ResolveTypeUnsupportedComputedKey,
+    /// `$()` of the reactivity transform was called without an argument
+    ReactivityTransformMissingArgument,
+    /// Rest elements and nested patterns are not supported when destructuring `$()`,
+    /// e.g. `const { a: { b }, ...rest } = $(useFoo())`
+    ReactivityTransformUnsupportedDestructure,
     /// Disallow non-type exports inside `<script setup>`
     SetupExport,
+    /// `withDefaults` was called without a type-only `defineProps`,
+    /// e.g. `withDefaults(defineProps({ count: Number }), { count: 0 })`
+    WithDefaultsWithoutTypeOnlyProps,
+}
+
+#[derive(Debug)]
+pub struct TemplateError {
+    pub span: Span,
+    pub kind: TemplateErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TemplateErrorKind {
+    /// Literal value passed to a component prop does not match the prop type,
+    /// e.g. `:count="'five'"` for `count: Number`
+    ComponentPropTypeMismatch,
+    /// `$parent` or `$root` access in the template, `provide`/`inject` should be used instead
+    DeprecatedGlobalAccess,
+    /// Identifier in the template is neither declared in the script
+    /// nor in the template scopes, and is not a component instance property
+    UndefinedReference,
+    /// `<script setup>` binding is never referenced in the template.
+    /// Carries the binding name, because setup bindings do not keep their declaration spans
+    UnusedBinding(FervidAtom),
+    /// Dynamic argument of `v-bind` is never a string or `null`, e.g. `:[1]="foo"`
+    VBindDynamicArgumentInvalid,
+    /// Dynamic argument of `v-bind` references a variable unknown to the compiler,
+    /// thus it cannot be checked at compile-time to be a string or `null`
+    VBindDynamicArgumentUnknown,
+    /// `:value` together with an `@input` or `@change` handler assigning to the same expression,
+    /// e.g. `<input :value="text" @input="text = $event.target.value">`, `v-model` can be used instead
+    VBindManualTwoWayBinding,
+    /// Dynamic `:href`, `:src` or `:action` binding, which may lead to `javascript:` URL injection
+    VBindUnsanitizedUrl,
+    /// `v-for` over a dynamic iterable without a `key`
+    VForMissingKey,
+    /// `v-for` without a `key` as a direct child of `<TransitionGroup>`, which requires keys
+    VForMissingKeyInTransitionGroup,
+    /// `v-model` with an argument on a native element, e.g. `<input v-model:value="text">`.
+    /// Only components support named models
+    VModelArgumentOnElement,
+    /// `v-model` value is not an assignable expression, e.g. `v-model="foo + 1"`
+    VModelInvalidExpression,
+    /// `v-model` on a constant binding, e.g. `v-model="CONST"` for `const CONST = 1`
+    VModelOnConst,
+    /// `v-model` on an `<input>` with a dynamic `:type`,
+    /// which cannot be checked at compile-time not to be `file`
+    VModelOnDynamicInputType,
+    /// `v-model` on `<input type="file">`, which is not supported.
+    /// `@change` should be used instead, e.g. `@change="onFileChange"`
+    VModelOnFileInput,
+    /// `v-model` on a native element other than `<input>`, `<select>` and `<textarea>`
+    VModelOnInvalidElement,
+    /// `v-model` on an optional chain, e.g. `v-model="user?.name"`, which may not be assignable
+    VModelOnOptionalChain,
+    /// `v-model` on a `defineProps()` field, e.g. `props.foo` or `foo`,
+    /// without `update:foo` in `defineEmits` (`defineModel` should be used instead)
+    VModelOnPropWithoutEmit,
+    /// `v-model` on a `computed` declared without a setter
+    VModelOnReadonlyComputed,
+    /// Handler of `v-on` is empty, e.g. `@click=""`
+    VOnEmptyHandler,
+    /// `v-on` without a handler and without modifiers, e.g. `@click`.
+    /// A handler expression is required, e.g. `@click="click"` or `@click="handleClick"`
+    VOnMissingHandler,
+    /// `.left`, `.right` or `.middle` on an event which is not a mouse event, e.g. `@keydown.left`
+    VOnMouseModifierOnNonMouseEvent,
+    /// `.native` modifier on a component in compat mode, it is deprecated
+    VOnNativeModifierDeprecated,
+    /// `.native` modifier on a native element, it does nothing
+    VOnNativeModifierOnElement,
+    /// `.native` modifier on a component, it was removed in Vue 3
+    VOnNativeModifierRemoved,
+    /// `.stop` on `touchstart` or `touchmove` without `.prevent`,
+    /// `stopPropagation()` may be ignored by iOS for these passive events
+    VOnStopModifierOnPassiveTouch,
+    /// `v-slot` on a native element, e.g. `<div v-slot="props">`
+    VSlotMisplaced,
+    /// `v-slot` on a component together with `<template v-slot>` children
+    VSlotMixedUsage,
+}
+
+impl Severity for TemplateError {
+    fn get_severity(&self) -> SeverityLevel {
+        match self.kind {
+            TemplateErrorKind::UnusedBinding(_)
+            | TemplateErrorKind::VBindDynamicArgumentUnknown
+            | TemplateErrorKind::VBindManualTwoWayBinding
+            | TemplateErrorKind::VOnStopModifierOnPassiveTouch => SeverityLevel::Hint,
+            TemplateErrorKind::ComponentPropTypeMismatch
+            | TemplateErrorKind::DeprecatedGlobalAccess
+            | TemplateErrorKind::VBindDynamicArgumentInvalid
+            | TemplateErrorKind::VBindUnsanitizedUrl
+            | TemplateErrorKind::VForMissingKey
+            | TemplateErrorKind::VModelOnDynamicInputType
+            | TemplateErrorKind::VModelOnOptionalChain
+            | TemplateErrorKind::VModelOnPropWithoutEmit
+            | TemplateErrorKind::VModelOnReadonlyComputed
+            | TemplateErrorKind::VOnEmptyHandler
+            | TemplateErrorKind::VOnMouseModifierOnNonMouseEvent
+            | TemplateErrorKind::VOnNativeModifierDeprecated
+            | TemplateErrorKind::VOnNativeModifierOnElement => SeverityLevel::Warning,
+            TemplateErrorKind::UndefinedReference
+            | TemplateErrorKind::VForMissingKeyInTransitionGroup
+            | TemplateErrorKind::VModelArgumentOnElement
+            | TemplateErrorKind::VModelInvalidExpression
+            | TemplateErrorKind::VModelOnConst
+            | TemplateErrorKind::VModelOnFileInput
+            | TemplateErrorKind::VModelOnInvalidElement
+            | TemplateErrorKind::VOnMissingHandler
+            | TemplateErrorKind::VOnNativeModifierRemoved
+            | TemplateErrorKind::VSlotMisplaced
+            | TemplateErrorKind::VSlotMixedUsage => SeverityLevel::RecoverableError,
+        }
+    }
 }
 
 impl From<CssError> for TransformError {
@@ -62,11 +204,18 @@ impl From<ScriptError> for TransformError {
     }
 }
 
+impl From<TemplateError> for TransformError {
+    fn from(value: TemplateError) -> Self {
+        TransformError::TemplateError(value)
+    }
+}
+
 impl Spanned for TransformError {
     fn span(&self) -> Span {
         match self {
             TransformError::CssError(e) => e.span,
             TransformError::ScriptError(e) => e.span,
+            TransformError::TemplateError(e) => e.span,
         }
     }
 }
@@ -1,9 +1,12 @@
 use error::TransformError;
-use fervid_core::{SfcDescriptor, SfcScriptBlock, SfcScriptLang};
-use misc::infer_name;
+use fervid_core::{FervidAtom, SfcDescriptor, SfcScriptBlock};
+use misc::{infer_name, self_reference_name};
 use script::transform_and_record_scripts;
-use style::{attach_scope_id, create_style_scope, transform_style_blocks};
-use template::transform_and_record_template;
+use style::{
+    attach_scope_id, create_style_scope, inject_css_modules, inject_legacy_style_vars,
+    transform_style_blocks,
+};
+use template::{collect_static_lists, transform_and_record_template};
 
 #[macro_use]
 extern crate lazy_static;
@@ -32,10 +35,18 @@ pub fn transform_sfc<'o>(
     // Create the bindings helper
     let mut bindings_helper = BindingsHelper::default();
     bindings_helper.is_prod = options.is_prod;
+    bindings_helper.is_compat = options.is_compat;
+    bindings_helper.reactivity_transform = options.reactivity_transform;
+    bindings_helper.sanitize_href = options.sanitize_href;
+    bindings_helper.warn_deprecated_globals = options.warn_deprecated_globals;
+    bindings_helper.unroll_static_for = options.unroll_static_for;
+    bindings_helper.whitespace = options.whitespace;
+    bindings_helper.is_custom_element = options.is_custom_element;
+    bindings_helper.component_prop_types = options.component_prop_types;
 
     // TS if any of scripts is TS.
     // Unlike the official compiler, we don't care if languages are mixed, because nothing changes.
-    let recognize_lang = |script: &SfcScriptBlock| matches!(script.lang, SfcScriptLang::Typescript);
+    let recognize_lang = |script: &SfcScriptBlock| script.lang.is_ts();
     bindings_helper.is_ts = sfc_descriptor
         .script_setup
         .as_ref()
@@ -53,10 +64,41 @@ pub fn transform_sfc<'o>(
         errors,
     );
 
+    bindings_helper.self_name = self_reference_name(options.filename).map(FervidAtom::from);
+
+    // Own props are known when the component references itself in the template
+    if let Some(name) = self_reference_name(options.filename) {
+        if !bindings_helper.prop_types.is_empty() {
+            let prop_types = bindings_helper.prop_types.clone();
+            bindings_helper
+                .component_prop_types
+                .insert(name.into(), prop_types);
+        }
+    }
+
+    // Static lists are only needed to unroll `v-for`s over them
+    if options.unroll_static_for {
+        bindings_helper.static_lists = collect_static_lists(
+            &transform_result.module,
+            transform_result.setup_fn.as_deref(),
+        );
+    }
+
+    // CSS Modules are resolved in the template using `_useCssModule`
+    inject_css_modules(&sfc_descriptor.styles, &mut bindings_helper, errors);
+
+    // COMPAT: `<style vars>` is compiled to `_useCssVars`
+    inject_legacy_style_vars(
+        &sfc_descriptor.styles,
+        &mut transform_result,
+        &mut bindings_helper,
+        errors,
+    );
+
     // Transform the template if it is present
     let mut template_block = None;
     if let Some(mut template) = sfc_descriptor.template {
-        transform_and_record_template(&mut template, &mut bindings_helper);
+        transform_and_record_template(&mut template, &mut bindings_helper, errors);
         if !template.roots.is_empty() {
             template_block = Some(template);
         }
@@ -50,6 +50,18 @@ pub fn infer_name(exported_obj: &mut ObjectLit, filename: &str) {
         }))))
 }
 
+/// Name by which a component can reference itself in its `<template>`,
+/// e.g. `FooBar` for `src/components/FooBar.vue`
+pub fn self_reference_name(filename: &str) -> Option<&str> {
+    let basename = filename.rsplit(|c| c == '/' || c == '\\').next()?;
+    let name = basename.strip_suffix(".vue").unwrap_or(basename);
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
 #[inline]
 fn is_valid_name_sym(sym: &Atom) -> bool {
     sym == "name" || sym == "__name"
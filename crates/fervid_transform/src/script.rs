@@ -21,6 +21,8 @@ mod resolve_type;
 mod setup;
 pub mod utils;
 
+pub use imports::prepend_imports;
+
 /// Transforms two script modules: `<script>` and `<script setup>`.
 /// Returns a combined Module and a default export object.
 ///
@@ -152,6 +154,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_merges_options_factory_with_script_setup() {
+        let script_content = "export default function() { return { inheritAttrs: false } }";
+        let script_setup_content = "const foo = 1";
+
+        let script = SfcScriptBlock {
+            content: Box::new(
+                parse_javascript_module(script_content, 0, Default::default())
+                    .expect("script should be parseable")
+                    .0,
+            ),
+            lang: fervid_core::SfcScriptLang::Es,
+            is_setup: false,
+            span: Span::default(),
+        };
+        let script_setup = SfcScriptBlock {
+            content: Box::new(
+                parse_javascript_module(script_setup_content, 0, Default::default())
+                    .expect("script setup should be parseable")
+                    .0,
+            ),
+            lang: fervid_core::SfcScriptLang::Es,
+            is_setup: true,
+            span: Span::default(),
+        };
+
+        let mut bindings_helper = BindingsHelper::default();
+        let mut errors = Vec::new();
+        let res = transform_and_record_scripts(
+            Some(script_setup),
+            Some(script),
+            &mut bindings_helper,
+            &mut errors,
+        );
+
+        // Factory is removed from the module, its options are merged
+        assert!(res.module.body.is_empty());
+        assert!(res.export_obj.props.iter().any(|prop| {
+            prop.as_prop()
+                .and_then(|prop| prop.as_key_value())
+                .and_then(|key_value| key_value.key.as_ident())
+                .is_some_and(|key| &key.sym == "inheritAttrs")
+        }));
+        assert!(res.setup_fn.is_some());
+    }
+
     fn check_import_dedupe(script_content: &str, script_setup_content: &str, expected: &str) {
         macro_rules! ts {
             ($input: expr) => {
@@ -114,6 +114,41 @@ pub fn categorize_expr(expr: &Expr, vue_user_imports: &VueResolvedImports) -> Bi
     }
 }
 
+/// Checks whether an expression is a getter-only `computed`.
+/// Only a function argument is known to have no setter, anything else is assumed writable.
+/// ```js
+/// import { computed } from 'vue'
+///
+/// const
+///     foo = computed(() => 1),                    // readonly
+///     bar = computed({ get: () => 1, set() {} }), // writable
+///     baz = computed(options);                    // unknown, assumed writable
+/// ```
+pub fn is_readonly_computed(expr: &Expr, vue_user_imports: &VueResolvedImports) -> bool {
+    let Expr::Call(call_expr) = unroll_paren_seq(expr) else {
+        return false;
+    };
+    let Callee::Expr(ref callee_expr) = call_expr.callee else {
+        return false;
+    };
+    let Expr::Ident(ref callee_ident) = **callee_expr else {
+        return false;
+    };
+    if vue_user_imports.computed.is_none()
+        || Some(callee_ident.to_id()) != vue_user_imports.computed
+    {
+        return false;
+    }
+
+    matches!(
+        call_expr
+            .args
+            .first()
+            .map(|arg| unroll_paren_seq(&arg.expr)),
+        Some(Expr::Arrow(_) | Expr::Fn(_))
+    )
+}
+
 /// Enriches binding types with additional information obtained from analyzing RHS
 #[inline]
 pub fn enrich_binding_types(
@@ -1,17 +1,25 @@
 use fervid_core::{fervid_atom, BindingTypes, FervidAtom};
 use swc_core::ecma::{
-    ast::{Id, Ident, ImportSpecifier, Module, ModuleDecl, ModuleExportName, ModuleItem},
+    ast::{
+        Decl, Id, Ident, ImportDecl, ImportSpecifier, Module, ModuleDecl, ModuleExportName,
+        ModuleItem, Stmt,
+    },
     atoms::JsWord,
 };
 
 use crate::{
-    atoms::{COMPUTED, DEFINE_EMITS, DEFINE_EXPOSE, DEFINE_PROPS, REACTIVE, REF, VUE},
+    atoms::{
+        COMPUTED, DEFINE_EMITS, DEFINE_EXPOSE, DEFINE_MODEL, DEFINE_OPTIONS, DEFINE_PROPS,
+        DEFINE_SLOTS, REACTIVE, REF, VUE, VUE_MACROS, WITH_DEFAULTS,
+    },
     error::{ScriptError, ScriptErrorKind, TransformError },
+    script::common::extract_variables_from_pat,
     structs::VueResolvedImports,
     BindingsHelper, ImportBinding, SetupBinding,
 };
 
-/// Collects imports and removes duplicates
+/// Collects imports and removes duplicates.
+/// Type-only imports have no runtime representation, thus they are removed as well.
 pub fn process_imports(
     module: &mut Module,
     bindings_helper: &mut BindingsHelper,
@@ -23,13 +31,18 @@ pub fn process_imports(
             return true;
         };
 
-        // Do not process type-only declarations, do not collect
+        // Do not collect type-only declarations, e.g. `import type { Foo } from './types'`
         if import_decl.type_only {
-            return true;
+            return false;
         }
 
         let source = &import_decl.src.value;
 
+        // `import { $ref } from 'vue/macros'` only serves the reactivity transform
+        if bindings_helper.reactivity_transform && *source == *VUE_MACROS {
+            return false;
+        }
+
         // Checks if the import is of `.vue`, i.e. eligible to be a component
         // Should this handle complex queries?
         let is_dot_vue_import = source.ends_with(".vue");
@@ -52,7 +65,7 @@ pub fn process_imports(
         });
 
         // Do not retain emptied imports, i.e. the fully deduplicated ones (`import { foo } from './foo'` -> `import {} from './foo'`).
-        // This is not a side effect, because we only removed duplicate imports
+        // This is not a side effect, because we only removed duplicate or type-only imports
         !(prev_len > 0 && import_decl.specifiers.is_empty())
     });
 }
@@ -93,8 +106,9 @@ pub fn register_import(
         // e.g. `import { foo } from 'mod.js'` -> local = foo, imported = None
         // e.g. `import { foo as bar } from 'mod.js'` -> local = bar, imported = Some(foo)
         ImportSpecifier::Named(named_spec) => {
+            // `type Foo` in `import { type Foo, Bar } from './foo'`
             if named_spec.is_type_only {
-                return true;
+                return false;
             }
 
             // `imported_as` is the variable name, `imported_word` is the imported symbol
@@ -113,6 +127,10 @@ pub fn register_import(
                 if *imported_word == *DEFINE_PROPS
                     || *imported_word == *DEFINE_EMITS
                     || *imported_word == *DEFINE_EXPOSE
+                    || *imported_word == *DEFINE_MODEL
+                    || *imported_word == *DEFINE_OPTIONS
+                    || *imported_word == *DEFINE_SLOTS
+                    || *imported_word == *WITH_DEFAULTS
                 {
                     errors.push(TransformError::ScriptError(ScriptError {
                         span: named_spec.span,
@@ -180,6 +198,131 @@ pub fn register_import(
     true
 }
 
+/// Adds the `imports` to the beginning of the `module`, e.g. to make some imports global.
+///
+/// Names which are already declared in the `module` are not imported again,
+/// so that the user declarations always take precedence.
+/// Named imports are merged into an existing import from the same source.
+pub fn prepend_imports(module: &mut Module, imports: Vec<ImportDecl>) {
+    let mut declared = collect_module_declarations(module);
+    let mut prepended = Vec::with_capacity(imports.len());
+
+    for mut import_decl in imports {
+        if import_decl.type_only {
+            continue;
+        }
+
+        // Side-effect import, e.g. `import 'normalize.css'`
+        if import_decl.specifiers.is_empty() {
+            let is_imported = module
+                .body
+                .iter()
+                .chain(prepended.iter())
+                .filter_map(|module_item| module_item.as_module_decl()?.as_import())
+                .any(|existing| existing.src.value == import_decl.src.value);
+            if !is_imported {
+                prepended.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)));
+            }
+            continue;
+        }
+
+        import_decl.specifiers.retain(|specifier| {
+            let local = &get_import_specifier_local(specifier).sym;
+            !is_type_only_specifier(specifier) && !declared.contains(local)
+        });
+        if import_decl.specifiers.is_empty() {
+            continue;
+        }
+        declared.extend(
+            import_decl
+                .specifiers
+                .iter()
+                .map(|specifier| get_import_specifier_local(specifier).sym.to_owned()),
+        );
+
+        // Only named imports can be merged, e.g. `import { a } from 'x'` and `import { b } from 'x'`
+        let is_all_named = import_decl
+            .specifiers
+            .iter()
+            .all(|specifier| matches!(specifier, ImportSpecifier::Named(_)));
+        let mergeable = module
+            .body
+            .iter_mut()
+            .chain(prepended.iter_mut())
+            .filter_map(|module_item| match module_item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(existing)) => Some(existing),
+                _ => None,
+            })
+            .find(|existing| {
+                existing.src.value == import_decl.src.value
+                    && !existing.type_only
+                    && !existing
+                        .specifiers
+                        .iter()
+                        .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)))
+            });
+
+        match mergeable {
+            Some(existing) if is_all_named => existing.specifiers.extend(import_decl.specifiers),
+            _ => prepended.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))),
+        }
+    }
+
+    module.body.splice(0..0, prepended);
+}
+
+/// Collects the names declared at the top level of a module, including the imported ones
+fn collect_module_declarations(module: &Module) -> Vec<FervidAtom> {
+    let mut declared = Vec::new();
+
+    for module_item in module.body.iter() {
+        let decl = match module_item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                declared.extend(
+                    import_decl
+                        .specifiers
+                        .iter()
+                        .map(|specifier| get_import_specifier_local(specifier).sym.to_owned()),
+                );
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => &export_decl.decl,
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            _ => continue,
+        };
+
+        match decl {
+            Decl::Class(class_decl) => declared.push(class_decl.ident.sym.to_owned()),
+            Decl::Fn(fn_decl) => declared.push(fn_decl.ident.sym.to_owned()),
+            Decl::Var(var_decl) => {
+                // Destructures are included, e.g. `useRoute` in `const { useRoute } = x`
+                let mut bindings = Vec::new();
+                for declarator in var_decl.decls.iter() {
+                    extract_variables_from_pat(&declarator.name, &mut bindings, true);
+                }
+                declared.extend(bindings.into_iter().map(|binding| binding.0));
+            }
+            _ => {}
+        }
+    }
+
+    declared
+}
+
+#[inline]
+fn get_import_specifier_local(specifier: &ImportSpecifier) -> &Ident {
+    match specifier {
+        ImportSpecifier::Named(named) => &named.local,
+        ImportSpecifier::Default(default) => &default.local,
+        ImportSpecifier::Namespace(namespace) => &namespace.local,
+    }
+}
+
+#[inline]
+fn is_type_only_specifier(specifier: &ImportSpecifier) -> bool {
+    matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only)
+}
+
 #[inline]
 fn collect_vue_import(imported_word: &JsWord, used_as: Id, vue_imports: &mut VueResolvedImports) {
     if *imported_word == *REF {
@@ -347,8 +490,161 @@ mod tests {
         )
     }
 
+    #[test]
+    fn it_strips_type_imports() {
+        for is_from_setup in [true, false] {
+            let mut module = parse_typescript_module(
+                r"
+                import type { Foo } from './types'
+                import type TypedComp from './TypedComp.vue'
+                import { type Baz, Bar } from './bar'
+                import { type Qux } from './qux'
+                import Comp from './Comp.vue'
+                ",
+                0,
+                Default::default(),
+            )
+            .unwrap()
+            .0;
+
+            let mut bindings_helper = BindingsHelper::default();
+            let mut errors = Vec::new();
+            process_imports(
+                &mut module,
+                &mut bindings_helper,
+                is_from_setup,
+                &mut errors,
+            );
+            assert!(errors.is_empty());
+
+            // Only the runtime imports are left in the output
+            let imported: Vec<&str> = module
+                .body
+                .iter()
+                .filter_map(|module_item| module_item.as_module_decl()?.as_import())
+                .flat_map(|import_decl| import_decl.specifiers.iter())
+                .map(|specifier| match specifier {
+                    ImportSpecifier::Named(named) => &*named.local.sym,
+                    ImportSpecifier::Default(default) => &*default.local.sym,
+                    ImportSpecifier::Namespace(namespace) => &*namespace.local.sym,
+                })
+                .collect();
+            assert_eq!(imported, vec!["Bar", "Comp"]);
+
+            // Type-only names are never available to the template, e.g. as components
+            let mut collected: Vec<&str> = bindings_helper
+                .user_imports
+                .keys()
+                .map(|local| &**local)
+                .collect();
+            collected.sort();
+            assert_eq!(collected, vec!["Bar", "Comp"]);
+        }
+    }
+
+    #[test]
+    fn it_removes_compiler_macro_imports() {
+        let mut module = parse_typescript_module(
+            "import { defineSlots, withDefaults as wd, ref } from 'vue'",
+            0,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        let mut bindings_helper = BindingsHelper::default();
+        let mut errors = Vec::new();
+        process_imports(&mut module, &mut bindings_helper, true, &mut errors);
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(
+            error,
+            TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::CompilerMacroImport,
+                ..
+            })
+        )));
+
+        // Other imports are kept
+        assert_eq!(
+            bindings_helper.vue_resolved_imports.ref_import,
+            Some((fervid_atom!("ref"), SyntaxContext::default()))
+        );
+    }
+
     #[test]
     fn it_deduplicates_imports() {
         
     }
+
+    #[test]
+    fn it_prepends_imports() {
+        let prepend = |input: &str, imports: &str| {
+            let mut module = parse_typescript_module(input, 0, Default::default())
+                .unwrap()
+                .0;
+            let import_decls = parse_typescript_module(imports, 0, Default::default())
+                .unwrap()
+                .0
+                .body
+                .into_iter()
+                .filter_map(|module_item| module_item.module_decl()?.import())
+                .collect();
+            prepend_imports(&mut module, import_decls);
+
+            // `source: local1, local2`
+            module
+                .body
+                .iter()
+                .filter_map(|module_item| module_item.as_module_decl()?.as_import())
+                .map(|import_decl| {
+                    let locals: Vec<&str> = import_decl
+                        .specifiers
+                        .iter()
+                        .map(|specifier| &*get_import_specifier_local(specifier).sym)
+                        .collect();
+                    format!("{}: {}", import_decl.src.value, locals.join(", "))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Single import
+        assert_eq!(
+            prepend(
+                "import { ref } from 'vue'",
+                "import { useRouter } from 'vue-router'"
+            ),
+            vec!["vue-router: useRouter", "vue: ref"]
+        );
+
+        // Duplicate of an existing import, and an import merged into an existing one
+        assert_eq!(
+            prepend(
+                "import { useRouter } from 'vue-router'",
+                "import { useRouter, useRoute } from 'vue-router'"
+            ),
+            vec!["vue-router: useRouter, useRoute"]
+        );
+
+        // Conflicts with the user import or declaration, user wins
+        assert_eq!(
+            prepend(
+                "import { useRouter } from './my-router'\nconst useRoute = () => {}",
+                "import { useRouter, useRoute } from 'vue-router'\nimport Foo from './Foo.vue'"
+            ),
+            vec!["./Foo.vue: Foo", "./my-router: useRouter"]
+        );
+
+        // Conflicts with a destructured declaration
+        assert_eq!(
+            prepend(
+                "const { useRoute } = x\nconst [useRouter] = y",
+                "import { useRouter, useRoute, RouterLink } from 'vue-router'"
+            ),
+            vec!["vue-router: RouterLink"]
+        );
+
+        // Empty list
+        assert_eq!(prepend("import { ref } from 'vue'", ""), vec!["vue: ref"]);
+    }
 }
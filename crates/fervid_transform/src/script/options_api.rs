@@ -1,8 +1,9 @@
 use swc_core::{
     common::DUMMY_SP,
     ecma::ast::{
-        Callee, Expr, ExprOrSpread, Module, ModuleDecl, ModuleItem, ObjectLit, PropOrSpread,
-        SpreadElement,
+        BlockStmtOrExpr, Callee, DefaultDecl, ExportDefaultDecl, Expr, ExprOrSpread, Function,
+        Invalid, Module, ModuleDecl, ModuleItem, ObjectLit, PropOrSpread, ReturnStmt,
+        SpreadElement, Stmt,
     },
 };
 
@@ -86,6 +87,11 @@ fn find_default_export_obj(module: &mut Module) -> Option<ObjectLit> {
         .iter()
         .position(|module_item| match module_item {
             ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(_)) => true,
+            // `export default function ComponentFactory() { return { /* ... */ } }`
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Fn(fn_expr),
+                ..
+            })) => get_factory_return(&fn_expr.function).is_some(),
             _ => false,
         });
 
@@ -95,12 +101,17 @@ fn find_default_export_obj(module: &mut Module) -> Option<ObjectLit> {
 
     let item = module.body.remove(idx);
     // TODO What to do with weird default exports?
-    let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(expr)) = item else {
-        unreachable!()
+    let expr = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(expr)) => *expr.expr,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+            decl: DefaultDecl::Fn(fn_expr),
+            ..
+        })) => Expr::Fn(fn_expr),
+        _ => unreachable!(),
     };
 
     // TODO Unroll paren/seq, unroll `defineComponent` as in `fervid_script`
-    let expr = unroll_default_export_expr(*expr.expr);
+    let expr = unroll_default_export_expr(expr);
 
     match expr {
         // Object is the preferred syntax
@@ -173,6 +184,90 @@ fn unroll_default_export_expr(mut expr: Expr) -> Expr {
             *expr
         }
 
+        // Options factory is treated the same as the options it returns
+        // `export default function() { return { /* ... */ } }`
+        Expr::Fn(ref mut fn_expr) => {
+            if get_factory_return(&fn_expr.function).is_none() {
+                return expr;
+            }
+
+            let body = fn_expr.function.body.as_mut();
+            match body.and_then(|body| body.stmts.pop()) {
+                Some(Stmt::Return(ReturnStmt {
+                    arg: Some(returned),
+                    ..
+                })) => unroll_default_export_expr(unwrap_parens(*returned)),
+                _ => unreachable!(),
+            }
+        }
+
+        // `export default () => ({ /* ... */ })`
+        Expr::Arrow(ref mut arrow_expr)
+            if arrow_expr.params.is_empty() && !arrow_expr.is_async && !arrow_expr.is_generator =>
+        {
+            let returned = match *arrow_expr.body {
+                BlockStmtOrExpr::Expr(ref mut body_expr) => Some(body_expr),
+                BlockStmtOrExpr::BlockStmt(ref mut block) => match block.stmts.as_mut_slice() {
+                    [Stmt::Return(return_stmt)] => return_stmt.arg.as_mut(),
+                    _ => None,
+                },
+            };
+
+            match returned {
+                Some(returned) if is_options_expr(returned) => {
+                    let returned = std::mem::replace(
+                        &mut **returned,
+                        Expr::Invalid(Invalid { span: DUMMY_SP }),
+                    );
+                    unroll_default_export_expr(unwrap_parens(returned))
+                }
+                _ => expr,
+            }
+        }
+
+        _ => expr,
+    }
+}
+
+/// Options factory has no parameters and only returns an object or a `defineComponent` call.
+/// Otherwise, the function may be a functional component and must be kept as is.
+fn get_factory_return(function: &Function) -> Option<&Expr> {
+    if !function.params.is_empty() || function.is_async || function.is_generator {
+        return None;
+    }
+
+    let Some(ref body) = function.body else {
+        return None;
+    };
+
+    let [Stmt::Return(return_stmt)] = body.stmts.as_slice() else {
+        return None;
+    };
+
+    return_stmt
+        .arg
+        .as_deref()
+        .filter(|returned| is_options_expr(returned))
+}
+
+/// `{ /* ... */ }` or `defineComponent({ /* ... */ })`, possibly in parens
+fn is_options_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Object(_) => true,
+        Expr::Call(call_expr) => match call_expr.callee {
+            Callee::Expr(ref callee) => {
+                matches!(**callee, Expr::Ident(ref ident) if &ident.sym == "defineComponent")
+            }
+            _ => false,
+        },
+        Expr::Paren(paren_expr) => is_options_expr(&paren_expr.expr),
+        _ => false,
+    }
+}
+
+fn unwrap_parens(expr: Expr) -> Expr {
+    match expr {
+        Expr::Paren(paren_expr) => unwrap_parens(*paren_expr.expr),
         _ => expr,
     }
 }
@@ -326,6 +421,106 @@ mod tests {
         // should_err!("export default wrongDefineComponent({})");
     }
 
+    #[test]
+    fn it_analyzes_options_factory() {
+        let expected = OptionsApiBindings {
+            name: Some(FervidAtom::from("TestComponent")),
+            props: vec![FervidAtom::from("foo")],
+            methods: vec![FervidAtom::from("bar")],
+            ..Default::default()
+        };
+
+        // Function declaration
+        test_js_and_ts!(
+            r"
+            export default function ComponentFactory() {
+                return {
+                    name: 'TestComponent',
+                    props: ['foo'],
+                    methods: {
+                        bar() {}
+                    }
+                }
+            }
+            ",
+            expected
+        );
+
+        // Arrow function
+        test_js_and_ts!(
+            r"
+            export default () => ({
+                name: 'TestComponent',
+                props: ['foo'],
+                methods: {
+                    bar() {}
+                }
+            })
+            ",
+            expected
+        );
+        test_js_and_ts!(
+            r"
+            export default () => {
+                return defineComponent({
+                    name: 'TestComponent',
+                    props: ['foo'],
+                    methods: {
+                        bar() {}
+                    }
+                })
+            }
+            ",
+            expected
+        );
+
+        // Module-level variables stay available to the returned options
+        let mut parsed = parse_javascript_module(
+            r"
+            const name = 'TestComponent'
+            export default function() {
+                return { name }
+            }
+            ",
+            0,
+            Default::default(),
+        )
+        .expect("parsing js should not err")
+        .0;
+        let default_export_obj = transform_and_record_script_options_api(
+            &mut parsed,
+            Default::default(),
+            &mut Default::default(),
+            &mut Default::default(),
+        )
+        .default_export_obj
+        .expect("factory should be unrolled");
+        assert_eq!(default_export_obj.props.len(), 1);
+        assert_eq!(parsed.body.len(), 1);
+
+        // Functions with parameters or statements are kept as is
+        let mut parsed = parse_javascript_module(
+            r"
+            export default function(props) {
+                return { props }
+            }
+            ",
+            0,
+            Default::default(),
+        )
+        .expect("parsing js should not err")
+        .0;
+        let default_export_obj = transform_and_record_script_options_api(
+            &mut parsed,
+            Default::default(),
+            &mut Default::default(),
+            &mut Default::default(),
+        )
+        .default_export_obj;
+        assert!(default_export_obj.is_none());
+        assert_eq!(parsed.body.len(), 1);
+    }
+
     #[test]
     fn it_sees_name() {
         let test_name = OptionsApiBindings {
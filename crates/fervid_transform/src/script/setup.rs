@@ -1,20 +1,22 @@
-use fervid_core::{BindingTypes, SfcScriptBlock};
+use fervid_core::{BindingTypes, SfcScriptBlock, TemplateGenerationMode};
 use swc_core::{
     common::{Span, DUMMY_SP},
     ecma::ast::{
-        BindingIdent, BlockStmt, Decl, ExprStmt, Function, Ident, KeyValuePatProp, KeyValueProp,
-        ModuleDecl, ModuleItem, ObjectPat, ObjectPatProp, Param, Pat, Prop, PropName, PropOrSpread,
-        Stmt, VarDeclKind,
+        BindingIdent, BlockStmt, CallExpr, Callee, Decl, Expr, ExprStmt, Function, Ident,
+        KeyValuePatProp, KeyValueProp, ModuleDecl, ModuleItem, ObjectPat, ObjectPatProp, Param,
+        Pat, Prop, PropName, PropOrSpread, Stmt, VarDeclKind,
     },
 };
 
 use crate::{
-    atoms::{EMIT, EMITS, EMIT_HELPER, EXPOSE, EXPOSE_HELPER, PROPS, PROPS_HELPER},
+    atoms::{
+        EMIT, EMITS, EMIT_HELPER, EXPOSE, EXPOSE_HELPER, PROPS, PROPS_HELPER, USE_MODEL_HELPER,
+    },
     error::{ScriptError, ScriptErrorKind, TransformError},
     script::{
         common::{
             categorize_class, categorize_expr, categorize_fn_decl, enrich_binding_types,
-            extract_variables_from_pat,
+            extract_variables_from_pat, is_readonly_computed,
         },
         setup::macros::TransformMacroResult,
         utils::is_static,
@@ -23,12 +25,16 @@ use crate::{
     BindingsHelper, SetupBinding,
 };
 
+mod async_context;
 mod await_detection;
 mod macros;
+mod reactivity_transform;
 
 use self::{
+    async_context::transform_async_context,
     await_detection::detect_await_module_item,
     macros::{postprocess_macros, transform_script_setup_macro_expr},
+    reactivity_transform::{mark_ref_bindings, transform_reactivity_macros},
 };
 
 use super::imports::process_imports;
@@ -68,6 +74,17 @@ pub fn transform_and_record_script_setup(
         }
     }
 
+    // 3. Apply the reactivity transform (`$ref`, `$computed`, `$()`, `$$()`) when enabled
+    let reactivity_refs = if bindings_helper.reactivity_transform {
+        Some(transform_reactivity_macros(
+            &mut script_setup.content,
+            bindings_helper,
+            errors,
+        ))
+    } else {
+        None
+    };
+
     // Go over the whole script setup: process all the statements and declarations
     for module_item in script_setup.content.body {
         let stmt = match module_item {
@@ -119,7 +136,8 @@ pub fn transform_and_record_script_setup(
             }
 
             Stmt::Decl(decl) => {
-                transform_decl_stmt(decl, bindings_helper, &mut sfc_object_helper).map(Stmt::Decl)
+                transform_decl_stmt(decl, bindings_helper, &mut sfc_object_helper, errors)
+                    .map(Stmt::Decl)
             }
 
             // By default, just return the same statement
@@ -134,6 +152,43 @@ pub fn transform_and_record_script_setup(
     // Post-process macros, e.g. merge models to `props` and `emits`
     postprocess_macros(bindings_helper, &mut sfc_object_helper);
 
+    // Variables declared with reactivity transform macros are refs
+    if let Some(ref refs) = reactivity_refs {
+        mark_ref_bindings(&mut bindings_helper.setup_bindings, refs);
+    }
+
+    // Top-level `await` must restore the current instance after it resumes
+    if sfc_object_helper.is_async_setup {
+        transform_async_context(&mut setup_body_stmts, &mut bindings_helper.vue_imports);
+    }
+
+    // When the setup bindings are returned to the render function, they would be
+    // accessible to the parent using a template ref. `__expose()` closes the instance.
+    if !sfc_object_helper.is_setup_expose_referenced
+        && !matches!(
+            bindings_helper.template_generation_mode,
+            TemplateGenerationMode::Inline
+        )
+    {
+        sfc_object_helper.is_setup_expose_referenced = true;
+        setup_body_stmts.insert(
+            0,
+            Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                        span: DUMMY_SP,
+                        sym: EXPOSE_HELPER.to_owned(),
+                        optional: false,
+                    }))),
+                    args: vec![],
+                    type_args: None,
+                })),
+            }),
+        );
+    }
+
     // Should we check that this function was not assigned anywhere else?
     let setup_fn = Some(Box::new(Function {
         params: get_setup_fn_params(&sfc_object_helper),
@@ -162,6 +217,7 @@ fn transform_decl_stmt(
     decl: Decl,
     bindings_helper: &mut BindingsHelper,
     sfc_object_helper: &mut SfcExportedObjectHelper,
+    errors: &mut Vec<TransformError>,
 ) -> Option<Decl> {
     /// Pushes the binding type and returns the same passed `Decl`
     macro_rules! push_return {
@@ -196,16 +252,47 @@ fn transform_decl_stmt(
 
                 // Process RHS
                 if let Some(ref init_expr) = var_declarator.init {
-                    let transform_macro_result = transform_script_setup_macro_expr(
+                    let transform_macro_result = match transform_script_setup_macro_expr(
                         init_expr,
                         bindings_helper,
                         sfc_object_helper,
                         true,
-                    );
+                    ) {
+                        // The declaration is left as is
+                        TransformMacroResult::Error(err) => {
+                            errors.push(err);
+                            TransformMacroResult::NotAMacro
+                        }
+                        result => result,
+                    };
 
                     if let TransformMacroResult::ValidMacro(transformed_expr) =
                         transform_macro_result
                     {
+                        match (&var_declarator.name, transformed_expr.as_deref()) {
+                            // Remember the name `defineProps()` result was assigned to
+                            (Pat::Ident(ref binding_ident), Some(Expr::Ident(ref props_ident)))
+                                if props_ident.sym == *PROPS_HELPER =>
+                            {
+                                bindings_helper.props_binding =
+                                    Some(binding_ident.id.sym.to_owned());
+                            }
+
+                            // `const model = defineModel()` is a ref
+                            (_, Some(Expr::Call(ref call_expr)))
+                                if is_use_model_call(call_expr) =>
+                            {
+                                enrich_binding_types(
+                                    &mut collected_bindings,
+                                    BindingTypes::SetupRef,
+                                    is_const,
+                                    is_ident,
+                                );
+                            }
+
+                            _ => {}
+                        }
+
                         // Macros always overwrite the RHS
                         var_declarator.init = transformed_expr;
                     } else if is_const && is_ident {
@@ -215,6 +302,14 @@ fn transform_decl_stmt(
                             categorize_expr(init_expr, &bindings_helper.vue_resolved_imports);
 
                         enrich_binding_types(&mut collected_bindings, rhs_type, is_const, is_ident);
+
+                        if is_readonly_computed(init_expr, &bindings_helper.vue_resolved_imports) {
+                            if let Pat::Ident(ref binding_ident) = var_declarator.name {
+                                bindings_helper
+                                    .readonly_computeds
+                                    .push(binding_ident.id.sym.to_owned());
+                            }
+                        }
                     }
                 }
 
@@ -299,6 +394,15 @@ fn check_export(module_decl: &ModuleDecl) -> Option<Span> {
     }
 }
 
+/// `_useModel(__props, "modelValue")` generated from `defineModel()`
+fn is_use_model_call(call_expr: &CallExpr) -> bool {
+    let Callee::Expr(ref callee_expr) = call_expr.callee else {
+        return false;
+    };
+
+    matches!(**callee_expr, Expr::Ident(ref ident) if ident.sym == *USE_MODEL_HELPER)
+}
+
 /// Used to populate the params to `setup()`, such as `__props`, `emit`, etc.
 fn get_setup_fn_params(sfc_object_helper: &SfcExportedObjectHelper) -> Vec<Param> {
     let has_ctx_param =
@@ -372,46 +476,79 @@ fn get_setup_fn_params(sfc_object_helper: &SfcExportedObjectHelper) -> Vec<Param
 mod tests {
     use crate::{
         error::{ScriptError, ScriptErrorKind, TransformError},
-        test_utils::parser::*,
-        BindingsHelper, SetupBinding,
+        test_utils::{parser::*, to_str},
+        BindingsHelper, PropType, SetupBinding,
+    };
+    use fervid_core::{
+        fervid_atom, BindingTypes, FervidAtom, SfcScriptBlock, SfcScriptLang,
+        TemplateGenerationMode, VueImports,
+    };
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{Expr, FnExpr, ObjectLit},
     };
-    use fervid_core::{fervid_atom, BindingTypes, SfcScriptBlock};
-    use swc_core::common::DUMMY_SP;
 
-    use super::transform_and_record_script_setup;
+    use super::{transform_and_record_script_setup, TransformScriptSetupResult};
+
+    /// Parses the `input` as `<script setup>` and transforms it using the `bindings_helper`
+    pub(super) fn transform_setup(
+        input: &str,
+        lang: SfcScriptLang,
+        bindings_helper: &mut BindingsHelper,
+    ) -> (TransformScriptSetupResult, Vec<TransformError>) {
+        let parsed = match lang {
+            SfcScriptLang::Typescript => parse_typescript_module(input, 0, Default::default()),
+            _ => parse_javascript_module(input, 0, Default::default()),
+        }
+        .expect("the input must be parseable")
+        .0;
 
-    fn analyze_bindings(script_setup: SfcScriptBlock) -> Vec<SetupBinding> {
-        let mut bindings_helper = BindingsHelper::default();
         let mut errors = Vec::new();
-        transform_and_record_script_setup(script_setup, &mut bindings_helper, &mut errors);
+        let result = transform_and_record_script_setup(
+            SfcScriptBlock {
+                content: Box::new(parsed),
+                lang,
+                is_setup: true,
+                span: DUMMY_SP,
+            },
+            bindings_helper,
+            &mut errors,
+        );
+
+        (result, errors)
+    }
+
+    fn analyze_bindings(input: &str, lang: SfcScriptLang) -> Vec<SetupBinding> {
+        let mut bindings_helper = BindingsHelper::default();
+        transform_setup(input, lang, &mut bindings_helper);
 
         bindings_helper.setup_bindings
     }
 
     fn analyze_js_bindings(input: &str) -> Vec<SetupBinding> {
-        let parsed = parse_javascript_module(input, 0, Default::default())
-            .expect("analyze_js expects the input to be parseable")
-            .0;
-
-        analyze_bindings(SfcScriptBlock {
-            content: Box::new(parsed),
-            lang: fervid_core::SfcScriptLang::Es,
-            is_setup: true,
-            span: DUMMY_SP,
-        })
+        analyze_bindings(input, SfcScriptLang::Es)
     }
 
     fn analyze_ts_bindings(input: &str) -> Vec<SetupBinding> {
-        let parsed = parse_typescript_module(input, 0, Default::default())
-            .expect("analyze_ts expects the input to be parseable")
-            .0;
-
-        analyze_bindings(SfcScriptBlock {
-            content: Box::new(parsed),
-            lang: fervid_core::SfcScriptLang::Typescript,
-            is_setup: true,
-            span: DUMMY_SP,
-        })
+        analyze_bindings(input, SfcScriptLang::Typescript)
+    }
+
+    /// Stringifies the generated `setup` function
+    fn setup_fn_to_str(result: TransformScriptSetupResult) -> String {
+        to_str(&Expr::Fn(FnExpr {
+            ident: None,
+            function: result.setup_fn.expect("setup must be generated"),
+        }))
+    }
+
+    /// Stringifies the runtime `emits` declaration, also returns the collected event names
+    fn emits_of(input: &str, lang: SfcScriptLang) -> (Option<String>, Vec<FervidAtom>) {
+        let mut bindings_helper = BindingsHelper::default();
+        let (result, errors) = transform_setup(input, lang, &mut bindings_helper);
+        assert!(errors.is_empty());
+
+        let emits = result.sfc_object_helper.emits.map(|emits| to_str(&emits));
+        (emits, bindings_helper.emits)
     }
 
     macro_rules! test_js_and_ts {
@@ -703,25 +840,636 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_collects_prop_types() {
+        let mut bindings_helper = BindingsHelper::default();
+        transform_setup(
+            r"
+            defineProps({
+                count: Number,
+                id: [String, Number],
+                label: { type: String, required: true },
+                date: Date,
+                untyped: {},
+                dynamic: someType
+            })
+            ",
+            SfcScriptLang::Es,
+            &mut bindings_helper,
+        );
+
+        let prop_types = &bindings_helper.prop_types;
+        let get = |name: &str| prop_types.get(&FervidAtom::from(name)).map(|t| t.to_vec());
+        assert_eq!(get("count"), Some(vec![PropType::Number]));
+        assert_eq!(get("id"), Some(vec![PropType::String, PropType::Number]));
+        assert_eq!(get("label"), Some(vec![PropType::String]));
+        assert_eq!(get("date"), Some(vec![PropType::Other]));
+        assert_eq!(get("untyped"), None);
+        assert_eq!(get("dynamic"), Some(vec![PropType::Other]));
+    }
+
+    #[test]
+    fn it_collects_readonly_computeds() {
+        let mut bindings_helper = BindingsHelper::default();
+        transform_setup(
+            r"
+            import { computed } from 'vue'
+
+            const getter = computed(() => 42)
+            const getterFn = computed(function () { return 42 })
+            const writable = computed({ get: () => 42, set: (v) => {} })
+            const method = computed({ get() { return 42 }, set(v) {} })
+            const fromOptions = computed(options)
+            const noArgs = computed()
+            let reassignable = computed(() => 42)
+            ",
+            SfcScriptLang::Es,
+            &mut bindings_helper,
+        );
+
+        assert_eq!(
+            bindings_helper.readonly_computeds,
+            vec![fervid_atom!("getter"), fervid_atom!("getterFn")]
+        );
+    }
+
+    #[test]
+    fn it_collects_props_binding_and_emits() {
+        let mut bindings_helper = BindingsHelper::default();
+        transform_setup(
+            r"
+            const props = defineProps(['modelValue'])
+            defineEmits({ 'update:modelValue': null, change: (value) => !!value })
+            ",
+            SfcScriptLang::Es,
+            &mut bindings_helper,
+        );
+
+        assert_eq!(bindings_helper.props_binding, Some(fervid_atom!("props")));
+        assert_eq!(
+            bindings_helper.emits,
+            vec![fervid_atom!("update:modelValue"), fervid_atom!("change")]
+        );
+    }
+
+    #[test]
+    fn it_preserves_emits_validators() {
+        let js_emits_of = |input: &str| emits_of(input, SfcScriptLang::Es).0;
+
+        // Event with a validator
+        assert_eq!(
+            js_emits_of("defineEmits({ click: (payload) => typeof payload === 'number' })"),
+            Some(r#"{click:payload=>typeof payload==="number"}"#.to_owned())
+        );
+
+        // Events without validators
+        assert_eq!(
+            js_emits_of("defineEmits(['click', 'submit'])"),
+            Some(r#"["click","submit"]"#.to_owned())
+        );
+
+        // Mixed: `null` means no validation
+        assert_eq!(
+            js_emits_of("defineEmits({ click: null, submit: (payload) => !!payload })"),
+            Some("{click:null,submit:payload=>!!payload}".to_owned())
+        );
+
+        // Validators survive merging with models
+        assert_eq!(
+            js_emits_of(
+                "defineEmits({ click: (payload) => typeof payload === 'number' })
+                defineModel()"
+            ),
+            Some(
+                r#"_mergeModels({click:payload=>typeof payload==="number"},["update:modelValue"])"#
+                    .to_owned()
+            )
+        );
+
+        // Emitting a value of the wrong type can only be checked at runtime
+        assert_eq!(
+            js_emits_of(
+                "const emit = defineEmits({ click: (payload) => typeof payload === 'number' })
+                emit('click', 'not a number')"
+            ),
+            Some(r#"{click:payload=>typeof payload==="number"}"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn it_generates_runtime_emits_from_type() {
+        let ts_emits_of = |input: &str| emits_of(input, SfcScriptLang::Typescript);
+
+        let expected = (
+            Some(r#"["update:modelValue","close"]"#.to_owned()),
+            vec![fervid_atom!("update:modelValue"), fervid_atom!("close")],
+        );
+
+        // Runtime array
+        assert_eq!(
+            ts_emits_of("defineEmits(['update:modelValue', 'close'])"),
+            expected
+        );
+
+        // Call signatures, the declaration order is kept
+        assert_eq!(
+            ts_emits_of(
+                "const emit = defineEmits<{
+                    (e: 'update:modelValue', value: string): void
+                    (e: 'close'): void
+                }>()"
+            ),
+            expected
+        );
+
+        // Function type with a union
+        assert_eq!(
+            ts_emits_of("defineEmits<(e: 'update:modelValue' | 'close' | 'close') => void>()"),
+            expected
+        );
+
+        // Property syntax
+        assert_eq!(
+            ts_emits_of(
+                "defineEmits<{
+                    'update:modelValue': [value: string]
+                    close: []
+                }>()"
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn it_expands_define_model() {
+        let mut bindings_helper = BindingsHelper::default();
+        let (result, errors) = transform_setup(
+            r"
+            const model = defineModel()
+            const title = defineModel('title', { type: String, required: true })
+            defineModel('count')
+            ",
+            SfcScriptLang::Es,
+            &mut bindings_helper,
+        );
+        assert!(errors.is_empty());
+
+        // Each model is a prop with its modifiers and an update event
+        let sfc_object_helper = result.sfc_object_helper;
+        assert_eq!(
+            sfc_object_helper.props.map(|props| to_str(&props)),
+            Some(
+                concat!(
+                    r#"{"modelValue":{},"modelModifiers":{},"#,
+                    r#""title":{type:String,required:true},"titleModifiers":{},"#,
+                    r#""count":{},"countModifiers":{}}"#
+                )
+                .to_owned()
+            )
+        );
+        assert_eq!(
+            sfc_object_helper.emits.map(|emits| to_str(&emits)),
+            Some(r#"["update:modelValue","update:title","update:count"]"#.to_owned())
+        );
+        assert!(bindings_helper.vue_imports.contains(VueImports::UseModel));
+
+        // Local variables are refs, other models are accessed as props
+        assert_eq!(
+            bindings_helper.setup_bindings,
+            vec![
+                SetupBinding(fervid_atom!("model"), BindingTypes::SetupRef),
+                SetupBinding(fervid_atom!("title"), BindingTypes::SetupRef),
+                SetupBinding(fervid_atom!("modelValue"), BindingTypes::Props),
+                SetupBinding(fervid_atom!("count"), BindingTypes::Props),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_disallows_duplicate_define_model() {
+        let mut bindings_helper = BindingsHelper::default();
+        let (_, errors) = transform_setup(
+            r"
+            const title = defineModel('title')
+            const otherTitle = defineModel('title')
+            defineModel()
+            defineModel({ required: true })
+            ",
+            SfcScriptLang::Es,
+            &mut bindings_helper,
+        );
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| matches!(
+            error,
+            TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::DuplicateDefineModel,
+                ..
+            })
+        )));
+    }
+
+    #[test]
+    fn it_merges_define_options() {
+        let check = |input: &str| {
+            let (result, errors) = transform_setup(
+                input,
+                SfcScriptLang::Typescript,
+                &mut BindingsHelper::default(),
+            );
+
+            let fields = result.sfc_object_helper.untyped_fields;
+            let options = (!fields.is_empty()).then(|| {
+                to_str(&ObjectLit {
+                    span: DUMMY_SP,
+                    props: fields,
+                })
+            });
+            (options, errors)
+        };
+
+        macro_rules! assert_error {
+            ($errors: expr, $kind: pat) => {
+                assert!(matches!(
+                    $errors.as_slice(),
+                    [TransformError::ScriptError(ScriptError { kind: $kind, .. })]
+                ));
+            };
+        }
+
+        let (options, errors) =
+            check("defineOptions({ name: 'MyComp', inheritAttrs: false, components: { Foo } })");
+        assert!(errors.is_empty());
+        assert_eq!(
+            options,
+            Some(r#"{name:"MyComp",inheritAttrs:false,components:{Foo}}"#.to_owned())
+        );
+
+        // Options which have their own macros
+        for option in ["props: ['foo']", "emits: ['change']", "expose: []", "slots"] {
+            let (options, errors) = check(&format!("defineOptions({{ {} }})", option));
+            assert_eq!(options, None);
+            assert_error!(errors, ScriptErrorKind::DefineOptionsMacroOption);
+        }
+
+        // Duplicate call
+        let (options, errors) = check(
+            "defineOptions({ name: 'MyComp' })
+            defineOptions({ inheritAttrs: false })",
+        );
+        assert_eq!(options, Some(r#"{name:"MyComp"}"#.to_owned()));
+        assert_error!(errors, ScriptErrorKind::DuplicateDefineOptions);
+
+        // Type arguments
+        let (_, errors) = check("defineOptions<{ name: string }>({ name: 'MyComp' })");
+        assert_error!(errors, ScriptErrorKind::DefineOptionsTypeArguments);
+    }
+
+    #[test]
+    fn it_strips_define_slots() {
+        let check = |input: &str| {
+            let mut bindings_helper = BindingsHelper {
+                template_generation_mode: TemplateGenerationMode::Inline,
+                ..Default::default()
+            };
+            let (result, errors) =
+                transform_setup(input, SfcScriptLang::Typescript, &mut bindings_helper);
+            (setup_fn_to_str(result), bindings_helper, errors)
+        };
+
+        // Slots are only declared for the type checking
+        let (setup, bindings_helper, errors) = check(
+            "defineSlots<{ default(props: { item: string }): any }>()
+            const msg = 'hello'",
+        );
+        assert!(errors.is_empty());
+        assert_eq!(setup, r#"function(){const msg="hello"}"#);
+        assert!(!bindings_helper.vue_imports.contains(VueImports::UseSlots));
+
+        // Slots are used in `<script setup>`
+        let (setup, bindings_helper, errors) =
+            check("const slots = defineSlots<{ default(props: { item: string }): any }>()");
+        assert!(errors.is_empty());
+        assert_eq!(setup, "function(){const slots=_useSlots()}");
+        assert!(bindings_helper.vue_imports.contains(VueImports::UseSlots));
+
+        // Runtime arguments
+        let (_, _, errors) = check("defineSlots({ default: null })");
+        assert!(matches!(
+            errors.as_slice(),
+            [TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::DefineSlotsArguments,
+                ..
+            })]
+        ));
+
+        // Duplicate call
+        let (_, _, errors) = check(
+            "defineSlots<{ default(): any }>()
+            defineSlots<{ header(): any }>()",
+        );
+        assert!(matches!(
+            errors.as_slice(),
+            [TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::DuplicateDefineSlots,
+                ..
+            })]
+        ));
+    }
+
+    #[test]
+    fn it_exposes_setup_bindings_explicitly() {
+        let setup_of = |input: &str, template_generation_mode| {
+            let mut bindings_helper = BindingsHelper {
+                template_generation_mode,
+                ..Default::default()
+            };
+            let (result, errors) = transform_setup(input, SfcScriptLang::Es, &mut bindings_helper);
+            assert!(errors.is_empty());
+            setup_fn_to_str(result)
+        };
+
+        let with_expose = "
+            const count = ref(0)
+            function increment() { count.value++ }
+            defineExpose({ count, increment })
+            ";
+
+        // Only the exposed values are accessible through a template ref
+        let setup = setup_of(with_expose, TemplateGenerationMode::RenderFn);
+        assert!(setup.starts_with("function(__props,{expose:__expose}){const count"));
+        assert!(setup.contains("__expose({count,increment})"));
+        assert_eq!(setup.matches("__expose(").count(), 1);
+
+        // Without `defineExpose` nothing is exposed
+        let without_expose = "const count = ref(0)";
+        let setup = setup_of(without_expose, TemplateGenerationMode::RenderFn);
+        assert!(setup.starts_with("function(__props,{expose:__expose}){__expose();"));
+
+        // Inline template is returned from `setup`, the bindings are not exposed anyway
+        let setup = setup_of(without_expose, TemplateGenerationMode::Inline);
+        assert!(setup.starts_with("function(){const count"));
+
+        let setup = setup_of(with_expose, TemplateGenerationMode::Inline);
+        assert!(setup.contains("__expose({count,increment})"));
+    }
+
+    fn props_of(input: &str, is_prod: bool) -> (Option<String>, BindingsHelper) {
+        let mut bindings_helper = BindingsHelper {
+            is_prod,
+            ..Default::default()
+        };
+        let (result, errors) =
+            transform_setup(input, SfcScriptLang::Typescript, &mut bindings_helper);
+        assert!(errors.is_empty());
+
+        let props = result.sfc_object_helper.props.map(|props| to_str(&props));
+        (props, bindings_helper)
+    }
+
+    #[test]
+    fn it_generates_runtime_props_from_type() {
+        let input = r"
+            defineProps<{
+                count: number
+                label?: string
+                id: string | number
+                disabled?: boolean
+                onClick: () => void
+                items: readonly string[]
+                options: { foo: number }
+                date?: Date
+                size?: 'sm' | 'lg'
+                custom: Foo
+                flag: boolean | Foo
+            }>()
+            ";
+
+        let (props, bindings_helper) = props_of(input, false);
+        assert_eq!(
+            props,
+            Some(
+                concat!(
+                    r#"{"count":{type:Number,required:true},"#,
+                    r#""label":{type:String,required:false},"#,
+                    r#""id":{type:[String,Number],required:true},"#,
+                    r#""disabled":{type:Boolean,required:false},"#,
+                    r#""onClick":{type:Function,required:true},"#,
+                    r#""items":{type:Array,required:true},"#,
+                    r#""options":{type:Object,required:true},"#,
+                    r#""date":{type:Date,required:false},"#,
+                    r#""size":{type:String,required:false},"#,
+                    r#""custom":{type:null,required:true},"#,
+                    r#""flag":{type:Boolean,required:true,skipCheck:true}}"#
+                )
+                .to_owned()
+            )
+        );
+
+        // Props are available in the template
+        assert_eq!(bindings_helper.setup_bindings.len(), 11);
+        assert!(bindings_helper
+            .setup_bindings
+            .iter()
+            .all(|SetupBinding(_, binding_type)| *binding_type == BindingTypes::Props));
+        assert_eq!(
+            bindings_helper.setup_bindings[1],
+            SetupBinding(fervid_atom!("label"), BindingTypes::Props)
+        );
+
+        // Only the known types are used for checks
+        let prop_types = &bindings_helper.prop_types;
+        let get = |name: &str| prop_types.get(&FervidAtom::from(name)).map(|t| t.to_vec());
+        assert_eq!(get("count"), Some(vec![PropType::Number]));
+        assert_eq!(get("id"), Some(vec![PropType::String, PropType::Number]));
+        assert_eq!(get("date"), Some(vec![PropType::Other]));
+        assert_eq!(get("custom"), None);
+        assert_eq!(get("flag"), None);
+    }
+
+    #[test]
+    fn it_generates_production_props_from_type() {
+        let (props, _) = props_of(
+            r"
+            const props = defineProps<{
+                count?: number
+                disabled: boolean
+                format: (value: number) => string
+            }>()
+            ",
+            true,
+        );
+
+        // Only the types affecting casting are kept
+        assert_eq!(
+            props,
+            Some(r#"{"count":{},"disabled":{type:Boolean},"format":{type:Function}}"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn it_merges_static_with_defaults() {
+        let input = r"
+            withDefaults(defineProps<{
+                count?: number
+                options?: { size: number }
+                items?: string[]
+                format?: (value: number) => string
+                onClose?: () => void
+                label?: string
+            }>(), {
+                count: 0,
+                options: () => ({ size: 1, nested: { deep: true } }),
+                items: () => [],
+                format: (value) => value.toFixed(2)
+            })
+            ";
+
+        let (props, bindings_helper) = props_of(input, false);
+        assert_eq!(
+            props,
+            Some(
+                concat!(
+                    r#"{"count":{type:Number,required:false,default:0},"#,
+                    r#""options":{type:Object,required:false,default:()=>({size:1,nested:{deep:true}})},"#,
+                    r#""items":{type:Array,required:false,default:()=>[]},"#,
+                    r#""format":{type:Function,required:false,default:value=>value.toFixed(2)},"#,
+                    r#""onClose":{type:Function,required:false},"#,
+                    r#""label":{type:String,required:false}}"#
+                )
+                .to_owned()
+            )
+        );
+        assert_eq!(bindings_helper.setup_bindings.len(), 6);
+
+        // Function type is only needed in production when it has a default
+        let (props, _) = props_of(input, true);
+        assert_eq!(
+            props,
+            Some(
+                concat!(
+                    r#"{"count":{default:0},"#,
+                    r#""options":{default:()=>({size:1,nested:{deep:true}})},"#,
+                    r#""items":{default:()=>[]},"#,
+                    r#""format":{type:Function,default:value=>value.toFixed(2)},"#,
+                    r#""onClose":{},"#,
+                    r#""label":{}}"#
+                )
+                .to_owned()
+            )
+        );
+
+        // Method and shorthand defaults
+        let (props, _) = props_of(
+            r"
+            const props = withDefaults(defineProps<{ items?: string[], label?: string }>(), {
+                items() { return [] },
+                label
+            })
+            ",
+            true,
+        );
+        let props = props.expect("props must be generated");
+        assert!(props.starts_with(r#"{"items":{default(){"#));
+        assert!(props.ends_with(r#""label":{default:label}}"#));
+    }
+
+    #[test]
+    fn it_merges_runtime_with_defaults() {
+        let (props, bindings_helper) = props_of(
+            r"
+            const props = withDefaults(defineProps<{ count?: number }>(), { ...defaults })
+            ",
+            false,
+        );
+
+        assert_eq!(
+            props,
+            Some(
+                r#"_mergeDefaults({"count":{type:Number,required:false}},{...defaults})"#
+                    .to_owned()
+            )
+        );
+        assert!(bindings_helper
+            .vue_imports
+            .contains(VueImports::MergeDefaults));
+        assert_eq!(bindings_helper.props_binding, Some(fervid_atom!("props")));
+    }
+
+    #[test]
+    fn it_disallows_with_defaults_without_type_only_props() {
+        let mut bindings_helper = BindingsHelper::default();
+        let (_, errors) = transform_setup(
+            "withDefaults(defineProps({ count: Number }), { count: 0 })",
+            SfcScriptLang::Typescript,
+            &mut bindings_helper,
+        );
+
+        assert!(matches!(
+            errors.first(),
+            Some(TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::WithDefaultsWithoutTypeOnlyProps,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn it_preserves_props_defaults_and_validators() {
+        let (props, bindings_helper) = props_of(
+            r"
+            defineProps({
+                count: { type: Number, default: 0 },
+                label: { type: String, required: false, validator: (value) => value.length > 0 }
+            })
+            ",
+            true,
+        );
+
+        assert_eq!(
+            props,
+            Some(
+                "{count:{type:Number,default:0},label:{type:String,required:false,validator:value=>value.length>0}}"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            bindings_helper.setup_bindings,
+            vec![
+                SetupBinding(fervid_atom!("count"), BindingTypes::Props),
+                SetupBinding(fervid_atom!("label"), BindingTypes::Props),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_disallows_props_with_both_type_and_runtime_arguments() {
+        let mut bindings_helper = BindingsHelper::default();
+        let (_, errors) = transform_setup(
+            "defineProps<{ count: number }>({ count: Number })",
+            SfcScriptLang::Typescript,
+            &mut bindings_helper,
+        );
+
+        assert!(matches!(
+            errors.first(),
+            Some(TransformError::ScriptError(ScriptError {
+                kind: ScriptErrorKind::DefinePropsTypeAndNonTypeArguments,
+                ..
+            }))
+        ));
+    }
+
     // https://github.com/vuejs/core/blob/140a7681cc3bba22f55d97fd85a5eafe97a1230f/packages/compiler-sfc/__tests__/compileScript.spec.ts#L871-L890
     #[test]
     fn non_type_named_exports() {
         macro_rules! check {
             ($code: literal, $should_error: literal) => {
-                let parsed = parse_typescript_module($code, 0, Default::default())
-                    .expect("analyze_js expects the input to be parseable")
-                    .0;
-
-                let script_setup = SfcScriptBlock {
-                    content: Box::new(parsed),
-                    lang: fervid_core::SfcScriptLang::Typescript,
-                    is_setup: true,
-                    span: DUMMY_SP,
-                };
-
-                let mut bindings_helper = BindingsHelper::default();
-                let mut errors = Vec::new();
-                transform_and_record_script_setup(script_setup, &mut bindings_helper, &mut errors);
+                let (_, errors) = transform_setup(
+                    $code,
+                    SfcScriptLang::Typescript,
+                    &mut BindingsHelper::default(),
+                );
 
                 if $should_error {
                     let error = errors.first().expect("Should have error");
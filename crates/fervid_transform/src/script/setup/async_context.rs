@@ -0,0 +1,298 @@
+use fervid_core::{FervidAtom, VueImports, VueImportsSet};
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            ArrayPat, ArrowExpr, AssignExpr, AssignOp, AssignTarget, AssignTargetPat, AwaitExpr,
+            BindingIdent, BlockStmtOrExpr, CallExpr, Callee, Class, Constructor, Decl, Expr,
+            ExprOrSpread, Function, GetterProp, Ident, Invalid, ParenExpr, Pat, SeqExpr,
+            SetterProp, SimpleAssignTarget, Stmt, VarDecl, VarDeclKind, VarDeclarator,
+        },
+        visit::{VisitMut, VisitMutWith},
+    },
+};
+
+use crate::atoms::{RESTORE_HELPER, TEMP_HELPER, WITH_ASYNC_CONTEXT_HELPER};
+
+use super::await_detection::detect_await_expr;
+
+/// Wraps the top-level `await` expressions of `<script setup>` in `_withAsyncContext`,
+/// so that the current instance is restored after the `await`.
+/// Then declares the `__temp` and `__restore` variables used by the wrappers.
+///
+/// ## Example
+/// In:
+/// ```js
+/// await foo()
+/// const bar = await baz()
+/// ```
+///
+/// Out:
+/// ```js
+/// let __temp, __restore
+/// ;([__temp, __restore] = _withAsyncContext(() => foo())), await __temp, __restore()
+/// const bar = (([__temp, __restore] = _withAsyncContext(() => baz())), __temp = await __temp, __restore(), __temp)
+/// ```
+///
+/// Awaits inside the nested functions and classes are left as is.
+pub fn transform_async_context(stmts: &mut Vec<Stmt>, vue_imports: &mut VueImportsSet) {
+    let mut transformer = AsyncContextTransformer { found: false };
+    for stmt in stmts.iter_mut() {
+        stmt.visit_mut_with(&mut transformer);
+    }
+
+    if !transformer.found {
+        return;
+    }
+
+    *vue_imports |= VueImports::WithAsyncContext;
+
+    // `let __temp, __restore`
+    let declarator = |sym: &FervidAtom| VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(BindingIdent {
+            id: ident(sym),
+            type_ann: None,
+        }),
+        init: None,
+        definite: false,
+    };
+    stmts.insert(
+        0,
+        Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Let,
+            declare: false,
+            decls: vec![declarator(&TEMP_HELPER), declarator(&RESTORE_HELPER)],
+        }))),
+    );
+}
+
+struct AsyncContextTransformer {
+    found: bool,
+}
+
+impl VisitMut for AsyncContextTransformer {
+    fn visit_mut_stmt(&mut self, n: &mut Stmt) {
+        // The value of `await` is not used in the statement form
+        if let Stmt::Expr(expr_stmt) = n {
+            if let Expr::Await(await_expr) = expr_stmt.expr.as_mut() {
+                await_expr.arg.visit_mut_with(self);
+                let span = await_expr.span;
+                let arg = take_arg(await_expr);
+
+                expr_stmt.expr = Box::new(Expr::Seq(SeqExpr {
+                    span,
+                    exprs: vec![with_async_context(arg), await_temp(), restore_call()],
+                }));
+                self.found = true;
+                return;
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        n.visit_mut_children_with(self);
+
+        let Expr::Await(await_expr) = n else {
+            return;
+        };
+
+        let span = await_expr.span;
+        let arg = take_arg(await_expr);
+
+        // `__temp = await __temp`
+        let assign_temp = Box::new(Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: AssignOp::Assign,
+            left: AssignTarget::Simple(SimpleAssignTarget::Ident(BindingIdent {
+                id: ident(&TEMP_HELPER),
+                type_ann: None,
+            })),
+            right: await_temp(),
+        }));
+
+        *n = Expr::Paren(ParenExpr {
+            span,
+            expr: Box::new(Expr::Seq(SeqExpr {
+                span,
+                exprs: vec![
+                    with_async_context(arg),
+                    assign_temp,
+                    restore_call(),
+                    Box::new(Expr::Ident(ident(&TEMP_HELPER))),
+                ],
+            })),
+        });
+        self.found = true;
+    }
+
+    // Nested functions and classes have their own context
+    fn visit_mut_arrow_expr(&mut self, _n: &mut ArrowExpr) {}
+
+    fn visit_mut_class(&mut self, _n: &mut Class) {}
+
+    fn visit_mut_constructor(&mut self, _n: &mut Constructor) {}
+
+    fn visit_mut_function(&mut self, _n: &mut Function) {}
+
+    fn visit_mut_getter_prop(&mut self, _n: &mut GetterProp) {}
+
+    fn visit_mut_setter_prop(&mut self, _n: &mut SetterProp) {}
+}
+
+/// `([__temp, __restore] = _withAsyncContext(() => arg))`.
+/// The arrow is `async` when the argument itself awaits.
+fn with_async_context(arg: Box<Expr>) -> Box<Expr> {
+    let is_async = detect_await_expr(&arg);
+
+    let binding = |sym: &FervidAtom| {
+        Some(Pat::Ident(BindingIdent {
+            id: ident(sym),
+            type_ann: None,
+        }))
+    };
+
+    Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: AssignOp::Assign,
+            left: AssignTarget::Pat(AssignTargetPat::Array(ArrayPat {
+                span: DUMMY_SP,
+                elems: vec![binding(&TEMP_HELPER), binding(&RESTORE_HELPER)],
+                optional: false,
+                type_ann: None,
+            })),
+            right: Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: Callee::Expr(Box::new(Expr::Ident(ident(&WITH_ASYNC_CONTEXT_HELPER)))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Arrow(ArrowExpr {
+                        span: DUMMY_SP,
+                        params: vec![],
+                        body: Box::new(BlockStmtOrExpr::Expr(arg)),
+                        is_async,
+                        is_generator: false,
+                        type_params: None,
+                        return_type: None,
+                    })),
+                }],
+                type_args: None,
+            })),
+        })),
+    }))
+}
+
+fn take_arg(await_expr: &mut AwaitExpr) -> Box<Expr> {
+    std::mem::replace(
+        &mut await_expr.arg,
+        Box::new(Expr::Invalid(Invalid { span: DUMMY_SP })),
+    )
+}
+
+/// `await __temp`
+fn await_temp() -> Box<Expr> {
+    Box::new(Expr::Await(AwaitExpr {
+        span: DUMMY_SP,
+        arg: Box::new(Expr::Ident(ident(&TEMP_HELPER))),
+    }))
+}
+
+/// `__restore()`
+fn restore_call() -> Box<Expr> {
+    Box::new(Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: Callee::Expr(Box::new(Expr::Ident(ident(&RESTORE_HELPER)))),
+        args: vec![],
+        type_args: None,
+    }))
+}
+
+fn ident(sym: &FervidAtom) -> Ident {
+    Ident {
+        span: DUMMY_SP,
+        sym: sym.to_owned(),
+        optional: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::ecma::ast::{Module, ModuleItem};
+
+    use crate::test_utils::{parser::parse_typescript_module, to_str};
+
+    use super::*;
+
+    fn transform(input: &str) -> (String, VueImportsSet) {
+        let module = parse_typescript_module(input, 0, Default::default())
+            .expect("Should be parseable")
+            .0;
+
+        let mut stmts: Vec<Stmt> = module
+            .body
+            .into_iter()
+            .filter_map(|module_item| match module_item {
+                ModuleItem::Stmt(stmt) => Some(stmt),
+                ModuleItem::ModuleDecl(_) => None,
+            })
+            .collect();
+        let mut vue_imports = VueImportsSet::default();
+        transform_async_context(&mut stmts, &mut vue_imports);
+
+        let module = Module {
+            span: DUMMY_SP,
+            body: stmts.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: None,
+        };
+        (to_str(&module), vue_imports)
+    }
+
+    #[test]
+    fn it_wraps_expression_statement() {
+        let (code, vue_imports) = transform("await foo()");
+        assert!(code.starts_with("let __temp,__restore;"));
+        assert!(code.contains(
+            "([__temp,__restore]=_withAsyncContext(()=>foo())),await __temp,__restore()"
+        ));
+        assert!(vue_imports.contains(VueImports::WithAsyncContext));
+    }
+
+    #[test]
+    fn it_wraps_used_value() {
+        let (code, _) = transform("const a = 1 + (await foo)");
+        assert!(code.contains(
+            "(([__temp,__restore]=_withAsyncContext(()=>foo)),__temp=await __temp,__restore(),__temp)"
+        ));
+    }
+
+    #[test]
+    fn it_wraps_nested_await() {
+        let (code, _) = transform("await (await foo)");
+        assert!(code.contains("_withAsyncContext(async()=>"));
+        assert!(code.contains("_withAsyncContext(()=>foo)"));
+    }
+
+    #[test]
+    fn it_wraps_await_in_blocks() {
+        let (code, _) = transform("if (ok) { await foo }");
+        assert!(code.contains("_withAsyncContext(()=>foo)"));
+    }
+
+    #[test]
+    fn it_ignores_nested_functions() {
+        let input = "
+            async function a() { await b }
+            const c = async () => { await d }
+            const e = { async f() { await g } }
+            class H { async i() { await j } }";
+
+        let (code, vue_imports) = transform(input);
+        assert!(!code.contains("__temp"));
+        assert!(!vue_imports.contains(VueImports::WithAsyncContext));
+    }
+}
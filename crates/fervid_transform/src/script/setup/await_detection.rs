@@ -10,6 +10,13 @@ pub fn detect_await_module_item(module_item: &ModuleItem) -> bool {
     await_detector.found
 }
 
+/// Same as [`detect_await_module_item`], but for a single expression
+pub fn detect_await_expr(expr: &Expr) -> bool {
+    let mut await_detector = AwaitDetector::default();
+    expr.visit_with(&mut await_detector);
+    await_detector.found
+}
+
 #[derive(Default)]
 struct AwaitDetector {
     found: bool,
@@ -1,31 +1,45 @@
 use fervid_core::{fervid_atom, BindingTypes, FervidAtom, VueImports};
-use fxhash::FxHashSet;
+use fxhash::FxHashMap;
 use itertools::{Either, Itertools};
 use swc_core::{
-    common::{Spanned, DUMMY_SP},
+    common::{Span, Spanned, DUMMY_SP},
     ecma::ast::{
-        ArrayLit, Bool, CallExpr, Callee, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, ObjectLit,
-        Prop, PropName, PropOrSpread, Str, TsFnOrConstructorType, TsFnParam, TsLit, TsType,
+        ArrayLit, Bool, CallExpr, Callee, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, Null,
+        ObjectLit, Prop, PropName, PropOrSpread, Str, TsEntityName, TsFnOrConstructorType,
+        TsFnParam, TsKeywordTypeKind, TsLit, TsType, TsTypeElement, TsUnionOrIntersectionType,
     },
 };
 
 use crate::{
     atoms::{
         DEFINE_EMITS, DEFINE_EXPOSE, DEFINE_MODEL, DEFINE_OPTIONS, DEFINE_PROPS, DEFINE_SLOTS,
-        EMIT_HELPER, EXPOSE_HELPER, MERGE_MODELS_HELPER, MODEL_VALUE, PROPS_HELPER,
-        USE_MODEL_HELPER,
+        EMITS, EMIT_HELPER, EXPOSE, EXPOSE_HELPER, MERGE_DEFAULTS_HELPER, MERGE_MODELS_HELPER,
+        MODEL_VALUE, PROPS, PROPS_HELPER, SLOTS, USE_MODEL_HELPER, WITH_DEFAULTS,
     },
     error::{ScriptError, ScriptErrorKind, TransformError},
     script::{
         resolve_type::{
             resolve_type_elements, resolve_union_type, ResolvedElements, TypeResolveContext,
         },
-        utils::{collect_obj_fields, collect_string_arr},
+        utils::{
+            collect_obj_fields, collect_obj_prop_types, collect_obj_propname, collect_string_arr,
+            to_prop_type,
+        },
     },
     structs::{SfcDefineModel, SfcExportedObjectHelper},
     BindingsHelper, SetupBinding,
 };
 
+/// A prop declared using the type-only `defineProps`, e.g. `count?: number`
+struct RuntimeProp {
+    key: FervidAtom,
+    /// Constructor names, e.g. `String`, or `null` when the type is unknown
+    types: Vec<&'static str>,
+    required: bool,
+    /// Unknown type alongside a `Boolean` or a `Function`, which need to be kept for casting
+    skip_check: bool,
+}
+
 pub enum TransformMacroResult {
     NotAMacro,
     ValidMacro(Option<Box<Expr>>),
@@ -85,40 +99,33 @@ pub fn transform_script_setup_macro_expr(
     let sym = &callee_ident.sym;
     let span = call_expr.span;
     if DEFINE_PROPS.eq(sym) {
-        if let Some(arg0) = &call_expr.args.get(0) {
-            // TODO Check if this was re-assigned before
-            sfc_object_helper.props = Some(arg0.expr.to_owned());
-
-            // Add props as bindings
-            let mut raw_bindings = Vec::new();
-            match arg0.expr.as_ref() {
-                Expr::Array(props_arr) => {
-                    collect_string_arr(props_arr, &mut raw_bindings);
-                }
-                Expr::Object(props_obj) => {
-                    collect_obj_fields(props_obj, &mut raw_bindings);
-                }
-                _ => {}
-            }
-            bindings_helper.setup_bindings.extend(
-                raw_bindings
-                    .into_iter()
-                    .map(|raw| SetupBinding(raw, BindingTypes::Props)),
-            );
-        }
-
-        // Return `__props` when in var mode
-        if is_var_decl {
-            sfc_object_helper.is_setup_props_referenced = true;
-
-            valid_macro!(Some(Box::new(Expr::Ident(Ident {
+        transform_define_props(
+            call_expr,
+            None,
+            bindings_helper,
+            sfc_object_helper,
+            is_var_decl,
+        )
+    } else if WITH_DEFAULTS.eq(sym) {
+        // `withDefaults(defineProps<Props>(), { count: 0 })`
+        let define_props_call = call_expr.args.first().and_then(|arg| match *arg.expr {
+            Expr::Call(ref inner_call) if is_type_only_define_props(inner_call) => Some(inner_call),
+            _ => None,
+        });
+        let Some(define_props_call) = define_props_call else {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
                 span,
-                sym: PROPS_HELPER.to_owned(),
-                optional: false,
-            }))))
-        } else {
-            valid_macro!(None)
-        }
+                kind: ScriptErrorKind::WithDefaultsWithoutTypeOnlyProps,
+            }));
+        };
+
+        transform_define_props(
+            define_props_call,
+            call_expr.args.get(1).map(|arg| arg.expr.as_ref()),
+            bindings_helper,
+            sfc_object_helper,
+            is_var_decl,
+        )
     } else if DEFINE_EMITS.eq(sym) {
         // Validation: duplicate call
         if sfc_object_helper.emits.is_some() {
@@ -137,6 +144,18 @@ pub fn transform_script_setup_macro_expr(
         }
 
         if let Some(arg0) = &call_expr.args.get(0) {
+            match arg0.expr.as_ref() {
+                Expr::Array(emits_arr) => {
+                    collect_string_arr(emits_arr, &mut bindings_helper.emits);
+                }
+                Expr::Object(emits_obj) => {
+                    collect_obj_fields(emits_obj, &mut bindings_helper.emits);
+                }
+                _ => {}
+            }
+
+            // Runtime declaration is kept as-is.
+            // This way validator functions in the object form end up in the `emits` option.
             sfc_object_helper.emits = Some(arg0.expr.to_owned())
         } else if let Some(ref type_args) = call_expr.type_args {
             let Some(ts_type) = type_args.params.first() else {
@@ -151,6 +170,8 @@ pub fn transform_script_setup_macro_expr(
                 Err(e) => return TransformMacroResult::Error(TransformError::ScriptError(e)),
             };
 
+            bindings_helper.emits.extend(runtime_emits.iter().cloned());
+
             sfc_object_helper.emits = Some(Box::new(Expr::Array(ArrayLit {
                 span: DUMMY_SP,
                 elems: runtime_emits
@@ -203,11 +224,21 @@ pub fn transform_script_setup_macro_expr(
     } else if DEFINE_MODEL.eq(sym) {
         let define_model = read_define_model(&call_expr.args);
 
+        // Validation: duplicate model name
+        if sfc_object_helper
+            .models
+            .iter()
+            .any(|model| model.name == define_model.name)
+        {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span,
+                kind: ScriptErrorKind::DuplicateDefineModel,
+            }));
+        }
+
         // Add to imports
         bindings_helper.vue_imports |= VueImports::UseModel;
 
-        // TODO Add model identifier as a binding (when `is_var_decl == true`)
-
         let use_model_ident = Ident {
             span,
             sym: USE_MODEL_HELPER.to_owned(),
@@ -266,9 +297,27 @@ pub fn transform_script_setup_macro_expr(
             type_args: None,
         }))))
     } else if DEFINE_SLOTS.eq(sym) {
-        // Without a variable to bind to this macro means nothing
+        // Validation: duplicate call
+        if sfc_object_helper.has_define_slots {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span,
+                kind: ScriptErrorKind::DuplicateDefineSlots,
+            }));
+        }
+
+        // Validation: slots are only declared using types
+        if !call_expr.args.is_empty() {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span,
+                kind: ScriptErrorKind::DefineSlotsArguments,
+            }));
+        }
+
+        sfc_object_helper.has_define_slots = true;
+
+        // Without a variable to bind to this macro means nothing and is removed
         if !is_var_decl {
-            bail!();
+            return valid_macro!(None);
         }
 
         // Add to imports and get the identifier
@@ -295,6 +344,24 @@ pub fn transform_script_setup_macro_expr(
             bail!();
         }
 
+        // Validation: duplicate call
+        if sfc_object_helper.has_define_options {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span,
+                kind: ScriptErrorKind::DuplicateDefineOptions,
+            }));
+        }
+
+        // Validation: types
+        if let Some(ref type_args) = call_expr.type_args {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span: type_args.span,
+                kind: ScriptErrorKind::DefineOptionsTypeArguments,
+            }));
+        }
+
+        sfc_object_helper.has_define_options = true;
+
         // `defineOptions()` without arguments
         let Some(ExprOrSpread { spread: None, expr }) = call_expr.args.get(0) else {
             return valid_macro!(None);
@@ -311,6 +378,14 @@ pub fn transform_script_setup_macro_expr(
             return valid_macro!(None);
         };
 
+        // Validation: options which have their own macros
+        if let Some(span) = find_macro_option(options_object) {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span,
+                kind: ScriptErrorKind::DefineOptionsMacroOption,
+            }));
+        }
+
         // Copy the fields
         sfc_object_helper
             .untyped_fields
@@ -322,16 +397,262 @@ pub fn transform_script_setup_macro_expr(
     }
 }
 
+/// Processes `defineProps`, optionally with the defaults from `withDefaults`
+fn transform_define_props(
+    call_expr: &CallExpr,
+    defaults: Option<&Expr>,
+    bindings_helper: &mut BindingsHelper,
+    sfc_object_helper: &mut SfcExportedObjectHelper,
+    is_var_decl: bool,
+) -> TransformMacroResult {
+    // Validation: both runtime and types
+    if !call_expr.args.is_empty() && call_expr.type_args.is_some() {
+        return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+            span: call_expr.span,
+            kind: ScriptErrorKind::DefinePropsTypeAndNonTypeArguments,
+        }));
+    }
+
+    if let Some(arg0) = &call_expr.args.get(0) {
+        // TODO Check if this was re-assigned before
+        sfc_object_helper.props = Some(arg0.expr.to_owned());
+
+        // Add props as bindings
+        let mut raw_bindings = Vec::new();
+        match arg0.expr.as_ref() {
+            Expr::Array(props_arr) => {
+                collect_string_arr(props_arr, &mut raw_bindings);
+            }
+            Expr::Object(props_obj) => {
+                collect_obj_fields(props_obj, &mut raw_bindings);
+                collect_obj_prop_types(props_obj, &mut bindings_helper.prop_types);
+            }
+            _ => {}
+        }
+        bindings_helper.setup_bindings.extend(
+            raw_bindings
+                .into_iter()
+                .map(|raw| SetupBinding(raw, BindingTypes::Props)),
+        );
+    } else if let Some(ref type_args) = call_expr.type_args {
+        let Some(ts_type) = type_args.params.first() else {
+            return TransformMacroResult::Error(TransformError::ScriptError(ScriptError {
+                span: type_args.span,
+                kind: ScriptErrorKind::DefinePropsMalformed,
+            }));
+        };
+
+        let runtime_props = match extract_runtime_props(&ts_type) {
+            Ok(v) => v,
+            Err(e) => return TransformMacroResult::Error(TransformError::ScriptError(e)),
+        };
+
+        let mut props_obj = ObjectLit {
+            span: DUMMY_SP,
+            props: Vec::with_capacity(runtime_props.len()),
+        };
+
+        // Static defaults are put directly into the props declaration
+        let static_defaults = match defaults {
+            Some(Expr::Object(defaults_obj)) if is_static_defaults(defaults_obj) => {
+                Some(defaults_obj)
+            }
+            _ => None,
+        };
+
+        for runtime_prop in runtime_props {
+            // Only fully known types are used for checking the template
+            let is_known =
+                !runtime_prop.skip_check && runtime_prop.types.iter().all(|t| *t != "null");
+            if is_known {
+                bindings_helper.prop_types.insert(
+                    runtime_prop.key.to_owned(),
+                    runtime_prop.types.iter().map(|t| to_prop_type(t)).collect(),
+                );
+            }
+
+            bindings_helper.setup_bindings.push(SetupBinding(
+                runtime_prop.key.to_owned(),
+                BindingTypes::Props,
+            ));
+
+            let default = static_defaults.and_then(|obj| find_default(obj, &runtime_prop.key));
+            props_obj.props.push(runtime_prop.into_prop(
+                bindings_helper.is_prod,
+                static_defaults.is_some(),
+                default,
+            ));
+        }
+
+        let mut props_expr = Box::new(Expr::Object(props_obj));
+
+        // Other defaults are only known at runtime, e.g. `withDefaults(defineProps<Props>(), defaults)`
+        if let (Some(defaults), None) = (defaults, static_defaults) {
+            bindings_helper.vue_imports |= VueImports::MergeDefaults;
+
+            props_expr = Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                    span: DUMMY_SP,
+                    sym: MERGE_DEFAULTS_HELPER.to_owned(),
+                    optional: false,
+                }))),
+                args: vec![
+                    ExprOrSpread {
+                        spread: None,
+                        expr: props_expr,
+                    },
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(defaults.to_owned()),
+                    },
+                ],
+                type_args: None,
+            }));
+        }
+
+        sfc_object_helper.props = Some(props_expr);
+    }
+
+    // Return `__props` when in var mode
+    if is_var_decl {
+        sfc_object_helper.is_setup_props_referenced = true;
+
+        TransformMacroResult::ValidMacro(Some(Box::new(Expr::Ident(Ident {
+            span: call_expr.span,
+            sym: PROPS_HELPER.to_owned(),
+            optional: false,
+        }))))
+    } else {
+        TransformMacroResult::ValidMacro(None)
+    }
+}
+
+/// `defineProps<Props>()` without runtime arguments
+fn is_type_only_define_props(call_expr: &CallExpr) -> bool {
+    let Callee::Expr(ref callee_expr) = call_expr.callee else {
+        return false;
+    };
+
+    matches!(**callee_expr, Expr::Ident(ref ident) if DEFINE_PROPS.eq(&ident.sym))
+        && call_expr.type_args.is_some()
+        && call_expr.args.is_empty()
+}
+
+/// Defaults object without spreads and computed keys,
+/// so that each default can be matched to its prop during compilation
+fn is_static_defaults(defaults: &ObjectLit) -> bool {
+    defaults.props.iter().all(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return false;
+        };
+
+        let prop_name = match **prop {
+            Prop::KeyValue(ref kv) => &kv.key,
+            Prop::Method(ref method) => &method.key,
+            Prop::Getter(ref getter) => &getter.key,
+            Prop::Setter(ref setter) => &setter.key,
+            Prop::Shorthand(_) | Prop::Assign(_) => return true,
+        };
+
+        !matches!(prop_name, PropName::Computed(_))
+    })
+}
+
+/// Finds the default of a prop and turns it into a `default` field,
+/// e.g. `count: 0` -> `default: 0` or `items() { return [] }` -> `default() { return [] }`
+fn find_default(defaults: &ObjectLit, key: &FervidAtom) -> Option<Prop> {
+    let default_key = || {
+        PropName::Ident(Ident {
+            span: DUMMY_SP,
+            sym: fervid_atom!("default"),
+            optional: false,
+        })
+    };
+
+    defaults.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+
+        // `{ count }` -> `default: count`
+        if let Prop::Shorthand(ref ident) = **prop {
+            return (ident.sym == *key).then(|| {
+                Prop::KeyValue(KeyValueProp {
+                    key: default_key(),
+                    value: Box::new(Expr::Ident(ident.to_owned())),
+                })
+            });
+        }
+
+        let prop_name = match **prop {
+            Prop::KeyValue(ref kv) => &kv.key,
+            Prop::Method(ref method) => &method.key,
+            Prop::Getter(ref getter) => &getter.key,
+            _ => return None,
+        };
+
+        let mut prop_key = Vec::with_capacity(1);
+        collect_obj_propname(prop_name, &mut prop_key);
+        if prop_key.first() != Some(key) {
+            return None;
+        }
+
+        let mut default = (**prop).to_owned();
+        match default {
+            Prop::KeyValue(ref mut kv) => kv.key = default_key(),
+            Prop::Method(ref mut method) => method.key = default_key(),
+            Prop::Getter(ref mut getter) => getter.key = default_key(),
+            _ => {}
+        }
+
+        Some(default)
+    })
+}
+
+/// Finds `props`, `emits`, `expose` or `slots` in `defineOptions()`.
+/// These must be declared using `defineProps()`, `defineEmits()`, `defineExpose()` and `defineSlots()`.
+fn find_macro_option(options_object: &ObjectLit) -> Option<Span> {
+    options_object.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+
+        let mut key = Vec::with_capacity(1);
+        match **prop {
+            Prop::Shorthand(ref ident) => key.push(ident.sym.to_owned()),
+            Prop::KeyValue(ref kv) => collect_obj_propname(&kv.key, &mut key),
+            Prop::Method(ref method) => collect_obj_propname(&method.key, &mut key),
+            _ => {}
+        }
+
+        let key = key.pop()?;
+        let is_macro_option = key == *PROPS || key == *EMITS || key == *EXPOSE || key == *SLOTS;
+        is_macro_option.then(|| prop.span())
+    })
+}
+
 /// Mainly used to process `models` by adding them to `props` and `emits`
 pub fn postprocess_macros(
     bindings_helper: &mut BindingsHelper,
     sfc_object_helper: &mut SfcExportedObjectHelper,
 ) {
     let len = sfc_object_helper.models.len();
-    let mut new_props = Vec::<PropOrSpread>::with_capacity(len);
+    let mut new_props = Vec::<PropOrSpread>::with_capacity(len * 2);
     let mut new_emits = Vec::<Option<ExprOrSpread>>::with_capacity(len);
 
     for model in sfc_object_helper.models.drain(..) {
+        // The model is a prop, unless shadowed, e.g. by `const title = defineModel('title')`
+        let is_shadowed = bindings_helper
+            .setup_bindings
+            .iter()
+            .any(|SetupBinding(name, _)| *name == model.name);
+        if !is_shadowed {
+            bindings_helper
+                .setup_bindings
+                .push(SetupBinding(model.name.to_owned(), BindingTypes::Props));
+        }
+
         let model_value: Box<Expr> = match model.options {
             Some(options) => options.expr,
             None => Box::new(Expr::Object(ObjectLit {
@@ -354,6 +675,13 @@ pub fn postprocess_macros(
             }))),
         }));
 
+        // Modifiers are passed as a separate prop, e.g. `v-model:title.trim` -> `titleModifiers`
+        let modifiers_prop_name = if model.name == *MODEL_VALUE {
+            fervid_atom!("modelModifiers")
+        } else {
+            FervidAtom::from(format!("{}Modifiers", model.name))
+        };
+
         // Push an options object (or expr) into props
         new_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
             key: PropName::Str(Str {
@@ -363,6 +691,17 @@ pub fn postprocess_macros(
             }),
             value: model_value,
         }))));
+        new_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(Str {
+                span: DUMMY_SP,
+                value: modifiers_prop_name,
+                raw: None,
+            }),
+            value: Box::new(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            })),
+        }))));
     }
 
     match sfc_object_helper.props.take() {
@@ -537,10 +876,11 @@ fn is_local(options: Option<&ExprOrSpread>) -> bool {
 
 /// Extracts runtime emits from type-only `defineEmits` declaration
 /// Adapted from https://github.com/vuejs/core/blob/0ac0f2e338f6f8f0bea7237db539c68bfafb88ae/packages/compiler-sfc/src/script/defineEmits.ts#L73-L103
-fn extract_runtime_emits(type_arg: &TsType) -> Result<FxHashSet<FervidAtom>, ScriptError> {
+fn extract_runtime_emits(type_arg: &TsType) -> Result<Vec<FervidAtom>, ScriptError> {
     let mut ctx = TypeResolveContext::new("todo".to_owned());
 
-    let mut emits = FxHashSet::<FervidAtom>::default();
+    // Events are kept in the declaration order
+    let mut emits = Vec::<FervidAtom>::new();
 
     // Handle cases like `defineEmits<(e: 'foo' | 'bar') => void>()`
     if let TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(ref ts_fn_type)) = type_arg
@@ -561,8 +901,8 @@ fn extract_runtime_emits(type_arg: &TsType) -> Result<FxHashSet<FervidAtom>, Scr
     let ResolvedElements { props, calls } = resolve_type_elements(&mut ctx, type_arg)?;
 
     let mut has_property = false;
-    for key in props.into_keys() {
-        emits.insert(key);
+    for (key, _) in sorted_by_declaration(props) {
+        insert_event_name(&mut emits, key);
         has_property = true;
     }
 
@@ -597,7 +937,7 @@ fn extract_runtime_emits(type_arg: &TsType) -> Result<FxHashSet<FervidAtom>, Scr
 fn extract_event_names(
     ctx: &mut TypeResolveContext,
     event_name: &TsFnParam,
-    emits: &mut FxHashSet<FervidAtom>,
+    emits: &mut Vec<FervidAtom>,
 ) {
     let TsFnParam::Ident(ident) = event_name else {
         return;
@@ -615,19 +955,288 @@ fn extract_event_names(
             // No UnaryExpression
             match ts_lit_type.lit {
                 TsLit::Number(ref n) => {
-                    emits.insert(FervidAtom::from(n.value.to_string()));
+                    insert_event_name(emits, FervidAtom::from(n.value.to_string()));
                 }
                 TsLit::Str(ref s) => {
-                    emits.insert(s.value.to_owned());
+                    insert_event_name(emits, s.value.to_owned());
                 }
                 TsLit::Bool(ref b) => {
-                    emits.insert(FervidAtom::from(b.value.to_string()));
+                    insert_event_name(emits, FervidAtom::from(b.value.to_string()));
                 }
                 TsLit::BigInt(ref big_int) => {
-                    emits.insert(FervidAtom::from(big_int.value.to_string()));
+                    insert_event_name(emits, FervidAtom::from(big_int.value.to_string()));
                 }
                 TsLit::Tpl(_) => {}
             }
         }
     }
 }
+
+fn insert_event_name(emits: &mut Vec<FervidAtom>, event_name: FervidAtom) {
+    if !emits.contains(&event_name) {
+        emits.push(event_name);
+    }
+}
+
+/// Resolved elements are not ordered, but the generated code must follow the declaration
+fn sorted_by_declaration(
+    elements: FxHashMap<FervidAtom, TsTypeElement>,
+) -> impl ExactSizeIterator<Item = (FervidAtom, TsTypeElement)> {
+    elements.into_iter().sorted_by(|(a_key, a), (b_key, b)| {
+        a.span()
+            .lo
+            .cmp(&b.span().lo)
+            .then_with(|| (**a_key).cmp(&**b_key))
+    })
+}
+
+/// Extracts runtime props from type-only `defineProps` declaration
+/// Adapted from https://github.com/vuejs/core/blob/0ac0f2e338f6f8f0bea7237db539c68bfafb88ae/packages/compiler-sfc/src/script/defineProps.ts#L187-L251
+fn extract_runtime_props(type_arg: &TsType) -> Result<Vec<RuntimeProp>, ScriptError> {
+    let mut ctx = TypeResolveContext::new("todo".to_owned());
+
+    let ResolvedElements { props, .. } = resolve_type_elements(&mut ctx, type_arg)?;
+
+    let props = sorted_by_declaration(props);
+
+    let mut runtime_props = Vec::with_capacity(props.len());
+    for (key, element) in props {
+        let mut types = Vec::<&'static str>::with_capacity(1);
+        let (type_ann, optional) = match element {
+            TsTypeElement::TsPropertySignature(ref s) => (s.type_ann.as_deref(), s.optional),
+            TsTypeElement::TsGetterSignature(ref s) => (s.type_ann.as_deref(), s.optional),
+            TsTypeElement::TsMethodSignature(ref s) => {
+                types.push("Function");
+                (None, s.optional)
+            }
+            _ => continue,
+        };
+
+        if let Some(type_ann) = type_ann {
+            infer_runtime_type(&type_ann.type_ann, &mut types);
+        } else if types.is_empty() {
+            types.push(UNKNOWN_TYPE);
+        }
+
+        let mut types = types.into_iter().unique().collect_vec();
+
+        // Boolean and Function are kept, because they are needed for casting
+        let mut skip_check = false;
+        if types.contains(&UNKNOWN_TYPE) {
+            if types.contains(&"Boolean") || types.contains(&"Function") {
+                types.retain(|t| *t != UNKNOWN_TYPE);
+                skip_check = true;
+            } else {
+                types = vec!["null"];
+            }
+        }
+
+        runtime_props.push(RuntimeProp {
+            key,
+            types,
+            required: !optional,
+            skip_check,
+        });
+    }
+
+    Ok(runtime_props)
+}
+
+const UNKNOWN_TYPE: &str = "Unknown";
+
+/// Infers the runtime constructors of a TS type, e.g. `String` for `string`.
+/// Types which cannot be inferred without resolving them produce `Unknown`.
+/// Adapted from https://github.com/vuejs/core/blob/0ac0f2e338f6f8f0bea7237db539c68bfafb88ae/packages/compiler-sfc/src/script/resolveType.ts#L1438
+fn infer_runtime_type(ts_type: &TsType, out: &mut Vec<&'static str>) {
+    match ts_type {
+        TsType::TsKeywordType(keyword) => out.push(match keyword.kind {
+            TsKeywordTypeKind::TsStringKeyword => "String",
+            TsKeywordTypeKind::TsNumberKeyword | TsKeywordTypeKind::TsBigIntKeyword => "Number",
+            TsKeywordTypeKind::TsBooleanKeyword => "Boolean",
+            TsKeywordTypeKind::TsObjectKeyword => "Object",
+            TsKeywordTypeKind::TsNullKeyword => "null",
+            TsKeywordTypeKind::TsSymbolKeyword => "Symbol",
+            _ => UNKNOWN_TYPE,
+        }),
+
+        // `{ (): void }` is a function, `{ foo: string }` is an object
+        TsType::TsTypeLit(type_lit) => {
+            if type_lit.members.is_empty() {
+                out.push("Object");
+            }
+            for member in type_lit.members.iter() {
+                out.push(match member {
+                    TsTypeElement::TsCallSignatureDecl(_)
+                    | TsTypeElement::TsConstructSignatureDecl(_) => "Function",
+                    _ => "Object",
+                });
+            }
+        }
+
+        TsType::TsFnOrConstructorType(_) => out.push("Function"),
+
+        TsType::TsArrayType(_) | TsType::TsTupleType(_) => out.push("Array"),
+
+        TsType::TsLitType(lit_type) => out.push(match lit_type.lit {
+            TsLit::Str(_) | TsLit::Tpl(_) => "String",
+            TsLit::Bool(_) => "Boolean",
+            TsLit::Number(_) | TsLit::BigInt(_) => "Number",
+        }),
+
+        TsType::TsTypeRef(type_ref) => {
+            let name = match type_ref.type_name {
+                TsEntityName::Ident(ref ident) => &ident.sym,
+                TsEntityName::TsQualifiedName(ref qualified_name) => &qualified_name.right.sym,
+            };
+
+            out.push(match &**name {
+                "Array" | "ReadonlyArray" => "Array",
+                "Function" => "Function",
+                "Object" | "Partial" | "Required" | "Readonly" | "Record" | "Pick" | "Omit"
+                | "InstanceType" => "Object",
+                "Uppercase" | "Lowercase" | "Capitalize" | "Uncapitalize" => "String",
+                "Date" => "Date",
+                "Error" => "Error",
+                "Map" => "Map",
+                "Promise" => "Promise",
+                "Set" => "Set",
+                "WeakMap" => "WeakMap",
+                "WeakSet" => "WeakSet",
+                _ => UNKNOWN_TYPE,
+            });
+        }
+
+        TsType::TsParenthesizedType(paren) => infer_runtime_type(&paren.type_ann, out),
+        TsType::TsOptionalType(optional) => infer_runtime_type(&optional.type_ann, out),
+
+        // `readonly string[]`
+        TsType::TsTypeOperator(type_operator) => infer_runtime_type(&type_operator.type_ann, out),
+
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union_type)) => {
+            for t in union_type.types.iter() {
+                infer_runtime_type(t, out);
+            }
+        }
+
+        // `string & {}` is still a string
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+            intersection_type,
+        )) => {
+            let mut intersected = Vec::with_capacity(intersection_type.types.len());
+            for t in intersection_type.types.iter() {
+                infer_runtime_type(t, &mut intersected);
+            }
+
+            if intersected.iter().all(|t| *t == UNKNOWN_TYPE) {
+                out.push(UNKNOWN_TYPE);
+            } else {
+                out.extend(intersected.into_iter().filter(|t| *t != UNKNOWN_TYPE));
+            }
+        }
+
+        _ => out.push(UNKNOWN_TYPE),
+    }
+}
+
+impl RuntimeProp {
+    /// Generates `"key": { type: String, required: true, default: "foo" }`.
+    /// In production only `Boolean` and `Function` keep their type, because they affect casting.
+    fn into_prop(
+        self,
+        is_prod: bool,
+        has_static_defaults: bool,
+        default: Option<Prop>,
+    ) -> PropOrSpread {
+        macro_rules! field {
+            ($name: literal, $value: expr) => {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(Ident {
+                        span: DUMMY_SP,
+                        sym: fervid_atom!($name),
+                        optional: false,
+                    }),
+                    value: Box::new($value),
+                })))
+            };
+        }
+
+        // A function default is either a factory or the value itself, depending on the type
+        let keeps_fn_type = !has_static_defaults || default.is_some();
+        let keeps_type = !is_prod
+            || self
+                .types
+                .iter()
+                .any(|t| *t == "Boolean" || (*t == "Function" && keeps_fn_type));
+
+        let mut fields = Vec::with_capacity(4);
+        if keeps_type {
+            fields.push(field!("type", self.types_expr()));
+        }
+        if !is_prod {
+            fields.push(field!(
+                "required",
+                Expr::Lit(Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: self.required,
+                }))
+            ));
+            if self.skip_check {
+                fields.push(field!(
+                    "skipCheck",
+                    Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: true,
+                    }))
+                ));
+            }
+        }
+        if let Some(default) = default {
+            fields.push(PropOrSpread::Prop(Box::new(default)));
+        }
+
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Str(Str {
+                span: DUMMY_SP,
+                value: self.key,
+                raw: None,
+            }),
+            value: Box::new(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: fields,
+            })),
+        })))
+    }
+
+    /// `String`, `[String, Number]` or `null`
+    fn types_expr(&self) -> Expr {
+        fn to_expr(constructor: &str) -> Expr {
+            if constructor == "null" {
+                return Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
+            }
+
+            Expr::Ident(Ident {
+                span: DUMMY_SP,
+                sym: FervidAtom::from(constructor),
+                optional: false,
+            })
+        }
+
+        if let [single] = self.types.as_slice() {
+            return to_expr(single);
+        }
+
+        Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: self
+                .types
+                .iter()
+                .map(|t| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(to_expr(t)),
+                    })
+                })
+                .collect_vec(),
+        })
+    }
+}
@@ -0,0 +1,743 @@
+//! Experimental reactivity transform (formerly known as `$ref` sugar).
+//!
+//! Enabled by `reactivity_transform` option. Rewrites `let count = $ref(0)` into `let count = _ref(0)`
+//! and adds `.value` to the usages of `count`.
+//!
+//! See https://vuejs.org/guide/extras/reactivity-transform.html
+
+use fervid_core::{fervid_atom, BindingTypes, FervidAtom, VueImports};
+use fxhash::FxHashSet;
+use swc_core::{
+    common::{Span, Spanned, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrowExpr, BindingIdent, BlockStmt, CallExpr, Callee, CatchClause, Decl, Expr,
+            ExprOrSpread, ForHead, ForInStmt, ForOfStmt, ForStmt, Function, Ident, KeyValueProp,
+            Lit, MemberExpr, MemberProp, Module, ModuleItem, Number, ObjectPatProp, Pat, Prop,
+            PropName, SimpleAssignTarget, Stmt, Str, VarDeclOrExpr, VarDeclarator,
+        },
+        visit::{VisitMut, VisitMutWith},
+    },
+};
+
+use crate::{
+    error::{ScriptError, ScriptErrorKind, TransformError},
+    script::common::extract_variables_from_pat,
+    BindingsHelper, SetupBinding,
+};
+
+/// Applies the reactivity transform to the `<script setup>` module.
+/// Returns the identifiers which became refs, so that they can be marked as [`BindingTypes::SetupRef`].
+pub fn transform_reactivity_macros(
+    module: &mut Module,
+    bindings_helper: &mut BindingsHelper,
+    errors: &mut Vec<TransformError>,
+) -> FxHashSet<FervidAtom> {
+    let mut refs = FxHashSet::<FervidAtom>::default();
+    let mut temp_counter = 0;
+
+    // Only the top-level declarations are considered
+    for module_item in module.body.iter_mut() {
+        let ModuleItem::Stmt(Stmt::Decl(Decl::Var(ref mut var_decl))) = module_item else {
+            continue;
+        };
+
+        let old_decls = std::mem::take(&mut var_decl.decls);
+        for var_declarator in old_decls.into_iter() {
+            transform_var_declarator(
+                var_declarator,
+                &mut var_decl.decls,
+                &mut refs,
+                &mut temp_counter,
+                bindings_helper,
+                errors,
+            );
+        }
+    }
+
+    // Rewrite usages of the collected refs
+    let mut visitor = RefUsageVisitor { refs: &mut refs };
+    for module_item in module.body.iter_mut() {
+        if let ModuleItem::Stmt(stmt) = module_item {
+            stmt.visit_mut_with(&mut visitor);
+        }
+    }
+
+    refs
+}
+
+/// Marks the setup bindings created by the reactivity transform as refs
+pub fn mark_ref_bindings(setup_bindings: &mut [SetupBinding], refs: &FxHashSet<FervidAtom>) {
+    for binding in setup_bindings.iter_mut() {
+        if refs.contains(&binding.0) {
+            binding.1 = BindingTypes::SetupRef;
+        }
+    }
+}
+
+fn transform_var_declarator(
+    mut var_declarator: VarDeclarator,
+    out: &mut Vec<VarDeclarator>,
+    refs: &mut FxHashSet<FervidAtom>,
+    temp_counter: &mut usize,
+    bindings_helper: &mut BindingsHelper,
+    errors: &mut Vec<TransformError>,
+) {
+    let Some(macro_sym) = get_macro_call_sym(var_declarator.init.as_deref()) else {
+        out.push(var_declarator);
+        return;
+    };
+
+    // `$ref`, `$computed`, etc.
+    if let Some(vue_import) = get_ref_macro_import(&macro_sym) {
+        if let Some(Expr::Call(ref mut call_expr)) = var_declarator.init.as_deref_mut() {
+            bindings_helper.vue_imports |= vue_import;
+            call_expr.callee = Callee::Expr(Box::new(Expr::Ident(Ident {
+                span: call_expr.span,
+                sym: vue_import.as_atom(),
+                optional: false,
+            })));
+        }
+
+        if let Pat::Ident(ref binding_ident) = var_declarator.name {
+            refs.insert(binding_ident.sym.to_owned());
+        }
+
+        out.push(var_declarator);
+        return;
+    }
+
+    // Anything other than `$()` is not a macro
+    if macro_sym != "$" {
+        out.push(var_declarator);
+        return;
+    }
+
+    // Unwrap `$(expr)` into `expr`
+    let Some(Expr::Call(ref mut call_expr)) = var_declarator.init.as_deref_mut() else {
+        unreachable!("`get_macro_call_sym` only matches calls")
+    };
+    let span = call_expr.span;
+    if call_expr.args.is_empty() {
+        errors.push(TransformError::ScriptError(ScriptError {
+            span,
+            kind: ScriptErrorKind::ReactivityTransformMissingArgument,
+        }));
+        out.push(var_declarator);
+        return;
+    }
+    let ExprOrSpread { expr: inner, .. } = call_expr.args.remove(0);
+
+    macro_rules! unsupported_destructure {
+        ($span: expr) => {
+            errors.push(TransformError::ScriptError(ScriptError {
+                span: $span,
+                kind: ScriptErrorKind::ReactivityTransformUnsupportedDestructure,
+            }))
+        };
+    }
+
+    match var_declarator.name {
+        // `const foo = $(useFoo())`
+        Pat::Ident(ref binding_ident) => {
+            refs.insert(binding_ident.sym.to_owned());
+            var_declarator.init = Some(inner);
+            out.push(var_declarator);
+        }
+
+        // `const { x, y: z } = $(useFoo())` or `const [x, y] = $(useFoo())`
+        Pat::Object(_) | Pat::Array(_) => {
+            *temp_counter += 1;
+            let temp_sym: FervidAtom = format!("__$temp_{}", temp_counter).into();
+
+            out.push(VarDeclarator {
+                span,
+                name: Pat::Ident(BindingIdent {
+                    id: Ident {
+                        span: DUMMY_SP,
+                        sym: temp_sym.to_owned(),
+                        optional: false,
+                    },
+                    type_ann: None,
+                }),
+                init: Some(inner),
+                definite: false,
+            });
+
+            bindings_helper.vue_imports |= VueImports::ToRef;
+
+            macro_rules! push_to_ref {
+                ($local: expr, $key: expr, $default: expr) => {{
+                    let local: FervidAtom = $local;
+                    refs.insert(local.to_owned());
+                    out.push(VarDeclarator {
+                        span,
+                        name: Pat::Ident(BindingIdent {
+                            id: Ident {
+                                span,
+                                sym: local,
+                                optional: false,
+                            },
+                            type_ann: None,
+                        }),
+                        init: Some(Box::new(generate_to_ref(&temp_sym, $key, $default, span))),
+                        definite: false,
+                    });
+                }};
+            }
+
+            match var_declarator.name {
+                Pat::Object(obj_pat) => {
+                    for prop in obj_pat.props.into_iter() {
+                        match prop {
+                            ObjectPatProp::KeyValue(key_value) => {
+                                let key = match key_value.key {
+                                    PropName::Ident(ident) => str_lit(ident.sym, span),
+                                    PropName::Str(s) => Expr::Lit(Lit::Str(s)),
+                                    PropName::Num(n) => Expr::Lit(Lit::Num(n)),
+                                    PropName::Computed(c) => *c.expr,
+                                    PropName::BigInt(b) => Expr::Lit(Lit::BigInt(b)),
+                                };
+
+                                match *key_value.value {
+                                    Pat::Ident(local) => push_to_ref!(local.id.sym, key, None),
+                                    Pat::Assign(assign) => match *assign.left {
+                                        Pat::Ident(ref local) => push_to_ref!(
+                                            local.id.sym.to_owned(),
+                                            key,
+                                            Some(assign.right)
+                                        ),
+                                        ref nested => unsupported_destructure!(nested.span()),
+                                    },
+                                    // Nested destructures are not supported
+                                    nested => unsupported_destructure!(nested.span()),
+                                }
+                            }
+
+                            ObjectPatProp::Assign(assign) => {
+                                let sym = assign.key.sym.to_owned();
+                                push_to_ref!(sym.to_owned(), str_lit(sym, span), assign.value);
+                            }
+
+                            // Rest is not supported
+                            ObjectPatProp::Rest(rest) => unsupported_destructure!(rest.span),
+                        }
+                    }
+                }
+
+                Pat::Array(arr_pat) => {
+                    for (idx, elem) in arr_pat.elems.into_iter().enumerate() {
+                        let key = Expr::Lit(Lit::Num(Number {
+                            span,
+                            value: idx as f64,
+                            raw: None,
+                        }));
+
+                        match elem {
+                            Some(Pat::Ident(local)) => push_to_ref!(local.id.sym, key, None),
+                            Some(Pat::Assign(assign)) => match *assign.left {
+                                Pat::Ident(ref local) => {
+                                    push_to_ref!(local.id.sym.to_owned(), key, Some(assign.right))
+                                }
+                                ref nested => unsupported_destructure!(nested.span()),
+                            },
+                            // Holes, e.g. `const [, y] = $(useFoo())`
+                            None => {}
+                            // Rest and nested destructures are not supported
+                            Some(unsupported) => unsupported_destructure!(unsupported.span()),
+                        }
+                    }
+                }
+
+                _ => unreachable!(),
+            }
+        }
+
+        // Other patterns are left as-is
+        _ => {
+            var_declarator.init = Some(inner);
+            out.push(var_declarator);
+        }
+    }
+}
+
+/// Returns the symbol of the callee if expression is a `$xxx()` call
+fn get_macro_call_sym(expr: Option<&Expr>) -> Option<FervidAtom> {
+    let Some(Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    })) = expr
+    else {
+        return None;
+    };
+
+    match **callee {
+        Expr::Ident(ref ident) if ident.sym.starts_with('$') => Some(ident.sym.to_owned()),
+        _ => None,
+    }
+}
+
+fn get_ref_macro_import(sym: &str) -> Option<VueImports> {
+    match sym {
+        "$ref" => Some(VueImports::Ref),
+        "$computed" => Some(VueImports::Computed),
+        "$shallowRef" => Some(VueImports::ShallowRef),
+        "$customRef" => Some(VueImports::CustomRef),
+        "$toRef" => Some(VueImports::ToRef),
+        _ => None,
+    }
+}
+
+/// `_toRef(__$temp_1, 'key', default)`
+fn generate_to_ref(
+    temp_sym: &FervidAtom,
+    key: Expr,
+    default: Option<Box<Expr>>,
+    span: Span,
+) -> Expr {
+    let mut args = Vec::with_capacity(3);
+    args.push(ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Ident(Ident {
+            span,
+            sym: temp_sym.to_owned(),
+            optional: false,
+        })),
+    });
+    args.push(ExprOrSpread {
+        spread: None,
+        expr: Box::new(key),
+    });
+    if let Some(default) = default {
+        args.push(ExprOrSpread {
+            spread: None,
+            expr: default,
+        });
+    }
+
+    Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+            span,
+            sym: VueImports::ToRef.as_atom(),
+            optional: false,
+        }))),
+        args,
+        type_args: None,
+    })
+}
+
+fn str_lit(value: FervidAtom, span: Span) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span,
+        value,
+        raw: None,
+    }))
+}
+
+/// `ident.value`
+fn ref_value(ident: Ident) -> MemberExpr {
+    MemberExpr {
+        span: ident.span,
+        obj: Box::new(Expr::Ident(ident)),
+        prop: MemberProp::Ident(Ident {
+            span: DUMMY_SP,
+            sym: fervid_atom!("value"),
+            optional: false,
+        }),
+    }
+}
+
+struct RefUsageVisitor<'r> {
+    refs: &'r mut FxHashSet<FervidAtom>,
+}
+
+impl RefUsageVisitor<'_> {
+    /// Temporarily removes the refs shadowed by `declared` while visiting with `f`
+    fn with_shadowed(&mut self, declared: Vec<FervidAtom>, f: impl FnOnce(&mut Self)) {
+        let shadowed: Vec<FervidAtom> = declared
+            .into_iter()
+            .filter_map(|sym| self.refs.take(&sym))
+            .collect();
+
+        f(self);
+
+        self.refs.extend(shadowed);
+    }
+}
+
+/// Collects the variables declared by the patterns
+fn pats_variables<'p>(pats: impl Iterator<Item = &'p Pat>) -> Vec<FervidAtom> {
+    let mut declared = Vec::new();
+    for pat in pats {
+        extract_variables_from_pat(pat, &mut declared, false);
+    }
+
+    declared
+        .into_iter()
+        .map(|SetupBinding(sym, _)| sym)
+        .collect()
+}
+
+/// Collects the variables, functions and classes declared directly in a block
+fn block_variables(stmts: &[Stmt]) -> Vec<FervidAtom> {
+    let mut declared = Vec::new();
+    for stmt in stmts.iter() {
+        match stmt {
+            Stmt::Decl(Decl::Var(var_decl)) => {
+                declared.extend(pats_variables(var_decl.decls.iter().map(|d| &d.name)));
+            }
+            Stmt::Decl(Decl::Fn(fn_decl)) => declared.push(fn_decl.ident.sym.to_owned()),
+            Stmt::Decl(Decl::Class(class_decl)) => declared.push(class_decl.ident.sym.to_owned()),
+            _ => {}
+        }
+    }
+
+    declared
+}
+
+/// Collects the variables declared in the head of a `for` loop
+fn for_head_variables(init: Option<&VarDeclOrExpr>) -> Vec<FervidAtom> {
+    match init {
+        Some(VarDeclOrExpr::VarDecl(var_decl)) => {
+            pats_variables(var_decl.decls.iter().map(|d| &d.name))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Collects the variables declared in the head of a `for..in` or `for..of` loop
+fn for_in_head_variables(left: &ForHead) -> Vec<FervidAtom> {
+    match left {
+        ForHead::VarDecl(var_decl) => pats_variables(var_decl.decls.iter().map(|d| &d.name)),
+        ForHead::UsingDecl(using_decl) => pats_variables(using_decl.decls.iter().map(|d| &d.name)),
+        // `for (x of xs)` assigns to an existing variable
+        ForHead::Pat(_) => Vec::new(),
+    }
+}
+
+impl VisitMut for RefUsageVisitor<'_> {
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        match n {
+            Expr::Ident(ident) if self.refs.contains(&ident.sym) => {
+                *n = Expr::Member(ref_value(ident.to_owned()));
+            }
+
+            // `$$(foo)` escapes the transform and passes the ref itself
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) if args.len() == 1 && matches!(**callee, Expr::Ident(ref i) if i.sym == "$$") => {
+                let arg = args.remove(0);
+                *n = *arg.expr;
+            }
+
+            _ => n.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_prop(&mut self, n: &mut Prop) {
+        match n {
+            // `{ foo }` -> `{ foo: foo.value }`
+            Prop::Shorthand(ident) if self.refs.contains(&ident.sym) => {
+                *n = Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(ident.to_owned()),
+                    value: Box::new(Expr::Member(ref_value(ident.to_owned()))),
+                });
+            }
+            _ => n.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_simple_assign_target(&mut self, n: &mut SimpleAssignTarget) {
+        match n {
+            SimpleAssignTarget::Ident(binding_ident) if self.refs.contains(&binding_ident.sym) => {
+                *n = SimpleAssignTarget::Member(ref_value(binding_ident.id.to_owned()));
+            }
+            _ => n.visit_mut_children_with(self),
+        }
+    }
+
+    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+        // Declared names are not usages, but the defaults are,
+        // e.g. `count` in `const { a = count } = obj`
+        n.name.visit_mut_with(self);
+        n.init.visit_mut_with(self);
+    }
+
+    fn visit_mut_function(&mut self, n: &mut Function) {
+        // Param defaults are visited as well, e.g. `count` in `function (x = count) {}`
+        let params = pats_variables(n.params.iter().map(|p| &p.pat));
+        self.with_shadowed(params, |this| {
+            n.params.visit_mut_with(this);
+            n.body.visit_mut_with(this);
+        });
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        let params = pats_variables(n.params.iter());
+        self.with_shadowed(params, |this| {
+            n.params.visit_mut_with(this);
+            n.body.visit_mut_with(this);
+        });
+    }
+
+    fn visit_mut_block_stmt(&mut self, n: &mut BlockStmt) {
+        let declared = block_variables(&n.stmts);
+        self.with_shadowed(declared, |this| n.visit_mut_children_with(this));
+    }
+
+    fn visit_mut_catch_clause(&mut self, n: &mut CatchClause) {
+        let declared = pats_variables(n.param.iter());
+        self.with_shadowed(declared, |this| n.body.visit_mut_with(this));
+    }
+
+    fn visit_mut_for_stmt(&mut self, n: &mut ForStmt) {
+        let declared = for_head_variables(n.init.as_ref());
+        self.with_shadowed(declared, |this| n.visit_mut_children_with(this));
+    }
+
+    fn visit_mut_for_in_stmt(&mut self, n: &mut ForInStmt) {
+        n.right.visit_mut_with(self);
+        let declared = for_in_head_variables(&n.left);
+        self.with_shadowed(declared, |this| n.body.visit_mut_with(this));
+    }
+
+    fn visit_mut_for_of_stmt(&mut self, n: &mut ForOfStmt) {
+        n.right.visit_mut_with(self);
+        let declared = for_in_head_variables(&n.left);
+        self.with_shadowed(declared, |this| n.body.visit_mut_with(this));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{fervid_atom, BindingTypes, SfcScriptLang};
+
+    use crate::{
+        error::{ScriptErrorKind, TransformError},
+        script::setup::tests::transform_setup,
+        test_utils::to_str,
+        BindingsHelper, SetupBinding,
+    };
+
+    fn transform(input: &str, reactivity_transform: bool) -> (String, BindingsHelper) {
+        let (code, bindings_helper, errors) = transform_with_errors(input, reactivity_transform);
+        assert!(errors.is_empty());
+        (code, bindings_helper)
+    }
+
+    fn transform_with_errors(
+        input: &str,
+        reactivity_transform: bool,
+    ) -> (String, BindingsHelper, Vec<TransformError>) {
+        let mut bindings_helper = BindingsHelper::default();
+        bindings_helper.reactivity_transform = reactivity_transform;
+
+        let (result, errors) = transform_setup(input, SfcScriptLang::Es, &mut bindings_helper);
+
+        let setup_fn = result.setup_fn.expect("Should have setup");
+        let body = setup_fn.body.expect("Should have body");
+        (to_str(&body), bindings_helper, errors)
+    }
+
+    fn error_kinds(errors: &[TransformError]) -> Vec<&ScriptErrorKind> {
+        errors
+            .iter()
+            .map(|e| match e {
+                TransformError::ScriptError(e) => &e.kind,
+                _ => panic!("Not a script error"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_transforms_ref() {
+        let (code, bindings_helper) = transform(
+            r"
+            let count = $ref(0)
+            count++
+            console.log(count)
+            ",
+            true,
+        );
+
+        assert_eq!(
+            code,
+            "{let count=_ref(0);count.value++;console.log(count.value);}"
+        );
+        assert!(bindings_helper
+            .vue_imports
+            .contains(fervid_core::VueImports::Ref));
+        assert_eq!(
+            bindings_helper.setup_bindings,
+            vec![SetupBinding(fervid_atom!("count"), BindingTypes::SetupRef)]
+        );
+    }
+
+    #[test]
+    fn it_transforms_computed() {
+        let (code, _) = transform(
+            r"
+            let a = $ref(1)
+            const b = $computed(() => a + 1)
+            const obj = { a, b }
+            a = b
+            ",
+            true,
+        );
+
+        assert_eq!(
+            code,
+            "{let a=_ref(1);const b=_computed(()=>a.value+1);const obj={a:a.value,b:b.value};a.value=b.value;}"
+        );
+    }
+
+    #[test]
+    fn it_supports_escape_hatch() {
+        let (code, _) = transform(
+            r"
+            let count = $ref(0)
+            useCount($$(count))
+            ",
+            true,
+        );
+
+        assert_eq!(code, "{let count=_ref(0);useCount(count);}");
+    }
+
+    #[test]
+    fn it_transforms_destructures() {
+        let (code, bindings_helper) = transform(
+            r"
+            const { x, y: z, w = 1 } = $(useFoo())
+            console.log(x, z, w)
+            ",
+            true,
+        );
+
+        assert_eq!(
+            code,
+            "{const __$temp_1=useFoo(),x=_toRef(__$temp_1,\"x\"),z=_toRef(__$temp_1,\"y\"),w=_toRef(__$temp_1,\"w\",1);console.log(x.value,z.value,w.value);}"
+        );
+        assert!(bindings_helper
+            .vue_imports
+            .contains(fervid_core::VueImports::ToRef));
+    }
+
+    #[test]
+    fn it_respects_shadowing() {
+        let (code, _) = transform(
+            r"
+            let count = $ref(0)
+            const inc = (count) => count + 1
+            ",
+            true,
+        );
+
+        assert_eq!(code, "{let count=_ref(0);const inc=count=>count+1;}");
+    }
+
+    #[test]
+    fn it_respects_block_scoped_shadowing() {
+        let (code, _) = transform(
+            r"
+            let count = $ref(0)
+            function reset() {
+                const count = 0
+                return count
+            }
+            if (count) {
+                let count = 1
+                count++
+            }
+            for (const count of list) console.log(count)
+            try {} catch (count) { count }
+            console.log(count)
+            ",
+            true,
+        );
+
+        assert_eq!(
+            code,
+            "{let count=_ref(0);function reset(){const count=0;return count;}if(count.value){let count=1;count++;}for(const count of list)console.log(count);try{}catch(count){count;}console.log(count.value);}"
+        );
+    }
+
+    #[test]
+    fn it_transforms_defaults() {
+        let (code, _) = transform(
+            r"
+            let count = $ref(0)
+            const inc = (x = count) => x + 1
+            function dec(x = count) { return x - 1 }
+            const { a = count } = obj
+            const [b = count] = arr
+            ",
+            true,
+        );
+
+        assert_eq!(
+            code,
+            "{let count=_ref(0);const inc=(x=count.value)=>x+1;function dec(x=count.value){return x-1;}const{a=count.value}=obj;const[b=count.value]=arr;}"
+        );
+    }
+
+    #[test]
+    fn it_removes_macros_import() {
+        let (code, bindings_helper) = transform(
+            r"
+            import { $ref } from 'vue/macros'
+            let count = $ref(0)
+            ",
+            true,
+        );
+
+        assert_eq!(code, "{let count=_ref(0);}");
+        assert!(bindings_helper.user_imports.is_empty());
+    }
+
+    #[test]
+    fn it_reports_unsupported_forms() {
+        // `$()` without an argument is kept as-is
+        let (code, _, errors) = transform_with_errors("const foo = $()", true);
+        assert_eq!(code, "{const foo=$();}");
+        assert!(matches!(
+            error_kinds(&errors)[..],
+            [ScriptErrorKind::ReactivityTransformMissingArgument]
+        ));
+
+        // Rest elements and nested destructures
+        let (_, _, errors) = transform_with_errors(
+            r"
+            const { a, b: { c }, ...rest } = $(useFoo())
+            const [d, [e], ...others] = $(useBar())
+            ",
+            true,
+        );
+        assert!(matches!(
+            error_kinds(&errors)[..],
+            [
+                ScriptErrorKind::ReactivityTransformUnsupportedDestructure,
+                ScriptErrorKind::ReactivityTransformUnsupportedDestructure,
+                ScriptErrorKind::ReactivityTransformUnsupportedDestructure,
+                ScriptErrorKind::ReactivityTransformUnsupportedDestructure,
+            ]
+        ));
+    }
+
+    #[test]
+    fn it_does_not_transform_when_disabled() {
+        let (code, bindings_helper) = transform(
+            r"
+            let count = $ref(0)
+            count++
+            ",
+            false,
+        );
+
+        assert_eq!(code, "{let count=$ref(0);count++;}");
+        assert!(bindings_helper.vue_imports.is_empty());
+    }
+}
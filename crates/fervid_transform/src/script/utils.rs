@@ -1,11 +1,14 @@
 //! A collection of utils for working with SWC structs
 
 use fervid_core::{fervid_atom, FervidAtom};
+use fxhash::FxHashMap;
 use swc_core::ecma::ast::{
     ArrayLit, BlockStmt, Callee, Expr, ExprOrSpread, Function, Lit, Module, ModuleDecl, ModuleItem,
     ObjectLit, Prop, PropName, PropOrSpread, ReturnStmt, Stmt, Tpl,
 };
 
+use crate::{PropType, PropTypes};
+
 #[deprecated]
 pub fn find_default_export(module: &Module) -> Option<&ObjectLit> {
     let define_component = fervid_atom!("defineComponent");
@@ -134,6 +137,83 @@ pub fn collect_obj_prop_or_spread(prop_or_spread: &PropOrSpread, out: &mut Vec<F
     };
 }
 
+/// Collects the runtime prop types from the props object,
+/// e.g. `count: Number`, `id: [String, Number]` or `label: { type: String }`.
+/// Props with types which cannot be statically determined are skipped.
+pub fn collect_obj_prop_types(object: &ObjectLit, out: &mut FxHashMap<FervidAtom, PropTypes>) {
+    for prop in object.props.iter() {
+        let PropOrSpread::Prop(prop) = prop else {
+            continue;
+        };
+        let Prop::KeyValue(ref key_value) = **prop else {
+            continue;
+        };
+
+        let mut key = Vec::with_capacity(1);
+        collect_obj_propname(&key_value.key, &mut key);
+        let Some(key) = key.pop() else {
+            continue;
+        };
+
+        // `{ type: String, required: true }`
+        let mut type_expr = key_value.value.as_ref();
+        if let Expr::Object(options) = type_expr {
+            let type_value = options.props.iter().find_map(|prop| {
+                let PropOrSpread::Prop(prop) = prop else {
+                    return None;
+                };
+                let Prop::KeyValue(ref kv) = **prop else {
+                    return None;
+                };
+                match kv.key {
+                    PropName::Ident(ref id) if id.sym == "type" => Some(kv.value.as_ref()),
+                    _ => None,
+                }
+            });
+            let Some(type_value) = type_value else {
+                continue;
+            };
+            type_expr = type_value;
+        }
+
+        let mut types = PropTypes::new();
+        let is_known = match type_expr {
+            Expr::Ident(ident) => {
+                types.push(to_prop_type(&ident.sym));
+                true
+            }
+            Expr::Array(arr) => arr.elems.iter().all(|elem| match elem {
+                Some(ExprOrSpread { spread: None, expr }) => match expr.as_ref() {
+                    Expr::Ident(ident) => {
+                        types.push(to_prop_type(&ident.sym));
+                        true
+                    }
+                    _ => false,
+                },
+                _ => false,
+            }),
+            _ => false,
+        };
+
+        if is_known && !types.is_empty() {
+            out.insert(key, types);
+        }
+    }
+}
+
+#[inline]
+pub fn to_prop_type(constructor: &str) -> PropType {
+    match constructor {
+        "Array" => PropType::Array,
+        "Boolean" => PropType::Boolean,
+        "Function" => PropType::Function,
+        "Number" => PropType::Number,
+        "Object" => PropType::Object,
+        "String" => PropType::String,
+        _ => PropType::Other,
+    }
+}
+
 /// Collects the property name of an object, e.g. `foo` in `{ foo: 'bar' }`
 #[inline]
 pub fn collect_obj_propname(prop_name: &PropName, out: &mut Vec<FervidAtom>) {
@@ -1,14 +1,18 @@
 //! Exports data structs used by the crate
 
 use fervid_core::{
-    BindingTypes, ComponentBinding, CustomDirectiveBinding, FervidAtom, SfcCustomBlock,
-    SfcStyleBlock, SfcTemplateBlock, TemplateGenerationMode, VueImportsSet,
+    BindingTypes, ComponentBinding, CustomDirectiveBinding, CustomElementPredicate, FervidAtom,
+    SfcCustomBlock, SfcStyleBlock, SfcTemplateBlock, TemplateGenerationMode, VueImportsSet,
+    WhitespaceMode,
 };
 use fxhash::FxHashMap as HashMap;
 use smallvec::SmallVec;
-use swc_core::ecma::{
-    ast::{Expr, ExprOrSpread, Function, Id, Module, ObjectLit, PropOrSpread},
-    atoms::JsWord,
+use swc_core::{
+    common::Span,
+    ecma::{
+        ast::{Expr, ExprOrSpread, Function, Id, Lit, Module, ObjectLit, PropOrSpread},
+        atoms::JsWord,
+    },
 };
 
 /// A helper which encompasses all the logic related to bindings,
@@ -22,14 +26,48 @@ pub struct BindingsHelper {
     pub custom_directives: HashMap<FervidAtom, CustomDirectiveBinding>,
     /// Are we compiling for DEV or PROD
     pub is_prod: bool,
+    /// Whether Vue 2 compatibility mode (`@vue/compat`) is enabled
+    pub is_compat: bool,
     /// Is Typescript or Javascript used
     pub is_ts: bool,
+    /// Whether the experimental reactivity transform (`$ref`, `$computed`, `$$`) is enabled
+    pub reactivity_transform: bool,
+    /// Whether dynamic `:href`, `:src` and `:action` bindings are wrapped in `sanitizeUrl()`,
+    /// which is a user-provided binding resolved as any other template identifier
+    pub sanitize_href: bool,
+    /// Whether `$parent` and `$root` access in the `<template>` produces a warning
+    pub warn_deprecated_globals: bool,
+    /// Whether `v-for` over small lists of literals is unrolled at compile time
+    pub unroll_static_for: bool,
+    /// How the whitespace in the `<template>` is handled
+    pub whitespace: WhitespaceMode,
+    /// Tags which are rendered as plain elements instead of being resolved as components
+    pub is_custom_element: Option<CustomElementPredicate>,
+    /// Top-level `const`s initialized with an array of literals, e.g. `const sizes = ['s', 'm']`.
+    /// Only collected when `unroll_static_for` is enabled.
+    pub static_lists: HashMap<FervidAtom, Vec<Lit>>,
+    /// Spans of `$parent` and `$root` accesses found while transforming the `<template>`
+    pub deprecated_globals_spans: Vec<Span>,
     /// Scopes of the `<template>` for in-template variable resolutions
     pub template_scopes: Vec<TemplateScope>,
     /// Bindings in `<script setup>`
     pub setup_bindings: Vec<SetupBinding>,
+    /// `computed`s in `<script setup>` declared with only a getter, e.g. `computed(() => 1)`
+    pub readonly_computeds: Vec<FervidAtom>,
     /// Bindings in `<script>`
     pub options_api_bindings: Option<Box<OptionsApiBindings>>,
+    /// Local name of the `defineProps()` result, e.g. `props` in `const props = defineProps()`
+    pub props_binding: Option<FervidAtom>,
+    /// Events declared in `defineEmits`, e.g. `update:modelValue`
+    pub emits: Vec<FervidAtom>,
+    /// Names of the `<style module>` blocks, e.g. `$style` or `classes`
+    pub css_modules: Vec<FervidAtom>,
+    /// Runtime types of the props declared in `defineProps`
+    pub prop_types: HashMap<FervidAtom, PropTypes>,
+    /// Name by which the component can reference itself in its `<template>`, derived from the filename
+    pub self_name: Option<FervidAtom>,
+    /// Runtime prop types of the components known to the `<template>`, keyed by component name
+    pub component_prop_types: ComponentPropTypes,
     /// The mode with which `<template>` variables are resolved.
     /// Also controls in which mode should the template be generated:
     /// - inline as last statement of `setup` or
@@ -37,6 +75,8 @@ pub struct BindingsHelper {
     pub template_generation_mode: TemplateGenerationMode,
     /// Identifiers used in the template and their respective binding types
     pub used_bindings: HashMap<FervidAtom, BindingTypes>,
+    /// Identifiers in the template which were not resolved to any binding, with their spans
+    pub unresolved_references: Vec<(FervidAtom, Span)>,
     /// Imported symbols
     pub user_imports: HashMap<FervidAtom, ImportBinding>,
     /// Internal Vue imports used by built-in components, directives and others
@@ -63,6 +103,25 @@ pub struct OptionsApiBindings {
     pub imports: Vec<SetupBinding>,
 }
 
+/// Runtime type of a prop, e.g. `Number` in `defineProps({ count: Number })`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PropType {
+    Array,
+    Boolean,
+    Function,
+    Number,
+    Object,
+    String,
+    /// Any other constructor, e.g. `Date` or a user class
+    Other,
+}
+
+/// All the runtime types a prop accepts, e.g. `[String, Number]`
+pub type PropTypes = SmallVec<[PropType; 2]>;
+
+/// Prop types of the components, keyed by component name and then by prop name
+pub type ComponentPropTypes = HashMap<FervidAtom, HashMap<FervidAtom, PropTypes>>;
+
 /// Identifier plus a binding type
 #[derive(Debug, PartialEq)]
 pub struct SetupBinding(pub FervidAtom, pub BindingTypes);
@@ -106,6 +165,10 @@ pub struct SfcDefineModel {
 pub struct SfcExportedObjectHelper {
     /// `emits` property
     pub emits: Option<Box<Expr>>,
+    /// Whether `defineOptions()` was already called
+    pub has_define_options: bool,
+    /// Whether `defineSlots()` was already called
+    pub has_define_slots: bool,
     /// Should `async setup` be generated (when `await` was used)
     pub is_async_setup: bool,
     /// Whether `__emit` was referenced (e.g. as a result of `const foo = defineEmits()`)
@@ -133,6 +196,15 @@ pub struct TransformScriptsResult {
 
 pub struct TransformSfcOptions<'s> {
     pub is_prod: bool,
+    pub is_compat: bool,
+    pub reactivity_transform: bool,
+    pub sanitize_href: bool,
+    pub warn_deprecated_globals: bool,
+    pub unroll_static_for: bool,
+    pub whitespace: WhitespaceMode,
+    pub is_custom_element: Option<CustomElementPredicate>,
+    /// Prop types of the components defined outside of the SFC
+    pub component_prop_types: ComponentPropTypes,
     pub scope_id: &'s str,
     pub filename: &'s str,
 }
@@ -1,11 +1,18 @@
-use fervid_core::{fervid_atom, SfcStyleBlock};
+use fervid_core::{fervid_atom, FervidAtom, SfcStyleBlock, TemplateGenerationMode, VueImports};
 use fervid_css::*;
 use swc_core::{
-    common::DUMMY_SP,
-    ecma::ast::{Expr, Ident, KeyValueProp, Lit, Prop, PropName, PropOrSpread, Str},
+    common::{Span, DUMMY_SP},
+    ecma::ast::{
+        ArrowExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr, Callee, Expr, ExprOrSpread,
+        ExprStmt, Function, Ident, Invalid, KeyValueProp, Lit, MethodProp, ObjectLit, ParenExpr,
+        Pat, Prop, PropName, PropOrSpread, RestPat, ReturnStmt, Stmt, Str,
+    },
 };
 
-use crate::{error::TransformError, structs::TransformScriptsResult};
+use crate::{
+    error::TransformError, structs::TransformScriptsResult,
+    template::expr_transform::BindingsHelperTransform, BindingsHelper,
+};
 
 const CSS_PREFIX: &'static str = "data-v-";
 
@@ -73,7 +80,626 @@ pub fn transform_style_blocks(
     true
 }
 
+/// Records the name of every `<style module>`, so that the template can access the class names,
+/// e.g. `$style.red` or `classes.red`.
+///
+/// The modules are not declared in `setup`, because the user `setup` may be an Options API one
+/// and the names could clash with user bindings. Instead, a module name which does not resolve
+/// to any binding is compiled to `_useCssModule("name")` in the template.
+pub fn inject_css_modules(
+    style_blocks: &[SfcStyleBlock],
+    bindings_helper: &mut BindingsHelper,
+    errors: &mut Vec<TransformError>,
+) {
+    for style_block in style_blocks.iter() {
+        let Some(ref module_name) = style_block.module_name else {
+            continue;
+        };
+
+        if bindings_helper.css_modules.contains(module_name) {
+            errors.push(TransformError::CssError(CssError {
+                span: style_block.span,
+                kind: CssErrorKind::DuplicateModuleName,
+            }));
+            continue;
+        }
+
+        bindings_helper.css_modules.push(module_name.to_owned());
+    }
+}
+
+/// Compiles the legacy `<style vars="{ color }">` to `_useCssVars(_ctx => ({ color: _ctx.color }))`
+/// in `setup`, so that the CSS can use `var(--color)`.
+///
+/// `vars` was removed in favor of `v-bind()` in CSS, therefore it is only compiled in compat mode
+/// and is always reported together with the suggested migration.
+pub fn inject_legacy_style_vars(
+    style_blocks: &[SfcStyleBlock],
+    transform_result: &mut TransformScriptsResult,
+    bindings_helper: &mut BindingsHelper,
+    errors: &mut Vec<TransformError>,
+) {
+    let mut stmts = Vec::new();
+
+    for style_block in style_blocks.iter() {
+        let Some(ref vars) = style_block.vars else {
+            continue;
+        };
+
+        let suggestion = suggest_v_bind(vars);
+        let kind = if bindings_helper.is_compat {
+            stmts.push(use_css_vars_stmt(vars));
+            CssErrorKind::DeprecatedStyleVars(suggestion)
+        } else {
+            CssErrorKind::StyleVarsWithoutCompat(suggestion)
+        };
+
+        errors.push(TransformError::CssError(CssError {
+            span: style_block.span,
+            kind,
+        }));
+    }
+
+    if stmts.is_empty() {
+        return;
+    }
+    bindings_helper.vue_imports |= VueImports::UseCssVars;
+
+    prepend_to_setup(transform_result, stmts);
+}
+
+/// Inserts the statements at the beginning of `setup`.
+/// Without `<script setup>`, they are merged into the `setup` option of the exported object,
+/// and `setup` is only created when the object does not have one.
+fn prepend_to_setup(transform_result: &mut TransformScriptsResult, stmts: Vec<Stmt>) {
+    if transform_result.setup_fn.is_none() {
+        if let Some(setup_body) = options_setup_body(&mut transform_result.export_obj) {
+            setup_body.stmts.splice(0..0, stmts);
+            return;
+        }
+    }
+
+    let setup_fn = transform_result.setup_fn.get_or_insert_with(|| {
+        Box::new(Function {
+            params: vec![],
+            decorators: vec![],
+            span: DUMMY_SP,
+            body: None,
+            is_generator: false,
+            is_async: false,
+            type_params: None,
+            return_type: None,
+        })
+    });
+    let setup_body = setup_fn.body.get_or_insert_with(|| BlockStmt {
+        span: DUMMY_SP,
+        stmts: Vec::with_capacity(stmts.len()),
+    });
+    setup_body.stmts.splice(0..0, stmts);
+}
+
+/// Finds the body of the user `setup`, e.g. `setup() {}` or `setup: () => {}`.
+/// Other forms, e.g. `setup: useFoo`, are wrapped as `setup: (...args) => { return useFoo(...args) }`.
+fn options_setup_body(export_obj: &mut ObjectLit) -> Option<&mut BlockStmt> {
+    let setup_prop = export_obj.props.iter_mut().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        let is_setup = match prop.as_ref() {
+            Prop::Method(MethodProp { key, .. }) | Prop::KeyValue(KeyValueProp { key, .. }) => {
+                is_setup_key(key)
+            }
+            Prop::Shorthand(ident) => ident.sym == "setup",
+            _ => false,
+        };
+        is_setup.then_some(prop)
+    })?;
+
+    // `{ setup }` -> `{ setup: setup }`
+    if let Prop::Shorthand(ref ident) = **setup_prop {
+        **setup_prop = Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(ident.to_owned()),
+            value: Box::new(Expr::Ident(ident.to_owned())),
+        });
+    }
+
+    let setup_value = match setup_prop.as_mut() {
+        Prop::Method(method_prop) => return method_prop.function.body.as_mut(),
+        Prop::KeyValue(key_value) => &mut key_value.value,
+        _ => return None,
+    };
+
+    if !matches!(**setup_value, Expr::Fn(_) | Expr::Arrow(_)) {
+        *setup_value = Box::new(wrap_setup(setup_value));
+    }
+
+    match setup_value.as_mut() {
+        Expr::Fn(fn_expr) => fn_expr.function.body.as_mut(),
+        Expr::Arrow(arrow_expr) => {
+            // `() => expr` -> `() => { return expr }`
+            if let BlockStmtOrExpr::Expr(ref mut body_expr) = *arrow_expr.body {
+                let arg = match *std::mem::replace(
+                    body_expr,
+                    Box::new(Expr::Invalid(Invalid { span: DUMMY_SP })),
+                ) {
+                    Expr::Paren(paren_expr) => paren_expr.expr,
+                    arg => Box::new(arg),
+                };
+                *arrow_expr.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(arg),
+                    })],
+                });
+            }
+
+            match *arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(ref mut block) => Some(block),
+                BlockStmtOrExpr::Expr(_) => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_setup_key(key: &PropName) -> bool {
+    match key {
+        PropName::Ident(ident) => ident.sym == "setup",
+        PropName::Str(s) => s.value == "setup",
+        _ => false,
+    }
+}
+
+/// `(...args) => { return setup(...args) }`
+fn wrap_setup(setup: &mut Box<Expr>) -> Expr {
+    let args = Ident {
+        span: DUMMY_SP,
+        sym: fervid_atom!("args"),
+        optional: false,
+    };
+    let setup = std::mem::replace(setup, Box::new(Expr::Invalid(Invalid { span: DUMMY_SP })));
+
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![Pat::Rest(RestPat {
+            span: DUMMY_SP,
+            dot3_token: DUMMY_SP,
+            arg: Box::new(Pat::Ident(BindingIdent {
+                id: args.to_owned(),
+                type_ann: None,
+            })),
+            type_ann: None,
+        })],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
+                        span: DUMMY_SP,
+                        expr: setup,
+                    }))),
+                    args: vec![ExprOrSpread {
+                        spread: Some(DUMMY_SP),
+                        expr: Box::new(Expr::Ident(args)),
+                    }],
+                    type_args: None,
+                }))),
+            })],
+        })),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
+/// `_useCssVars(_ctx => (vars))`
+fn use_css_vars_stmt(vars: &Expr) -> Stmt {
+    // Like in Vue 3.0, the variables are read from the component instance,
+    // thus every identifier is resolved as `_ctx.ident`
+    let mut vars = Box::new(vars.to_owned());
+    let mut ctx_bindings_helper = BindingsHelper::default();
+    ctx_bindings_helper.template_generation_mode = TemplateGenerationMode::Inline;
+    ctx_bindings_helper.transform_expr(&mut vars, 0);
+
+    let getter = Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![Pat::Ident(BindingIdent {
+            id: Ident {
+                span: DUMMY_SP,
+                sym: fervid_atom!("_ctx"),
+                optional: false,
+            },
+            type_ann: None,
+        })],
+        body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Paren(ParenExpr {
+            span: DUMMY_SP,
+            expr: vars,
+        })))),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    });
+
+    Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                span: DUMMY_SP,
+                sym: VueImports::UseCssVars.as_atom(),
+                optional: false,
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(getter),
+            }],
+            type_args: None,
+        })),
+    })
+}
+
+/// Suggests how to migrate `<style vars>` to `v-bind()` in CSS, e.g. for `vars="{ color }"`:
+/// "remove `vars` and replace `var(--color)` with `v-bind(color)`"
+fn suggest_v_bind(vars: &Expr) -> String {
+    let Expr::Object(vars_obj) = vars else {
+        return String::from("remove `vars` and use `v-bind()` in CSS instead of `var()`");
+    };
+
+    let replacements: Vec<String> = vars_obj
+        .props
+        .iter()
+        .filter_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                return None;
+            };
+
+            // `{ color }` -> `v-bind(color)`, `{ main: color }` -> `v-bind(color)`
+            let (css_var, binding) = match prop.as_ref() {
+                Prop::Shorthand(ident) => (&*ident.sym, &*ident.sym),
+                Prop::KeyValue(key_value) => {
+                    let css_var = match key_value.key {
+                        PropName::Ident(ref ident) => &*ident.sym,
+                        PropName::Str(ref s) => &*s.value,
+                        _ => return None,
+                    };
+                    match *key_value.value {
+                        Expr::Ident(ref ident) => (css_var, &*ident.sym),
+                        _ => (css_var, css_var),
+                    }
+                }
+                _ => return None,
+            };
+
+            Some(format!("`var(--{css_var})` with `v-bind({binding})`"))
+        })
+        .collect();
+
+    if replacements.is_empty() {
+        return String::from("remove `vars` and use `v-bind()` in CSS instead of `var()`");
+    }
+
+    format!("remove `vars` and replace {}", replacements.join(", "))
+}
+
+/// `const name = _useCssModule("name")`
+/// `_useCssModule("name")`
+pub(crate) fn use_css_module_call(module_name: &FervidAtom, span: Span) -> Expr {
+    Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+            span,
+            sym: VueImports::UseCssModule.as_atom(),
+            optional: false,
+        }))),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: module_name.to_owned(),
+                raw: None,
+            }))),
+        }],
+        type_args: None,
+    })
+}
+
 #[inline]
 pub fn should_transform_style_block(block: &SfcStyleBlock) -> bool {
     block.is_scoped && block.lang == "css"
 }
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{error::Severity, Interpolation, Node, SfcTemplateBlock};
+    use swc_core::{
+        common::{BytePos, Span},
+        ecma::ast::{Module, ObjectLit},
+    };
+
+    use crate::{
+        template::transform_and_record_template,
+        test_utils::{js, to_str},
+    };
+
+    use super::*;
+
+    fn style_module(module_name: Option<&str>, lo: u32) -> SfcStyleBlock {
+        SfcStyleBlock {
+            lang: "css".into(),
+            content: ".red { color: red }".into(),
+            is_scoped: false,
+            module_name: module_name.map(Into::into),
+            vars: None,
+            span: Span::new(BytePos(lo), BytePos(lo + 10), Default::default()),
+        }
+    }
+
+    fn inject(style_blocks: &[SfcStyleBlock]) -> (BindingsHelper, Vec<TransformError>) {
+        let mut bindings_helper = BindingsHelper::default();
+        let mut errors = Vec::new();
+        inject_css_modules(style_blocks, &mut bindings_helper, &mut errors);
+        (bindings_helper, errors)
+    }
+
+    fn transform_template(value: &str, bindings_helper: &mut BindingsHelper) -> String {
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![Node::Interpolation(Interpolation {
+                value: js(value),
+                template_scope: 0,
+                patch_flag: false,
+                span: DUMMY_SP,
+            })],
+            span: DUMMY_SP,
+        };
+        let mut errors = Vec::new();
+        transform_and_record_template(&mut sfc_template, bindings_helper, &mut errors);
+
+        let Node::Interpolation(ref interpolation) = sfc_template.roots[0] else {
+            unreachable!()
+        };
+        to_str(&interpolation.value)
+    }
+
+    #[test]
+    fn it_records_named_css_modules() {
+        // <style module="a"> and <style module="b">
+        let (bindings_helper, errors) =
+            inject(&[style_module(Some("a"), 1), style_module(Some("b"), 20)]);
+        assert!(errors.is_empty());
+        assert_eq!(
+            bindings_helper.css_modules,
+            vec![fervid_atom!("a"), fervid_atom!("b")]
+        );
+
+        // <style module> and <style module="classes">
+        let (bindings_helper, errors) = inject(&[
+            style_module(Some("$style"), 1),
+            style_module(Some("classes"), 20),
+        ]);
+        assert!(errors.is_empty());
+        assert_eq!(
+            bindings_helper.css_modules,
+            vec![fervid_atom!("$style"), fervid_atom!("classes")]
+        );
+
+        // Regular `<style>` is not a module
+        let (bindings_helper, errors) = inject(&[style_module(None, 1)]);
+        assert!(errors.is_empty());
+        assert!(bindings_helper.css_modules.is_empty());
+    }
+
+    #[test]
+    fn it_resolves_css_modules_in_template() {
+        let (mut bindings_helper, _) = inject(&[
+            style_module(Some("$style"), 1),
+            style_module(Some("classes"), 20),
+        ]);
+
+        // {{ $style.red + classes.blue }}
+        let code = transform_template("$style.red + classes.blue", &mut bindings_helper);
+        assert_eq!(
+            code,
+            r#"_useCssModule("$style").red+_useCssModule("classes").blue"#
+        );
+        assert!(bindings_helper.unresolved_references.is_empty());
+        assert!(bindings_helper
+            .vue_imports
+            .contains(VueImports::UseCssModule));
+    }
+
+    #[test]
+    fn it_prefers_bindings_over_css_modules() {
+        let (mut bindings_helper, _) = inject(&[style_module(Some("classes"), 1)]);
+
+        // `const classes = ...` in `<script setup>`
+        bindings_helper.setup_bindings.push(crate::SetupBinding(
+            fervid_atom!("classes"),
+            fervid_core::BindingTypes::SetupConst,
+        ));
+
+        let code = transform_template("classes.blue", &mut bindings_helper);
+        assert_eq!(code, "$setup.classes.blue");
+        assert!(!bindings_helper
+            .vue_imports
+            .contains(VueImports::UseCssModule));
+    }
+
+    #[test]
+    fn it_reports_duplicate_css_module_names() {
+        let (bindings_helper, errors) =
+            inject(&[style_module(Some("a"), 1), style_module(Some("a"), 20)]);
+
+        // The first module wins
+        assert_eq!(bindings_helper.css_modules.len(), 1);
+
+        assert_eq!(errors.len(), 1);
+        let TransformError::CssError(ref css_error) = errors[0] else {
+            panic!("Not a CSS error");
+        };
+        assert!(matches!(css_error.kind, CssErrorKind::DuplicateModuleName));
+        assert_eq!(css_error.span.lo, BytePos(20));
+    }
+
+    fn inject_vars(
+        vars: &str,
+        is_compat: bool,
+    ) -> (TransformScriptsResult, BindingsHelper, Vec<TransformError>) {
+        inject_vars_with_export(
+            vars,
+            is_compat,
+            ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            },
+        )
+    }
+
+    fn inject_vars_with_export(
+        vars: &str,
+        is_compat: bool,
+        export_obj: ObjectLit,
+    ) -> (TransformScriptsResult, BindingsHelper, Vec<TransformError>) {
+        let mut style_block = style_module(None, 1);
+        style_block.vars = Some(js(vars));
+
+        let mut transform_result = TransformScriptsResult {
+            module: Box::new(Module {
+                span: DUMMY_SP,
+                body: vec![],
+                shebang: None,
+            }),
+            export_obj,
+            setup_fn: None,
+        };
+        let mut bindings_helper = BindingsHelper::default();
+        bindings_helper.is_compat = is_compat;
+        let mut errors = Vec::new();
+        inject_legacy_style_vars(
+            &[style_block],
+            &mut transform_result,
+            &mut bindings_helper,
+            &mut errors,
+        );
+        (transform_result, bindings_helper, errors)
+    }
+
+    fn setup_code(transform_result: &TransformScriptsResult) -> String {
+        let body = transform_result
+            .setup_fn
+            .as_ref()
+            .and_then(|setup_fn| setup_fn.body.as_ref())
+            .expect("setup should have a body");
+        to_str(body)
+    }
+
+    fn css_error_kind(error: &TransformError) -> &CssErrorKind {
+        let TransformError::CssError(ref css_error) = error else {
+            panic!("Not a CSS error");
+        };
+        &css_error.kind
+    }
+
+    #[test]
+    fn it_compiles_legacy_style_vars() {
+        // <style vars="{ color }">
+        let (transform_result, bindings_helper, errors) = inject_vars("{ color }", true);
+        assert_eq!(
+            setup_code(&transform_result),
+            "{_useCssVars((_ctx)=>({color:_ctx.color}));}"
+        );
+        assert!(bindings_helper.vue_imports.contains(VueImports::UseCssVars));
+
+        // Deprecation is still reported
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            css_error_kind(&errors[0]),
+            CssErrorKind::DeprecatedStyleVars(_)
+        ));
+    }
+
+    #[test]
+    fn it_compiles_multiple_legacy_style_vars() {
+        // <style vars="{ color, size: fontSize, height: lineHeight * 2 }">
+        let (transform_result, _, errors) =
+            inject_vars("{ color, size: fontSize, height: lineHeight * 2 }", true);
+        assert_eq!(
+            setup_code(&transform_result),
+            "{_useCssVars((_ctx)=>({color:_ctx.color,size:_ctx.fontSize,height:_ctx.lineHeight*2}));}"
+        );
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn it_merges_legacy_style_vars_into_options_setup() {
+        let test = |export_obj: &str, expected: &str| {
+            let Expr::Object(export_obj) = *js(export_obj) else {
+                panic!("Not an object");
+            };
+            let (transform_result, _, _) = inject_vars_with_export("{ color }", true, export_obj);
+
+            // User `setup` is kept and no other `setup` is added
+            assert!(transform_result.setup_fn.is_none());
+            let code = to_str(&transform_result.export_obj).replace(' ', "");
+            assert_eq!(code, expected);
+        };
+
+        test(
+            "{ data: () => ({}), setup() { return { a: 1 } } }",
+            "{data:()=>({}),setup(){_useCssVars((_ctx)=>({color:_ctx.color}));return{a:1};}}",
+        );
+        test(
+            "{ setup: function (props) { return {} } }",
+            "{setup:function(props){_useCssVars((_ctx)=>({color:_ctx.color}));return{};}}",
+        );
+        test(
+            "{ setup: (props) => ({ a: props.a }) }",
+            "{setup:(props)=>{_useCssVars((_ctx)=>({color:_ctx.color}));return{a:props.a};}}",
+        );
+        test(
+            "{ setup }",
+            "{setup:(...args)=>{_useCssVars((_ctx)=>({color:_ctx.color}));return(setup)(...args);}}",
+        );
+    }
+
+    #[test]
+    fn it_rejects_legacy_style_vars_without_compat() {
+        let (transform_result, bindings_helper, errors) = inject_vars("{ color }", false);
+        assert!(transform_result.setup_fn.is_none());
+        assert!(!bindings_helper.vue_imports.contains(VueImports::UseCssVars));
+
+        assert_eq!(errors.len(), 1);
+        let TransformError::CssError(ref css_error) = errors[0] else {
+            panic!("Not a CSS error");
+        };
+        assert!(matches!(
+            css_error.kind,
+            CssErrorKind::StyleVarsWithoutCompat(_)
+        ));
+        assert!(css_error.is_recoverable_error());
+    }
+
+    #[test]
+    fn it_suggests_v_bind_for_legacy_style_vars() {
+        let (_, _, errors) = inject_vars("{ color, size: fontSize }", true);
+        let CssErrorKind::DeprecatedStyleVars(suggestion) = css_error_kind(&errors[0]) else {
+            panic!("Not a deprecation");
+        };
+        assert_eq!(
+            suggestion,
+            "remove `vars` and replace `var(--color)` with `v-bind(color)`, `var(--size)` with `v-bind(fontSize)`"
+        );
+
+        // Same suggestion when `vars` are not supported
+        let (_, _, errors) = inject_vars("{ color }", false);
+        let CssErrorKind::StyleVarsWithoutCompat(suggestion) = css_error_kind(&errors[0]) else {
+            panic!("Not a compat error");
+        };
+        assert!(suggestion.contains("`v-bind(color)`"));
+    }
+}
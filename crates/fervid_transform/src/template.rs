@@ -1,11 +1,15 @@
 //! Handles template AST transformations.
 
 mod ast_transform;
+mod bindings_check;
 mod collect_vars;
 mod expr_transform;
 mod js_builtins;
 mod resolutions;
+mod static_v_for;
 mod v_on;
 mod utils;
 
 pub use ast_transform::transform_and_record_template;
+pub use bindings_check::check_template_bindings;
+pub use static_v_for::{collect_static_lists, unroll_static_v_for};
@@ -1,39 +1,68 @@
 use fervid_core::{
     check_attribute_name, fervid_atom, is_from_default_slot, is_html_tag, AttributeOrBinding,
     BindingTypes, BuiltinType, Conditional, ConditionalNodeSequence, ElementKind, ElementNode,
-    FervidAtom, Interpolation, Node, PatchFlags, SfcTemplateBlock, StartingTag, StrOrExpr,
-    TemplateGenerationMode, VBindDirective, VSlotDirective, VUE_BUILTINS,
+    FervidAtom, Interpolation, Node, PatchFlags, PatchHints, SfcTemplateBlock, StartingTag,
+    StrOrExpr, TemplateGenerationMode, VBindDirective, VModelDirective, VSlotDirective,
+    VueDirectives, WhitespaceMode, VUE_BUILTINS,
 };
+use fxhash::FxHashMap as HashMap;
 use smallvec::SmallVec;
 use swc_core::{
-    common::DUMMY_SP,
-    ecma::ast::{Bool, Expr, Ident, Lit},
+    common::{Span, DUMMY_SP},
+    ecma::ast::{
+        Bool, CallExpr, Callee, ComputedPropName, Expr, ExprOrSpread, Ident, Invalid, Lit,
+        MemberExpr, MemberProp,
+    },
 };
 
-use crate::{BindingsHelper, TemplateScope};
+use crate::{
+    atoms::SANITIZE_URL,
+    error::{TemplateError, TemplateErrorKind, TransformError},
+    BindingsHelper, PropType, PropTypes, TemplateScope,
+};
 
-use super::{collect_vars::collect_variables, expr_transform::BindingsHelperTransform};
+use super::{
+    collect_vars::{collect_references, collect_variables},
+    expr_transform::BindingsHelperTransform,
+    static_v_for::unroll_static_v_for,
+    utils::{to_camel_case, to_pascal_case},
+    v_on::unroll_v_on_event_arrays,
+};
 
 pub struct TemplateVisitor<'s> {
     pub bindings_helper: &'s mut BindingsHelper,
     pub current_scope: u32,
     pub v_for_scope: bool,
+    /// Whether the currently visited node is a direct child of `<TransitionGroup>`
+    pub is_transition_group_child: bool,
+    /// Whether the currently visited node is inside `<pre>`, where whitespace is preserved
+    pub is_in_pre: bool,
+    pub errors: Vec<TransformError>,
 }
 
 /// Transforms the AST template by using information from [`BindingsHelper`].
 ///
 /// The transformations tackled:
-/// - Optimizing the tree by removing white-space nodes;
+/// - Optimizing the tree by removing and condensing white-space (see [`WhitespaceMode`]);
 /// - Folding the conditional nodes (`v-if`, etc.) into a single `ConditionalNode`;
-/// - Transforming Js expressions by resolving variables inside them.
+/// - Transforming Js expressions by resolving variables inside them;
+/// - Unrolling `v-for` over static lists when `unroll_static_for` is enabled.
 pub fn transform_and_record_template(
     template: &mut SfcTemplateBlock,
     bindings_helper: &mut BindingsHelper,
+    errors: &mut Vec<TransformError>,
 ) {
+    // Must happen before the scopes are assigned, because unrolled `v-for`s do not create any
+    if bindings_helper.unroll_static_for {
+        unroll_static_v_for(&mut template.roots, &bindings_helper.static_lists);
+    }
+
     // Optimize conditional sequences within template root
+    condense_whitespace(&mut template.roots, bindings_helper.whitespace);
     optimize_children(&mut template.roots, ElementKind::Element);
 
     // Merge more than 1 child into a separate `<template>` element so that Fragment gets generated.
+    // The roots never change their order, thus it is `64 /* STABLE_FRAGMENT */`.
     // #11: Do this only when all children are `TextNode`s.
     if template.roots.len() > 1
         && !template
@@ -51,65 +80,125 @@ pub fn transform_and_record_template(
             },
             children: all_roots,
             template_scope: 0,
-            patch_hints: Default::default(),
+            patch_hints: PatchHints {
+                flags: PatchFlags::StableFragment.into(),
+                ..Default::default()
+            },
             span: template.span,
         });
         template.roots.push(new_root);
     }
 
-    let mut template_visitor = TemplateVisitor {
-        bindings_helper,
-        current_scope: 0,
-        v_for_scope: false,
-    };
+    let mut template_visitor = TemplateVisitor::new(bindings_helper);
 
     for node in template.roots.iter_mut() {
         node.visit_mut_with(&mut template_visitor);
     }
+
+    errors.append(&mut template_visitor.errors);
+
+    // `$parent` and `$root` accesses are collected while transforming the expressions
+    errors.extend(
+        template_visitor
+            .bindings_helper
+            .deprecated_globals_spans
+            .drain(..)
+            .map(|span| {
+                TransformError::TemplateError(TemplateError {
+                    span,
+                    kind: TemplateErrorKind::DeprecatedGlobalAccess,
+                })
+            }),
+    );
 }
 
-/// Optimizes the children by removing whitespace in between `ElementNode`s,
-/// as well as folding `v-if`/`v-else-if`/`v-else` sequences into a `ConditionalNodeSequence`
-fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
-    let children_len = children.len();
+/// Removes and condenses the whitespace the same way `condenseWhitespace` of the official compiler does.
+/// https://github.com/vuejs/core/blob/v3.4.21/packages/compiler-core/src/parser.ts
+fn condense_whitespace(children: &mut Vec<Node>, mode: WhitespaceMode) {
+    let should_condense = matches!(mode, WhitespaceMode::Condense);
+    let mut should_keep = Vec::with_capacity(children.len());
 
-    // Discard children mask, limited to 128 children. 0 means to preserve the node, 1 to discard
-    let mut discard_mask: u128 = 0;
+    for index in 0..children.len() {
+        let Node::Text(ref text, span) = children[index] else {
+            should_keep.push(true);
+            continue;
+        };
 
-    // Filter out whitespace text nodes at the beginning and end of ElementNode
-    match children.first() {
-        Some(Node::Text(v, _)) if v.trim().is_empty() => {
-            discard_mask |= 1 << 0;
-        }
-        _ => {}
-    }
-    match children.last() {
-        Some(Node::Text(v, _)) if v.trim().is_empty() => {
-            discard_mask |= 1 << (children_len - 1);
+        // Consecutive whitespace in text is condensed down to a single space
+        if !text.chars().all(is_html_whitespace) {
+            if should_condense {
+                if let Some(condensed) = condense_text(text) {
+                    children[index] = Node::Text(FervidAtom::from(condensed), span);
+                }
+            }
+            should_keep.push(true);
+            continue;
         }
-        _ => {}
-    }
 
-    // For removing the middle whitespace text nodes, we need sliding windows of three nodes
-    for (index, window) in children.windows(3).enumerate() {
-        match window {
-            [Node::Element(_) | Node::Comment(_, _), Node::Text(middle, _), Node::Element(_) | Node::Comment(_, _)]
-                if middle.trim().is_empty() =>
-            {
-                discard_mask |= 1 << (index + 1);
+        // Whitespace-only text is removed when it is the first or the last node.
+        // When condensing, it is also removed when it is between comments, between a comment and an element,
+        // or between elements if it contains a newline.
+        let prev = index
+            .checked_sub(1)
+            .and_then(|prev_index| children.get(prev_index));
+        let next = children.get(index + 1);
+        let should_remove = match (prev, next) {
+            (None, _) | (_, None) => true,
+            _ if !should_condense => false,
+            (Some(Node::Comment(_, _)), Some(Node::Comment(_, _) | Node::Element(_))) => true,
+            (Some(Node::Element(_)), Some(Node::Comment(_, _))) => true,
+            (Some(Node::Element(_)), Some(Node::Element(_))) => {
+                text.contains(|c| c == '\n' || c == '\r')
             }
-            _ => {}
+            _ => false,
+        };
+
+        // Otherwise, whitespace is condensed into a single space
+        if !should_remove {
+            children[index] = Node::Text(fervid_atom!(" "), span);
         }
+        should_keep.push(!should_remove);
     }
 
-    // Retain based on discard_mask. If a discard bit at `index` is set to 1, the node will be dropped
     let mut index = 0;
     children.retain(|_| {
-        let should_retain = discard_mask & (1 << index) == 0;
+        let keep = should_keep[index];
         index += 1;
-        should_retain
+        keep
     });
+}
+
+/// Replaces each whitespace sequence with a single space, or returns `None` if there is nothing to replace
+fn condense_text(text: &str) -> Option<String> {
+    let needs_condensing =
+        text.contains(|c: char| c != ' ' && is_html_whitespace(c)) || text.contains("  ");
+    if !needs_condensing {
+        return None;
+    }
+
+    let mut condensed = String::with_capacity(text.len());
+    let mut is_prev_whitespace = false;
+    for c in text.chars() {
+        let is_whitespace = is_html_whitespace(c);
+        if !is_whitespace {
+            condensed.push(c);
+        } else if !is_prev_whitespace {
+            condensed.push(' ');
+        }
+        is_prev_whitespace = is_whitespace;
+    }
+
+    Some(condensed)
+}
+
+/// Unlike `char::is_whitespace`, this does not include `&nbsp;`
+#[inline]
+fn is_html_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0C')
+}
 
+/// Optimizes the children by folding `v-if`/`v-else-if`/`v-else` sequences into a `ConditionalNodeSequence`
+fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
     // For components, reorder children so that named slots come first
     if matches!(element_kind, ElementKind::Component) && children.len() > 0 {
         children.sort_by(|a, b| {
@@ -125,11 +214,18 @@ fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
         let mut seq: Option<ConditionalNodeSequence> = None;
         let mut new_children = Vec::with_capacity(children.len());
 
+        // Whitespace after a conditional node, e.g. `<h1 v-if="a"></h1> <h2 v-else></h2>`.
+        // It is dropped when the sequence continues and kept otherwise.
+        let mut pending_whitespace: Option<Node> = None;
+
         /// Finishes the sequence. Pass `child` to also push the current child
         macro_rules! finish_seq {
             () => {
                 if let Some(seq) = seq.take() {
-                    new_children.push(Node::ConditionalSeq(seq))
+                    new_children.push(optimize_slot_existence_check(seq))
+                }
+                if let Some(whitespace) = pending_whitespace.take() {
+                    new_children.push(whitespace)
                 }
             };
             ($child: expr) => {
@@ -151,14 +247,18 @@ fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
 
         for mut child in children.drain(..) {
             // Only process `ElementNode`s.
-            // Otherwise, when we have an `if` node, ignore `Comment`s and finish sequence.
+            // Otherwise, when we have an `if` node, ignore `Comment`s and whitespace, and finish sequence.
             let Node::Element(child_element) = &mut child else {
-                if let (Node::Comment(_, _), Some(_)) = (&child, seq.as_ref()) {
-                    continue;
-                } else {
-                    finish_seq!(child);
-                    continue;
+                match (&child, seq.as_ref()) {
+                    (Node::Comment(_, _), Some(_)) => {}
+                    (Node::Text(text, _), Some(_)) if text.chars().all(is_html_whitespace) => {
+                        pending_whitespace = Some(child);
+                    }
+                    _ => {
+                        finish_seq!(child);
+                    }
                 }
+                continue;
             };
 
             let Some(ref mut directives) = child_element.starting_tag.directives else {
@@ -189,6 +289,7 @@ fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
                     continue;
                 };
 
+                pending_whitespace = None;
                 seq.else_if_nodes.push(Conditional {
                     condition: *v_else_if,
                     node: deref_element!(child),
@@ -204,6 +305,7 @@ fn optimize_children(children: &mut Vec<Node>, element_kind: ElementKind) {
                     continue;
                 };
 
+                pending_whitespace = None;
                 cond_seq.else_node = Some(Box::new(deref_element!(child)));
 
                 // `else` node always finishes the sequence
@@ -270,6 +372,79 @@ fn optimize_v_if_plus_v_for(mut parent: ElementNode) -> ElementNode {
     child
 }
 
+/// Drops the redundant check in `<template v-if="$slots.foo"><slot name="foo" /></template>`.
+/// A slot outlet without fallback content renders nothing when the slot is not passed,
+/// so a single `renderSlot` call is enough.
+fn optimize_slot_existence_check(seq: ConditionalNodeSequence) -> Node {
+    if !seq.else_if_nodes.is_empty() || seq.else_node.is_some() {
+        return Node::ConditionalSeq(seq);
+    }
+
+    let Some(checked_slot_name) = get_checked_slot_name(&seq.if_node.condition) else {
+        return Node::ConditionalSeq(seq);
+    };
+
+    let slot_outlet = &seq.if_node.node;
+    if slot_outlet.starting_tag.tag_name != "slot" {
+        return Node::ConditionalSeq(seq);
+    }
+
+    // Fallback content must not be rendered when the slot is missing
+    let has_fallback = slot_outlet
+        .children
+        .iter()
+        .any(|child| !matches!(child, Node::Text(text, _) if text.trim().is_empty()));
+    let has_directives = slot_outlet
+        .starting_tag
+        .directives
+        .as_ref()
+        .is_some_and(|d| d.v_for.is_some() || d.v_once.is_some() || !d.custom.is_empty());
+    if has_fallback || has_directives {
+        return Node::ConditionalSeq(seq);
+    }
+
+    // Static name only, `default` when omitted
+    let mut slot_name = Some(fervid_atom!("default"));
+    for attr in slot_outlet.starting_tag.attributes.iter() {
+        match attr {
+            AttributeOrBinding::RegularAttribute { name, value, .. } if name == "name" => {
+                slot_name = Some(value.to_owned());
+            }
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(name)),
+                ..
+            }) if name == "name" => {
+                slot_name = None;
+            }
+            _ => {}
+        }
+    }
+    if slot_name != Some(checked_slot_name) {
+        return Node::ConditionalSeq(seq);
+    }
+
+    Node::Element(seq.if_node.node)
+}
+
+/// `foo` in `$slots.foo` or `$slots['foo']`
+fn get_checked_slot_name(condition: &Expr) -> Option<FervidAtom> {
+    let Expr::Member(MemberExpr { obj, prop, .. }) = condition else {
+        return None;
+    };
+    if !obj.as_ident().is_some_and(|obj| &obj.sym == "$slots") {
+        return None;
+    }
+
+    match prop {
+        MemberProp::Ident(prop_ident) => Some(prop_ident.sym.to_owned()),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match **expr {
+            Expr::Lit(Lit::Str(ref prop_str)) => Some(prop_str.value.to_owned()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 trait Visitor {
     fn visit_element_node(&mut self, element_node: &mut ElementNode);
     fn visit_conditional_node(&mut self, conditional_node: &mut ConditionalNodeSequence);
@@ -285,6 +460,17 @@ impl<'a> Visitor for TemplateVisitor<'_> {
         let parent_scope = self.current_scope;
         let mut scope_to_use = parent_scope;
 
+        // `v-pre` takes precedence over everything else, its subtree is left raw
+        let is_v_pre = element_node
+            .starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|d| d.v_pre.is_some());
+        if is_v_pre {
+            self.mark_v_pre_subtree(element_node);
+            return;
+        }
+
         // Mark the node with a correct type (element, component or built-in)
         let element_kind = self.recognize_element_kind(&element_node.starting_tag);
         let is_component = matches!(element_kind, ElementKind::Component);
@@ -292,8 +478,26 @@ impl<'a> Visitor for TemplateVisitor<'_> {
 
         if is_component {
             self.maybe_resolve_component(&element_node.starting_tag.tag_name);
+            self.check_component_prop_types(&element_node.starting_tag);
+            self.mark_class_and_style_props(element_node);
+            self.desugar_component_v_slot(element_node);
+        } else if matches!(element_kind, ElementKind::Element)
+            && element_node.starting_tag.tag_name != "template"
+            && element_node
+                .starting_tag
+                .directives
+                .as_ref()
+                .is_some_and(|d| d.v_slot.is_some())
+        {
+            self.report_error(element_node.span, TemplateErrorKind::VSlotMisplaced);
         }
 
+        // `@[['click', 'hover']]` is the same as `@click` and `@hover`
+        unroll_v_on_event_arrays(&mut element_node.starting_tag.attributes);
+
+        // Expressions are compared before they get transformed
+        self.check_manual_two_way_binding(&element_node.starting_tag, element_kind);
+
         // `v-for` has special behavior with `ref`
         let old_v_for_scope = self.v_for_scope;
 
@@ -333,10 +537,19 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                 let mut scope = &mut self.bindings_helper.template_scopes[scope_to_use as usize];
                 collect_variables(&v_for.itervar, &mut scope);
 
-                // Transform the iterable
+                // Transform the iterable.
+                // It is evaluated outside the loop, so the iterator variables must not shadow it,
+                // e.g. in `v-for="item in item.children"` the iterable `item` is an outer binding
                 let is_dynamic = self
                     .bindings_helper
-                    .transform_expr(&mut v_for.iterable, scope_to_use);
+                    .transform_expr(&mut v_for.iterable, parent_scope);
+
+                // Look for `key`. Fragment is either keyed or unkeyed.
+                let has_key = element_node
+                    .starting_tag
+                    .attributes
+                    .iter()
+                    .any(|attr| check_attribute_name(attr, "key"));
 
                 // Add patch flags
                 if !is_dynamic {
@@ -344,19 +557,22 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                     // when iterable is non-dynamic (number, string) (`v-for="i in 3"`)
                     v_for.patch_flags |= PatchFlags::StableFragment;
                 } else {
-                    // Look for `key`. Fragment is either keyed or unkeyed.
-                    let has_key = element_node
-                        .starting_tag
-                        .attributes
-                        .iter()
-                        .any(|attr| check_attribute_name(attr, "key"));
-
                     v_for.patch_flags |= if has_key {
                         PatchFlags::KeyedFragment
                     } else {
                         PatchFlags::UnkeyedFragment
                     };
                 }
+
+                // FLIP animations of `<TransitionGroup>` rely on keys, even for static lists
+                if !has_key && (is_dynamic || self.is_transition_group_child) {
+                    let kind = if self.is_transition_group_child {
+                        TemplateErrorKind::VForMissingKeyInTransitionGroup
+                    } else {
+                        TemplateErrorKind::VForMissingKey
+                    };
+                    self.report_error(v_for.span, kind);
+                }
             }
 
             // Collect `v-slot` bindings
@@ -370,9 +586,10 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                     collect_variables(v_slot_value, &mut scope);
                 }
 
-                // Transform `v-slot` argument if it is dynamic
+                // Transform `v-slot` argument if it is dynamic.
+                // Same as `v-for` iterable, it is not affected by the slot props
                 if let Some(StrOrExpr::Expr(expr)) = slot_name {
-                    self.bindings_helper.transform_expr(expr, scope_to_use);
+                    self.bindings_helper.transform_expr(expr, parent_scope);
                 }
             }
         }
@@ -395,6 +612,27 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                 //    If there is, check if it is a component
                 // 2. Check if
                 AttributeOrBinding::VBind(v_bind) => {
+                    // `:foo-bar.camel` binds `fooBar`
+                    if v_bind.is_camel {
+                        if let Some(StrOrExpr::Str(ref mut argument)) = v_bind.argument {
+                            let mut camelized = String::with_capacity(argument.len());
+                            to_camel_case(argument, &mut camelized);
+                            *argument = camelized.into();
+                        }
+                    }
+
+                    if let Some(StrOrExpr::Expr(ref dynamic_argument)) = v_bind.argument {
+                        self.check_v_bind_dynamic_argument(
+                            dynamic_argument,
+                            scope_to_use,
+                            v_bind.span,
+                        );
+                    }
+
+                    if matches!(element_kind, ElementKind::Element) {
+                        self.check_url_binding(v_bind);
+                    }
+
                     let has_bindings = self
                         .bindings_helper
                         .transform_expr(&mut v_bind.value, scope_to_use);
@@ -446,7 +684,7 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                     // They are added to PROPS for the components.
                     if is_component {
                         patch_hints.flags |= PatchFlags::Props;
-                        patch_hints.props.push(argument.to_owned());
+                        patch_hints.props.push(prefixed_prop_name(argument, v_bind));
                         continue;
                     }
 
@@ -456,7 +694,7 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                         patch_hints.flags |= PatchFlags::Style;
                     } else {
                         patch_hints.flags |= PatchFlags::Props;
-                        patch_hints.props.push(argument.to_owned());
+                        patch_hints.props.push(prefixed_prop_name(argument, v_bind));
                     }
                 }
 
@@ -470,6 +708,10 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                         should_use_block = true;
                     }
 
+                    self.check_v_on_handler(v_on);
+                    self.check_v_on_native_modifier(v_on, element_kind);
+                    self.check_v_on_touch_stop_modifier(v_on);
+                    self.check_v_on_mouse_button_modifier(v_on);
                     self.transform_v_on(v_on, scope_to_use);
 
                     // TODO Transform the event name beforehand (?) and make sure the condition is 100% the same
@@ -491,6 +733,11 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                         has_vnode_hook |= has_v_node;
                     } else {
                         // https://github.com/vuejs/core/blob/f1068fc60ca511f68ff0aaedcc18b39124791d29/packages/compiler-core/src/transforms/transformElement.ts#L605
+                        // `v-on="handlers"` provides unknown keys, same as `v-bind="obj"`
+                        patch_hints.flags &=
+                            !(PatchFlags::Props | PatchFlags::Class | PatchFlags::Style);
+                        patch_hints.flags |= PatchFlags::FullProps;
+                        patch_hints.props.clear();
                         has_dynamic_keys = true;
                     }
                 }
@@ -552,12 +799,34 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                     }
                 };
             }
-            maybe_transform!(v_html);
             maybe_transform!(v_memo);
             maybe_transform!(v_show);
-            maybe_transform!(v_text);
 
+            // `v-text` and `v-html` are generated as the `textContent` and `innerHTML` props
+            let v_text_has_bindings = maybe_transform!(v_text);
+            let v_html_has_bindings = maybe_transform!(v_html);
+            if !patch_hints.flags.contains(PatchFlags::FullProps) {
+                if v_text_has_bindings {
+                    patch_hints.flags |= PatchFlags::Props;
+                    patch_hints.props.push(fervid_atom!("textContent"));
+                }
+                if v_html_has_bindings {
+                    patch_hints.flags |= PatchFlags::Props;
+                    patch_hints.props.push(fervid_atom!("innerHTML"));
+                }
+            }
+
+            let is_element = matches!(element_kind, ElementKind::Element);
+            let tag_name = &element_node.starting_tag.tag_name;
+            let is_input = is_element && tag_name == "input";
             for v_model in directives.v_model.iter_mut() {
+                if is_element {
+                    self.check_v_model_element(tag_name, v_model);
+                }
+                if is_input {
+                    self.check_v_model_input_type(&element_node.starting_tag.attributes, v_model);
+                }
+                self.check_v_model_target(v_model, element_kind, scope_to_use);
                 self.bindings_helper
                     .transform_v_model(v_model, scope_to_use, patch_hints);
             }
@@ -587,7 +856,11 @@ impl<'a> Visitor for TemplateVisitor<'_> {
             }
         }
 
-        // Merge conditional nodes and clean up whitespace
+        // Clean up whitespace and merge conditional nodes
+        let is_in_pre = self.is_in_pre || element_node.starting_tag.tag_name == "pre";
+        if !is_in_pre {
+            condense_whitespace(&mut element_node.children, self.bindings_helper.whitespace);
+        }
         optimize_children(&mut element_node.children, element_kind);
 
         // Patch flag for HTML elements which only contain interpolation and text,
@@ -598,6 +871,14 @@ impl<'a> Visitor for TemplateVisitor<'_> {
         let mut has_dynamic_interpolation = false;
 
         // Recursively visit children
+        let old_is_transition_group_child = std::mem::replace(
+            &mut self.is_transition_group_child,
+            matches!(
+                element_kind,
+                ElementKind::Builtin(BuiltinType::TransitionGroup)
+            ),
+        );
+        let old_is_in_pre = std::mem::replace(&mut self.is_in_pre, is_in_pre);
         for child in element_node.children.iter_mut() {
             child.visit_mut_with(self);
 
@@ -615,6 +896,8 @@ impl<'a> Visitor for TemplateVisitor<'_> {
                 Node::Text(_, _) | Node::Comment(_, _) => {}
             }
         }
+        self.is_transition_group_child = old_is_transition_group_child;
+        self.is_in_pre = old_is_in_pre;
 
         // Add `ref_for` and `ref_key`
         if has_ref && self.v_for_scope {
@@ -645,6 +928,15 @@ impl<'a> Visitor for TemplateVisitor<'_> {
         }
         self.v_for_scope = old_v_for_scope;
 
+        // Slots with the names only known at runtime cannot be compared on patch
+        if matches!(
+            element_kind,
+            ElementKind::Component | ElementKind::Builtin(_)
+        ) && has_dynamic_slot_name(&element_node.starting_tag, &element_node.children)
+        {
+            patch_hints.flags |= PatchFlags::DynamicSlots;
+        }
+
         // Apply other flags
         // https://github.com/vuejs/core/blob/ee4cd78a06e6aa92b12564e527d131d1064c2cd0/packages/compiler-core/src/transforms/transformElement.ts#L732
         if !has_dynamic_keys && has_hydration_event_binding {
@@ -699,12 +991,38 @@ impl<'a> Visitor for TemplateVisitor<'_> {
     }
 }
 
+impl<'s> TemplateVisitor<'s> {
+    pub fn new(bindings_helper: &'s mut BindingsHelper) -> Self {
+        TemplateVisitor {
+            bindings_helper,
+            current_scope: 0,
+            v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Reports an error found in the `<template>`
+    pub(crate) fn report_error(&mut self, span: Span, kind: TemplateErrorKind) {
+        self.errors
+            .push(TransformError::TemplateError(TemplateError { span, kind }));
+    }
+}
+
 impl TemplateVisitor<'_> {
     // TODO Maybe do this in parser instead, because it sometimes needs this info
     fn recognize_element_kind(&self, starting_tag: &StartingTag) -> ElementKind {
         let tag_name = &starting_tag.tag_name;
 
-        // First, check for a built-in
+        // Custom elements take precedence, same as in the official compiler
+        if let Some(ref is_custom_element) = self.bindings_helper.is_custom_element {
+            if is_custom_element.matches(tag_name) {
+                return ElementKind::Element;
+            }
+        }
+
+        // Then check for a built-in
         if let Some(builtin_type) = VUE_BUILTINS.get(&tag_name) {
             // Special case for `<component>`. If it does not have `is`, this is not a built-in
             if tag_name.eq("component") {
@@ -728,147 +1046,2163 @@ impl TemplateVisitor<'_> {
             ElementKind::Component
         }
     }
-}
-
-impl VisitMut for Node {
-    fn visit_mut_with(&mut self, visitor: &mut impl Visitor) {
-        match self {
-            Node::Element(el) => visitor.visit_element_node(el),
-            Node::ConditionalSeq(cond) => visitor.visit_conditional_node(cond),
-            Node::Interpolation(interpolation) => visitor.visit_interpolation(interpolation),
-            _ => {}
-        }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use fervid_core::{ElementKind, Node, PatchHints, VForDirective, VueDirectives};
-    use swc_core::common::DUMMY_SP;
+    /// Dynamic argument of `v-bind` must evaluate to a string or `null`.
+    /// Only literals can be checked at compile-time. Other expressions only produce a hint
+    /// when they use a variable unknown to the compiler.
+    fn check_v_bind_dynamic_argument(&mut self, argument: &Expr, scope_to_use: u32, span: Span) {
+        let kind = match argument {
+            // Always valid
+            Expr::Lit(Lit::Str(_) | Lit::Null(_)) | Expr::Tpl(_) => return,
 
-    use crate::test_utils::{js, to_str};
+            Expr::Paren(paren) => {
+                return self.check_v_bind_dynamic_argument(&paren.expr, scope_to_use, span)
+            }
 
-    use super::*;
+            // Never a string or `null`
+            Expr::Lit(_)
+            | Expr::Array(_)
+            | Expr::Object(_)
+            | Expr::Arrow(_)
+            | Expr::Fn(_)
+            | Expr::Class(_) => TemplateErrorKind::VBindDynamicArgumentInvalid,
+
+            _ => {
+                let has_unknown_reference = collect_references(argument).iter().any(|reference| {
+                    matches!(
+                        self.bindings_helper
+                            .get_var_binding_type(scope_to_use, reference),
+                        BindingTypes::Unresolved
+                    )
+                });
+                if !has_unknown_reference {
+                    return;
+                }
 
-    /// Special case: `<component>` without `is` attribute is not a builtin
-    #[test]
-    fn it_distinguishes_component_builtin_and_not() {
-        let starting_tag = StartingTag {
-            tag_name: "component".into(),
-            attributes: vec![],
-            directives: None,
+                TemplateErrorKind::VBindDynamicArgumentUnknown
+            }
         };
 
-        let mut bindings_helper = Default::default();
-        let template_visitor = TemplateVisitor {
-            bindings_helper: &mut bindings_helper,
-            current_scope: 0,
-            v_for_scope: false,
-        };
-        assert!(matches!(
-            template_visitor.recognize_element_kind(&starting_tag),
-            ElementKind::Component
-        ));
+        self.report_error(span, kind);
     }
 
-    #[test]
-    fn it_folds_basic_seq() {
-        // <template><div>
-        //   text
-        //   <h1 v-if="true">if</h1>
-        //   <h2 v-else-if="foo">else-if</h2>
-        //   <h3 v-else>else</h3>
-        // </div></template>
-        let mut sfc_template = SfcTemplateBlock {
-            lang: "html".into(),
-            roots: vec![Node::Element(ElementNode {
-                starting_tag: StartingTag {
-                    tag_name: "div".into(),
-                    attributes: vec![],
-                    directives: None,
-                },
-                children: vec![text_node(), if_node(), else_if_node(), else_node()],
-                template_scope: 0,
-                kind: ElementKind::Element,
-                patch_hints: Default::default(),
-                span: DUMMY_SP,
-            })],
-            span: DUMMY_SP,
+    /// Dynamic `:href`, `:src` and `:action` may lead to `javascript:` URL injection.
+    /// Depending on `sanitize_href`, they are either wrapped in `sanitizeUrl()` or reported.
+    /// Literal URLs are left as-is.
+    ///
+    /// No import is generated for `sanitizeUrl`, it must be provided by the user,
+    /// e.g. imported in `<script setup>` or registered in `app.config.globalProperties`.
+    /// It is resolved as any other template identifier.
+    fn check_url_binding(&mut self, v_bind: &mut VBindDirective) {
+        let Some(StrOrExpr::Str(ref argument)) = v_bind.argument else {
+            return;
         };
+        if !matches!(&**argument, "href" | "src" | "action") || is_literal_url(&v_bind.value) {
+            return;
+        }
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        let span = v_bind.span;
+        if !self.bindings_helper.sanitize_href {
+            self.report_error(span, TemplateErrorKind::VBindUnsanitizedUrl);
+            return;
+        }
 
-        // Template roots: one div
-        assert_eq!(1, sfc_template.roots.len());
-        let Node::Element(ref div) = sfc_template.roots[0] else {
-            panic!("Root is not an element")
-        };
+        // `sanitizeUrl(value)`, where `sanitizeUrl` is resolved as any other template variable
+        let value = std::mem::replace(&mut v_bind.value, Box::new(Expr::Invalid(Invalid { span })));
+        v_bind.value = Box::new(Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                span,
+                sym: SANITIZE_URL.to_owned(),
+                optional: false,
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: value,
+            }],
+            type_args: None,
+        }));
+    }
 
-        // Text and conditional seq
-        assert_eq!(2, div.children.len());
-        check_text_node(&div.children[0]);
-        let Node::ConditionalSeq(seq) = &div.children[1] else {
-            panic!("Not a conditional sequence")
-        };
+    /// Checks that the `v-model` value can be assigned to.
+    /// Must be called before the `v-model` is transformed.
+    ///
+    /// Named models of components, e.g. `<MyComp v-model:title="title">`, are always valid,
+    /// because the component itself decides when `update:title` is emitted.
+    fn check_v_model_target(
+        &mut self,
+        v_model: &VModelDirective,
+        element_kind: ElementKind,
+        scope_to_use: u32,
+    ) {
+        let is_named_component_model =
+            matches!(element_kind, ElementKind::Component) && v_model.argument.is_some();
+
+        let kind = match *v_model.value {
+            Expr::Ident(ref ident) if !is_named_component_model => {
+                match self
+                    .bindings_helper
+                    .get_var_binding_type(scope_to_use, &ident.sym)
+                {
+                    BindingTypes::LiteralConst
+                    | BindingTypes::SetupConst
+                    | BindingTypes::SetupReactiveConst => TemplateErrorKind::VModelOnConst,
+                    BindingTypes::SetupRef
+                        if self.bindings_helper.readonly_computeds.contains(&ident.sym) =>
+                    {
+                        TemplateErrorKind::VModelOnReadonlyComputed
+                    }
+                    // Props are accessible in the template directly, e.g. `v-model="foo"`
+                    BindingTypes::Props if !self.is_prop_forwarded(&ident.sym) => {
+                        TemplateErrorKind::VModelOnPropWithoutEmit
+                    }
+                    _ => return,
+                }
+            }
 
-        // <h1 v-if="true">if</h1>
-        check_if_node(&seq.if_node);
+            // `props.foo` is read-only, it can only be forwarded by emitting `update:foo`
+            Expr::Member(MemberExpr {
+                ref obj, ref prop, ..
+            }) if !is_named_component_model
+                && obj.as_ident().is_some_and(|obj_ident| {
+                    self.bindings_helper.props_binding.as_ref() == Some(&obj_ident.sym)
+                        && !matches!(
+                            self.bindings_helper
+                                .get_var_binding_type(scope_to_use, &obj_ident.sym),
+                            BindingTypes::TemplateLocal
+                        )
+                }) =>
+            {
+                // `props.foo` or `props['foo']`
+                let prop_name = match prop {
+                    MemberProp::Ident(prop_ident) => &prop_ident.sym,
+                    MemberProp::Computed(ComputedPropName { expr, .. }) => match **expr {
+                        Expr::Lit(Lit::Str(ref prop_str)) => &prop_str.value,
+                        _ => return,
+                    },
+                    _ => return,
+                };
 
-        // <h2 v-else-if="foo">else-if</h3>
-        assert_eq!(1, seq.else_if_nodes.len());
-        check_else_if_node(&seq.else_if_nodes[0]);
+                if self.is_prop_forwarded(prop_name) {
+                    return;
+                }
 
-        // <h3 v-else>else</h3>
-        check_else_node(seq.else_node.as_ref());
-    }
+                TemplateErrorKind::VModelOnPropWithoutEmit
+            }
 
-    #[test]
-    fn it_folds_roots() {
-        // <template>
-        //   <h1 v-if="true">if</h1>
-        //   <h2 v-else-if="foo">else-if</h2>
-        //   <h3 v-else>else</h3>
-        // </template>
-        let mut sfc_template = SfcTemplateBlock {
-            lang: "html".into(),
-            roots: vec![if_node(), else_if_node(), else_node()],
-            span: DUMMY_SP,
-        };
+            // `user?.name = $event` is a syntax error, unless the chain is not optional
+            Expr::OptChain(_) => TemplateErrorKind::VModelOnOptionalChain,
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+            Expr::Array(_)
+            | Expr::Ident(_)
+            | Expr::Member(_)
+            | Expr::Object(_)
+            | Expr::Paren(_)
+            | Expr::SuperProp(_) => return,
 
-        // Template roots: one conditional sequence
-        assert_eq!(1, sfc_template.roots.len());
-        let Node::ConditionalSeq(ref seq) = sfc_template.roots[0] else {
-            panic!("Root is not a conditional sequence")
+            _ => TemplateErrorKind::VModelInvalidExpression,
         };
 
-        // <h1 v-if="true">if</h1>
-        check_if_node(&seq.if_node);
+        self.report_error(v_model.span, kind);
+    }
 
-        // <h2 v-else-if="foo">else-if</h3>
-        assert_eq!(1, seq.else_if_nodes.len());
-        check_else_if_node(&seq.else_if_nodes[0]);
+    /// Checks that `v-model` on a native element can be handled by the runtime directives.
+    /// Named models, e.g. `v-model:title="title"`, are only supported by components.
+    fn check_v_model_element(&mut self, tag_name: &str, v_model: &VModelDirective) {
+        if v_model.argument.is_some() {
+            self.report_error(v_model.span, TemplateErrorKind::VModelArgumentOnElement);
+        }
 
-        // <h3 v-else>else</h3>
-        check_else_node(seq.else_node.as_ref());
+        if !matches!(tag_name, "input" | "select" | "textarea") {
+            self.report_error(v_model.span, TemplateErrorKind::VModelOnInvalidElement);
+        }
     }
 
-    #[test]
-    fn it_folds_multiple_ifs() {
-        // <template>
-        //   <h1 v-if="true">if</h1>
-        //   <h1 v-if="true">if</h1>
-        // </template>
-        let mut sfc_template = SfcTemplateBlock {
-            lang: "html".into(),
-            roots: vec![if_node(), if_node()],
-            span: DUMMY_SP,
-        };
+    /// Checks that `v-model` on an `<input>` is not used for files.
+    /// `vModelText` cannot assign to `input.value` of a file input, `@change` should be used instead.
+    fn check_v_model_input_type(
+        &mut self,
+        attributes: &[AttributeOrBinding],
+        v_model: &VModelDirective,
+    ) {
+        let type_attr = attributes.iter().find(|attr| match attr {
+            AttributeOrBinding::RegularAttribute { name, .. } => name == "type",
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(name)),
+                ..
+            }) => name == "type",
+            _ => false,
+        });
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        let kind = match type_attr {
+            // type="file"
+            Some(AttributeOrBinding::RegularAttribute { value, .. })
+                if value.eq_ignore_ascii_case("file") =>
+            {
+                TemplateErrorKind::VModelOnFileInput
+            }
 
-        // Template roots: two conditional sequences inside one root
+            // :type="inputType"
+            Some(AttributeOrBinding::VBind(_)) => TemplateErrorKind::VModelOnDynamicInputType,
+
+            _ => return,
+        };
+
+        self.report_error(v_model.span, kind);
+    }
+
+    /// Whether `update:<prop_name>` is declared in `defineEmits`
+    fn is_prop_forwarded(&self, prop_name: &str) -> bool {
+        let update_event = format!("update:{}", prop_name);
+        self.bindings_helper
+            .emits
+            .iter()
+            .any(|emit| &**emit == update_event.as_str())
+    }
+
+    /// Desugars `<Comp v-slot="props">...</Comp>`
+    /// into `<Comp><template v-slot="props">...</template></Comp>`.
+    fn desugar_component_v_slot(&mut self, element_node: &mut ElementNode) {
+        let Some(ref mut directives) = element_node.starting_tag.directives else {
+            return;
+        };
+        let Some(v_slot) = directives.v_slot.take() else {
+            return;
+        };
+
+        // `<template v-slot:name>` children are kept as they are
+        let (named_slots, default_children): (Vec<Node>, Vec<Node>) =
+            std::mem::take(&mut element_node.children)
+                .into_iter()
+                .partition(is_template_v_slot);
+
+        if !named_slots.is_empty() {
+            self.report_error(element_node.span, TemplateErrorKind::VSlotMixedUsage);
+        }
+
+        let mut children = Vec::with_capacity(named_slots.len() + 1);
+        children.push(Node::Element(ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: fervid_atom!("template"),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_slot: Some(v_slot),
+                    ..Default::default()
+                })),
+            },
+            children: default_children,
+            template_scope: element_node.template_scope,
+            patch_hints: Default::default(),
+            span: element_node.span,
+        }));
+        children.extend(named_slots);
+
+        element_node.children = children;
+    }
+
+    /// Checks the literal values passed to a component against its known prop types.
+    /// Dynamic values cannot be checked at compile-time and are skipped.
+    fn check_component_prop_types(&mut self, starting_tag: &StartingTag) {
+        let Some(prop_types) = find_component_prop_types(
+            &self.bindings_helper.component_prop_types,
+            &starting_tag.tag_name,
+        ) else {
+            return;
+        };
+
+        for attr in starting_tag.attributes.iter() {
+            let (name, value_type, span) = match attr {
+                // `<Comp flag>` and `<Comp flag="flag">` are cast to `true` for `Boolean` props
+                AttributeOrBinding::RegularAttribute { name, value, span } => {
+                    let value_type = if value.is_empty() || value == name {
+                        None
+                    } else {
+                        Some(PropType::String)
+                    };
+                    (name, value_type, *span)
+                }
+                AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str(name)),
+                    value,
+                    span,
+                    ..
+                }) => {
+                    let Some(value_type) = get_literal_prop_type(value) else {
+                        continue;
+                    };
+                    (name, Some(value_type), *span)
+                }
+                _ => continue,
+            };
+
+            let mut prop_name = String::with_capacity(name.len());
+            to_camel_case(name, &mut prop_name);
+            let Some(expected) = prop_types.get(&FervidAtom::from(prop_name)) else {
+                continue;
+            };
+
+            let is_mismatch = match value_type {
+                Some(value_type) => {
+                    !expected.contains(&value_type) && !expected.contains(&PropType::Other)
+                }
+                // Boolean casting, or an empty string
+                None => {
+                    !expected.contains(&PropType::Boolean) && !expected.contains(&PropType::String)
+                }
+            };
+
+            if is_mismatch {
+                self.report_error(span, TemplateErrorKind::ComponentPropTypeMismatch);
+            }
+        }
+    }
+
+    /// `class` and `style` fall through to the root of a component via `$attrs`,
+    /// unless the component declares them as props.
+    /// Components with unknown props are assumed to let them fall through.
+    fn mark_class_and_style_props(&self, element_node: &mut ElementNode) {
+        let Some(prop_types) = find_component_prop_types(
+            &self.bindings_helper.component_prop_types,
+            &element_node.starting_tag.tag_name,
+        ) else {
+            return;
+        };
+
+        let patch_hints = &mut element_node.patch_hints;
+        patch_hints.is_class_prop = prop_types.contains_key(&fervid_atom!("class"));
+        patch_hints.is_style_prop = prop_types.contains_key(&fervid_atom!("style"));
+    }
+
+    /// Marks all the nodes inside `v-pre` as plain elements.
+    /// Components and built-ins are not recognized, and nothing gets resolved.
+    fn mark_v_pre_subtree(&self, element_node: &mut ElementNode) {
+        element_node.kind = ElementKind::Element;
+        element_node.template_scope = self.current_scope;
+
+        if !self.is_in_pre && element_node.starting_tag.tag_name != "pre" {
+            condense_whitespace(&mut element_node.children, self.bindings_helper.whitespace);
+        }
+        optimize_children(&mut element_node.children, ElementKind::Element);
+
+        for child in element_node.children.iter_mut() {
+            if let Node::Element(child_element) = child {
+                self.mark_v_pre_subtree(child_element);
+            }
+        }
+    }
+}
+
+/// Whether the component or any of its `<template v-slot>` children
+/// has a dynamic slot name, e.g. `<template #[name]>`
+fn has_dynamic_slot_name(starting_tag: &StartingTag, children: &[Node]) -> bool {
+    let is_dynamic = |starting_tag: &StartingTag| {
+        starting_tag
+            .directives
+            .as_ref()
+            .and_then(|directives| directives.v_slot.as_ref())
+            .is_some_and(VSlotDirective::is_dynamic_slot)
+    };
+
+    is_dynamic(starting_tag)
+        || children.iter().any(|child| match child {
+            Node::Element(child_element) => is_dynamic(&child_element.starting_tag),
+            _ => false,
+        })
+}
+
+/// Name of the bound prop in the generated code,
+/// e.g. `.textContent` for `:textContent.prop` and `^title` for `:title.attr`
+fn prefixed_prop_name(argument: &FervidAtom, v_bind: &VBindDirective) -> FervidAtom {
+    if v_bind.is_prop {
+        FervidAtom::from(format!(".{}", argument))
+    } else if v_bind.is_attr {
+        FervidAtom::from(format!("^{}", argument))
+    } else {
+        argument.to_owned()
+    }
+}
+
+/// Whether the URL is a string literal or a template literal without expressions
+fn is_literal_url(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Str(_)) => true,
+        Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+        Expr::Paren(paren) => is_literal_url(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Whether the node is `<template v-slot>`
+fn is_template_v_slot(node: &Node) -> bool {
+    let Node::Element(element_node) = node else {
+        return false;
+    };
+
+    element_node.starting_tag.tag_name == "template"
+        && element_node
+            .starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|d| d.v_slot.is_some())
+}
+
+/// Finds the known prop types of a component either by its tag name or its PascalCase name
+fn find_component_prop_types<'h>(
+    component_prop_types: &'h HashMap<FervidAtom, HashMap<FervidAtom, PropTypes>>,
+    tag_name: &FervidAtom,
+) -> Option<&'h HashMap<FervidAtom, PropTypes>> {
+    if component_prop_types.is_empty() {
+        return None;
+    }
+
+    let mut pascal_name = String::with_capacity(tag_name.len());
+    to_pascal_case(tag_name, &mut pascal_name);
+    component_prop_types
+        .get(tag_name)
+        .or_else(|| component_prop_types.get(&FervidAtom::from(pascal_name)))
+}
+
+/// Gets the runtime type of a literal value passed to a prop.
+/// `None` means the type is not known at compile-time.
+fn get_literal_prop_type(expr: &Expr) -> Option<PropType> {
+    match expr {
+        Expr::Paren(paren) => get_literal_prop_type(&paren.expr),
+        Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => Some(PropType::String),
+        Expr::Lit(Lit::Num(_)) => Some(PropType::Number),
+        Expr::Lit(Lit::Bool(_)) => Some(PropType::Boolean),
+        Expr::Array(_) => Some(PropType::Array),
+        Expr::Object(_) => Some(PropType::Object),
+        Expr::Arrow(_) | Expr::Fn(_) => Some(PropType::Function),
+        _ => None,
+    }
+}
+
+impl VisitMut for Node {
+    fn visit_mut_with(&mut self, visitor: &mut impl Visitor) {
+        match self {
+            Node::Element(el) => visitor.visit_element_node(el),
+            Node::ConditionalSeq(cond) => visitor.visit_conditional_node(cond),
+            Node::Interpolation(interpolation) => visitor.visit_interpolation(interpolation),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{ElementKind, Node, PatchHints, VForDirective, VOnDirective, VueDirectives};
+    use swc_core::{common::DUMMY_SP, ecma::ast::Pat};
+
+    use crate::{
+        test_utils::{element, js, to_str, transform_template},
+        OptionsApiBindings, SetupBinding,
+    };
+
+    use super::*;
+
+    /// Special case: `<component>` without `is` attribute is not a builtin
+    #[test]
+    fn it_distinguishes_component_builtin_and_not() {
+        let starting_tag = StartingTag {
+            tag_name: "component".into(),
+            attributes: vec![],
+            directives: None,
+        };
+
+        let mut bindings_helper = Default::default();
+        let template_visitor = TemplateVisitor {
+            bindings_helper: &mut bindings_helper,
+            current_scope: 0,
+            v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
+        };
+        assert!(matches!(
+            template_visitor.recognize_element_kind(&starting_tag),
+            ElementKind::Component
+        ));
+    }
+
+    #[test]
+    fn it_leaves_v_pre_subtree_raw() {
+        // <div v-pre v-for="item in list"><MyComponent>{{ item }}</MyComponent></div>
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![AttributeOrBinding::RegularAttribute {
+                        name: "v-for".into(),
+                        value: "item in list".into(),
+                        span: DUMMY_SP,
+                    }],
+                    directives: Some(Box::new(VueDirectives {
+                        v_pre: Some(()),
+                        ..Default::default()
+                    })),
+                },
+                children: vec![Node::Element(ElementNode {
+                    kind: ElementKind::Element,
+                    starting_tag: StartingTag {
+                        tag_name: "MyComponent".into(),
+                        attributes: vec![],
+                        directives: None,
+                    },
+                    children: vec![Node::Text("{{ item }}".into(), DUMMY_SP)],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })],
+            span: DUMMY_SP,
+        };
+
+        let mut bindings_helper = BindingsHelper::default();
+        transform_and_record_template(&mut sfc_template, &mut bindings_helper, &mut Vec::new());
+
+        let Node::Element(ref div) = sfc_template.roots[0] else {
+            panic!("Root is not an element")
+        };
+        assert!(div.patch_hints.flags.is_empty());
+        let Node::Element(ref component) = div.children[0] else {
+            panic!("Child is not an element")
+        };
+
+        // Components are not recognized inside `v-pre`
+        assert!(matches!(component.kind, ElementKind::Element));
+        assert!(bindings_helper.components.is_empty());
+        assert!(bindings_helper.template_scopes.is_empty());
+        assert!(matches!(&component.children[0], Node::Text(text, _) if text == "{{ item }}"));
+    }
+
+    #[test]
+    fn it_sets_props_patch_flag_for_boolean_attributes() {
+        // <input :attr="value" />
+        let transform = |attr: &str, value: &str| {
+            let mut sfc_template = SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(ElementNode {
+                    kind: ElementKind::Element,
+                    starting_tag: StartingTag {
+                        tag_name: "input".into(),
+                        attributes: vec![AttributeOrBinding::VBind(VBindDirective {
+                            argument: Some(StrOrExpr::Str(attr.into())),
+                            value: js(value),
+                            is_camel: false,
+                            is_prop: false,
+                            is_attr: false,
+                            span: DUMMY_SP,
+                        })],
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                span: DUMMY_SP,
+            };
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper
+                .setup_bindings
+                .push(SetupBinding(fervid_atom!("cond"), BindingTypes::SetupRef));
+            transform_and_record_template(&mut sfc_template, &mut bindings_helper, &mut Vec::new());
+
+            let Node::Element(input) = sfc_template.roots.pop().unwrap() else {
+                panic!("Root is not an element")
+            };
+            input
+        };
+
+        // `:disabled="false"` stays a prop, the runtime removes the attribute
+        let input = transform("disabled", "false");
+        let AttributeOrBinding::VBind(ref v_bind) = input.starting_tag.attributes[0] else {
+            panic!("Not a v-bind")
+        };
+        assert_eq!(to_str(&v_bind.value), "false");
+        assert!(input.patch_hints.flags.is_empty());
+
+        // Dynamic value
+        let input = transform("disabled", "cond");
+        assert_eq!(input.patch_hints.flags, PatchFlags::Props);
+        assert_eq!(input.patch_hints.props, vec![fervid_atom!("disabled")]);
+
+        // Not a boolean attribute, same handling
+        let input = transform("title", "cond");
+        assert_eq!(input.patch_hints.flags, PatchFlags::Props);
+        assert_eq!(input.patch_hints.props, vec![fervid_atom!("title")]);
+    }
+
+    #[test]
+    fn it_sets_props_patch_flag_for_v_text_and_v_html() {
+        // <div v-text="value" /> or <div v-html="value" />
+        let transform = |directives: VueDirectives| {
+            let mut sfc_template = SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(ElementNode {
+                    kind: ElementKind::Element,
+                    starting_tag: StartingTag {
+                        tag_name: "div".into(),
+                        attributes: vec![],
+                        directives: Some(Box::new(directives)),
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                span: DUMMY_SP,
+            };
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings.push(SetupBinding(
+                fervid_atom!("content"),
+                BindingTypes::SetupRef,
+            ));
+            transform_and_record_template(&mut sfc_template, &mut bindings_helper, &mut Vec::new());
+
+            let Node::Element(div) = sfc_template.roots.pop().unwrap() else {
+                panic!("Root is not an element")
+            };
+            div.patch_hints
+        };
+
+        // Dynamic `v-html`
+        let patch_hints = transform(VueDirectives {
+            v_html: Some(js("content")),
+            ..Default::default()
+        });
+        assert_eq!(patch_hints.flags, PatchFlags::Props);
+        assert_eq!(patch_hints.props, vec![fervid_atom!("innerHTML")]);
+
+        // Static `v-html`
+        let patch_hints = transform(VueDirectives {
+            v_html: Some(js("'<b>bold</b>'")),
+            ..Default::default()
+        });
+        assert!(patch_hints.flags.is_empty());
+        assert!(patch_hints.props.is_empty());
+
+        // Dynamic `v-text`
+        let patch_hints = transform(VueDirectives {
+            v_text: Some(js("content")),
+            ..Default::default()
+        });
+        assert_eq!(patch_hints.flags, PatchFlags::Props);
+        assert_eq!(patch_hints.props, vec![fervid_atom!("textContent")]);
+
+        // Static `v-text`
+        let patch_hints = transform(VueDirectives {
+            v_text: Some(js("'text'")),
+            ..Default::default()
+        });
+        assert!(patch_hints.flags.is_empty());
+    }
+
+    #[test]
+    fn it_computes_patch_flags() {
+        let transform = |attributes: Vec<AttributeOrBinding>, children: Vec<Node>| {
+            let mut sfc_template = SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(ElementNode {
+                    kind: ElementKind::Element,
+                    starting_tag: StartingTag {
+                        tag_name: "div".into(),
+                        attributes,
+                        directives: None,
+                    },
+                    children,
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                span: DUMMY_SP,
+            };
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper
+                .setup_bindings
+                .push(SetupBinding(fervid_atom!("cond"), BindingTypes::SetupRef));
+            transform_and_record_template(&mut sfc_template, &mut bindings_helper, &mut Vec::new());
+
+            let Node::Element(div) = sfc_template.roots.pop().unwrap() else {
+                panic!("Root is not an element")
+            };
+            div.patch_hints
+        };
+
+        let v_bind = |argument: Option<StrOrExpr>| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument,
+                value: js("cond"),
+                is_camel: false,
+                is_prop: false,
+                is_attr: false,
+                span: DUMMY_SP,
+            })
+        };
+
+        // <div :class="cond" :style="cond" :title="cond">
+        let patch_hints = transform(
+            vec![
+                v_bind(Some(StrOrExpr::Str("class".into()))),
+                v_bind(Some(StrOrExpr::Str("style".into()))),
+                v_bind(Some(StrOrExpr::Str("title".into()))),
+            ],
+            vec![],
+        );
+        assert_eq!(
+            patch_hints.flags,
+            PatchFlags::Class | PatchFlags::Style | PatchFlags::Props
+        );
+        assert_eq!(patch_hints.props, vec![fervid_atom!("title")]);
+
+        // <div :foo-bar.camel="cond" :text-content.prop="cond" :title.attr="cond">
+        let with_modifiers = |argument: &str, is_camel: bool, is_prop: bool, is_attr: bool| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(argument.into())),
+                value: js("cond"),
+                is_camel,
+                is_prop,
+                is_attr,
+                span: DUMMY_SP,
+            })
+        };
+        let patch_hints = transform(
+            vec![
+                with_modifiers("foo-bar", true, false, false),
+                with_modifiers("text-content", false, true, false),
+                with_modifiers("title", false, false, true),
+            ],
+            vec![],
+        );
+        assert_eq!(
+            patch_hints.flags,
+            PatchFlags::Props | PatchFlags::NeedHydration
+        );
+        assert_eq!(
+            patch_hints.props,
+            vec![
+                fervid_atom!("fooBar"),
+                FervidAtom::from(".text-content"),
+                FervidAtom::from("^title")
+            ]
+        );
+
+        // <div :title="cond" :[cond]="cond">
+        let patch_hints = transform(
+            vec![
+                v_bind(Some(StrOrExpr::Str("title".into()))),
+                v_bind(Some(StrOrExpr::Expr(js("cond")))),
+            ],
+            vec![],
+        );
+        assert_eq!(patch_hints.flags, PatchFlags::FullProps);
+        assert!(patch_hints.props.is_empty());
+
+        // <div :title="cond" v-on="cond">
+        let patch_hints = transform(
+            vec![
+                v_bind(Some(StrOrExpr::Str("title".into()))),
+                AttributeOrBinding::VOn(VOnDirective {
+                    event: None,
+                    handler: Some(js("cond")),
+                    modifiers: vec![],
+                    span: DUMMY_SP,
+                }),
+            ],
+            vec![],
+        );
+        assert_eq!(patch_hints.flags, PatchFlags::FullProps);
+        assert!(patch_hints.props.is_empty());
+
+        // <div :title="cond" @[cond]="cond">
+        let patch_hints = transform(
+            vec![
+                v_bind(Some(StrOrExpr::Str("title".into()))),
+                AttributeOrBinding::VOn(VOnDirective {
+                    event: Some(StrOrExpr::Expr(js("cond"))),
+                    handler: Some(js("cond")),
+                    modifiers: vec![],
+                    span: DUMMY_SP,
+                }),
+            ],
+            vec![],
+        );
+        assert_eq!(patch_hints.flags, PatchFlags::FullProps);
+        assert!(patch_hints.props.is_empty());
+
+        // <div>text {{ cond }}</div>
+        let interpolation = Node::Interpolation(Interpolation {
+            value: js("cond"),
+            template_scope: 0,
+            patch_flag: false,
+            span: DUMMY_SP,
+        });
+        let patch_hints = transform(
+            vec![],
+            vec![Node::Text("text ".into(), DUMMY_SP), interpolation],
+        );
+        assert_eq!(patch_hints.flags, PatchFlags::Text);
+
+        // <div title="static">text</div>
+        let patch_hints = transform(
+            vec![AttributeOrBinding::RegularAttribute {
+                name: "title".into(),
+                value: "static".into(),
+                span: DUMMY_SP,
+            }],
+            vec![Node::Text("text".into(), DUMMY_SP)],
+        );
+        assert!(patch_hints.flags.is_empty());
+    }
+
+    #[test]
+    fn it_checks_v_bind_dynamic_argument() {
+        // <div :[argument]="value" />
+        let check = |argument: &str| {
+            let mut roots = vec![element(
+                "div",
+                vec![AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Expr(js(argument))),
+                    value: js("value"),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                })],
+                None,
+                vec![],
+            )];
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper
+                .setup_bindings
+                .push(SetupBinding(fervid_atom!("known"), BindingTypes::SetupRef));
+
+            let errors = transform_template(&mut roots, &mut bindings_helper);
+
+            let Node::Element(ref div) = roots[0] else {
+                panic!("Root is not an element")
+            };
+            assert!(div.patch_hints.flags.contains(PatchFlags::FullProps));
+
+            errors
+        };
+
+        // Known variables and globals: no hint
+        assert!(check("known").is_empty());
+        assert!(check("'foo-' + known.name").is_empty());
+        assert!(check("String(known)").is_empty());
+
+        // Unknown variable: hint
+        assert_eq!(
+            check("'foo' + bar"),
+            vec![TemplateErrorKind::VBindDynamicArgumentUnknown]
+        );
+
+        // Null and strings: no warning
+        assert!(check("null").is_empty());
+        assert!(check("'foo'").is_empty());
+        assert!(check("`foo-${bar}`").is_empty());
+
+        // Numeric: warning
+        assert_eq!(
+            check("1"),
+            vec![TemplateErrorKind::VBindDynamicArgumentInvalid]
+        );
+
+        // Complex expression: hint
+        assert_eq!(
+            check("cond ? foo.bar() : null"),
+            vec![TemplateErrorKind::VBindDynamicArgumentUnknown]
+        );
+
+        // Arrow params are not references to outer variables
+        assert!(check("known.find((item) => item.active).name").is_empty());
+        assert!(check("known.find(({ id: key }) => key === known.id)").is_empty());
+        assert_eq!(
+            check("known.find((item) => item === bar)"),
+            vec![TemplateErrorKind::VBindDynamicArgumentUnknown]
+        );
+        assert_eq!(
+            check("known.map((item = bar) => item)[0]"),
+            vec![TemplateErrorKind::VBindDynamicArgumentUnknown]
+        );
+    }
+
+    #[test]
+    fn it_checks_url_bindings() {
+        // <a href="/static" :href="value" />
+        let check = |value: &str, sanitize_href: bool, is_imported: bool| {
+            let mut roots = vec![element(
+                "a",
+                vec![
+                    AttributeOrBinding::RegularAttribute {
+                        name: "href".into(),
+                        value: "/static".into(),
+                        span: DUMMY_SP,
+                    },
+                    AttributeOrBinding::VBind(VBindDirective {
+                        argument: Some(StrOrExpr::Str("href".into())),
+                        value: js(value),
+                        is_camel: false,
+                        is_prop: false,
+                        is_attr: false,
+                        span: DUMMY_SP,
+                    }),
+                ],
+                None,
+                vec![],
+            )];
+
+            // import { sanitizeUrl } from './utils'
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.sanitize_href = sanitize_href;
+            if is_imported {
+                bindings_helper
+                    .setup_bindings
+                    .push(SetupBinding("sanitizeUrl".into(), BindingTypes::SetupConst));
+            }
+
+            let errors = transform_template(&mut roots, &mut bindings_helper);
+
+            let Node::Element(ref a) = roots[0] else {
+                panic!("Root is not an element")
+            };
+            let AttributeOrBinding::VBind(ref v_bind) = a.starting_tag.attributes[1] else {
+                panic!("Not a v-bind")
+            };
+
+            (to_str(&v_bind.value), errors)
+        };
+
+        // Literal URL: no warning
+        let (value, errors) = check("'https://example.com'", false, false);
+        assert_eq!(value, "\"https://example.com\"");
+        assert!(errors.is_empty());
+
+        // Dynamic expression: warning
+        let (value, errors) = check("url", false, false);
+        assert_eq!(value, "_ctx.url");
+        assert_eq!(errors, vec![TemplateErrorKind::VBindUnsanitizedUrl]);
+
+        // Sanitization enabled: wrapper is generated, `sanitizeUrl` is a global property
+        let (value, errors) = check("url", true, false);
+        assert_eq!(value, "_ctx.sanitizeUrl(_ctx.url)");
+        assert!(errors.is_empty());
+
+        // Sanitization enabled: `sanitizeUrl` is imported in `<script setup>`
+        let (value, errors) = check("url", true, true);
+        assert_eq!(value, "$setup.sanitizeUrl(_ctx.url)");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn it_checks_v_model_targets() {
+        // <tag_name v-model:argument="value" />
+        let check = |tag_name: &str, argument: Option<&str>, value: &str| {
+            let mut roots = vec![element(
+                tag_name,
+                vec![],
+                Some(Box::new(VueDirectives {
+                    v_model: vec![VModelDirective {
+                        argument: argument.map(|a| StrOrExpr::Str(a.into())),
+                        value: js(value),
+                        update_handler: None,
+                        modifiers: vec![],
+                        span: DUMMY_SP,
+                    }],
+                    ..Default::default()
+                })),
+                vec![],
+            )];
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings = vec![
+                SetupBinding("count".into(), BindingTypes::SetupRef),
+                SetupBinding("double".into(), BindingTypes::SetupRef),
+                SetupBinding("CONST".into(), BindingTypes::LiteralConst),
+                SetupBinding("title".into(), BindingTypes::SetupRef),
+            ];
+            bindings_helper.readonly_computeds = vec!["double".into()];
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        // Ref: valid
+        assert!(check("input", None, "count").is_empty());
+
+        // Member expression: valid
+        assert!(check("input", None, "form.name").is_empty());
+
+        // Computed index: valid
+        assert!(check("input", None, "form.items[count]").is_empty());
+
+        // Optional chain: warning
+        assert_eq!(
+            check("input", None, "user?.name"),
+            vec![TemplateErrorKind::VModelOnOptionalChain]
+        );
+
+        // Nullish coalescing: error
+        assert_eq!(
+            check("input", None, "user.name ?? fallback"),
+            vec![TemplateErrorKind::VModelInvalidExpression]
+        );
+
+        // Computed without a setter: warning
+        assert_eq!(
+            check("input", None, "double"),
+            vec![TemplateErrorKind::VModelOnReadonlyComputed]
+        );
+
+        // Constant: error
+        assert_eq!(
+            check("input", None, "CONST"),
+            vec![TemplateErrorKind::VModelOnConst]
+        );
+
+        // Not assignable: error
+        assert_eq!(
+            check("input", None, "count + 1"),
+            vec![TemplateErrorKind::VModelInvalidExpression]
+        );
+
+        // Component with an argument: valid
+        assert!(check("MyComp", Some("title"), "title").is_empty());
+        assert!(check("MyComp", Some("title"), "double").is_empty());
+        assert!(check("MyComp", Some("title"), "CONST").is_empty());
+
+        // Component without an argument: checked as usual
+        assert_eq!(
+            check("MyComp", None, "CONST"),
+            vec![TemplateErrorKind::VModelOnConst]
+        );
+
+        // Native element with an argument: error
+        assert_eq!(
+            check("input", Some("value"), "count"),
+            vec![TemplateErrorKind::VModelArgumentOnElement]
+        );
+
+        // Other form elements: valid
+        assert!(check("select", None, "count").is_empty());
+        assert!(check("textarea", None, "count").is_empty());
+
+        // Not a form element: error
+        assert_eq!(
+            check("div", None, "count"),
+            vec![TemplateErrorKind::VModelOnInvalidElement]
+        );
+        assert_eq!(
+            check("div", Some("title"), "title"),
+            vec![
+                TemplateErrorKind::VModelArgumentOnElement,
+                TemplateErrorKind::VModelOnInvalidElement
+            ]
+        );
+    }
+
+    #[test]
+    fn it_checks_v_model_on_file_inputs() {
+        // <input attributes v-model="file" />
+        let check = |attributes: Vec<AttributeOrBinding>, with_v_model: bool| {
+            let v_model = with_v_model.then(|| VModelDirective {
+                argument: None,
+                value: js("file"),
+                update_handler: None,
+                modifiers: vec![],
+                span: DUMMY_SP,
+            });
+
+            let mut roots = vec![element(
+                "input",
+                attributes,
+                Some(Box::new(VueDirectives {
+                    v_model: v_model.into_iter().collect(),
+                    ..Default::default()
+                })),
+                vec![],
+            )];
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings = vec![
+                SetupBinding("file".into(), BindingTypes::SetupRef),
+                SetupBinding("fileType".into(), BindingTypes::SetupRef),
+                SetupBinding("onChange".into(), BindingTypes::SetupConst),
+            ];
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        let type_attr = |value: &str| AttributeOrBinding::RegularAttribute {
+            name: "type".into(),
+            value: value.into(),
+            span: DUMMY_SP,
+        };
+
+        // type="file" v-model="file": error
+        assert_eq!(
+            check(vec![type_attr("file")], true),
+            vec![TemplateErrorKind::VModelOnFileInput]
+        );
+
+        // type="file" @change="onChange": valid
+        let on_change = AttributeOrBinding::VOn(VOnDirective {
+            event: Some(StrOrExpr::Str("change".into())),
+            handler: Some(js("onChange")),
+            modifiers: vec![],
+            span: DUMMY_SP,
+        });
+        assert!(check(vec![type_attr("file"), on_change], false).is_empty());
+
+        // :type="fileType" v-model="file": warning
+        let dynamic_type = AttributeOrBinding::VBind(VBindDirective {
+            argument: Some(StrOrExpr::Str("type".into())),
+            value: js("fileType"),
+            is_camel: false,
+            is_prop: false,
+            is_attr: false,
+            span: DUMMY_SP,
+        });
+        assert_eq!(
+            check(vec![dynamic_type], true),
+            vec![TemplateErrorKind::VModelOnDynamicInputType]
+        );
+
+        // Other types: valid
+        for input_type in ["text", "checkbox", "radio", "number", "email"] {
+            assert!(check(vec![type_attr(input_type)], true).is_empty());
+        }
+        assert!(check(vec![], true).is_empty());
+    }
+
+    #[test]
+    fn it_checks_v_model_prop_forwarding() {
+        // <tag_name v-model:argument="value" />
+        let check = |tag_name: &str, argument: Option<&str>, value: &str| {
+            let mut roots = vec![element(
+                tag_name,
+                vec![],
+                Some(Box::new(VueDirectives {
+                    v_model: vec![VModelDirective {
+                        argument: argument.map(|a| StrOrExpr::Str(a.into())),
+                        value: js(value),
+                        update_handler: None,
+                        modifiers: vec![],
+                        span: DUMMY_SP,
+                    }],
+                    ..Default::default()
+                })),
+                vec![],
+            )];
+
+            // const props = defineProps(['modelValue', 'name', 'value', 'form'])
+            // defineEmits(['update:modelValue', 'update:name'])
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings = vec![
+                SetupBinding("modelValue".into(), BindingTypes::Props),
+                SetupBinding("name".into(), BindingTypes::Props),
+                SetupBinding("value".into(), BindingTypes::Props),
+                SetupBinding("form".into(), BindingTypes::Props),
+                SetupBinding("props".into(), BindingTypes::SetupMaybeRef),
+            ];
+            bindings_helper.props_binding = Some("props".into());
+            bindings_helper.emits = vec!["update:modelValue".into(), "update:name".into()];
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        // Matching emit: valid
+        assert!(check("input", None, "props.modelValue").is_empty());
+
+        // No matching emit: warning
+        assert_eq!(
+            check("input", None, "props.value"),
+            vec![TemplateErrorKind::VModelOnPropWithoutEmit]
+        );
+
+        // `v-model:name` forwarding
+        assert!(check("MyInput", Some("name"), "props.name").is_empty());
+
+        // Forwarding to another wrapper
+        assert!(check("MyInput", None, "props.modelValue").is_empty());
+
+        // Nested field of a prop is mutated in place, not forwarded
+        assert!(check("input", None, "props.form.name").is_empty());
+    }
+
+    #[test]
+    fn it_warns_about_v_model_on_props() {
+        // <tag_name v-model:argument="value" />
+        let check = |tag_name: &str, argument: Option<&str>, value: &str| {
+            let mut roots = vec![element(
+                tag_name,
+                vec![],
+                Some(Box::new(VueDirectives {
+                    v_model: vec![VModelDirective {
+                        argument: argument.map(|a| StrOrExpr::Str(a.into())),
+                        value: js(value),
+                        update_handler: None,
+                        modifiers: vec![],
+                        span: DUMMY_SP,
+                    }],
+                    ..Default::default()
+                })),
+                vec![],
+            )];
+
+            // const props = defineProps(['count'])
+            // const counter = ref(0)
+            // data() { return { message: '' } }
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings = vec![
+                SetupBinding("count".into(), BindingTypes::Props),
+                SetupBinding("props".into(), BindingTypes::SetupMaybeRef),
+                SetupBinding("counter".into(), BindingTypes::SetupRef),
+            ];
+            bindings_helper
+                .options_api_bindings
+                .get_or_insert_with(Default::default)
+                .data
+                .push("message".into());
+            bindings_helper.props_binding = Some("props".into());
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        // Props object member
+        assert_eq!(
+            check("input", None, "props.count"),
+            vec![TemplateErrorKind::VModelOnPropWithoutEmit]
+        );
+        assert_eq!(
+            check("input", None, "props['count']"),
+            vec![TemplateErrorKind::VModelOnPropWithoutEmit]
+        );
+
+        // Prop accessed directly
+        assert_eq!(
+            check("input", None, "count"),
+            vec![TemplateErrorKind::VModelOnPropWithoutEmit]
+        );
+
+        // Ref and its `.value`
+        assert!(check("input", None, "counter").is_empty());
+        assert!(check("input", None, "counter.value").is_empty());
+
+        // Regular data
+        assert!(check("input", None, "message").is_empty());
+
+        // `v-model:arg` on a component
+        assert!(check("MyInput", Some("title"), "message").is_empty());
+        assert!(check("MyInput", Some("title"), "counter").is_empty());
+        assert!(check("MyInput", Some("title"), "props.count").is_empty());
+        assert!(check("MyInput", Some("title"), "count").is_empty());
+
+        // Default model of a component
+        assert_eq!(
+            check("MyInput", None, "props.count"),
+            vec![TemplateErrorKind::VModelOnPropWithoutEmit]
+        );
+    }
+
+    #[test]
+    fn it_warns_about_deprecated_globals() {
+        // <tag_name :foo="attr_value">{{ interpolation }}</tag_name>
+        let check = |tag_name: &str, attr_value: &str, interpolation: &str, warn: bool| {
+            let mut roots = vec![element(
+                tag_name,
+                vec![AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str("foo".into())),
+                    value: js(attr_value),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                })],
+                None,
+                vec![Node::Interpolation(Interpolation {
+                    value: js(interpolation),
+                    template_scope: 0,
+                    patch_flag: false,
+                    span: DUMMY_SP,
+                })],
+            )];
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.warn_deprecated_globals = warn;
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        // `{{ $parent.title }}`: warning
+        assert_eq!(
+            check("div", "foo", "$parent.title", true),
+            vec![TemplateErrorKind::DeprecatedGlobalAccess]
+        );
+
+        // `:foo="$root.config"`: warning
+        assert_eq!(
+            check("div", "$root.config", "foo", true),
+            vec![TemplateErrorKind::DeprecatedGlobalAccess]
+        );
+
+        // Option disabled: no warning
+        assert!(check("div", "$root.config", "$parent.title", false).is_empty());
+
+        // Component which relies on `$parent`: still a warning
+        assert_eq!(
+            check("MyComp", "$parent.title", "foo", true),
+            vec![TemplateErrorKind::DeprecatedGlobalAccess]
+        );
+
+        // Other instance properties are fine
+        assert!(check("div", "$attrs.foo", "$slots.default", true).is_empty());
+    }
+
+    #[test]
+    fn it_desugars_v_slot_on_component() {
+        // <tag_name v-slot="<value>"><children></tag_name>
+        let check = |tag_name: &str, value: Option<&str>, children: Vec<Node>| {
+            let mut roots = vec![element(
+                tag_name,
+                vec![],
+                Some(Box::new(VueDirectives {
+                    v_slot: Some(VSlotDirective {
+                        slot_name: None,
+                        value: value.map(|v| Box::new(Pat::Expr(js(v)))),
+                    }),
+                    ..Default::default()
+                })),
+                children,
+            )];
+
+            let errors = transform_template(&mut roots, &mut Default::default());
+
+            let Node::Element(root) = roots.remove(0) else {
+                panic!("Root is not an element")
+            };
+
+            (root, errors)
+        };
+
+        let interpolation = |value: &str| {
+            Node::Interpolation(Interpolation {
+                value: js(value),
+                template_scope: 0,
+                patch_flag: false,
+                span: DUMMY_SP,
+            })
+        };
+
+        let get_v_slot = |node: &Node| {
+            let Node::Element(element) = node else {
+                panic!("Not an element")
+            };
+            assert_eq!("template", element.starting_tag.tag_name.as_ref());
+            element
+                .starting_tag
+                .directives
+                .as_ref()
+                .and_then(|d| d.v_slot.to_owned())
+                .expect("Should have v-slot")
+        };
+
+        // <my-comp v-slot="{ item }">{{ item }}</my-comp>
+        let (root, errors) = check("my-comp", Some("{ item }"), vec![interpolation("item")]);
+        assert!(errors.is_empty());
+        assert!(root
+            .starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|d| d.v_slot.is_none()));
+        assert_eq!(1, root.children.len());
+        let v_slot = get_v_slot(&root.children[0]);
+        assert!(v_slot.slot_name.is_none());
+        assert!(v_slot.value.is_some());
+        let Node::Element(ref default_slot) = root.children[0] else {
+            unreachable!()
+        };
+        let Some(Node::Interpolation(ref item)) = default_slot.children.first() else {
+            panic!("Not an interpolation")
+        };
+        assert_eq!("item", to_str(&item.value));
+
+        // <my-comp v-slot="{ item }">{{ item }}<template #header>{{ item }}</template></my-comp>
+        let named_slot = Node::Element(ElementNode {
+            kind: ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: "template".into(),
+                attributes: vec![],
+                directives: Some(Box::new(VueDirectives {
+                    v_slot: Some(VSlotDirective {
+                        slot_name: Some(StrOrExpr::Str("header".into())),
+                        value: None,
+                    }),
+                    ..Default::default()
+                })),
+            },
+            children: vec![interpolation("item")],
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        });
+        let (root, errors) = check(
+            "my-comp",
+            Some("{ item }"),
+            vec![interpolation("item"), named_slot],
+        );
+        assert_eq!(errors, vec![TemplateErrorKind::VSlotMixedUsage]);
+        assert_eq!(2, root.children.len());
+        assert!(get_v_slot(&root.children[0]).slot_name.is_none());
+        assert!(matches!(
+            get_v_slot(&root.children[1]).slot_name,
+            Some(StrOrExpr::Str(ref name)) if name == "header"
+        ));
+
+        // <div v-slot="{ item }">{{ item }}</div>
+        let (root, errors) = check("div", Some("{ item }"), vec![interpolation("item")]);
+        assert_eq!(errors, vec![TemplateErrorKind::VSlotMisplaced]);
+        assert!(matches!(root.children[0], Node::Interpolation(_)));
+
+        // <my-comp v-slot="">text</my-comp>
+        let (root, errors) = check("my-comp", None, vec![interpolation("'text'")]);
+        assert!(errors.is_empty());
+        assert_eq!(1, root.children.len());
+        assert!(get_v_slot(&root.children[0]).value.is_none());
+    }
+
+    #[test]
+    fn it_marks_class_and_style_props() {
+        // <tag_name :class="classes" :style="styles" />
+        let transform = |tag_name: &str| {
+            let v_bind = |argument: &str, value: &str| {
+                AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str(argument.into())),
+                    value: js(value),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                })
+            };
+
+            let mut sfc_template = SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(ElementNode {
+                    kind: ElementKind::Element,
+                    starting_tag: StartingTag {
+                        tag_name: tag_name.into(),
+                        attributes: vec![
+                            v_bind("is", "'div'"),
+                            v_bind("class", "classes"),
+                            v_bind("style", "styles"),
+                        ],
+                        directives: None,
+                    },
+                    children: vec![],
+                    template_scope: 0,
+                    patch_hints: Default::default(),
+                    span: DUMMY_SP,
+                })],
+                span: DUMMY_SP,
+            };
+
+            // MyComp: defineProps({ class: [String, Object] })
+            // PlainComp: defineProps({ label: String })
+            let mut bindings_helper = BindingsHelper::default();
+            let mut my_comp_props = fxhash::FxHashMap::default();
+            my_comp_props.insert(
+                fervid_atom!("class"),
+                smallvec::smallvec![PropType::String, PropType::Object],
+            );
+            let mut plain_comp_props = fxhash::FxHashMap::default();
+            plain_comp_props.insert(fervid_atom!("label"), smallvec::smallvec![PropType::String]);
+            bindings_helper
+                .component_prop_types
+                .insert(fervid_atom!("MyComp"), my_comp_props);
+            bindings_helper
+                .component_prop_types
+                .insert(fervid_atom!("PlainComp"), plain_comp_props);
+
+            transform_and_record_template(&mut sfc_template, &mut bindings_helper, &mut Vec::new());
+
+            let Node::Element(element) = sfc_template.roots.pop().unwrap() else {
+                panic!("Root is not an element")
+            };
+            (
+                element.patch_hints.is_class_prop,
+                element.patch_hints.is_style_prop,
+            )
+        };
+
+        // `class` is consumed, `style` falls through
+        assert_eq!(transform("my-comp"), (true, false));
+        assert_eq!(transform("MyComp"), (true, false));
+
+        // Both fall through
+        assert_eq!(transform("plain-comp"), (false, false));
+
+        // Unknown props
+        assert_eq!(transform("other-comp"), (false, false));
+        assert_eq!(transform("component"), (false, false));
+    }
+
+    #[test]
+    fn it_checks_component_prop_types() {
+        // <MyComp <attributes> />
+        let check = |attributes: Vec<AttributeOrBinding>| {
+            let mut roots = vec![element("my-comp", attributes, None, vec![])];
+
+            // defineProps({ count: Number, disabled: Boolean, label: [String, Number] })
+            let mut bindings_helper = BindingsHelper::default();
+            let mut prop_types = fxhash::FxHashMap::default();
+            prop_types.insert(fervid_atom!("count"), smallvec::smallvec![PropType::Number]);
+            prop_types.insert(
+                fervid_atom!("disabled"),
+                smallvec::smallvec![PropType::Boolean],
+            );
+            prop_types.insert(
+                fervid_atom!("label"),
+                smallvec::smallvec![PropType::String, PropType::Number],
+            );
+            bindings_helper
+                .component_prop_types
+                .insert(fervid_atom!("MyComp"), prop_types);
+
+            transform_template(&mut roots, &mut bindings_helper)
+        };
+
+        let v_bind = |argument: &str, value: &str| {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(argument.into())),
+                value: js(value),
+                is_camel: false,
+                is_prop: false,
+                is_attr: false,
+                span: DUMMY_SP,
+            })
+        };
+
+        let regular = |name: &str, value: &str| AttributeOrBinding::RegularAttribute {
+            name: name.into(),
+            value: value.into(),
+            span: DUMMY_SP,
+        };
+
+        // String literal for a number prop
+        assert_eq!(
+            check(vec![v_bind("count", "'five'")]),
+            vec![TemplateErrorKind::ComponentPropTypeMismatch]
+        );
+        assert_eq!(
+            check(vec![regular("count", "5")]),
+            vec![TemplateErrorKind::ComponentPropTypeMismatch]
+        );
+
+        // Number for a boolean prop
+        assert_eq!(
+            check(vec![v_bind("disabled", "1")]),
+            vec![TemplateErrorKind::ComponentPropTypeMismatch]
+        );
+
+        // Correct types
+        assert!(check(vec![
+            v_bind("count", "5"),
+            v_bind("disabled", "false"),
+            v_bind("label", "42"),
+            regular("label", "text"),
+        ])
+        .is_empty());
+
+        // Boolean casting
+        assert!(check(vec![regular("disabled", "")]).is_empty());
+        assert!(check(vec![regular("disabled", "disabled")]).is_empty());
+
+        // Dynamic bindings and unknown props cannot be checked
+        assert!(check(vec![v_bind("count", "someCount")]).is_empty());
+        assert!(check(vec![v_bind("unknown", "'five'")]).is_empty());
+    }
+
+    #[test]
+    fn it_checks_v_for_keys_in_transition_group() {
+        let key_attr = || {
+            vec![AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str("key".into())),
+                value: js("item.id"),
+                is_camel: false,
+                is_prop: false,
+                is_attr: false,
+                span: DUMMY_SP,
+            })]
+        };
+
+        // <wrapper><li v-for="item in items" :key?><span :key? /></li></wrapper>
+        let check = |wrapper: &str, key_on_li: bool, key_on_span: bool| {
+            let v_for = Some(Box::new(VueDirectives {
+                v_for: Some(VForDirective {
+                    iterable: js("items"),
+                    itervar: js("item"),
+                    patch_flags: Default::default(),
+                    span: DUMMY_SP,
+                }),
+                ..Default::default()
+            }));
+            let span = element(
+                "span",
+                if key_on_span { key_attr() } else { vec![] },
+                None,
+                vec![],
+            );
+            let li = element(
+                "li",
+                if key_on_li { key_attr() } else { vec![] },
+                v_for,
+                vec![span],
+            );
+
+            let mut roots = vec![element(wrapper, vec![], None, vec![li])];
+
+            transform_template(&mut roots, &mut Default::default())
+        };
+
+        // Keyed list: no error
+        assert!(check("TransitionGroup", true, false).is_empty());
+
+        // Unkeyed list: error
+        assert_eq!(
+            check("TransitionGroup", false, false),
+            vec![TemplateErrorKind::VForMissingKeyInTransitionGroup]
+        );
+
+        // Key on a nested element does not count
+        assert_eq!(
+            check("transition-group", false, true),
+            vec![TemplateErrorKind::VForMissingKeyInTransitionGroup]
+        );
+
+        // `<Transition>` only gets the usual warning
+        assert_eq!(
+            check("Transition", false, false),
+            vec![TemplateErrorKind::VForMissingKey]
+        );
+    }
+
+    #[test]
+    fn it_transforms_v_for_destructure() {
+        // <div v-for="<itervar> in list" :key="<key>">{{ <interpolation> }}</div>
+        let check = |itervar: &str, key: Option<&str>, interpolation: &str| {
+            let attributes = match key {
+                Some(key) => vec![AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str("key".into())),
+                    value: js(key),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                })],
+                None => vec![],
+            };
+
+            let mut roots = vec![element(
+                "div",
+                attributes,
+                Some(Box::new(VueDirectives {
+                    v_for: Some(VForDirective {
+                        iterable: js("list"),
+                        itervar: js(itervar),
+                        patch_flags: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                    ..Default::default()
+                })),
+                vec![Node::Interpolation(Interpolation {
+                    value: js(interpolation),
+                    template_scope: 0,
+                    patch_flag: false,
+                    span: DUMMY_SP,
+                })],
+            )];
+
+            let errors = transform_template(&mut roots, &mut Default::default());
+
+            let Node::Element(ref div) = roots[0] else {
+                panic!("Root is not an element")
+            };
+            let key = div.starting_tag.attributes.first().map(|attr| match attr {
+                AttributeOrBinding::VBind(v_bind) => to_str(&v_bind.value),
+                _ => panic!("Not a v-bind"),
+            });
+            let Some(Node::Interpolation(ref interpolation)) = div.children.first() else {
+                panic!("Child is not an interpolation")
+            };
+
+            (key, to_str(&interpolation.value), errors)
+        };
+
+        // Pair
+        let (key, interpolation, errors) = check("[a, b]", Some("a"), "a + b + c");
+        assert_eq!(key.as_deref(), Some("a"));
+        assert_eq!(interpolation, "a+b+_ctx.c");
+        assert!(errors.is_empty());
+
+        // Triple
+        let (key, interpolation, errors) = check("[a, b, c]", Some("a"), "a + b + c");
+        assert_eq!(key.as_deref(), Some("a"));
+        assert_eq!(interpolation, "a+b+c");
+        assert!(errors.is_empty());
+
+        // Rest element
+        let (key, interpolation, errors) =
+            check("[first, ...rest]", Some("first"), "first + rest.length");
+        assert_eq!(key.as_deref(), Some("first"));
+        assert_eq!(interpolation, "first+rest.length");
+        assert!(errors.is_empty());
+
+        // Default value, identifiers in the default are not bound
+        let (key, interpolation, errors) = check("[a, b = c]", Some("a"), "a + b + c");
+        assert_eq!(key.as_deref(), Some("a"));
+        assert_eq!(interpolation, "a+b+_ctx.c");
+        assert!(errors.is_empty());
+
+        // Missing key
+        let (key, interpolation, errors) = check("[a, b]", None, "a + b");
+        assert!(key.is_none());
+        assert_eq!(interpolation, "a+b");
+        assert_eq!(errors, vec![TemplateErrorKind::VForMissingKey]);
+    }
+
+    #[test]
+    fn it_shadows_outer_bindings_with_v_for_variables() {
+        fn v_for_div(itervar: &str, iterable: &str, children: Vec<Node>) -> Node {
+            Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![AttributeOrBinding::VBind(VBindDirective {
+                        argument: Some(StrOrExpr::Str("key".into())),
+                        value: js(itervar),
+                        is_camel: false,
+                        is_prop: false,
+                        is_attr: false,
+                        span: DUMMY_SP,
+                    })],
+                    directives: Some(Box::new(VueDirectives {
+                        v_for: Some(VForDirective {
+                            iterable: js(iterable),
+                            itervar: js(itervar),
+                            patch_flags: Default::default(),
+                            span: DUMMY_SP,
+                        }),
+                        ..Default::default()
+                    })),
+                },
+                children,
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })
+        }
+
+        fn interpolation(value: &str) -> Node {
+            Node::Interpolation(Interpolation {
+                value: js(value),
+                template_scope: 0,
+                patch_flag: false,
+                span: DUMMY_SP,
+            })
+        }
+
+        fn get_v_for(node: &Node) -> (&ElementNode, String) {
+            let Node::Element(ref el) = node else {
+                panic!("Not an element")
+            };
+            let v_for = el
+                .starting_tag
+                .directives
+                .as_ref()
+                .and_then(|d| d.v_for.as_ref())
+                .expect("Must have v-for");
+            (el, to_str(&v_for.iterable))
+        }
+
+        fn get_interpolation(node: &Node) -> String {
+            let Node::Interpolation(ref interpolation) = node else {
+                panic!("Not an interpolation")
+            };
+            to_str(&interpolation.value)
+        }
+
+        // <div v-for="item in item.children" :key="item">{{ item }}{{ label }}</div>
+        // <div v-for="entry in entries" :key="entry">
+        //   <div v-for="entry in entry.items" :key="entry">{{ entry }}</div>
+        //   {{ entry }}
+        // </div>
+        let mut roots = vec![
+            v_for_div(
+                "item",
+                "item.children",
+                vec![interpolation("item"), interpolation("label")],
+            ),
+            v_for_div(
+                "entry",
+                "entries",
+                vec![
+                    v_for_div("entry", "entry.items", vec![interpolation("entry")]),
+                    interpolation("entry"),
+                ],
+            ),
+        ];
+
+        // `item` and `label` are props, `entry` and `entries` are data
+        let mut bindings_helper = BindingsHelper::default();
+        bindings_helper.options_api_bindings = Some(Box::new(OptionsApiBindings {
+            props: vec![fervid_atom!("item"), fervid_atom!("label")],
+            data: vec![fervid_atom!("entry"), fervid_atom!("entries")],
+            ..Default::default()
+        }));
+
+        let errors = transform_template(&mut roots, &mut bindings_helper);
+        assert!(errors.is_empty());
+
+        // Loop variable shadows a prop, but the iterable still refers to the prop
+        let (item_div, iterable) = get_v_for(&roots[0]);
+        assert_eq!(iterable, "$props.item.children");
+        assert_eq!(get_interpolation(&item_div.children[0]), "item");
+
+        // Outer scope is still accessible from the inside of the loop
+        assert_eq!(get_interpolation(&item_div.children[1]), "$props.label");
+
+        // Loop variable shadows a data property
+        let (outer_div, iterable) = get_v_for(&roots[1]);
+        assert_eq!(iterable, "$data.entries");
+        assert_eq!(get_interpolation(&outer_div.children[1]), "entry");
+
+        // Nested `v-for` with the same variable name
+        let (inner_div, iterable) = get_v_for(&outer_div.children[0]);
+        assert_eq!(iterable, "entry.items");
+        assert_eq!(get_interpolation(&inner_div.children[0]), "entry");
+    }
+
+    #[test]
+    fn it_resolves_slot_scope_in_v_for() {
+        fn element(tag_name: &str, directives: VueDirectives, children: Vec<Node>) -> Node {
+            Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: tag_name.into(),
+                    attributes: vec![],
+                    directives: Some(Box::new(directives)),
+                },
+                children,
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })
+        }
+
+        // <tag_name v-for="itervar in iterable" :key="key">
+        fn v_for_element(
+            tag_name: &str,
+            itervar: &str,
+            iterable: &str,
+            key: &str,
+            children: Vec<Node>,
+        ) -> Node {
+            let mut node = element(
+                tag_name,
+                VueDirectives {
+                    v_for: Some(VForDirective {
+                        iterable: js(iterable),
+                        itervar: js(itervar),
+                        patch_flags: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                    ..Default::default()
+                },
+                children,
+            );
+            let Node::Element(ref mut el) = node else {
+                unreachable!()
+            };
+            el.starting_tag
+                .attributes
+                .push(AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some(StrOrExpr::Str("key".into())),
+                    value: js(key),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                }));
+            node
+        }
+
+        // <tag_name><template #default="value">children</template></tag_name>
+        fn component_with_slot(tag_name: &str, value: &str, children: Vec<Node>) -> Node {
+            let slot = element(
+                "template",
+                VueDirectives {
+                    v_slot: Some(VSlotDirective {
+                        slot_name: Some(StrOrExpr::Str("default".into())),
+                        value: Some(Box::new(Pat::Expr(js(value)))),
+                    }),
+                    ..Default::default()
+                },
+                children,
+            );
+            element(tag_name, VueDirectives::default(), vec![slot])
+        }
+
+        fn interpolation(value: &str) -> Node {
+            Node::Interpolation(Interpolation {
+                value: js(value),
+                template_scope: 0,
+                patch_flag: false,
+                span: DUMMY_SP,
+            })
+        }
+
+        /// Returns the element inside the default slot of a component
+        fn slot_child(node: &Node) -> &ElementNode {
+            let Node::Element(ref component) = node else {
+                panic!("Not an element")
+            };
+            let Some(Node::Element(ref slot)) = component.children.first() else {
+                panic!("Slot is not an element")
+            };
+            let Some(Node::Element(ref child)) = slot.children.first() else {
+                panic!("Slot child is not an element")
+            };
+            child
+        }
+
+        fn get_iterable(el: &ElementNode) -> String {
+            let v_for = el
+                .starting_tag
+                .directives
+                .as_ref()
+                .and_then(|d| d.v_for.as_ref())
+                .expect("Must have v-for");
+            to_str(&v_for.iterable)
+        }
+
+        fn get_interpolation(node: &Node) -> String {
+            let Node::Interpolation(ref interpolation) = node else {
+                panic!("Not an interpolation")
+            };
+            to_str(&interpolation.value)
+        }
+
+        // <MyList><template #default="{ list }">
+        //   <div v-for="item in list" :key="item">{{ item }}{{ list.length }}</div>
+        // </template></MyList>
+        // <MyList><template #default="{ list }">
+        //   <div v-for="(item, list) in list" :key="item">{{ list }}</div>
+        // </template></MyList>
+        // <MyGroups><template #default="{ groups }">
+        //   <div v-for="group in groups" :key="group.id">
+        //     <MyRows><template #default="{ rows }">
+        //       <span v-for="row in rows" :key="row.id">{{ row }}{{ group }}{{ other }}</span>
+        //     </template></MyRows>
+        //   </div>
+        // </template></MyGroups>
+        let mut roots = vec![
+            component_with_slot(
+                "MyList",
+                "{ list }",
+                vec![v_for_element(
+                    "div",
+                    "item",
+                    "list",
+                    "item",
+                    vec![interpolation("item"), interpolation("list.length")],
+                )],
+            ),
+            component_with_slot(
+                "MyList",
+                "{ list }",
+                vec![v_for_element(
+                    "div",
+                    "(item, list)",
+                    "list",
+                    "item",
+                    vec![interpolation("list")],
+                )],
+            ),
+            component_with_slot(
+                "MyGroups",
+                "{ groups }",
+                vec![v_for_element(
+                    "div",
+                    "group",
+                    "groups",
+                    "group.id",
+                    vec![component_with_slot(
+                        "MyRows",
+                        "{ rows }",
+                        vec![v_for_element(
+                            "span",
+                            "row",
+                            "rows",
+                            "row.id",
+                            vec![
+                                interpolation("row"),
+                                interpolation("group"),
+                                interpolation("other"),
+                            ],
+                        )],
+                    )],
+                )],
+            ),
+        ];
+
+        // All the names are also data properties, which the slot props must shadow
+        let mut bindings_helper = BindingsHelper::default();
+        bindings_helper.options_api_bindings = Some(Box::new(OptionsApiBindings {
+            data: vec![
+                fervid_atom!("list"),
+                fervid_atom!("groups"),
+                fervid_atom!("rows"),
+                fervid_atom!("group"),
+                fervid_atom!("other"),
+            ],
+            ..Default::default()
+        }));
+
+        let errors = transform_template(&mut roots, &mut bindings_helper);
+        assert!(errors.is_empty());
+
+        // Slot scope variable in the `v-for` source and body
+        let div = slot_child(&roots[0]);
+        assert_eq!(get_iterable(div), "list");
+        assert_eq!(get_interpolation(&div.children[0]), "item");
+        assert_eq!(get_interpolation(&div.children[1]), "list.length");
+
+        // `v-for` index shadows the slot prop inside the loop, but not in the source
+        let div = slot_child(&roots[1]);
+        assert_eq!(get_iterable(div), "list");
+        assert_eq!(get_interpolation(&div.children[0]), "list");
+        let index_scope = &bindings_helper.template_scopes[div.template_scope as usize];
+        assert!(index_scope.variables.contains(&fervid_atom!("list")));
+
+        // Doubly-nested slot and `v-for`
+        let div = slot_child(&roots[2]);
+        assert_eq!(get_iterable(div), "groups");
+        let span = slot_child(&div.children[0]);
+        assert_eq!(get_iterable(span), "rows");
+        assert_eq!(get_interpolation(&span.children[0]), "row");
+        assert_eq!(get_interpolation(&span.children[1]), "group");
+        assert_eq!(get_interpolation(&span.children[2]), "$data.other");
+    }
+
+    #[test]
+    fn it_folds_basic_seq() {
+        // <template><div>
+        //   text
+        //   <h1 v-if="true">if</h1>
+        //   <h2 v-else-if="foo">else-if</h2>
+        //   <h3 v-else>else</h3>
+        // </div></template>
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![Node::Element(ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: "div".into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children: vec![text_node(), if_node(), else_if_node(), else_node()],
+                template_scope: 0,
+                kind: ElementKind::Element,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })],
+            span: DUMMY_SP,
+        };
+
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
+
+        // Template roots: one div
+        assert_eq!(1, sfc_template.roots.len());
+        let Node::Element(ref div) = sfc_template.roots[0] else {
+            panic!("Root is not an element")
+        };
+
+        // Text and conditional seq
+        assert_eq!(2, div.children.len());
+        check_text_node(&div.children[0]);
+        let Node::ConditionalSeq(seq) = &div.children[1] else {
+            panic!("Not a conditional sequence")
+        };
+
+        // <h1 v-if="true">if</h1>
+        check_if_node(&seq.if_node);
+
+        // <h2 v-else-if="foo">else-if</h3>
+        assert_eq!(1, seq.else_if_nodes.len());
+        check_else_if_node(&seq.else_if_nodes[0]);
+
+        // <h3 v-else>else</h3>
+        check_else_node(seq.else_node.as_ref());
+    }
+
+    #[test]
+    fn it_folds_roots() {
+        // <template>
+        //   <h1 v-if="true">if</h1>
+        //   <h2 v-else-if="foo">else-if</h2>
+        //   <h3 v-else>else</h3>
+        // </template>
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![if_node(), else_if_node(), else_node()],
+            span: DUMMY_SP,
+        };
+
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
+
+        // Template roots: one conditional sequence
+        assert_eq!(1, sfc_template.roots.len());
+        let Node::ConditionalSeq(ref seq) = sfc_template.roots[0] else {
+            panic!("Root is not a conditional sequence")
+        };
+
+        // <h1 v-if="true">if</h1>
+        check_if_node(&seq.if_node);
+
+        // <h2 v-else-if="foo">else-if</h3>
+        assert_eq!(1, seq.else_if_nodes.len());
+        check_else_if_node(&seq.else_if_nodes[0]);
+
+        // <h3 v-else>else</h3>
+        check_else_node(seq.else_node.as_ref());
+    }
+
+    #[test]
+    fn it_folds_multiple_ifs() {
+        // <template>
+        //   <h1 v-if="true">if</h1>
+        //   <h1 v-if="true">if</h1>
+        // </template>
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![if_node(), if_node()],
+            span: DUMMY_SP,
+        };
+
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
+
+        // Template roots: two conditional sequences inside one root
         assert_eq!(1, sfc_template.roots.len());
         let Node::Element(ref root) = sfc_template.roots[0] else {
             panic!("root is not an element")
@@ -900,7 +3234,7 @@ mod tests {
             span: DUMMY_SP,
         };
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
 
         // Template roots: two conditional sequences inside one root
         assert_eq!(1, sfc_template.roots.len());
@@ -932,7 +3266,7 @@ mod tests {
             span: DUMMY_SP,
         };
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
 
         // Template root children: still two
         assert_eq!(1, sfc_template.roots.len());
@@ -962,7 +3296,7 @@ mod tests {
             ],
             span: DUMMY_SP,
         };
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
         assert_eq!(2, sfc_template.roots.len());
 
         // Should get merged
@@ -995,7 +3329,7 @@ mod tests {
             ],
             span: DUMMY_SP,
         };
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
         assert_eq!(1, sfc_template.roots.len());
     }
 
@@ -1037,7 +3371,7 @@ mod tests {
             span: DUMMY_SP,
         };
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
 
         // Template roots: one div
         assert_eq!(1, sfc_template.roots.len());
@@ -1093,7 +3427,7 @@ mod tests {
             span: DUMMY_SP,
         };
 
-        transform_and_record_template(&mut sfc_template, &mut Default::default());
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
 
         // Template root: both children nodes are still present
         assert_eq!(1, sfc_template.roots.len());
@@ -1103,6 +3437,104 @@ mod tests {
         assert_eq!(2, root.children.len());
     }
 
+    #[test]
+    fn it_optimizes_slot_existence_check() {
+        let element = |tag_name: &str, attributes: Vec<AttributeOrBinding>, children: Vec<Node>| {
+            Node::Element(ElementNode {
+                kind: ElementKind::Element,
+                starting_tag: StartingTag {
+                    tag_name: tag_name.into(),
+                    attributes,
+                    directives: None,
+                },
+                children,
+                template_scope: 0,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })
+        };
+        let slot = |name: &str, children: Vec<Node>| {
+            let name_attr = AttributeOrBinding::RegularAttribute {
+                name: "name".into(),
+                value: name.into(),
+                span: DUMMY_SP,
+            };
+            element("slot", vec![name_attr], children)
+        };
+
+        // <template v-if="condition">children</template>
+        let is_optimized = |condition: &str, children: Vec<Node>| {
+            let Node::Element(mut template) = element("template", vec![], children) else {
+                unreachable!()
+            };
+            template.starting_tag.directives = Some(Box::new(VueDirectives {
+                v_if: Some(js(condition)),
+                ..Default::default()
+            }));
+
+            let mut sfc_template = SfcTemplateBlock {
+                lang: "html".into(),
+                roots: vec![Node::Element(template)],
+                span: DUMMY_SP,
+            };
+            transform_and_record_template(
+                &mut sfc_template,
+                &mut Default::default(),
+                &mut Vec::new(),
+            );
+
+            match sfc_template.roots[0] {
+                Node::Element(ref slot_outlet) => {
+                    assert!(matches!(
+                        slot_outlet.kind,
+                        ElementKind::Builtin(BuiltinType::Slot)
+                    ));
+                    true
+                }
+                Node::ConditionalSeq(_) => false,
+                _ => panic!("Unexpected root"),
+            }
+        };
+
+        // <template v-if="$slots.footer"><slot name="footer" /></template>
+        assert!(is_optimized("$slots.footer", vec![slot("footer", vec![])]));
+        assert!(is_optimized(
+            "$slots['footer']",
+            vec![slot("footer", vec![])]
+        ));
+
+        // <template v-if="$slots.default"><slot /></template>
+        assert!(is_optimized(
+            "$slots.default",
+            vec![element("slot", vec![], vec![])]
+        ));
+
+        // Wrapper must not be rendered when the slot is missing
+        // <template v-if="$slots.footer"><footer><slot name="footer" /></footer></template>
+        assert!(!is_optimized(
+            "$slots.footer",
+            vec![element("footer", vec![], vec![slot("footer", vec![])])]
+        ));
+
+        // Additional content
+        // <template v-if="$slots.footer"><slot name="footer" /><hr /></template>
+        assert!(!is_optimized(
+            "$slots.footer",
+            vec![slot("footer", vec![]), element("hr", vec![], vec![])]
+        ));
+
+        // Different slot name
+        // <template v-if="$slots.header"><slot name="footer" /></template>
+        assert!(!is_optimized("$slots.header", vec![slot("footer", vec![])]));
+
+        // Fallback content must not be rendered when the slot is missing
+        // <template v-if="$slots.footer"><slot name="footer">Footer</slot></template>
+        assert!(!is_optimized(
+            "$slots.footer",
+            vec![slot("footer", vec![Node::Text("Footer".into(), DUMMY_SP)])]
+        ));
+    }
+
     #[test]
     fn it_optimizes_nested_fragments() {
         // For cloning
@@ -1170,7 +3602,11 @@ mod tests {
                 sfc_template.roots.push(Node::Element(div.clone()));
             }
             sfc_template.roots.push(Node::Element(template));
-            transform_and_record_template(&mut sfc_template, &mut Default::default());
+            transform_and_record_template(
+                &mut sfc_template,
+                &mut Default::default(),
+                &mut Vec::new(),
+            );
 
             let Some(Node::ConditionalSeq(cond)) = sfc_template.roots.pop() else {
                 panic!("root is not a conditional seq")
@@ -1330,6 +3766,138 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_condenses_whitespace() {
+        fn element(tag_name: &str, children: Vec<Node>) -> Node {
+            Node::Element(ElementNode {
+                starting_tag: StartingTag {
+                    tag_name: tag_name.into(),
+                    attributes: vec![],
+                    directives: None,
+                },
+                children,
+                template_scope: 0,
+                kind: ElementKind::Element,
+                patch_hints: Default::default(),
+                span: DUMMY_SP,
+            })
+        }
+
+        fn text(text: &str) -> Node {
+            Node::Text(text.into(), DUMMY_SP)
+        }
+
+        // <template><div>
+        //   <span></span>
+        //   <span></span> <span></span>  a
+        //   b  <pre>  x
+        //   y  </pre>
+        // </div></template>
+        let make_template = || SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![element(
+                "div",
+                vec![
+                    text("\n  "),
+                    element("span", vec![]),
+                    text("\n  "),
+                    element("span", vec![]),
+                    text(" "),
+                    element("span", vec![]),
+                    text("  a\n  b  "),
+                    element("pre", vec![text("  x\n  y  ")]),
+                    text("\n"),
+                ],
+            )],
+            span: DUMMY_SP,
+        };
+
+        fn texts(template: &SfcTemplateBlock) -> Vec<String> {
+            let Node::Element(ref div) = template.roots[0] else {
+                panic!("Root is not an element")
+            };
+
+            div.children
+                .iter()
+                .map(|child| match child {
+                    Node::Text(text, _) => text.to_string(),
+                    Node::Element(element) => match element.children.first() {
+                        Some(Node::Text(text, _)) => {
+                            format!("<{}>{}", element.starting_tag.tag_name, text)
+                        }
+                        _ => format!("<{}>", element.starting_tag.tag_name),
+                    },
+                    _ => panic!("Unexpected node"),
+                })
+                .collect()
+        }
+
+        // Condense (default)
+        let mut template = make_template();
+        transform_and_record_template(&mut template, &mut Default::default(), &mut Vec::new());
+        assert_eq!(
+            texts(&template),
+            vec![
+                "<span>",
+                "<span>",
+                " ",
+                "<span>",
+                " a b ",
+                "<pre>  x\n  y  "
+            ]
+        );
+
+        // Preserve
+        let mut template = make_template();
+        let mut bindings_helper = BindingsHelper {
+            whitespace: WhitespaceMode::Preserve,
+            ..Default::default()
+        };
+        transform_and_record_template(&mut template, &mut bindings_helper, &mut Vec::new());
+        assert_eq!(
+            texts(&template),
+            vec![
+                "<span>",
+                " ",
+                "<span>",
+                " ",
+                "<span>",
+                "  a\n  b  ",
+                "<pre>  x\n  y  "
+            ]
+        );
+    }
+
+    #[test]
+    fn it_folds_seq_separated_by_whitespace() {
+        // <template>
+        //   <h1 v-if="true">if</h1> <h2 v-else-if="foo">else-if</h2>
+        //   <h3 v-else>else</h3>
+        // </template>
+        let mut sfc_template = SfcTemplateBlock {
+            lang: "html".into(),
+            roots: vec![
+                if_node(),
+                Node::Text(" ".into(), DUMMY_SP),
+                else_if_node(),
+                Node::Text("\n  ".into(), DUMMY_SP),
+                else_node(),
+            ],
+            span: DUMMY_SP,
+        };
+
+        transform_and_record_template(&mut sfc_template, &mut Default::default(), &mut Vec::new());
+
+        assert_eq!(1, sfc_template.roots.len());
+        let Node::ConditionalSeq(ref seq) = sfc_template.roots[0] else {
+            panic!("Root is not a conditional sequence")
+        };
+        check_if_node(&seq.if_node);
+        assert_eq!(1, seq.else_if_nodes.len());
+        check_else_if_node(&seq.else_if_nodes[0]);
+        check_else_node(seq.else_node.as_ref());
+    }
+
     // text
     fn text_node() -> Node {
         Node::Text("text".into(), DUMMY_SP)
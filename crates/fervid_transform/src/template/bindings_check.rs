@@ -0,0 +1,156 @@
+//! Checks the `<template>` bindings against the script bindings.
+
+use fervid_core::{BindingTypes, ComponentBinding, CustomDirectiveBinding};
+use phf::{phf_set, Set};
+use swc_core::{common::DUMMY_SP, ecma::ast::Expr};
+
+use crate::{
+    error::{TemplateError, TemplateErrorKind, TransformError},
+    BindingsHelper,
+};
+
+/// Public properties of a component instance, always available in the template
+static VUE_INSTANCE_PROPERTIES: Set<&'static str> = phf_set! {
+    "$",
+    "$attrs",
+    "$data",
+    "$el",
+    "$emit",
+    "$forceUpdate",
+    "$nextTick",
+    "$options",
+    "$parent",
+    "$props",
+    "$refs",
+    "$root",
+    "$slots",
+    "$watch",
+};
+
+/// Lints the bindings of an already transformed `<template>`:
+/// - `<script setup>` bindings never referenced in the template are reported as hints;
+/// - template identifiers which are not declared anywhere are reported as errors.
+///
+/// This check is not a part of [`crate::transform_sfc`], because the bindings may come
+/// from sources the compiler does not analyze, e.g. mixins or global properties.
+pub fn check_template_bindings(bindings_helper: &BindingsHelper) -> Vec<TransformError> {
+    let mut errors = Vec::new();
+
+    for binding in bindings_helper.setup_bindings.iter() {
+        let is_used = bindings_helper.used_bindings.contains_key(&binding.0)
+            || is_used_as_component(bindings_helper, &binding.0)
+            || is_used_as_directive(bindings_helper, &binding.0);
+
+        if !is_used {
+            // `SetupBinding`s do not keep their declaration spans, report the name instead
+            errors.push(TransformError::TemplateError(TemplateError {
+                span: DUMMY_SP,
+                kind: TemplateErrorKind::UnusedBinding(binding.0.to_owned()),
+            }));
+        }
+    }
+
+    for (sym, span) in bindings_helper.unresolved_references.iter() {
+        if VUE_INSTANCE_PROPERTIES.contains(sym) {
+            continue;
+        }
+
+        errors.push(TransformError::TemplateError(TemplateError {
+            span: *span,
+            kind: TemplateErrorKind::UndefinedReference,
+        }));
+    }
+
+    errors
+}
+
+fn is_used_as_component(bindings_helper: &BindingsHelper, sym: &str) -> bool {
+    bindings_helper.components.values().any(|component| {
+        matches!(component, ComponentBinding::Resolved(expr) if is_ident_named(expr, sym))
+    })
+}
+
+fn is_used_as_directive(bindings_helper: &BindingsHelper, sym: &str) -> bool {
+    bindings_helper.custom_directives.values().any(|directive| {
+        matches!(directive, CustomDirectiveBinding::Resolved(expr) if is_ident_named(expr, sym))
+    })
+}
+
+/// Resolved components and directives are either `ident` or `unref(ident)`
+fn is_ident_named(expr: &Expr, sym: &str) -> bool {
+    match expr {
+        Expr::Ident(ident) => &ident.sym == sym,
+        Expr::Call(call_expr) => call_expr
+            .args
+            .first()
+            .is_some_and(|arg| is_ident_named(&arg.expr, sym)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{Interpolation, Node, VForDirective, VueDirectives};
+
+    use crate::{
+        test_utils::{element, js, template_error_kinds, transform_template},
+        BindingsHelper, SetupBinding,
+    };
+
+    use super::*;
+
+    #[test]
+    fn it_checks_template_bindings() {
+        // <div v-for="item in list">{{ interpolation }}</div>
+        let check = |interpolation: &str| {
+            let mut roots = vec![element(
+                "div",
+                vec![],
+                Some(Box::new(VueDirectives {
+                    v_for: Some(VForDirective {
+                        iterable: js("list"),
+                        itervar: js("item"),
+                        patch_flags: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                    ..Default::default()
+                })),
+                vec![Node::Interpolation(Interpolation {
+                    value: js(interpolation),
+                    template_scope: 0,
+                    patch_flag: false,
+                    span: DUMMY_SP,
+                })],
+            )];
+
+            let mut bindings_helper = BindingsHelper::default();
+            bindings_helper.setup_bindings = vec![
+                SetupBinding("list".into(), BindingTypes::SetupRef),
+                SetupBinding("title".into(), BindingTypes::Props),
+            ];
+
+            // Keys are intentionally omitted, that warning is irrelevant here
+            transform_template(&mut roots, &mut bindings_helper);
+
+            template_error_kinds(check_template_bindings(&bindings_helper))
+        };
+
+        // All bindings used
+        assert!(check("item + title").is_empty());
+
+        // Unused prop
+        assert_eq!(
+            check("item"),
+            vec![TemplateErrorKind::UnusedBinding("title".into())]
+        );
+
+        // Undefined reference
+        assert_eq!(
+            check("item + title + missing"),
+            vec![TemplateErrorKind::UndefinedReference]
+        );
+
+        // Instance properties are always available
+        assert!(check("$emit('select', item, title)").is_empty());
+    }
+}
@@ -1,4 +1,8 @@
-use swc_core::ecma::{ast::Ident, visit::{Visit, VisitWith}};
+use fervid_core::FervidAtom;
+use swc_core::ecma::{
+    ast::{ArrowExpr, Expr, Ident, MemberProp, PropName, TsTypeAnn},
+    visit::{Visit, VisitWith},
+};
 
 use crate::TemplateScope;
 
@@ -38,12 +42,89 @@ impl Visit for IdentifierVisitor {
                 continue;
             }
 
+            // This is shorthand with a default `a = 1` in `{ a = 1 }`
+            if let Some(assign) = prop.as_assign() {
+                self.collected.push(assign.key.to_owned());
+                continue;
+            }
+
             // This is key-value `a: b` in `{ a: b }`
             let Some(keyvalue) = prop.as_key_value() else { continue };
 
-            // We only support renaming things (therefore value must be an identifier)
-            let Some(value) = keyvalue.value.as_ident() else { continue };
-            self.collected.push(value.to_owned());
+            // Nested destructure or a default, e.g. `a: [b, c]` or `a: b = 1`
+            keyvalue.value.visit_with(self);
+        }
+    }
+
+    fn visit_assign_expr(&mut self, n: &swc_core::ecma::ast::AssignExpr) {
+        // Default value `b = 0` in `[a, b = 0]` only binds the left side
+        n.left.visit_with(self);
+    }
+}
+
+/// Collects the variables referenced by an expression,
+/// e.g. `foo` and `baz` in `foo.bar[baz]`
+pub fn collect_references(expr: &Expr) -> Vec<FervidAtom> {
+    let mut visitor = ReferenceVisitor { collected: vec![] };
+    expr.visit_with(&mut visitor);
+    visitor.collected
+}
+
+struct ReferenceVisitor {
+    collected: Vec<FervidAtom>,
+}
+
+impl Visit for ReferenceVisitor {
+    fn visit_ident(&mut self, n: &Ident) {
+        self.collected.push(n.sym.to_owned());
+    }
+
+    // `bar` in `foo.bar` is not a reference
+    fn visit_member_prop(&mut self, n: &MemberProp) {
+        if let MemberProp::Computed(computed) = n {
+            computed.visit_with(self);
         }
     }
+
+    // `bar` in `{ bar: foo }` is not a reference
+    fn visit_prop_name(&mut self, n: &PropName) {
+        if let PropName::Computed(computed) = n {
+            computed.visit_with(self);
+        }
+    }
+
+    // `item` in `(item) => item` is bound by the arrow itself
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        let mut params_visitor = ParamsVisitor { bound: vec![] };
+        n.params.visit_with(&mut params_visitor);
+
+        let mut arrow_visitor = ReferenceVisitor { collected: vec![] };
+        n.visit_children_with(&mut arrow_visitor);
+
+        self.collected.extend(
+            arrow_visitor
+                .collected
+                .into_iter()
+                .filter(|reference| !params_visitor.bound.contains(reference)),
+        );
+    }
+}
+
+/// Collects the variables bound by the function params, e.g. `a` and `c` in `({ a, b: c }) => {}`
+struct ParamsVisitor {
+    bound: Vec<FervidAtom>,
+}
+
+impl Visit for ParamsVisitor {
+    fn visit_ident(&mut self, n: &Ident) {
+        self.bound.push(n.sym.to_owned());
+    }
+
+    // Default values and computed keys are not bindings, e.g. `b` in `(a = b) => {}`
+    fn visit_expr(&mut self, _n: &Expr) {}
+
+    // `b` in `({ b: c }) => {}` is a key
+    fn visit_prop_name(&mut self, _n: &PropName) {}
+
+    fn visit_ts_type_ann(&mut self, _n: &TsTypeAnn) {}
 }
@@ -17,8 +17,8 @@ use swc_core::{
 };
 
 use crate::{
-    script::common::extract_variables_from_pat, template::js_builtins::JS_BUILTINS, BindingsHelper,
-    SetupBinding,
+    script::common::extract_variables_from_pat, style::use_css_module_call,
+    template::js_builtins::JS_BUILTINS, BindingsHelper, SetupBinding,
 };
 
 use super::utils::wrap_in_event_arrow;
@@ -61,6 +61,8 @@ pub enum IdentTransformStrategy {
     Prefix(FervidAtom),
     /// Generate `isRef(e) ? e.value++ : e++`
     IsRefCheckUpdate,
+    /// Replace with `_useCssModule("name")` for a `<style module="name">`
+    UseCssModule,
 }
 
 pub trait BindingsHelperTransform {
@@ -445,14 +447,24 @@ impl<'s> VisitMut for TransformVisitor<'s> {
                 );
                 return;
             }
+
+            IdentTransformStrategy::UseCssModule => {
+                self.bindings_helper.vue_imports |= VueImports::UseCssModule;
+                *expr = use_css_module_call(&ident.sym, span);
+                return;
+            }
         }
     }
 
     fn visit_mut_member_expr(&mut self, n: &mut MemberExpr) {
-        if n.obj.is_ident() {
-            n.obj.visit_mut_with(self)
-        } else {
-            n.visit_mut_children_with(self);
+        n.obj.visit_mut_with(self);
+
+        // Computed key is only read, e.g. `index` in `items[index] = $event`
+        if let MemberProp::Computed(ref mut computed) = n.prop {
+            let old_is_in_assign_target = self.is_in_assign_target;
+            self.is_in_assign_target = false;
+            computed.expr.visit_mut_with(self);
+            self.is_in_assign_target = old_is_in_assign_target;
         }
     }
 
@@ -563,7 +575,8 @@ impl<'s> VisitMut for TransformVisitor<'s> {
                         }
 
                         IdentTransformStrategy::Unref
-                        | IdentTransformStrategy::IsRefCheckUpdate => {
+                        | IdentTransformStrategy::IsRefCheckUpdate
+                        | IdentTransformStrategy::UseCssModule => {
                             // TODO Error: this is not a valid transform strategy
                             // Error hint: this is a bug in `fervid`, please report it
                         }
@@ -641,7 +654,9 @@ impl<'s> VisitMut for TransformVisitor<'s> {
                         return;
                     }
 
-                    IdentTransformStrategy::Unref | IdentTransformStrategy::IsRefCheckUpdate => {
+                    IdentTransformStrategy::Unref
+                    | IdentTransformStrategy::IsRefCheckUpdate
+                    | IdentTransformStrategy::UseCssModule => {
                         // TODO Error: this is not a valid transform strategy
                         // (technically this is a syntax error, so should be impossible)
                     }
@@ -735,6 +750,28 @@ impl TransformVisitor<'_> {
             .bindings_helper
             .get_var_binding_type(self.current_scope, symbol);
 
+        if let BindingTypes::Unresolved = binding_type {
+            // CSS modules only resolve when there is no binding with the same name
+            if self.bindings_helper.css_modules.contains(symbol) {
+                self.has_js_bindings = true;
+                return IdentTransformStrategy::UseCssModule;
+            }
+
+            self.bindings_helper
+                .unresolved_references
+                .push((symbol.to_owned(), ident.span));
+        }
+
+        // `$parent` and `$root` still resolve from the instance, but their usage is discouraged
+        if self.bindings_helper.warn_deprecated_globals
+            && matches!(binding_type, BindingTypes::Unresolved)
+            && matches!(&**symbol, "$parent" | "$root")
+        {
+            self.bindings_helper
+                .deprecated_globals_spans
+                .push(ident.span);
+        }
+
         // Template local binding doesn't need any processing
         if let BindingTypes::TemplateLocal = binding_type {
             self.has_js_bindings = true;
@@ -1083,6 +1120,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_transforms_template_literals() {
+        let mut helper = BindingsHelper::default();
+
+        macro_rules! test {
+            ($expr: literal, $expected: literal) => {
+                let mut expr = js($expr);
+                helper.transform_expr(&mut expr, 0);
+
+                assert_eq!(to_str(&expr), $expected);
+            };
+        }
+
+        // E.g. `<Teleport :to="...">`
+        test!("`.container-${id}`", "`.container-${_ctx.id}`");
+        test!(
+            "`#${getTarget(id).trim()}-modal`",
+            "`#${_ctx.getTarget(_ctx.id).trim()}-modal`"
+        );
+        test!(
+            "isMobile ? `#mobile-${id}` : '#desktop'",
+            "_ctx.isMobile?`#mobile-${_ctx.id}`:\"#desktop\""
+        );
+        test!("resolveTarget(id)", "_ctx.resolveTarget(_ctx.id)");
+
+        // Locals are left as is
+        test!("id => `.container-${id}`", "id=>`.container-${id}`");
+    }
+
     #[test]
     fn it_transforms_v_model() {
         let mut helper = BindingsHelper::default();
@@ -1209,6 +1275,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_transforms_v_model_complex_targets() {
+        let mut helper = BindingsHelper::default();
+        helper.setup_bindings.push(SetupBinding(
+            FervidAtom::from("items"),
+            BindingTypes::SetupRef,
+        ));
+        helper.setup_bindings.push(SetupBinding(
+            FervidAtom::from("index"),
+            BindingTypes::SetupMaybeRef,
+        ));
+
+        macro_rules! test {
+            ($value: literal, $expected_value: literal, $expected_handler: literal) => {
+                let mut v_model = VModelDirective {
+                    argument: None,
+                    value: js($value),
+                    update_handler: None,
+                    modifiers: vec![],
+                    span: DUMMY_SP,
+                };
+                let mut patch_hints = PatchHints::default();
+                helper.transform_v_model(&mut v_model, 0, &mut patch_hints);
+                assert_eq!(to_str(&v_model.value), $expected_value);
+                assert_eq!(
+                    to_str(&v_model.update_handler.expect("Handler cannot be None")),
+                    $expected_handler
+                );
+            };
+        }
+
+        // DEV
+        test!(
+            "user.name",
+            "_ctx.user.name",
+            "$event=>_ctx.user.name=$event"
+        );
+        test!(
+            "items[index]",
+            "$setup.items[$setup.index]",
+            "$event=>$setup.items[$setup.index]=$event"
+        );
+        test!(
+            "user.tags[index].label",
+            "_ctx.user.tags[$setup.index].label",
+            "$event=>_ctx.user.tags[$setup.index].label=$event"
+        );
+
+        // PROD, the index is only read and thus unwrapped
+        helper.is_prod = true;
+        helper.template_generation_mode = TemplateGenerationMode::Inline;
+        test!(
+            "items[index]",
+            "items.value[_unref(index)]",
+            "$event=>items.value[_unref(index)]=$event"
+        );
+        test!(
+            "user.tags[index + 1]",
+            "_ctx.user.tags[_unref(index)+1]",
+            "$event=>_ctx.user.tags[_unref(index)+1]=$event"
+        );
+    }
+
     #[test]
     fn it_transforms_v_model_arg() {
         let mut helper = BindingsHelper::default();
@@ -394,6 +394,9 @@ mod tests {
             bindings_helper,
             current_scope: 0,
             v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
         }
     }
 }
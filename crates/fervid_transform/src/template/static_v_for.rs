@@ -0,0 +1,800 @@
+//! Compile-time unrolling of `v-for` over static lists, enabled by `unroll_static_for`.
+//!
+//! `<li v-for="size in ['s', 'm'] as const">{{ size }}</li>` is compiled as if it was
+//! `<li>{{ 's' }}</li><li>{{ 'm' }}</li>`, so that no `_renderList` call is generated.
+//!
+//! Lists declared in the script are only unrolled when the script never uses them,
+//! since a `const` array can still be mutated, e.g. `sizes.push('l')`.
+
+use fervid_core::{
+    AttributeOrBinding, ElementNode, FervidAtom, Node, StrOrExpr, VSlotDirective, VueDirectives,
+};
+use fxhash::FxHashMap as HashMap;
+use smallvec::SmallVec;
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            BindingIdent, Decl, ExportNamedSpecifier, Expr, ExprOrSpread, Function, KeyValueProp,
+            Lit, Module, ModuleExportName, ModuleItem, Number, Pat, Prop, PropName, Stmt,
+            UpdateExpr, VarDeclKind,
+        },
+        visit::{Visit, VisitMut, VisitMutWith, VisitWith},
+    },
+};
+
+use crate::TemplateScope;
+
+use super::collect_vars::collect_variables;
+
+/// Longer lists are still rendered using `_renderList` to keep the code size in check
+const MAX_UNROLLED_ITEMS: usize = 8;
+
+/// Collects the top-level `const`s of the module and of `setup`
+/// which are initialized with an array of literals, e.g. `const sizes = ['s', 'm'] as const`.
+///
+/// Lists which are exported or referenced anywhere in the script are skipped,
+/// because their contents may change, e.g. `sizes.push('l')` or `sort(sizes)`.
+/// The `return` of `setup` only exposes the bindings to the template and is not checked.
+pub fn collect_static_lists(
+    module: &Module,
+    setup_fn: Option<&Function>,
+) -> HashMap<FervidAtom, Vec<Lit>> {
+    let mut static_lists = HashMap::default();
+
+    let module_decls = module
+        .body
+        .iter()
+        .filter_map(|module_item| match module_item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => Some(var_decl),
+            _ => None,
+        });
+
+    let setup_decls = setup_fn
+        .and_then(|setup_fn| setup_fn.body.as_ref())
+        .into_iter()
+        .flat_map(|body| body.stmts.iter())
+        .filter_map(|stmt| match stmt {
+            Stmt::Decl(Decl::Var(var_decl)) => Some(var_decl),
+            _ => None,
+        });
+
+    for var_decl in module_decls.chain(setup_decls) {
+        if !matches!(var_decl.kind, VarDeclKind::Const) {
+            continue;
+        }
+
+        for declarator in var_decl.decls.iter() {
+            let (Pat::Ident(binding), Some(init)) = (&declarator.name, declarator.init.as_ref())
+            else {
+                continue;
+            };
+
+            if let Some(items) = get_static_items(init) {
+                static_lists.insert(binding.id.sym.to_owned(), items);
+            }
+        }
+    }
+
+    let mut usage_finder = StaticListUsageFinder {
+        static_lists: &mut static_lists,
+    };
+    module.visit_with(&mut usage_finder);
+    if let Some(body) = setup_fn.and_then(|setup_fn| setup_fn.body.as_ref()) {
+        for stmt in body.stmts.iter() {
+            if !matches!(stmt, Stmt::Return(_)) {
+                stmt.visit_with(&mut usage_finder);
+            }
+        }
+    }
+
+    static_lists
+}
+
+/// Replaces the elements with `v-for` over a static list by a copy per each list item.
+/// The list is either an array of literals or one of the `static_lists`.
+///
+/// Elements are left as is when unrolling them is not trivial,
+/// e.g. when the iteration variables are re-declared or assigned to.
+pub fn unroll_static_v_for(nodes: &mut Vec<Node>, static_lists: &HashMap<FervidAtom, Vec<Lit>>) {
+    let mut unroller = StaticForUnroller {
+        static_lists,
+        local_vars: Vec::new(),
+    };
+
+    unroller.unroll_children(nodes);
+}
+
+struct StaticForUnroller<'l> {
+    static_lists: &'l HashMap<FervidAtom, Vec<Lit>>,
+    /// Variables of the enclosing `v-for`s and `v-slot`s, which shadow the `static_lists`
+    local_vars: Vec<FervidAtom>,
+}
+
+impl StaticForUnroller<'_> {
+    fn unroll_children(&mut self, nodes: &mut Vec<Node>) {
+        let mut idx = 0;
+        while idx < nodes.len() {
+            let Node::Element(ref mut element_node) = nodes[idx] else {
+                idx += 1;
+                continue;
+            };
+
+            match self.try_unroll(element_node) {
+                Some(unrolled) => {
+                    let unrolled_len = unrolled.len();
+                    nodes.splice(idx..=idx, unrolled);
+                    idx += unrolled_len;
+                }
+                None => {
+                    self.unroll_element_children(element_node);
+                    idx += 1;
+                }
+            }
+        }
+    }
+
+    fn unroll_element_children(&mut self, element_node: &mut ElementNode) {
+        let local_vars_len = self.local_vars.len();
+
+        if let Some(ref directives) = element_node.starting_tag.directives {
+            let mut scope = TemplateScope {
+                variables: SmallVec::new(),
+                parent: 0,
+            };
+
+            if let Some(ref v_for) = directives.v_for {
+                collect_variables(v_for.itervar.as_ref(), &mut scope);
+            }
+            if let Some(VSlotDirective {
+                value: Some(ref value),
+                ..
+            }) = directives.v_slot
+            {
+                collect_variables(value.as_ref(), &mut scope);
+            }
+
+            self.local_vars.extend(scope.variables);
+        }
+
+        self.unroll_children(&mut element_node.children);
+        self.local_vars.truncate(local_vars_len);
+    }
+
+    fn try_unroll(&mut self, element_node: &ElementNode) -> Option<Vec<Node>> {
+        let directives = element_node.starting_tag.directives.as_ref()?;
+        let v_for = directives.v_for.as_ref()?;
+
+        // Conditions apply to the whole list, and `v-else` cannot follow several copies
+        if directives.v_if.is_some()
+            || directives.v_else_if.is_some()
+            || directives.v_else.is_some()
+            || directives.v_slot.is_some()
+        {
+            return None;
+        }
+
+        let items = self.resolve_static_items(&v_for.iterable)?;
+        if items.len() > MAX_UNROLLED_ITEMS {
+            return None;
+        }
+
+        let itervars = get_itervar_names(&v_for.itervar)?;
+
+        let mut template = element_node.to_owned();
+        if let Some(ref mut directives) = template.starting_tag.directives {
+            directives.v_for = None;
+        }
+
+        if !can_substitute(&mut template, &itervars) {
+            return None;
+        }
+
+        // `<template v-for>` only groups its children
+        let is_grouping = template.starting_tag.tag_name == "template";
+
+        let mut unrolled = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            let index = Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: index as f64,
+                raw: None,
+            }));
+
+            // Value and index, e.g. `(item, index)`
+            let replacements: Vec<(FervidAtom, Expr)> = itervars
+                .iter()
+                .cloned()
+                .zip([Expr::Lit(item), index])
+                .collect();
+
+            let mut copy = template.to_owned();
+            visit_mut_element_exprs(
+                &mut copy,
+                &mut ItervarReplacer {
+                    replacements: &replacements,
+                },
+            );
+            self.unroll_element_children(&mut copy);
+
+            if is_grouping {
+                unrolled.append(&mut copy.children);
+            } else {
+                unrolled.push(Node::Element(copy));
+            }
+        }
+
+        Some(unrolled)
+    }
+
+    fn resolve_static_items(&self, iterable: &Expr) -> Option<Vec<Lit>> {
+        match iterable {
+            Expr::Ident(ident) if !self.local_vars.contains(&ident.sym) => {
+                self.static_lists.get(&ident.sym).cloned()
+            }
+            Expr::Ident(_) => None,
+            _ => get_static_items(iterable),
+        }
+    }
+}
+
+/// Gets the items of an array of literals, optionally with `as const`
+fn get_static_items(expr: &Expr) -> Option<Vec<Lit>> {
+    match expr {
+        Expr::Paren(paren_expr) => get_static_items(&paren_expr.expr),
+        Expr::TsConstAssertion(const_assertion) => get_static_items(&const_assertion.expr),
+        Expr::Array(array_lit) => array_lit
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Some(ExprOrSpread { spread: None, expr }) => match expr.as_ref() {
+                    Expr::Lit(lit @ (Lit::Str(_) | Lit::Num(_) | Lit::Bool(_) | Lit::Null(_))) => {
+                        Some(lit.to_owned())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Supports `item` and `(item, index)`, but not destructuring
+fn get_itervar_names(itervar: &Expr) -> Option<Vec<FervidAtom>> {
+    match itervar {
+        Expr::Ident(ident) => Some(vec![ident.sym.to_owned()]),
+        Expr::Paren(paren_expr) => get_itervar_names(&paren_expr.expr),
+        Expr::Seq(seq_expr) if seq_expr.exprs.len() <= 2 => seq_expr
+            .exprs
+            .iter()
+            .map(|expr| match expr.as_ref() {
+                Expr::Ident(ident) => Some(ident.sym.to_owned()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Checks that the iteration variables can be replaced by literals,
+/// i.e. they are neither re-declared nor assigned to
+fn can_substitute(element_node: &mut ElementNode, names: &[FervidAtom]) -> bool {
+    if has_conflicting_directives(element_node, names) {
+        return false;
+    }
+
+    let mut checker = ItervarUsageChecker {
+        names,
+        is_substitutable: true,
+    };
+    visit_mut_element_exprs(element_node, &mut checker);
+
+    checker.is_substitutable
+}
+
+fn has_conflicting_directives(element_node: &ElementNode, names: &[FervidAtom]) -> bool {
+    if let Some(ref directives) = element_node.starting_tag.directives {
+        let mut scope = TemplateScope {
+            variables: SmallVec::new(),
+            parent: 0,
+        };
+
+        if let Some(ref v_for) = directives.v_for {
+            collect_variables(v_for.itervar.as_ref(), &mut scope);
+        }
+        if let Some(VSlotDirective {
+            value: Some(ref value),
+            ..
+        }) = directives.v_slot
+        {
+            collect_variables(value.as_ref(), &mut scope);
+        }
+
+        if scope
+            .variables
+            .iter()
+            .any(|variable| names.contains(variable))
+        {
+            return true;
+        }
+
+        // `v-model="item"` would assign to a literal
+        let is_modelled = directives.v_model.iter().any(|v_model| {
+            matches!(v_model.value.as_ref(), Expr::Ident(ident) if names.contains(&ident.sym))
+        });
+        if is_modelled {
+            return true;
+        }
+    }
+
+    element_node.children.iter().any(|child| {
+        matches!(child, Node::Element(child_element) if has_conflicting_directives(child_element, names))
+    })
+}
+
+/// Visits the expressions of the element and its descendants.
+/// `v-for` and `v-slot` declarations are not visited.
+fn visit_mut_element_exprs(element_node: &mut ElementNode, visitor: &mut impl VisitMut) {
+    for attribute in element_node.starting_tag.attributes.iter_mut() {
+        match attribute {
+            AttributeOrBinding::VBind(v_bind) => {
+                if let Some(StrOrExpr::Expr(ref mut argument)) = v_bind.argument {
+                    visitor.visit_mut_expr(argument);
+                }
+                visitor.visit_mut_expr(&mut v_bind.value);
+            }
+            AttributeOrBinding::VOn(v_on) => {
+                if let Some(StrOrExpr::Expr(ref mut event)) = v_on.event {
+                    visitor.visit_mut_expr(event);
+                }
+                if let Some(ref mut handler) = v_on.handler {
+                    visitor.visit_mut_expr(handler);
+                }
+            }
+            AttributeOrBinding::RegularAttribute { .. } => {}
+        }
+    }
+
+    if let Some(ref mut directives) = element_node.starting_tag.directives {
+        let VueDirectives {
+            custom,
+            v_else_if,
+            v_for,
+            v_html,
+            v_if,
+            v_memo,
+            v_model,
+            v_show,
+            v_slot,
+            v_text,
+            ..
+        } = &mut **directives;
+
+        for custom_directive in custom.iter_mut() {
+            if let Some(StrOrExpr::Expr(ref mut argument)) = custom_directive.argument {
+                visitor.visit_mut_expr(argument);
+            }
+            if let Some(ref mut value) = custom_directive.value {
+                visitor.visit_mut_expr(value);
+            }
+        }
+
+        for v_model in v_model.iter_mut() {
+            if let Some(StrOrExpr::Expr(ref mut argument)) = v_model.argument {
+                visitor.visit_mut_expr(argument);
+            }
+            visitor.visit_mut_expr(&mut v_model.value);
+        }
+
+        if let Some(VSlotDirective {
+            slot_name: Some(StrOrExpr::Expr(slot_name)),
+            ..
+        }) = v_slot
+        {
+            visitor.visit_mut_expr(slot_name);
+        }
+
+        if let Some(v_for) = v_for {
+            visitor.visit_mut_expr(&mut v_for.iterable);
+        }
+
+        for expr in [v_else_if, v_html, v_if, v_memo, v_show, v_text]
+            .into_iter()
+            .flatten()
+        {
+            visitor.visit_mut_expr(expr);
+        }
+    }
+
+    for child in element_node.children.iter_mut() {
+        match child {
+            Node::Element(child_element) => visit_mut_element_exprs(child_element, visitor),
+            Node::Interpolation(interpolation) => visitor.visit_mut_expr(&mut interpolation.value),
+            _ => {}
+        }
+    }
+}
+
+/// Removes the static lists referenced by the visited code.
+/// Declarations are not references, because only `Expr`s and shorthands are checked.
+struct StaticListUsageFinder<'l> {
+    static_lists: &'l mut HashMap<FervidAtom, Vec<Lit>>,
+}
+
+impl Visit for StaticListUsageFinder<'_> {
+    fn visit_expr(&mut self, n: &Expr) {
+        if let Expr::Ident(ident) = n {
+            self.static_lists.remove(&ident.sym);
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+
+    // `{ sizes }`
+    fn visit_prop(&mut self, n: &Prop) {
+        if let Prop::Shorthand(ident) = n {
+            self.static_lists.remove(&ident.sym);
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+
+    // `export { sizes }`
+    fn visit_export_named_specifier(&mut self, n: &ExportNamedSpecifier) {
+        if let ModuleExportName::Ident(ref ident) = n.orig {
+            self.static_lists.remove(&ident.sym);
+        }
+    }
+}
+
+struct ItervarUsageChecker<'n> {
+    names: &'n [FervidAtom],
+    is_substitutable: bool,
+}
+
+impl VisitMut for ItervarUsageChecker<'_> {
+    // Declarations and assignments, e.g. `item => item` or `item = 1`
+    fn visit_mut_binding_ident(&mut self, n: &mut BindingIdent) {
+        if self.names.contains(&n.id.sym) {
+            self.is_substitutable = false;
+        }
+    }
+
+    fn visit_mut_update_expr(&mut self, n: &mut UpdateExpr) {
+        if matches!(n.arg.as_ref(), Expr::Ident(ident) if self.names.contains(&ident.sym)) {
+            self.is_substitutable = false;
+        }
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+struct ItervarReplacer<'r> {
+    replacements: &'r [(FervidAtom, Expr)],
+}
+
+impl ItervarReplacer<'_> {
+    fn find(&self, sym: &FervidAtom) -> Option<&Expr> {
+        self.replacements
+            .iter()
+            .find_map(|(name, replacement)| (name == sym).then_some(replacement))
+    }
+}
+
+impl VisitMut for ItervarReplacer<'_> {
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        if let Expr::Ident(ident) = n {
+            if let Some(replacement) = self.find(&ident.sym) {
+                *n = replacement.to_owned();
+            }
+            return;
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_prop(&mut self, n: &mut Prop) {
+        // `{ item }` becomes `{ item: 'a' }`
+        if let Prop::Shorthand(ident) = n {
+            if let Some(replacement) = self.find(&ident.sym) {
+                *n = Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(ident.to_owned()),
+                    value: Box::new(replacement.to_owned()),
+                });
+            }
+            return;
+        }
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fervid_core::{Interpolation, StartingTag, VBindDirective, VForDirective};
+
+    use crate::test_utils::{parser::parse_typescript_module, to_str, ts};
+
+    use super::*;
+
+    fn v_for_element(
+        tag_name: &str,
+        itervar: &str,
+        iterable: &str,
+        attributes: Vec<AttributeOrBinding>,
+        children: Vec<Node>,
+    ) -> Node {
+        Node::Element(ElementNode {
+            kind: fervid_core::ElementKind::Element,
+            starting_tag: StartingTag {
+                tag_name: tag_name.into(),
+                attributes,
+                directives: Some(Box::new(VueDirectives {
+                    v_for: Some(VForDirective {
+                        iterable: ts(iterable),
+                        itervar: ts(itervar),
+                        patch_flags: Default::default(),
+                        span: DUMMY_SP,
+                    }),
+                    ..Default::default()
+                })),
+            },
+            children,
+            template_scope: 0,
+            patch_hints: Default::default(),
+            span: DUMMY_SP,
+        })
+    }
+
+    fn interpolation(value: &str) -> Node {
+        Node::Interpolation(Interpolation {
+            value: ts(value),
+            template_scope: 0,
+            patch_flag: false,
+            span: DUMMY_SP,
+        })
+    }
+
+    fn v_bind(name: &str, value: &str) -> AttributeOrBinding {
+        AttributeOrBinding::VBind(VBindDirective {
+            argument: Some(name.into()),
+            value: ts(value),
+            is_camel: false,
+            is_prop: false,
+            is_attr: false,
+            span: DUMMY_SP,
+        })
+    }
+
+    fn as_element(node: &Node) -> &ElementNode {
+        let Node::Element(element_node) = node else {
+            panic!("Not an element");
+        };
+        element_node
+    }
+
+    fn is_v_for(node: &Node) -> bool {
+        as_element(node)
+            .starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|directives| directives.v_for.is_some())
+    }
+
+    /// Stringified interpolations of an element
+    fn interpolations(node: &Node) -> Vec<String> {
+        as_element(node)
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Interpolation(interpolation) => Some(to_str(&interpolation.value)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_unrolls_small_static_list() {
+        // <li v-for="(item, index) in ['a', 'b', 'c'] as const" :key="item">{{ index }}{{ { item } }}</li>
+        let mut nodes = vec![v_for_element(
+            "li",
+            "(item, index)",
+            r#"["a", "b", "c"] as const"#,
+            vec![v_bind("key", "item")],
+            vec![interpolation("index"), interpolation("{ item }")],
+        )];
+
+        unroll_static_v_for(&mut nodes, &HashMap::default());
+
+        assert_eq!(nodes.len(), 3);
+        assert!(!nodes.iter().any(is_v_for));
+        assert_eq!(interpolations(&nodes[0]), vec!["0", r#"{item:"a"}"#]);
+        assert_eq!(interpolations(&nodes[2]), vec!["2", r#"{item:"c"}"#]);
+
+        let AttributeOrBinding::VBind(ref key) = as_element(&nodes[1]).starting_tag.attributes[0]
+        else {
+            panic!("Not a v-bind");
+        };
+        assert_eq!(to_str(&key.value), r#""b""#);
+
+        // `<template v-for>` is replaced by its children
+        let mut nodes = vec![v_for_element(
+            "template",
+            "item",
+            "[1, 2]",
+            vec![],
+            vec![interpolation("item"), interpolation("item * 10")],
+        )];
+        unroll_static_v_for(&mut nodes, &HashMap::default());
+        let values: Vec<String> = nodes
+            .iter()
+            .map(|node| match node {
+                Node::Interpolation(interpolation) => to_str(&interpolation.value),
+                _ => panic!("Not an interpolation"),
+            })
+            .collect();
+        assert_eq!(values, vec!["1", "1*10", "2", "2*10"]);
+    }
+
+    #[test]
+    fn it_does_not_unroll_large_static_list() {
+        let iterable = format!(
+            "[{}] as const",
+            (0..=MAX_UNROLLED_ITEMS)
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let mut nodes = vec![v_for_element(
+            "li",
+            "item",
+            &iterable,
+            vec![],
+            vec![interpolation("item")],
+        )];
+
+        unroll_static_v_for(&mut nodes, &HashMap::default());
+
+        assert_eq!(nodes.len(), 1);
+        assert!(is_v_for(&nodes[0]));
+    }
+
+    #[test]
+    fn it_does_not_unroll_dynamic_list() {
+        let check = |iterable: &str, interpolation_value: &str| {
+            let mut nodes = vec![v_for_element(
+                "li",
+                "item",
+                iterable,
+                vec![],
+                vec![interpolation(interpolation_value)],
+            )];
+
+            unroll_static_v_for(&mut nodes, &HashMap::default());
+
+            nodes.len() == 1 && is_v_for(&nodes[0])
+        };
+
+        assert!(check("list", "item"));
+        assert!(check("[a, b]", "item"));
+        assert!(check("[...list, 1]", "item"));
+        assert!(check("10", "item"));
+
+        // Static list, but `item` is re-declared or assigned to
+        assert!(check("[1, 2]", "list.map(item => item + 1)"));
+        assert!(check("[1, 2]", "item++"));
+        assert!(!check("[1, 2]", "list.map(other => other + item)"));
+    }
+
+    #[test]
+    fn it_unrolls_nested_static_level() {
+        // <tr v-for="row in rows"><td v-for="col in ['name', 'age'] as const">{{ row[col] }}</td></tr>
+        let inner = v_for_element(
+            "td",
+            "col",
+            r#"["name", "age"] as const"#,
+            vec![],
+            vec![interpolation("row[col]")],
+        );
+        let mut nodes = vec![v_for_element("tr", "row", "rows", vec![], vec![inner])];
+
+        unroll_static_v_for(&mut nodes, &HashMap::default());
+
+        assert_eq!(nodes.len(), 1);
+        assert!(is_v_for(&nodes[0]));
+
+        let cells = &as_element(&nodes[0]).children;
+        assert_eq!(cells.len(), 2);
+        assert_eq!(interpolations(&cells[0]), vec![r#"row["name"]"#]);
+        assert_eq!(interpolations(&cells[1]), vec![r#"row["age"]"#]);
+
+        // Inner level is not unrolled when its itervar shadows the outer one
+        let inner = v_for_element("td", "row", "row.cells", vec![], vec![interpolation("row")]);
+        let mut nodes = vec![v_for_element("tr", "row", "[1, 2]", vec![], vec![inner])];
+        unroll_static_v_for(&mut nodes, &HashMap::default());
+        assert_eq!(nodes.len(), 1);
+    }
+
+    #[test]
+    fn it_unrolls_static_list_constants() {
+        let (module, _) = parse_typescript_module(
+            r#"
+            const sizes = ["s", "m"] as const
+            const colors = ["red", "green", "blue"]
+            let mutable = [1, 2]
+            const computed = [a, b]
+            "#,
+            0,
+            Default::default(),
+        )
+        .unwrap();
+
+        let static_lists = collect_static_lists(&module, None);
+        assert_eq!(static_lists.len(), 2);
+        assert_eq!(
+            static_lists.get(&FervidAtom::from("sizes")).map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(
+            static_lists.get(&FervidAtom::from("colors")).map(Vec::len),
+            Some(3)
+        );
+
+        let mut nodes = vec![v_for_element(
+            "li",
+            "size",
+            "sizes",
+            vec![],
+            vec![interpolation("size")],
+        )];
+        unroll_static_v_for(&mut nodes, &static_lists);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(interpolations(&nodes[1]), vec![r#""m""#]);
+
+        // `sizes` is shadowed by the outer `v-for`
+        let inner = v_for_element("li", "size", "sizes", vec![], vec![interpolation("size")]);
+        let mut nodes = vec![v_for_element("ul", "sizes", "groups", vec![], vec![inner])];
+        unroll_static_v_for(&mut nodes, &static_lists);
+        assert!(is_v_for(&as_element(&nodes[0]).children[0]));
+    }
+
+    #[test]
+    fn it_skips_static_lists_used_in_script() {
+        let (module, _) = parse_typescript_module(
+            r#"
+            const pushed = ["s", "m"]
+            pushed.push("l")
+            const sorted = [3, 1, 2]
+            sort(sorted)
+            const wrapped = [1, 2]
+            const holder = { wrapped }
+            const reexported = [1, 2]
+            export { reexported }
+            export const exported = [1, 2]
+            const unused = ["a", "b"]
+            "#,
+            0,
+            Default::default(),
+        )
+        .unwrap();
+
+        let static_lists = collect_static_lists(&module, None);
+        assert_eq!(static_lists.len(), 1);
+        assert!(static_lists.contains_key(&FervidAtom::from("unused")));
+
+        // `v-for` over a list mutated in the script is kept
+        let mut nodes = vec![v_for_element(
+            "li",
+            "size",
+            "pushed",
+            vec![],
+            vec![interpolation("size")],
+        )];
+        unroll_static_v_for(&mut nodes, &static_lists);
+        assert_eq!(nodes.len(), 1);
+        assert!(is_v_for(&nodes[0]));
+    }
+}
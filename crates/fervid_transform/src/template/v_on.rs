@@ -1,18 +1,39 @@
-use fervid_core::{fervid_atom, BindingTypes, FervidAtom, StrOrExpr, VOnDirective};
+use fervid_core::{
+    fervid_atom, AttributeOrBinding, BindingTypes, ElementKind, FervidAtom, StartingTag, StrOrExpr,
+    VBindDirective, VOnDirective,
+};
 use swc_core::{
-    common::DUMMY_SP,
-    ecma::ast::{
-        ArrowExpr, BinExpr, BinaryOp, BindingIdent, BlockStmtOrExpr, CallExpr, Callee, Expr,
-        ExprOrSpread, Ident, Pat, RestPat,
+    common::{EqIgnoreSpan, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrowExpr, AssignExpr, AssignOp, AssignTarget, BinExpr, BinaryOp, BindingIdent,
+            BlockStmtOrExpr, CallExpr, Callee, Expr, ExprOrSpread, Ident, Lit, Pat, RestPat,
+            SimpleAssignTarget,
+        },
+        visit::{Visit, VisitWith},
     },
 };
 
+use crate::error::TemplateErrorKind;
+
 use super::{
     ast_transform::TemplateVisitor,
     expr_transform::BindingsHelperTransform,
     utils::{to_camel_case, to_pascal_case, wrap_in_event_arrow},
 };
 
+/// Events which have the `button` property
+const MOUSE_EVENTS: [&str; 8] = [
+    "auxclick",
+    "click",
+    "contextmenu",
+    "dblclick",
+    "mousedown",
+    "mouseup",
+    "pointerdown",
+    "pointerup",
+];
+
 impl TemplateVisitor<'_> {
     pub fn transform_v_on(&mut self, v_on: &mut VOnDirective, scope_to_use: u32) {
         match v_on.event.as_mut() {
@@ -25,7 +46,13 @@ impl TemplateVisitor<'_> {
                     .transform_expr(dynamic_event, scope_to_use);
             }
 
-            None => {}
+            // `v-on="{ click: onClick }"` binds an object of handlers, it is used as is
+            None => {
+                if let Some(ref mut handlers) = v_on.handler {
+                    self.bindings_helper.transform_expr(handlers, scope_to_use);
+                }
+                return;
+            }
         }
 
         if let Some(mut handler) = v_on.handler.take() {
@@ -119,6 +146,243 @@ impl TemplateVisitor<'_> {
     }
 }
 
+impl TemplateVisitor<'_> {
+    /// Reports `v-on` with a missing or an empty handler, e.g. `@click` or `@click=""`.
+    /// Modifiers without a handler are fine, e.g. `@submit.prevent` only prevents the default.
+    /// Must be called before the handler is transformed.
+    pub fn check_v_on_handler(&mut self, v_on: &VOnDirective) {
+        // `v-on="handlers"` has no event
+        if v_on.event.is_none() {
+            return;
+        }
+
+        let kind = match v_on.handler {
+            None if v_on.modifiers.is_empty() => TemplateErrorKind::VOnMissingHandler,
+            Some(ref handler) if is_empty_handler(handler) => TemplateErrorKind::VOnEmptyHandler,
+            _ => return,
+        };
+
+        self.report_error(v_on.span, kind);
+    }
+
+    /// Strips the Vue 2 `.native` modifier and reports its usage.
+    /// In Vue 3, listeners on a component always fall through to its root element.
+    pub fn check_v_on_native_modifier(
+        &mut self,
+        v_on: &mut VOnDirective,
+        element_kind: ElementKind,
+    ) {
+        let modifiers_len = v_on.modifiers.len();
+        v_on.modifiers.retain(|modifier| modifier != "native");
+        if v_on.modifiers.len() == modifiers_len {
+            return;
+        }
+
+        let kind = match element_kind {
+            ElementKind::Element => TemplateErrorKind::VOnNativeModifierOnElement,
+            _ if self.bindings_helper.is_compat => TemplateErrorKind::VOnNativeModifierDeprecated,
+            _ => TemplateErrorKind::VOnNativeModifierRemoved,
+        };
+
+        self.report_error(v_on.span, kind);
+    }
+
+    /// Reports `.stop` on `touchstart` and `touchmove`, which browsers treat as passive.
+    /// On iOS, `stopPropagation()` in a passive listener may be ignored unless `.prevent` is added.
+    /// `.passive` does not matter: it only makes the default explicit, and without `.stop`
+    /// there is nothing to report.
+    /// This is only a diagnostic, the generated code stays the same.
+    pub fn check_v_on_touch_stop_modifier(&mut self, v_on: &VOnDirective) {
+        let Some(StrOrExpr::Str(ref event)) = v_on.event else {
+            return;
+        };
+        if !matches!(&**event, "touchstart" | "touchmove") {
+            return;
+        }
+
+        let has_modifier = |name: &str| v_on.modifiers.iter().any(|modifier| modifier == name);
+        if !has_modifier("stop") || has_modifier("prevent") {
+            return;
+        }
+
+        self.report_error(v_on.span, TemplateErrorKind::VOnStopModifierOnPassiveTouch);
+    }
+
+    /// Reports the mouse button modifiers (`.left`, `.right`, `.middle`) on non-mouse events.
+    /// `withModifiers` checks `event.button` for them, which only mouse events have.
+    /// Note that on keyboard events Vue treats `.left` and `.right` as the arrow keys.
+    pub fn check_v_on_mouse_button_modifier(&mut self, v_on: &VOnDirective) {
+        let Some(StrOrExpr::Str(ref event)) = v_on.event else {
+            return;
+        };
+        if MOUSE_EVENTS.contains(&&**event) {
+            return;
+        }
+
+        let has_mouse_modifier = v_on
+            .modifiers
+            .iter()
+            .any(|modifier| matches!(&**modifier, "left" | "right" | "middle"));
+        if !has_mouse_modifier {
+            return;
+        }
+
+        self.report_error(
+            v_on.span,
+            TemplateErrorKind::VOnMouseModifierOnNonMouseEvent,
+        );
+    }
+
+    /// Suggests `v-model` for a manual two-way binding on a native element,
+    /// e.g. `<input :value="text" @input="text = $event.target.value">`.
+    /// Must be called before the expressions are transformed.
+    pub fn check_manual_two_way_binding(
+        &mut self,
+        starting_tag: &StartingTag,
+        element_kind: ElementKind,
+    ) {
+        if !matches!(element_kind, ElementKind::Element) {
+            return;
+        }
+
+        let has_v_model = starting_tag
+            .directives
+            .as_ref()
+            .is_some_and(|directives| !directives.v_model.is_empty());
+        if has_v_model {
+            return;
+        }
+
+        let Some(value) = starting_tag.attributes.iter().find_map(|attr| match attr {
+            AttributeOrBinding::VBind(VBindDirective {
+                argument: Some(StrOrExpr::Str(name)),
+                value,
+                ..
+            }) if name == "value" => Some(value),
+            _ => None,
+        }) else {
+            return;
+        };
+
+        for attr in starting_tag.attributes.iter() {
+            let AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Str(event)),
+                handler: Some(handler),
+                span,
+                ..
+            }) = attr
+            else {
+                continue;
+            };
+            if !matches!(&**event, "input" | "change") {
+                continue;
+            }
+
+            let mut finder = AssignmentFinder {
+                target: value,
+                is_found: false,
+            };
+            finder.visit_expr(handler);
+
+            if finder.is_found {
+                self.report_error(*span, TemplateErrorKind::VBindManualTwoWayBinding);
+                return;
+            }
+        }
+    }
+}
+
+/// Looks for `target = ...` anywhere in a handler, including `$event => (target = ...)`
+struct AssignmentFinder<'t> {
+    target: &'t Expr,
+    is_found: bool,
+}
+
+impl Visit for AssignmentFinder<'_> {
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        let is_same_target = match (&n.left, self.target) {
+            (AssignTarget::Simple(SimpleAssignTarget::Ident(binding)), Expr::Ident(ident)) => {
+                binding.id.sym == ident.sym
+            }
+            (AssignTarget::Simple(SimpleAssignTarget::Member(member)), Expr::Member(target)) => {
+                member.eq_ignore_span(target)
+            }
+            _ => false,
+        };
+
+        if matches!(n.op, AssignOp::Assign) && is_same_target {
+            self.is_found = true;
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Unrolls the static arrays of events, e.g. `@[['click', 'hover']]="handler"`
+/// becomes `@click="handler" @hover="handler"`, and `@[[]]="handler"` is removed.
+///
+/// Arrays with dynamic event names, e.g. `@[[eventName, 'hover']]`, are left as-is
+/// and are bound at runtime using `_toHandlers`.
+///
+/// This syntax is a fervid-only extension: Vue expects the dynamic argument of `v-on`
+/// to be a single event name, so such templates are not portable to the official compiler.
+pub fn unroll_v_on_event_arrays(attributes: &mut Vec<AttributeOrBinding>) {
+    if !attributes
+        .iter()
+        .any(|attr| get_static_event_array(attr).is_some())
+    {
+        return;
+    }
+
+    let old_attributes = std::mem::take(attributes);
+    attributes.reserve(old_attributes.len());
+
+    for attr in old_attributes.into_iter() {
+        let Some(events) = get_static_event_array(&attr) else {
+            attributes.push(attr);
+            continue;
+        };
+        let AttributeOrBinding::VOn(v_on) = attr else {
+            unreachable!()
+        };
+
+        attributes.extend(events.into_iter().map(|event| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Str(event)),
+                handler: v_on.handler.to_owned(),
+                modifiers: v_on.modifiers.to_owned(),
+                span: v_on.span,
+            })
+        }));
+    }
+}
+
+/// Gets the event names of `@[['click', 'hover']]` if all of them are string literals
+fn get_static_event_array(attr: &AttributeOrBinding) -> Option<Vec<FervidAtom>> {
+    let AttributeOrBinding::VOn(VOnDirective {
+        event: Some(StrOrExpr::Expr(event)),
+        ..
+    }) = attr
+    else {
+        return None;
+    };
+    let Expr::Array(events) = unwrap_parens(event) else {
+        return None;
+    };
+
+    events
+        .elems
+        .iter()
+        .map(|elem| match elem {
+            Some(ExprOrSpread { spread: None, expr }) => match **expr {
+                Expr::Lit(Lit::Str(ref s)) => Some(s.value.to_owned()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 #[inline]
 fn transform_v_on_static_event(static_event: &mut FervidAtom) {
     let transformed_event = if static_event.starts_with("vue:") {
@@ -252,13 +516,28 @@ fn unwrap_parens(expr: &Expr) -> &Expr {
     return &p.expr;
 }
 
+/// The parser turns a handler without statements (e.g. `@click=""`) into `$event => {}`
+fn is_empty_handler(handler: &Expr) -> bool {
+    let Expr::Arrow(ArrowExpr { params, body, .. }) = handler else {
+        return false;
+    };
+
+    matches!(params.as_slice(), [Pat::Ident(param)] if param.id.sym == "$event")
+        && matches!(&**body, BlockStmtOrExpr::BlockStmt(block) if block.stmts.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use fervid_core::{
-        fervid_atom, BindingTypes, TemplateGenerationMode,
+        error::{Severity, SeverityLevel},
+        fervid_atom, BindingTypes, TemplateGenerationMode, VModelDirective, VueDirectives,
     };
 
-    use crate::{test_utils::{to_str, ts}, BindingsHelper, SetupBinding};
+    use crate::{
+        error::TemplateError,
+        test_utils::{template_error_kinds, to_str, ts},
+        BindingsHelper, SetupBinding,
+    };
 
     use super::*;
 
@@ -328,6 +607,9 @@ mod tests {
             bindings_helper: &mut bindings_helper,
             current_scope: 0,
             v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
         };
 
         macro_rules! test {
@@ -450,6 +732,12 @@ mod tests {
 
         // class
         test!("class FooBar {}", "$event=>class FooBar{}");
+
+        // multiple statements (wrapped by the parser)
+        test!(
+            "$event => { foo++; func(foo, bar) }",
+            "$event=>{foo.value++;func(foo.value,_ctx.bar);}"
+        );
     }
 
     // https://github.com/vuejs/core/blob/fef2acb2049fce3407dff17fe8af1836b97dfd73/packages/compiler-sfc/__tests__/compileScript.spec.ts#L495-L543
@@ -472,6 +760,9 @@ mod tests {
             bindings_helper: &mut bindings_helper,
             current_scope: 0,
             v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
         };
 
         macro_rules! test {
@@ -561,6 +852,9 @@ mod tests {
             bindings_helper: &mut bindings_helper,
             current_scope: 0,
             v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
         };
 
         macro_rules! test {
@@ -609,6 +903,9 @@ mod tests {
             bindings_helper: &mut bindings_helper,
             current_scope: 0,
             v_for_scope: false,
+            is_transition_group_child: false,
+            is_in_pre: false,
+            errors: Vec::new(),
         };
 
         macro_rules! test {
@@ -634,6 +931,346 @@ mod tests {
         test!("({ lett } = val)", "$event=>({lett:lett}=val)");
     }
 
+    #[test]
+    fn it_strips_native_modifier() {
+        macro_rules! check {
+            ($is_compat: expr, $element_kind: expr, $expected_kind: expr) => {{
+                let mut bindings_helper = BindingsHelper::default();
+                bindings_helper.is_compat = $is_compat;
+                let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+
+                // @click.native.stop="handler"
+                let mut v_on = VOnDirective {
+                    event: Some("click".into()),
+                    handler: Some(ts("handler")),
+                    modifiers: vec![fervid_atom!("native"), fervid_atom!("stop")],
+                    span: DUMMY_SP,
+                };
+                template_visitor.check_v_on_native_modifier(&mut v_on, $element_kind);
+
+                assert_eq!(v_on.modifiers, vec![fervid_atom!("stop")]);
+                assert_eq!(
+                    template_error_kinds(template_visitor.errors),
+                    vec![$expected_kind]
+                );
+                TemplateError {
+                    span: DUMMY_SP,
+                    kind: $expected_kind,
+                }
+                .get_severity()
+            }};
+        }
+
+        // Compat mode: deprecation warning
+        let severity = check!(
+            true,
+            ElementKind::Component,
+            TemplateErrorKind::VOnNativeModifierDeprecated
+        );
+        assert_eq!(severity, SeverityLevel::Warning);
+
+        // Strict mode (also the default): error
+        let severity = check!(
+            false,
+            ElementKind::Component,
+            TemplateErrorKind::VOnNativeModifierRemoved
+        );
+        assert_eq!(severity, SeverityLevel::RecoverableError);
+
+        // Native element: meaningless, warning
+        let severity = check!(
+            false,
+            ElementKind::Element,
+            TemplateErrorKind::VOnNativeModifierOnElement
+        );
+        assert_eq!(severity, SeverityLevel::Warning);
+        let severity = check!(
+            true,
+            ElementKind::Element,
+            TemplateErrorKind::VOnNativeModifierOnElement
+        );
+        assert_eq!(severity, SeverityLevel::Warning);
+
+        // No `.native` - no errors
+        let mut bindings_helper = BindingsHelper::default();
+        let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+        let mut v_on = VOnDirective {
+            event: Some("click".into()),
+            handler: Some(ts("handler")),
+            modifiers: vec![fervid_atom!("stop")],
+            span: DUMMY_SP,
+        };
+        template_visitor.check_v_on_native_modifier(&mut v_on, ElementKind::Component);
+        assert_eq!(v_on.modifiers.len(), 1);
+        assert!(template_visitor.errors.is_empty());
+    }
+
+    #[test]
+    fn it_checks_stop_modifier_on_touch_events() {
+        let check = |event: &str, modifiers: &[&str]| {
+            let mut bindings_helper = BindingsHelper::default();
+            let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+
+            let v_on = VOnDirective {
+                event: Some(event.into()),
+                handler: Some(ts("handler")),
+                modifiers: modifiers.iter().map(|m| FervidAtom::from(*m)).collect(),
+                span: DUMMY_SP,
+            };
+            template_visitor.check_v_on_touch_stop_modifier(&v_on);
+
+            template_error_kinds(template_visitor.errors)
+        };
+
+        // @touchstart.stop
+        assert_eq!(
+            check("touchstart", &["stop"]),
+            vec![TemplateErrorKind::VOnStopModifierOnPassiveTouch]
+        );
+
+        // @touchstart.stop.prevent
+        assert!(check("touchstart", &["stop", "prevent"]).is_empty());
+
+        // @click.stop
+        assert!(check("click", &["stop"]).is_empty());
+
+        // @touchmove.stop
+        assert_eq!(
+            check("touchmove", &["stop"]),
+            vec![TemplateErrorKind::VOnStopModifierOnPassiveTouch]
+        );
+
+        // @touchstart.passive
+        assert!(check("touchstart", &["passive"]).is_empty());
+
+        // @touchstart.stop.passive
+        assert_eq!(
+            check("touchstart", &["stop", "passive"]),
+            vec![TemplateErrorKind::VOnStopModifierOnPassiveTouch]
+        );
+    }
+
+    #[test]
+    fn it_checks_v_on_handler() {
+        let check = |handler: Option<&str>, modifiers: &[&str]| {
+            let mut bindings_helper = BindingsHelper::default();
+            let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+
+            let v_on = VOnDirective {
+                event: Some("click".into()),
+                handler: handler.map(ts),
+                modifiers: modifiers.iter().map(|m| FervidAtom::from(*m)).collect(),
+                span: DUMMY_SP,
+            };
+            template_visitor.check_v_on_handler(&v_on);
+
+            template_error_kinds(template_visitor.errors)
+        };
+
+        // @click
+        assert_eq!(check(None, &[]), vec![TemplateErrorKind::VOnMissingHandler]);
+
+        // @click="" is parsed as `$event => {}`
+        assert_eq!(
+            check(Some("$event => {}"), &[]),
+            vec![TemplateErrorKind::VOnEmptyHandler]
+        );
+
+        // @click.stop
+        assert!(check(None, &["stop"]).is_empty());
+
+        // @click="handler", @click="() => {}"
+        assert!(check(Some("handler"), &[]).is_empty());
+        assert!(check(Some("() => {}"), &[]).is_empty());
+
+        let missing = TemplateError {
+            span: DUMMY_SP,
+            kind: TemplateErrorKind::VOnMissingHandler,
+        };
+        assert_eq!(missing.get_severity(), SeverityLevel::RecoverableError);
+        let empty = TemplateError {
+            span: DUMMY_SP,
+            kind: TemplateErrorKind::VOnEmptyHandler,
+        };
+        assert_eq!(empty.get_severity(), SeverityLevel::Warning);
+    }
+
+    #[test]
+    fn it_checks_mouse_button_modifiers() {
+        let check = |event: &str, modifiers: &[&str]| {
+            let mut bindings_helper = BindingsHelper::default();
+            let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+
+            let v_on = VOnDirective {
+                event: Some(event.into()),
+                handler: Some(ts("handler")),
+                modifiers: modifiers.iter().map(|m| FervidAtom::from(*m)).collect(),
+                span: DUMMY_SP,
+            };
+            template_visitor.check_v_on_mouse_button_modifier(&v_on);
+
+            template_error_kinds(template_visitor.errors)
+        };
+
+        // @click.left, @mousedown.right, @mouseup.middle
+        assert!(check("click", &["left"]).is_empty());
+        assert!(check("mousedown", &["right"]).is_empty());
+        assert!(check("mouseup", &["middle"]).is_empty());
+
+        // @click.right.stop
+        assert!(check("click", &["right", "stop"]).is_empty());
+
+        // @keydown.left, @touchstart.middle.stop
+        assert_eq!(
+            check("keydown", &["left"]),
+            vec![TemplateErrorKind::VOnMouseModifierOnNonMouseEvent]
+        );
+        assert_eq!(
+            check("touchstart", &["middle", "stop"]),
+            vec![TemplateErrorKind::VOnMouseModifierOnNonMouseEvent]
+        );
+
+        // @keyup.stop
+        assert!(check("keyup", &["stop"]).is_empty());
+    }
+
+    #[test]
+    fn it_suggests_v_model_for_manual_two_way_binding() {
+        let check = |value: Option<&str>, event: &str, handler: &str, v_model: Option<&str>| {
+            let mut attributes = vec![AttributeOrBinding::VOn(VOnDirective {
+                event: Some(event.into()),
+                handler: Some(ts(handler)),
+                modifiers: vec![],
+                span: DUMMY_SP,
+            })];
+            if let Some(value) = value {
+                attributes.push(AttributeOrBinding::VBind(VBindDirective {
+                    argument: Some("value".into()),
+                    value: ts(value),
+                    is_camel: false,
+                    is_prop: false,
+                    is_attr: false,
+                    span: DUMMY_SP,
+                }));
+            }
+
+            let starting_tag = StartingTag {
+                tag_name: "input".into(),
+                attributes,
+                directives: v_model.map(|v_model| {
+                    Box::new(VueDirectives {
+                        v_model: vec![VModelDirective {
+                            argument: None,
+                            value: ts(v_model),
+                            update_handler: None,
+                            modifiers: vec![],
+                            span: DUMMY_SP,
+                        }],
+                        ..Default::default()
+                    })
+                }),
+            };
+
+            let mut bindings_helper = BindingsHelper::default();
+            let mut template_visitor = TemplateVisitor::new(&mut bindings_helper);
+            template_visitor.check_manual_two_way_binding(&starting_tag, ElementKind::Element);
+
+            template_error_kinds(template_visitor.errors)
+        };
+
+        // <input :value="text" @input="text = $event.target.value">
+        assert_eq!(
+            check(Some("text"), "input", "text = $event.target.value", None),
+            vec![TemplateErrorKind::VBindManualTwoWayBinding]
+        );
+
+        // <input :value="form.name" @change="e => form.name = e.target.value">
+        assert_eq!(
+            check(
+                Some("form.name"),
+                "change",
+                "e => form.name = e.target.value",
+                None
+            ),
+            vec![TemplateErrorKind::VBindManualTwoWayBinding]
+        );
+
+        // `:value` with an unrelated handler
+        assert!(check(Some("text"), "input", "onInput", None).is_empty());
+        assert!(check(Some("text"), "input", "other = $event.target.value", None).is_empty());
+        assert!(check(Some("text"), "focus", "text = ''", None).is_empty());
+
+        // Handler without `:value`
+        assert!(check(None, "input", "text = $event.target.value", None).is_empty());
+
+        // `v-model` is already used
+        assert!(check(
+            Some("text"),
+            "input",
+            "text = $event.target.value",
+            Some("other")
+        )
+        .is_empty());
+
+        let hint = TemplateError {
+            span: DUMMY_SP,
+            kind: TemplateErrorKind::VBindManualTwoWayBinding,
+        };
+        assert_eq!(hint.get_severity(), SeverityLevel::Hint);
+    }
+
+    #[test]
+    fn it_unrolls_event_arrays() {
+        let v_on = |event: &str| {
+            AttributeOrBinding::VOn(VOnDirective {
+                event: Some(StrOrExpr::Expr(ts(event))),
+                handler: Some(ts("handler")),
+                modifiers: vec![fervid_atom!("stop")],
+                span: DUMMY_SP,
+            })
+        };
+        let events = |attributes: &[AttributeOrBinding]| {
+            attributes
+                .iter()
+                .map(|attr| match attr {
+                    AttributeOrBinding::VOn(VOnDirective {
+                        event: Some(StrOrExpr::Str(event)),
+                        modifiers,
+                        ..
+                    }) => {
+                        assert_eq!(modifiers, &vec![fervid_atom!("stop")]);
+                        event.to_string()
+                    }
+                    AttributeOrBinding::VOn(VOnDirective {
+                        event: Some(StrOrExpr::Expr(event)),
+                        ..
+                    }) => format!("[{}]", to_str(event)),
+                    _ => panic!("Not a v-on"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // @[['click', 'hover']].stop="handler"
+        let mut attributes = vec![v_on("['click', 'hover']")];
+        unroll_v_on_event_arrays(&mut attributes);
+        assert_eq!(events(&attributes), vec!["click", "hover"]);
+
+        // @[['click']].stop="handler"
+        let mut attributes = vec![v_on("['click']")];
+        unroll_v_on_event_arrays(&mut attributes);
+        assert_eq!(events(&attributes), vec!["click"]);
+
+        // @[[]].stop="handler"
+        let mut attributes = vec![v_on("[]")];
+        unroll_v_on_event_arrays(&mut attributes);
+        assert!(attributes.is_empty());
+
+        // @[[dynamic, 'hover']].stop="handler" is bound at runtime
+        let mut attributes = vec![v_on("[dynamic, 'hover']"), v_on("['click']")];
+        unroll_v_on_event_arrays(&mut attributes);
+        assert_eq!(events(&attributes), vec![r#"[[dynamic,"hover"]]"#, "click"]);
+    }
+
     fn helper(bindings: Vec<SetupBinding>) -> BindingsHelper {
         let mut bindings_helper = BindingsHelper::default();
         bindings_helper.setup_bindings.extend(bindings);
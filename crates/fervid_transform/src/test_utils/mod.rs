@@ -1,10 +1,18 @@
 pub mod parser;
 
-use swc_core::common::{sync::Lrc, SourceMap};
+use fervid_core::{
+    AttributeOrBinding, ElementKind, ElementNode, SfcTemplateBlock, StartingTag, VueDirectives,
+};
+use swc_core::common::{sync::Lrc, SourceMap, DUMMY_SP};
 use swc_core::ecma::ast::Expr;
 use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Node};
 
 use self::parser::{parse_javascript_expr, parse_typescript_expr};
+use crate::{
+    error::{TemplateErrorKind, TransformError},
+    template::transform_and_record_template,
+    BindingsHelper,
+};
 
 pub fn js(raw: &str) -> Box<Expr> {
     parse_javascript_expr(raw, 0, Default::default()).unwrap().0
@@ -34,3 +42,53 @@ pub fn to_str(swc_node: &impl Node) -> String {
 
     String::from_utf8(buff).unwrap()
 }
+
+/// Element node, e.g. `<div>`, with its kind yet to be recognized
+pub fn element(
+    tag_name: &str,
+    attributes: Vec<AttributeOrBinding>,
+    directives: Option<Box<VueDirectives>>,
+    children: Vec<fervid_core::Node>,
+) -> fervid_core::Node {
+    fervid_core::Node::Element(ElementNode {
+        kind: ElementKind::Element,
+        starting_tag: StartingTag {
+            tag_name: tag_name.into(),
+            attributes,
+            directives,
+        },
+        children,
+        template_scope: 0,
+        patch_hints: Default::default(),
+        span: DUMMY_SP,
+    })
+}
+
+/// Transforms the `<template>` roots in place and returns the kinds of the reported errors
+pub fn transform_template(
+    roots: &mut Vec<fervid_core::Node>,
+    bindings_helper: &mut BindingsHelper,
+) -> Vec<TemplateErrorKind> {
+    let mut sfc_template = SfcTemplateBlock {
+        lang: "html".into(),
+        roots: std::mem::take(roots),
+        span: DUMMY_SP,
+    };
+
+    let mut errors = Vec::new();
+    transform_and_record_template(&mut sfc_template, bindings_helper, &mut errors);
+    *roots = sfc_template.roots;
+
+    template_error_kinds(errors)
+}
+
+/// Kinds of the errors, which all must be template errors
+pub fn template_error_kinds(errors: Vec<TransformError>) -> Vec<TemplateErrorKind> {
+    errors
+        .into_iter()
+        .map(|e| match e {
+            TransformError::TemplateError(e) => e.kind,
+            _ => panic!("Not a template error"),
+        })
+        .collect()
+}
@@ -31,11 +31,9 @@ pub fn compile_sync(source: &str, is_prod: Option<bool>) -> Result<WasmCompileRe
         source,
         CompileOptions {
             filename: "anonymous.vue".into(),
-            id: "".into(),
             is_prod,
             ssr: Some(false),
-            gen_default_as: None,
-            source_map: None,
+            ..Default::default()
         },
     );
 